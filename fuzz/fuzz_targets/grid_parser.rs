@@ -0,0 +1,18 @@
+//! Fuzzing de l'analyse du texte d'une grille : `GridParser::try_from` ne doit jamais paniquer,
+//! quel que soit le texte (valide ou non) qui lui est soumis.
+//!
+//! ```cmd
+//! $ cargo +nightly fuzz run grid_parser
+//! ```
+//!
+//! Le corpus initial (`fuzz/corpus/grid_parser`) reprend les grilles de test de `test_grids/`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use star_battle::GridParser;
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    let _ = GridParser::try_from(text.as_ref());
+});
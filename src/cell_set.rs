@@ -0,0 +1,157 @@
+//! Ensemble de cases d'une grille sous la forme d'un masque de bits.
+//!
+//! Un [`CellSet`] représente par exemple les cases d'une zone (voir [`GridHandler::surfer_set`]) : tester
+//! si une case appartient à l'ensemble est un simple test de bit, au lieu du parcours d'un
+//! `Vec<LineColumn>` retourné par [`GridHandler::surfer`].
+//!
+//! [`GridHandler::surfer`]: crate::GridHandler::surfer
+//! [`GridHandler::surfer_set`]: crate::GridHandler::surfer_set
+
+use crate::LineColumn;
+
+/// Nombre de cases par mot du masque
+const BITS_PER_WORD: usize = u64::BITS as usize;
+
+/// Ensemble de cases d'une grille de `nb_lines` x `nb_columns` cases (un bit par case)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CellSet {
+    /// Nombre de lignes de la grille
+    nb_lines: usize,
+
+    /// Nombre de colonnes de la grille
+    nb_columns: usize,
+
+    /// Masque des cases de l'ensemble (case d'index `line * nb_columns + column`)
+    words: Vec<u64>,
+}
+
+impl CellSet {
+    /// Constructeur : ensemble vide de cases d'une grille de `nb_lines` x `nb_columns` cases
+    #[must_use]
+    pub fn new(nb_lines: usize, nb_columns: usize) -> Self {
+        Self {
+            nb_lines,
+            nb_columns,
+            words: vec![0; (nb_lines * nb_columns).div_ceil(BITS_PER_WORD)],
+        }
+    }
+
+    /// Index d'une case (None si la case est en dehors de la grille)
+    const fn index(&self, line_column: LineColumn) -> Option<usize> {
+        if line_column.line < self.nb_lines && line_column.column < self.nb_columns {
+            Some(line_column.line * self.nb_columns + line_column.column)
+        } else {
+            None
+        }
+    }
+
+    /// Ajoute une case à l'ensemble.<br>
+    /// Retourne `false` si la case était déjà dans l'ensemble
+    /// # Panics
+    /// Panique si la case est en dehors de la grille
+    pub fn insert(&mut self, line_column: LineColumn) -> bool {
+        let index = self
+            .index(line_column)
+            .unwrap_or_else(|| panic!("Case {line_column} en dehors de la grille"));
+        let mask = 1 << (index % BITS_PER_WORD);
+        let word = &mut self.words[index / BITS_PER_WORD];
+        let is_new = *word & mask == 0;
+        *word |= mask;
+        is_new
+    }
+
+    /// Retire une case de l'ensemble.<br>
+    /// Retourne `false` si la case n'était pas dans l'ensemble
+    pub fn remove(&mut self, line_column: LineColumn) -> bool {
+        let Some(index) = self.index(line_column) else {
+            return false;
+        };
+        let mask = 1 << (index % BITS_PER_WORD);
+        let word = &mut self.words[index / BITS_PER_WORD];
+        let was_present = *word & mask != 0;
+        *word &= !mask;
+        was_present
+    }
+
+    /// Retourne `true` si la case est dans l'ensemble
+    #[must_use]
+    pub fn contains(&self, line_column: &LineColumn) -> bool {
+        self.index(*line_column).is_some_and(|index| {
+            self.words[index / BITS_PER_WORD] & (1 << (index % BITS_PER_WORD)) != 0
+        })
+    }
+
+    /// Nombre de cases de l'ensemble
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Retourne `true` si l'ensemble est vide
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+
+    /// Itérateur sur les cases de l'ensemble, ligne par ligne
+    pub fn iter(&self) -> impl Iterator<Item = LineColumn> + '_ {
+        (0..self.nb_lines * self.nb_columns)
+            .filter(|index| self.words[index / BITS_PER_WORD] & (1 << (index % BITS_PER_WORD)) != 0)
+            .map(|index| LineColumn::new(index / self.nb_columns, index % self.nb_columns))
+    }
+
+    /// Ajoute à l'ensemble les cases de `other` (ensemble de cases d'une grille de même taille)
+    pub fn union_with(&mut self, other: &Self) {
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word |= other_word;
+        }
+    }
+
+    /// Retire de l'ensemble les cases qui ne sont pas dans `other` (ensemble de cases d'une grille de même
+    /// taille)
+    pub fn intersect_with(&mut self, other: &Self) {
+        for (word, other_word) in self.words.iter_mut().zip(&other.words) {
+            *word &= other_word;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_set() {
+        // Grille de 17x17 cases : le masque occupe plusieurs mots
+        let mut cells = CellSet::new(17, 17);
+        assert!(cells.is_empty());
+        let a1 = LineColumn::new(0, 0);
+        let q17 = LineColumn::new(16, 16);
+        let c5 = LineColumn::new(4, 2);
+        assert!(cells.insert(q17));
+        assert!(cells.insert(a1));
+        assert!(cells.insert(c5));
+        assert!(!cells.insert(c5));
+        assert_eq!(cells.len(), 3);
+        assert!(cells.contains(&q17));
+        assert!(!cells.contains(&LineColumn::new(16, 15)));
+        assert!(!cells.contains(&LineColumn::new(17, 0)));
+        assert_eq!(cells.iter().collect::<Vec<_>>(), vec![a1, c5, q17]);
+
+        assert!(cells.remove(a1));
+        assert!(!cells.remove(a1));
+        assert!(!cells.remove(LineColumn::new(0, 20)));
+
+        let mut other = CellSet::new(17, 17);
+        other.insert(c5);
+        other.insert(a1);
+        let mut intersection = cells.clone();
+        intersection.intersect_with(&other);
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), vec![c5]);
+        cells.union_with(&other);
+        assert_eq!(cells.iter().collect::<Vec<_>>(), vec![a1, c5, q17]);
+    }
+}
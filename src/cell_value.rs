@@ -2,6 +2,7 @@
 
 /// Valeur possible d'une case de la grille
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellValue {
     /// Case dont le contenu est inconnu
     #[default]
@@ -13,3 +14,15 @@ pub enum CellValue {
     /// Case dont le contenu n'est pas une étoile
     NoStar,
 }
+
+impl CellValue {
+    /// Valeur suivante dans le cycle Inconnu → Etoile → Pas d'étoile → Inconnu
+    #[must_use]
+    pub const fn next(&self) -> Self {
+        match self {
+            Self::Unknown => Self::Star,
+            Self::Star => Self::NoStar,
+            Self::NoStar => Self::Unknown,
+        }
+    }
+}
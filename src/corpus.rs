@@ -0,0 +1,116 @@
+//! Grilles d'exemple embarquées (feature `fixtures`).
+//!
+//! Les grilles du répertoire `test_grids/` sont intégrées à la librairie pour être utilisées (exemples,
+//! tests, benchmarks, ...) sans dépendre de chemins de fichiers.
+
+use crate::GridParser;
+
+/// Grilles d'exemple : (nom, nombre d'étoiles, texte de la grille)
+const CORPUS: &[(&str, usize, &str)] = &[
+    ("test01", 1, include_str!("../test_grids/test01.txt")),
+    (
+        "facile01_2",
+        2,
+        include_str!("../test_grids/facile01_2.txt"),
+    ),
+    ("moyen01_2", 2, include_str!("../test_grids/moyen01_2.txt")),
+    (
+        "difficile01_2",
+        2,
+        include_str!("../test_grids/difficile01_2.txt"),
+    ),
+    (
+        "expert01_2",
+        2,
+        include_str!("../test_grids/expert01_2.txt"),
+    ),
+    (
+        "facile02_2",
+        2,
+        include_str!("../test_grids/facile02_2.txt"),
+    ),
+    ("moyen02_2", 2, include_str!("../test_grids/moyen02_2.txt")),
+    (
+        "difficile02_2",
+        2,
+        include_str!("../test_grids/difficile02_2.txt"),
+    ),
+    (
+        "expert02_2",
+        2,
+        include_str!("../test_grids/expert02_2.txt"),
+    ),
+    (
+        "facile03_2",
+        2,
+        include_str!("../test_grids/facile03_2.txt"),
+    ),
+    ("moyen03_2", 2, include_str!("../test_grids/moyen03_2.txt")),
+    (
+        "difficile03_2",
+        2,
+        include_str!("../test_grids/difficile03_2.txt"),
+    ),
+    (
+        "expert03_2",
+        2,
+        include_str!("../test_grids/expert03_2.txt"),
+    ),
+    (
+        "facile04_2",
+        2,
+        include_str!("../test_grids/facile04_2.txt"),
+    ),
+    ("moyen04_2", 2, include_str!("../test_grids/moyen04_2.txt")),
+    (
+        "difficile04_2",
+        2,
+        include_str!("../test_grids/difficile04_2.txt"),
+    ),
+    (
+        "expert04_2",
+        2,
+        include_str!("../test_grids/expert04_2.txt"),
+    ),
+    (
+        "test17x17_3",
+        3,
+        include_str!("../test_grids/test17x17_3.txt"),
+    ),
+];
+
+/// Itérateur sur les grilles d'exemple : (nom, nombre d'étoiles, grille).<br>
+/// Le nom est celui du fichier de la grille dans le répertoire `test_grids/` (sans l'extension `.txt`)
+///
+/// # Panics
+/// Panique si le texte d'une grille d'exemple n'est pas valide (ce qui est vérifié par les tests)
+pub fn iter() -> impl Iterator<Item = (&'static str, usize, GridParser)> {
+    CORPUS.iter().map(|(name, nb_stars, text)| {
+        let parser = GridParser::try_from(*text)
+            .unwrap_or_else(|e| panic!("Grille d'exemple '{name}' invalide: {e}"));
+        (*name, *nb_stars, parser)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridHandler;
+
+    #[test]
+    fn test_corpus() {
+        let corpus: Vec<_> = iter().collect();
+        assert_eq!(corpus.len(), 18);
+        for (name, nb_stars, parser) in corpus {
+            // Même grille que le fichier du répertoire `test_grids/`
+            let text = std::fs::read_to_string(format!("./test_grids/{name}.txt")).unwrap();
+            assert_eq!(
+                parser.to_string(),
+                GridParser::try_from(text.as_str()).unwrap().to_string()
+            );
+            let handler = GridHandler::new(&parser, nb_stars);
+            assert_eq!(handler.nb_stars(), nb_stars);
+        }
+    }
+}
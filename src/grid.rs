@@ -2,19 +2,36 @@
 
 use std::fmt::Display;
 
+use crate::display_line;
+use crate::grid_cells::GridCells;
+use crate::grid_observer::{ObserverSlot, SharedGridObserver};
 use crate::CellValue;
 use crate::GridCell;
 use crate::GridHandler;
 use crate::LineColumn;
 
+/// Caractère du codage compact d'une grille pour une étoile (voir [`Grid::encode`])
+const ENCODED_STAR: char = '*';
+
+/// Caractère du codage compact d'une grille pour une case sans étoile (voir [`Grid::encode`])
+const ENCODED_NO_STAR: char = '-';
+
+/// Caractère du codage compact d'une grille pour une case non définie (voir [`Grid::encode`])
+const ENCODED_UNKNOWN: char = '?';
+
 /// Cases de la grille
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Grid {
     /// Dimensions de la grille
     size: LineColumn,
 
-    /// Cases de la grille
-    cells: Vec<Vec<GridCell>>,
+    /// Cases de la grille (tableau de taille fixe pour les grilles courantes, voir [`GridCells`])
+    cells: GridCells,
+
+    /// Observateur éventuel des modifications des cases de la grille (voir [`Grid::set_observer`])
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observer: ObserverSlot,
 }
 
 impl Display for Grid {
@@ -37,23 +54,27 @@ impl From<&GridHandler> for Grid {
     fn from(value: &GridHandler) -> Self {
         let nb_lines = value.nb_lines();
         let nb_columns = value.nb_columns();
-        let mut cells = Vec::with_capacity(nb_lines);
-        for line in 0..nb_lines {
-            let mut cells_line = Vec::with_capacity(nb_columns);
-            for column in 0..nb_columns {
-                let line_column = LineColumn::new(line, column);
-                let grid_cell = GridCell {
-                    line_column,
-                    region: value.cell_region(line_column),
-                    value: CellValue::Unknown,
-                };
-                cells_line.push(grid_cell);
+        let cells = GridCells::new(nb_lines, nb_columns, |line_column| {
+            // Une case bloquée ne peut pas contenir d'étoile
+            // ... et une étoile donnée par la grille ne peut pas être modifiée
+            let given = value.is_given(line_column);
+            GridCell {
+                line_column,
+                region: value.cell_region(line_column),
+                value: if given {
+                    CellValue::Star
+                } else if value.is_blocked(line_column) {
+                    CellValue::NoStar
+                } else {
+                    CellValue::Unknown
+                },
+                given,
             }
-            cells.push(cells_line);
-        }
+        });
         Self {
             size: LineColumn::new(nb_lines, nb_columns),
             cells,
+            observer: ObserverSlot::default(),
         }
     }
 }
@@ -74,13 +95,176 @@ impl Grid {
     /// Retourne la case (non mutable) de la grille en (line, column)
     #[must_use]
     pub fn cell(&self, line_column: LineColumn) -> &GridCell {
-        &self.cells[line_column.line][line_column.column]
+        self.cells.cell(line_column)
     }
 
     /// Retourne la case (mutable) de la grille en (line, column)
     #[must_use]
     pub fn cell_mut(&mut self, line_column: LineColumn) -> &mut GridCell {
-        &mut self.cells[line_column.line][line_column.column]
+        self.cells.cell_mut(line_column)
+    }
+
+    /// Attache (ou détache avec `None`) un observateur prévenu de chaque changement de valeur d'une case
+    /// par une action ([`Grid::apply_action`], [`Grid::apply_actions_checked`], ...).<br>
+    /// Une copie de la grille n'est pas observée.
+    pub fn set_observer(&mut self, observer: Option<SharedGridObserver>) {
+        self.observer.set(observer);
+    }
+
+    /// Modifie la valeur d'une case et prévient l'éventuel observateur de la grille
+    pub(crate) fn set_cell_value(&mut self, line_column: LineColumn, value: CellValue) {
+        let cell = self.cells.cell_mut(line_column);
+        if cell.value != value {
+            let old = std::mem::replace(&mut cell.value, value);
+            self.observer.notify(line_column, &old, &cell.value);
+        }
+    }
+
+    /// Itérateur sur toutes les cases de la grille, ligne par ligne
+    pub fn iter_cells(&self) -> impl Iterator<Item = &GridCell> {
+        self.cells.lines().flatten()
+    }
+
+    /// Itérateur sur les cases de la grille qui ont une valeur particulière
+    pub fn iter_with_value(&self, value: CellValue) -> impl Iterator<Item = &GridCell> {
+        self.iter_cells().filter(move |cell| cell.value == value)
+    }
+
+    /// Itérateur sur les coordonnées des cases non définies de la grille
+    pub fn enumerate_unknown(&self) -> impl Iterator<Item = LineColumn> + '_ {
+        self.iter_with_value(CellValue::Unknown)
+            .map(|cell| cell.line_column)
+    }
+
+    /// Vérifie la cohérence interne de la grille (dimensions, coordonnées de chaque case et étoiles
+    /// données toujours présentes).<br>
+    /// Ce contrôle n'est fait qu'en mode debug (`debug_assertions`) pour détecter au plus tôt une erreur
+    /// du moteur de résolution.
+    /// # Panics
+    /// Panique en mode debug si la grille est incohérente
+    pub(crate) fn debug_check_consistency(&self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        assert!(
+            self.cells.nb_lines() == self.nb_lines(),
+            "Grille incohérente : {} lignes de cases pour une grille de {} lignes",
+            self.cells.nb_lines(),
+            self.nb_lines()
+        );
+        for (line, cells_line) in self.cells.lines().enumerate() {
+            assert!(
+                cells_line.len() == self.nb_columns(),
+                "Grille incohérente : {} cases dans la ligne {} pour une grille de {} colonnes",
+                cells_line.len(),
+                display_line(line),
+                self.nb_columns()
+            );
+            for (column, cell) in cells_line.iter().enumerate() {
+                let line_column = LineColumn::new(line, column);
+                assert!(
+                    cell.line_column == line_column,
+                    "Grille incohérente : la case {} est rangée en {line_column}",
+                    cell.line_column
+                );
+                assert!(
+                    !cell.is_given() || cell.is_star(),
+                    "Grille incohérente : l'étoile donnée {line_column} n'est plus une étoile"
+                );
+            }
+        }
+    }
+
+    /// Vérifie la cohérence interne de la grille (voir [`Grid::debug_check_consistency`]) et sa cohérence
+    /// avec la définition de la grille `handler` (dimensions, régions, cases bloquées et étoiles données).<br>
+    /// Ce contrôle n'est fait qu'en mode debug (`debug_assertions`).
+    /// # Panics
+    /// Panique en mode debug si la grille est incohérente ou ne correspond pas à `handler`
+    pub(crate) fn debug_check_consistency_with(&self, handler: &GridHandler) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        self.debug_check_consistency();
+        assert!(
+            self.nb_lines() == handler.nb_lines() && self.nb_columns() == handler.nb_columns(),
+            "Grille incohérente : grille de {}x{} cases pour une définition de {}x{} cases",
+            self.nb_lines(),
+            self.nb_columns(),
+            handler.nb_lines(),
+            handler.nb_columns()
+        );
+        for cell in self.iter_cells() {
+            let line_column = cell.line_column;
+            assert!(
+                cell.region == handler.cell_region(line_column),
+                "Grille incohérente : la case {line_column} est dans la région '{}' au lieu de '{}'",
+                cell.region,
+                handler.cell_region(line_column)
+            );
+            assert!(
+                cell.is_given() == handler.is_given(line_column),
+                "Grille incohérente : l'étoile donnée en {line_column} ne correspond pas à la définition"
+            );
+            assert!(
+                !handler.is_blocked(line_column) || cell.value == CellValue::NoStar,
+                "Grille incohérente : la case bloquée {line_column} n'est pas une case sans étoile"
+            );
+        }
+    }
+
+    /// Codage compact du contenu de la grille sur une seule ligne : un caractère par case, ligne par
+    /// ligne (`*` pour une étoile, `-` pour une case sans étoile et `?` pour une case non définie).<br>
+    /// Voir [`Grid::decode`] pour l'opération inverse.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        self.iter_cells()
+            .map(|cell| match cell.value {
+                CellValue::Star => ENCODED_STAR,
+                CellValue::NoStar => ENCODED_NO_STAR,
+                CellValue::Unknown => ENCODED_UNKNOWN,
+            })
+            .collect()
+    }
+
+    /// Construit une grille à partir de son codage compact (voir [`Grid::encode`]).<br>
+    /// Les espaces sont ignorés.
+    /// ### Errors
+    /// Retourne une erreur si le codage ne correspond pas aux dimensions de la grille, contient un caractère
+    /// inconnu ou est incompatible avec les cases bloquées (toujours `-`) ou les étoiles données par la grille
+    pub fn decode(handler: &GridHandler, encoded: &str) -> Result<Self, String> {
+        let mut grid = Self::from(handler);
+        let values = encoded
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                ENCODED_STAR => Ok(CellValue::Star),
+                ENCODED_NO_STAR => Ok(CellValue::NoStar),
+                ENCODED_UNKNOWN => Ok(CellValue::Unknown),
+                _ => Err(format!(
+                    "Caractère '{c}' inconnu dans le codage de la grille"
+                )),
+            })
+            .collect::<Result<Vec<CellValue>, String>>()?;
+        let nb_cells = grid.nb_lines() * grid.nb_columns();
+        if values.len() != nb_cells {
+            return Err(format!(
+                "Le codage de la grille contient {} cases au lieu de {nb_cells}",
+                values.len()
+            ));
+        }
+        for (index, value) in values.into_iter().enumerate() {
+            let line_column = LineColumn::new(index / grid.nb_columns(), index % grid.nb_columns());
+            let cell = grid.cell_mut(line_column);
+            if (cell.is_given() && value != CellValue::Star)
+                || (handler.is_blocked(line_column) && value != CellValue::NoStar)
+            {
+                return Err(format!(
+                    "Le codage de la grille est incompatible avec la case {line_column}"
+                ));
+            }
+            cell.value = value;
+        }
+        Ok(grid)
     }
 }
 
@@ -99,12 +283,56 @@ mod tests {
         assert_eq!(grid.nb_lines(), 5);
         assert_eq!(grid.nb_columns(), 5);
 
-        for line in 0..grid.nb_lines() {
-            for column in 0..grid.nb_columns() {
-                let line_column = LineColumn::new(line, column);
-                assert_eq!(grid.cell(line_column).value, CellValue::Unknown);
-            }
-        }
+        assert_eq!(grid.iter_cells().count(), 25);
+        assert!(grid.iter_cells().all(GridCell::is_unknown));
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let parser =
+            GridParser::try_from(vec!["AB.BB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"])
+                .unwrap();
+        let handler = GridHandler::new(&parser, 1);
+        let mut grid = Grid::from(&handler);
+        grid.cell_mut(LineColumn::new(1, 3)).value = CellValue::Star;
+        grid.cell_mut(LineColumn::new(4, 4)).value = CellValue::NoStar;
+
+        let encoded = grid.encode();
+        assert_eq!(encoded, "*?-?????*???????????????-");
+        assert_eq!(Grid::decode(&handler, &encoded), Ok(grid.clone()));
+        assert_eq!(
+            Grid::decode(&handler, "*?-?? ???*? ????? ????? ????-"),
+            Ok(grid)
+        );
+
+        // Codages invalides
+        assert!(Grid::decode(&handler, "*?-??").is_err());
+        assert!(Grid::decode(&handler, &encoded.replace('-', "x")).is_err());
+        // Étoile donnée en A1
+        assert!(Grid::decode(&handler, &encoded.replacen('*', "-", 1)).is_err());
+        // Case bloquée en C1
+        assert!(Grid::decode(&handler, &encoded.replacen('-', "*", 1)).is_err());
+        assert!(Grid::decode(&handler, &encoded.replacen('-', "?", 1)).is_err());
+    }
+
+    #[test]
+    fn test_iterators() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let handler = GridHandler::new(&parser, 1);
+        let mut grid = Grid::from(&handler);
+        grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::Star;
+        grid.cell_mut(LineColumn::new(0, 1)).value = CellValue::NoStar;
+        grid.cell_mut(LineColumn::new(1, 0)).value = CellValue::NoStar;
+
+        let stars: Vec<LineColumn> = grid
+            .iter_with_value(CellValue::Star)
+            .map(|cell| cell.line_column)
+            .collect();
+        assert_eq!(stars, vec![LineColumn::new(0, 0)]);
+        assert_eq!(grid.iter_with_value(CellValue::NoStar).count(), 2);
+        assert_eq!(grid.enumerate_unknown().count(), 22);
+        assert_eq!(grid.enumerate_unknown().next(), Some(LineColumn::new(0, 2)));
     }
 
     #[test]
@@ -120,4 +348,26 @@ mod tests {
         assert_eq!(grid.cell(line_column).value, CellValue::Unknown);
         assert_eq!(grid_cloned.cell(line_column).value, CellValue::Star);
     }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Grille incohérente : l'étoile donnée A1 n'est plus une étoile")]
+    fn test_debug_check_given_star() {
+        let (_, mut grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"; stars = 1];
+        // Modification directe (sans action) d'une étoile donnée : détectée à l'action suivante
+        grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::NoStar;
+        let _ = grid.apply_action(&crate::GridAction::SetNoStar(LineColumn::new(4, 4)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "Grille incohérente : la case A3 est dans la région 'C' au lieu de 'A'"
+    )]
+    fn test_debug_check_handler() {
+        // Grille d'une autre définition que celle du handler
+        let (_, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let (grid_handler, _) = grid!["ABBBB", "ABBBB", "ACBBB", "DDDDD", "DEEED"; stars = 1];
+        let _ = crate::get_good_rule(&grid_handler, &grid);
+    }
 }
@@ -2,12 +2,60 @@
 
 use std::fmt::Display;
 
+use crate::check_bad_rules;
+use crate::BadRuleError;
 use crate::CellValue;
 use crate::Grid;
+use crate::GridHandler;
 use crate::LineColumn;
+use crate::Locale;
+
+/// Erreur lors de l'application d'une action sur une grille
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GridActionError {
+    /// Modification d'une étoile donnée par la définition de la grille
+    #[error("L'étoile {0} est donnée par la grille et ne peut pas être modifiée")]
+    GivenStar(LineColumn),
+
+    /// Modification par une règle de construction d'une case déjà définie
+    #[error("La case {0} est déjà définie")]
+    CellAlreadyDefined(LineColumn),
+
+    /// Action sur une case en dehors de la grille
+    #[error("La case {0} est en dehors de la grille")]
+    OutsideGrid(LineColumn),
+
+    /// Étoile ou case inconnue sur une case bloquée (qui ne peut pas contenir d'étoile)
+    #[error("La case {0} est bloquée et ne peut pas contenir d'étoile")]
+    BlockedCell(LineColumn),
+}
+
+impl GridActionError {
+    /// Texte de l'erreur dans la langue `locale`
+    #[must_use]
+    pub fn text(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (_, Locale::French) => self.to_string(),
+            (Self::GivenStar(line_column), Locale::English) => {
+                format!("Star {line_column} is given by the grid and cannot be modified")
+            }
+            (Self::CellAlreadyDefined(line_column), Locale::English) => {
+                format!("Cell {line_column} is already defined")
+            }
+            (Self::OutsideGrid(line_column), Locale::English) => {
+                format!("Cell {line_column} is outside the grid")
+            }
+            (Self::BlockedCell(line_column), Locale::English) => {
+                format!("Cell {line_column} is blocked and cannot contain a star")
+            }
+        }
+    }
+}
 
 /// Énumération des actions possibles sur le contenu d'une grille
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GridAction {
     /// L'action d'indiquer le contenu indéfini d'une case
     SetUnknown(LineColumn),
@@ -17,80 +65,366 @@ pub enum GridAction {
 
     /// L'action de supprimer la possibilité d'une étoile à une case
     SetNoStar(LineColumn),
+
+    /// L'action de passer une case à la valeur suivante (voir [`CellValue::next`]) : Inconnu → Etoile →
+    /// Pas d'étoile → Inconnu
+    Toggle(LineColumn),
 }
 
 impl Display for GridAction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::SetUnknown(line_column) => write!(f, "{line_column}-> Inconnu"),
-            Self::SetStar(line_column) => write!(f, "{line_column}->Etoile"),
-            Self::SetNoStar(line_column) => write!(f, "{line_column}->Pas d'étoile"),
-        }
+        write!(f, "{}", self.text(Locale::French))
     }
 }
 
-/// Affichage d'une liste d'actions
-pub fn display_vec_actions(actions: &Vec<GridAction>) -> String {
-    let mut str_actions = String::new();
-    for action in actions {
-        if !str_actions.is_empty() {
-            str_actions.push_str(", ");
-        }
-        str_actions.push_str(&action.to_string());
-    }
-    str_actions
+/// Affichage d'une liste d'actions dans la langue `locale`
+pub fn display_vec_actions(actions: &[GridAction], locale: Locale) -> String {
+    actions
+        .iter()
+        .map(|action| action.text(locale))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 impl GridAction {
+    /// Texte de l'action dans la langue `locale`
+    #[must_use]
+    pub fn text(&self, locale: Locale) -> String {
+        self.text_with_cell(&self.line_column().to_string(), locale)
+    }
+
+    /// Texte de l'action dans la langue `locale` avec le texte `cell` pour sa case
+    pub(crate) fn text_with_cell(&self, cell: &str, locale: Locale) -> String {
+        match (self, locale) {
+            (Self::SetUnknown(_), Locale::French) => format!("{cell}-> Inconnu"),
+            (Self::SetUnknown(_), Locale::English) => format!("{cell}-> Unknown"),
+            (Self::SetStar(_), Locale::French) => format!("{cell}->Etoile"),
+            (Self::SetStar(_), Locale::English) => format!("{cell}->Star"),
+            (Self::SetNoStar(_), Locale::French) => format!("{cell}->Pas d'étoile"),
+            (Self::SetNoStar(_), Locale::English) => format!("{cell}->No star"),
+            (Self::Toggle(_), Locale::French) => format!("{cell}->Valeur suivante"),
+            (Self::Toggle(_), Locale::English) => format!("{cell}->Next value"),
+        }
+    }
+
     /// Retourne la `LineColumn` correspondant à l'action
     #[must_use]
     pub const fn line_column(&self) -> LineColumn {
         match self {
             Self::SetUnknown(line_column)
             | Self::SetStar(line_column)
-            | Self::SetNoStar(line_column) => *line_column,
+            | Self::SetNoStar(line_column)
+            | Self::Toggle(line_column) => *line_column,
         }
     }
 
-    /// Retourne la `CellValue` correspondant à l'action
+    /// Retourne la `CellValue` correspondant à l'action.<br>
+    /// Retourne None pour [`GridAction::Toggle`] dont la valeur dépend du contenu de la case (voir
+    /// [`GridAction::resolve`])
     #[must_use]
-    pub const fn value(&self) -> CellValue {
+    pub const fn value(&self) -> Option<CellValue> {
         match self {
-            Self::SetUnknown(_) => CellValue::Unknown,
-            Self::SetStar(_) => CellValue::Star,
-            Self::SetNoStar(_) => CellValue::NoStar,
+            Self::SetUnknown(_) => Some(CellValue::Unknown),
+            Self::SetStar(_) => Some(CellValue::Star),
+            Self::SetNoStar(_) => Some(CellValue::NoStar),
+            Self::Toggle(_) => None,
         }
     }
 
-    /// Applique une action à la grille
-    pub fn apply_action(&self, grid: &mut Grid) {
+    /// Action correspondant à la valeur `value` de la case `line_column`
+    #[must_use]
+    pub const fn from_value(line_column: LineColumn, value: &CellValue) -> Self {
+        match value {
+            CellValue::Unknown => Self::SetUnknown(line_column),
+            CellValue::Star => Self::SetStar(line_column),
+            CellValue::NoStar => Self::SetNoStar(line_column),
+        }
+    }
+
+    /// Action équivalente sur la grille `grid` qui définit directement la valeur de la case : une action
+    /// [`GridAction::Toggle`] est remplacée par l'action qui définit la valeur suivante de la case
+    #[must_use]
+    pub fn resolve(&self, grid: &Grid) -> Self {
         match self {
-            Self::SetUnknown(line_column) => {
-                grid.cell_mut(*line_column).value = CellValue::Unknown;
-            }
-            Self::SetStar(line_column) => {
-                grid.cell_mut(*line_column).value = CellValue::Star;
-            }
-            Self::SetNoStar(line_column) => {
-                grid.cell_mut(*line_column).value = CellValue::NoStar;
+            Self::Toggle(line_column) => {
+                Self::from_value(*line_column, &grid.cell(*line_column).value.next())
             }
+            _ => self.clone(),
         }
     }
+
+    /// Actions qui passent toutes les cases `cells` de la grille `grid` à la valeur suivant celle de la
+    /// première case.<br>
+    /// Comme un glisser de souris sur plusieurs cases d'un éditeur, les cases obtiennent toutes la même
+    /// valeur quelles que soient leurs valeurs initiales
+    #[must_use]
+    pub fn toggle_all(grid: &Grid, cells: &[LineColumn]) -> Vec<Self> {
+        let Some(first) = cells.first() else {
+            return Vec::new();
+        };
+        let value = grid.cell(*first).value.next();
+        cells
+            .iter()
+            .map(|line_column| Self::from_value(*line_column, &value))
+            .collect()
+    }
+
+    /// Applique une action à la grille
+    /// ### Errors
+    /// Retourne un [`GridActionError`] si l'action modifie une étoile donnée par la définition de la grille
+    pub fn apply_action(&self, grid: &mut Grid) -> Result<(), GridActionError> {
+        let cell = grid.cell(self.line_column());
+        let value = self.value().unwrap_or_else(|| cell.value.next());
+        if cell.is_given() && cell.value != value {
+            return Err(GridActionError::GivenStar(self.line_column()));
+        }
+        grid.set_cell_value(self.line_column(), value);
+        grid.debug_check_consistency();
+        Ok(())
+    }
 }
 
 impl Grid {
-    /// Applique une action à la grille
-    pub fn apply_action(&mut self, action: &GridAction) {
-        match action {
-            GridAction::SetUnknown(line_column) => {
-                self.cell_mut(*line_column).value = CellValue::Unknown;
+    /// Applique une action à une case de la grille
+    /// ### Errors
+    /// Retourne un [`GridActionError`] si l'action modifie une étoile donnée par la définition de la grille
+    pub fn apply_action(&mut self, action: &GridAction) -> Result<(), GridActionError> {
+        action.apply_action(self)
+    }
+
+    /// Applique une liste d'actions à la grille de manière atomique.<br>
+    /// Si une action est impossible (case en dehors de la grille, case bloquée, étoile donnée modifiée, ...)
+    /// ou si la grille obtenue n'est pas valide, toutes les actions sont annulées et la grille est laissée
+    /// dans son état initial
+    /// ### Errors
+    /// Retourne un [`BadRuleError`] si une action est impossible ou si la grille obtenue n'est pas valide
+    pub fn apply_actions_checked(
+        &mut self,
+        handler: &GridHandler,
+        actions: &[GridAction],
+    ) -> Result<(), BadRuleError> {
+        // Valeurs initiales des cases modifiées pour pouvoir les restaurer
+        let mut previous_values = Vec::with_capacity(actions.len());
+        let mut result = Ok(());
+        for action in actions {
+            let line_column = action.line_column();
+            if let Err(e) = check_action_cell(handler, line_column) {
+                result = Err(BadRuleError::from(e));
+                break;
+            }
+            let previous_value = self.cell(line_column).value.clone();
+            if handler.is_blocked(line_column)
+                && action.value().unwrap_or_else(|| previous_value.next()) != CellValue::NoStar
+            {
+                result = Err(BadRuleError::from(GridActionError::BlockedCell(
+                    line_column,
+                )));
+                break;
             }
-            GridAction::SetStar(line_column) => {
-                self.cell_mut(*line_column).value = CellValue::Star;
+            if let Err(e) = action.apply_action(self) {
+                result = Err(BadRuleError::from(e));
+                break;
             }
-            GridAction::SetNoStar(line_column) => {
-                self.cell_mut(*line_column).value = CellValue::NoStar;
+            previous_values.push((line_column, previous_value));
+        }
+        if result.is_ok() {
+            result = check_bad_rules(handler, self);
+        }
+        if result.is_ok() {
+            self.debug_check_consistency_with(handler);
+        } else {
+            // Restauration dans l'ordre inverse si une même case a été modifiée plusieurs fois
+            for (line_column, value) in previous_values.into_iter().rev() {
+                self.set_cell_value(line_column, value);
             }
         }
+        result
+    }
+
+    /// Passe les cases `cells` à la valeur suivant celle de la première case (voir
+    /// [`GridAction::toggle_all`]).<br>
+    /// Les actions sont toutes appliquées ou toutes refusées (voir [`Grid::apply_actions_checked`])
+    /// ### Errors
+    /// Retourne un [`BadRuleError`] si une action est impossible ou si la grille obtenue n'est pas valide
+    pub fn toggle_cells_checked(
+        &mut self,
+        handler: &GridHandler,
+        cells: &[LineColumn],
+    ) -> Result<(), BadRuleError> {
+        for line_column in cells {
+            check_action_cell(handler, *line_column)?;
+        }
+        let actions = GridAction::toggle_all(self, cells);
+        self.apply_actions_checked(handler, &actions)
+    }
+}
+
+/// Vérifie que la case `line_column` d'une action est dans la grille `handler`
+pub(crate) fn check_action_cell(
+    handler: &GridHandler,
+    line_column: LineColumn,
+) -> Result<(), GridActionError> {
+    if line_column.line < handler.nb_lines() && line_column.column < handler.nb_columns() {
+        Ok(())
+    } else {
+        Err(GridActionError::OutsideGrid(line_column))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_given_star() {
+        let (_, mut grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"; stars = 1];
+
+        // L'étoile donnée en A1 ne peut pas être modifiée
+        let given = LineColumn::new(0, 0);
+        assert!(grid.apply_action(&GridAction::SetStar(given)).is_ok());
+        assert_eq!(
+            grid.apply_action(&GridAction::SetNoStar(given)),
+            Err(GridActionError::GivenStar(given))
+        );
+        assert_eq!(
+            GridAction::SetUnknown(given).apply_action(&mut grid),
+            Err(GridActionError::GivenStar(given))
+        );
+        assert!(grid.cell(given).is_star());
+
+        // Les autres cases restent modifiables
+        assert!(grid
+            .apply_action(&GridAction::SetNoStar(LineColumn::new(1, 1)))
+            .is_ok());
+        assert!(grid.cell(LineColumn::new(1, 1)).is_no_star());
+    }
+    #[test]
+    fn test_apply_actions_checked() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"; stars = 1];
+
+        // Actions valides : elles sont toutes appliquées
+        let actions = vec![
+            GridAction::SetNoStar(LineColumn::new(0, 1)),
+            GridAction::SetNoStar(LineColumn::new(1, 0)),
+        ];
+        assert!(grid.apply_actions_checked(&grid_handler, &actions).is_ok());
+        assert!(grid.cell(LineColumn::new(0, 1)).is_no_star());
+        assert!(grid.cell(LineColumn::new(1, 0)).is_no_star());
+
+        // Une étoile adjacente à l'étoile donnée rend la grille invalide : rien n'est appliqué
+        let saved_grid = grid.clone();
+        let actions = vec![
+            GridAction::SetNoStar(LineColumn::new(4, 4)),
+            GridAction::SetStar(LineColumn::new(1, 1)),
+        ];
+        assert!(matches!(
+            grid.apply_actions_checked(&grid_handler, &actions),
+            Err(BadRuleError::StarAdjacent(_, _))
+        ));
+        assert_eq!(grid, saved_grid);
+
+        // La modification de l'étoile donnée est refusée : rien n'est appliqué
+        let given = LineColumn::new(0, 0);
+        let actions = vec![
+            GridAction::SetNoStar(LineColumn::new(4, 4)),
+            GridAction::SetNoStar(given),
+        ];
+        assert_eq!(
+            grid.apply_actions_checked(&grid_handler, &actions),
+            Err(BadRuleError::GridAction(GridActionError::GivenStar(given)))
+        );
+        assert_eq!(grid, saved_grid);
+
+        // Une case en dehors de la grille est refusée : rien n'est appliqué
+        let outside = LineColumn::new(9, 9);
+        let actions = vec![
+            GridAction::SetNoStar(LineColumn::new(4, 4)),
+            GridAction::SetStar(outside),
+        ];
+        assert_eq!(
+            grid.apply_actions_checked(&grid_handler, &actions),
+            Err(BadRuleError::GridAction(GridActionError::OutsideGrid(
+                outside
+            )))
+        );
+        assert_eq!(grid, saved_grid);
+        assert!(grid
+            .toggle_cells_checked(&grid_handler, &[LineColumn::new(0, 7)])
+            .is_err());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"; stars = 1];
+        let b3 = LineColumn::new(2, 1);
+        let toggle = GridAction::Toggle(b3);
+        assert_eq!(toggle.value(), None);
+        assert_eq!(toggle.resolve(&grid), GridAction::SetStar(b3));
+
+        // Cycle Inconnu → Etoile → Pas d'étoile → Inconnu
+        for value in [CellValue::Star, CellValue::NoStar, CellValue::Unknown] {
+            grid.apply_action(&toggle).unwrap();
+            assert_eq!(grid.cell(b3).value, value);
+        }
+
+        // L'étoile donnée en A1 ne change pas de valeur
+        let given = LineColumn::new(0, 0);
+        assert_eq!(
+            grid.apply_action(&GridAction::Toggle(given)),
+            Err(GridActionError::GivenStar(given))
+        );
+
+        // Toutes les cases prennent la valeur suivant celle de la première case
+        let c4 = LineColumn::new(3, 2);
+        let e5 = LineColumn::new(4, 4);
+        grid.apply_action(&GridAction::SetNoStar(c4)).unwrap();
+        assert_eq!(
+            GridAction::toggle_all(&grid, &[b3, c4, e5]),
+            vec![
+                GridAction::SetStar(b3),
+                GridAction::SetStar(c4),
+                GridAction::SetStar(e5)
+            ]
+        );
+        assert!(GridAction::toggle_all(&grid, &[]).is_empty());
+
+        // Les étoiles adjacentes en B3 et C4 rendent la grille invalide : rien n'est appliqué
+        let saved_grid = grid.clone();
+        assert!(matches!(
+            grid.toggle_cells_checked(&grid_handler, &[b3, c4]),
+            Err(BadRuleError::StarAdjacent(_, _))
+        ));
+        assert_eq!(grid, saved_grid);
+        grid.apply_action(&GridAction::SetStar(e5)).unwrap();
+        assert!(grid.toggle_cells_checked(&grid_handler, &[c4, e5]).is_ok());
+        assert!(grid.cell(c4).is_unknown());
+        assert!(grid.cell(e5).is_unknown());
+    }
+
+    #[test]
+    fn test_blocked_cell() {
+        let parser =
+            crate::GridParser::try_from(vec!["AB.BB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let grid_handler = GridHandler::new(&parser, 1);
+        let mut grid = Grid::from(&grid_handler);
+        let saved_grid = grid.clone();
+        let c1 = LineColumn::new(0, 2);
+        for action in [
+            GridAction::SetStar(c1),
+            GridAction::SetUnknown(c1),
+            GridAction::Toggle(c1),
+        ] {
+            assert_eq!(
+                grid.apply_actions_checked(&grid_handler, &[action]),
+                Err(BadRuleError::GridAction(GridActionError::BlockedCell(c1)))
+            );
+            assert_eq!(grid, saved_grid);
+        }
+        assert!(grid.toggle_cells_checked(&grid_handler, &[c1]).is_err());
+        assert!(grid
+            .apply_actions_checked(&grid_handler, &[GridAction::SetNoStar(c1)])
+            .is_ok());
     }
 }
@@ -5,12 +5,15 @@
 
 use crate::CellValue;
 use crate::Grid;
+use crate::GridActionError;
 use crate::GridHandler;
 use crate::GridSurfer;
 use crate::LineColumn;
+use crate::Locale;
 
 /// Erreur de cohérence de la grille
-#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BadRuleError {
     /// Etoile adjacente à une autre étoile
     #[error("Etoile {0} adjacente à l'étoile {1}")]
@@ -23,6 +26,30 @@ pub enum BadRuleError {
     /// Impossible de placer toutes les étoiles dans une 'zone'
     #[error("Impossible de placer toutes les étoiles dans '{0}'")]
     NotEnoughStarsInZone(GridSurfer),
+
+    /// Action impossible sur une case de la grille
+    #[error(transparent)]
+    GridAction(#[from] GridActionError),
+}
+
+impl BadRuleError {
+    /// Texte de l'erreur dans la langue `locale`
+    #[must_use]
+    pub fn text(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (Self::GridAction(e), _) => e.text(locale),
+            (_, Locale::French) => self.to_string(),
+            (Self::StarAdjacent(line_column, other), Locale::English) => {
+                format!("Star {line_column} adjacent to star {other}")
+            }
+            (Self::TooManyStarsInZone(zone), Locale::English) => {
+                format!("Too many stars in '{}'", zone.text(locale))
+            }
+            (Self::NotEnoughStarsInZone(zone), Locale::English) => {
+                format!("Unable to place all the stars in '{}'", zone.text(locale))
+            }
+        }
+    }
 }
 
 /// Vérification de la validité d'une grille
@@ -40,29 +67,29 @@ pub fn check_bad_rules(handler: &GridHandler, grid: &Grid) -> Result<(), BadRule
     for column in 0..handler.nb_columns() {
         check_zone(handler, grid, &GridSurfer::Column(column))?;
     }
+    for cage in handler.cages() {
+        check_zone(handler, grid, &cage)?;
+    }
     Ok(())
 }
 
 /// Parcours les cases de la grille pour vérifier qu'aucune étoile n'est adjacent à une autre étoile
 fn check_no_star_adjacent(handler: &GridHandler, grid: &Grid) -> Result<(), BadRuleError> {
-    for line_column in handler.surfer(grid, &GridSurfer::AllCells) {
-        let cell = grid.cell(line_column);
-        if cell.value == CellValue::Star {
-            for adjacent_line_column in handler.adjacent_cells(line_column) {
-                let adjacent_cell = grid.cell(adjacent_line_column);
-                if adjacent_cell.value == CellValue::Star {
-                    return Err(BadRuleError::StarAdjacent(
-                        line_column,
-                        adjacent_line_column,
-                    ));
-                }
+    for cell in grid.iter_with_value(CellValue::Star) {
+        for adjacent_line_column in handler.adjacent_cells(cell.line_column) {
+            if grid.cell(adjacent_line_column).is_star() {
+                return Err(BadRuleError::StarAdjacent(
+                    cell.line_column,
+                    adjacent_line_column,
+                ));
             }
         }
     }
     Ok(())
 }
 
-/// Vérifie la validité du nombre d'étoile sur une zone (line, colonne ou région).<br>
+/// Vérifie la validité du nombre d'étoile sur une zone (line, colonne, région ou cage) selon le nombre d'étoiles
+/// à placer dans cette zone.<br>
 fn check_zone(handler: &GridHandler, grid: &Grid, surfer: &GridSurfer) -> Result<(), BadRuleError> {
     let mut nb_stars = 0;
     let mut nb_possible_stars = 0;
@@ -75,9 +102,10 @@ fn check_zone(handler: &GridHandler, grid: &Grid, surfer: &GridSurfer) -> Result
         }
     }
 
-    if nb_stars > handler.nb_stars() {
+    let zone_nb_stars = handler.zone_nb_stars(surfer);
+    if nb_stars > zone_nb_stars {
         return Err(BadRuleError::TooManyStarsInZone(surfer.clone()));
-    } else if nb_stars + nb_possible_stars < handler.nb_stars() {
+    } else if nb_stars + nb_possible_stars < zone_nb_stars {
         return Err(BadRuleError::NotEnoughStarsInZone(surfer.clone()));
     }
 
@@ -92,11 +120,7 @@ mod tests {
 
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -155,6 +179,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zone_stars() {
+        // Régions 'A' et 'C' regroupées dans une région 'A' de 2 étoiles
+        let grid_parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "AABBB", "DDDDD", "DEEED"]).unwrap();
+        let grid_handler =
+            GridHandler::with_zone_stars(&grid_parser, 1, &[(GridSurfer::Region('A'), 2)]);
+        let mut grid = Grid::from(&grid_handler);
+
+        // 2 étoiles non adjacentes dans la région 'A' sont valides
+        grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::Star;
+        grid.cell_mut(LineColumn::new(2, 1)).value = CellValue::Star;
+        assert!(check_bad_rules(&grid_handler, &grid).is_ok());
+
+        // Mais pas une seule étoile
+        grid.cell_mut(LineColumn::new(2, 1)).value = CellValue::NoStar;
+        grid.cell_mut(LineColumn::new(1, 0)).value = CellValue::NoStar;
+        grid.cell_mut(LineColumn::new(2, 0)).value = CellValue::NoStar;
+        assert_eq!(
+            check_bad_rules(&grid_handler, &grid),
+            Err(BadRuleError::NotEnoughStarsInZone(GridSurfer::Region('A')))
+        );
+    }
+
+    #[test]
+    fn test_cage() {
+        // Cage d'une étoile sur la diagonale de la grille
+        let (grid_handler, mut grid) = get_test_grid();
+        let diagonal: Vec<LineColumn> = (0..5).map(|i| LineColumn::new(i, i)).collect();
+        let grid_handler = grid_handler.with_cage(&diagonal, 1);
+
+        // 2 étoiles valides pour les lignes, colonnes et régions mais sur la diagonale
+        grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::Star;
+        assert!(check_bad_rules(&grid_handler, &grid).is_ok());
+        grid.cell_mut(LineColumn::new(3, 3)).value = CellValue::Star;
+        assert_eq!(
+            check_bad_rules(&grid_handler, &grid),
+            Err(BadRuleError::TooManyStarsInZone(GridSurfer::Cage(0)))
+        );
+    }
+
     #[test]
     fn test_too_many_stars_in_line() {
         let (grid_handler, mut grid) = get_test_grid();
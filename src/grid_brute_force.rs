@@ -0,0 +1,472 @@
+//! Recherche exhaustive des solutions d'une grille.
+//!
+//! Contrairement aux règles de [`crate::get_good_rule`] qui imitent un raisonnement humain, ce module
+//! explore par 'force brute' (backtracking) toutes les façons de compléter une grille.
+//!
+//! Il est utilisé pour compter les solutions d'une grille (0, 1 ou plusieurs), ce qui permet par
+//! exemple au générateur de grilles de vérifier qu'une grille proposée n'a qu'une seule solution.
+
+use crate::check_bad_rules;
+use crate::CellValue;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridSurfer;
+use crate::LineColumn;
+
+/// Nombre de solutions d'une grille.<br>
+/// La recherche s'arrête dès que `max_solutions` solutions sont trouvées (`max_solutions` = 2 suffit
+/// pour savoir si une grille a une solution unique).
+#[must_use]
+pub fn count_solutions(handler: &GridHandler, grid: &Grid, max_solutions: usize) -> usize {
+    find_solutions(handler, grid, max_solutions).len()
+}
+
+/// Liste des solutions d'une grille (au plus `max_solutions` solutions).<br>
+/// Les cases déjà définies dans `grid` sont respectées.
+#[must_use]
+pub fn find_solutions(handler: &GridHandler, grid: &Grid, max_solutions: usize) -> Vec<Grid> {
+    if max_solutions == 0 || check_bad_rules(handler, grid).is_err() {
+        return Vec::new();
+    }
+    let mut search = BruteForce::new(handler, grid, max_solutions);
+    if search.propagate_initial() {
+        search.explore();
+    }
+    search.solutions
+}
+
+/// Table des nombres de façons de choisir `k` cases parmi `n` (plafonnés à `u64::MAX`) pour `n` jusqu'à
+/// `max_cells` et `k` jusqu'à `max_stars` : `table[n][k]`
+fn combinations_table(max_cells: usize, max_stars: usize) -> Vec<Vec<u64>> {
+    let mut table = vec![vec![0_u64; max_stars + 1]; max_cells + 1];
+    for n in 0..=max_cells {
+        table[n][0] = 1;
+        for k in 1..=max_stars.min(n) {
+            table[n][k] = table[n - 1][k - 1].saturating_add(table[n - 1][k]);
+        }
+    }
+    table
+}
+
+/// Contexte de la recherche exhaustive.<br>
+/// Chaque choix d'une case est suivi de la propagation de ses conséquences immédiates (cases adjacentes à
+/// une étoile, zones complètes ou qui n'ont plus que le nombre de cases nécessaires) et la case examinée à
+/// chaque étape est choisie dans la zone qui a le moins de façons de placer ses étoiles manquantes. Ces
+/// choix limitent fortement l'exploration des grandes grilles.
+struct BruteForce<'a> {
+    /// Grille initiale
+    grid: &'a Grid,
+
+    /// Nombre de colonnes de la grille (pour l'indexation des cases)
+    nb_columns: usize,
+
+    /// Valeur courante des cases (par index)
+    values: Vec<CellValue>,
+
+    /// Cases adjacentes de chaque case (par index)
+    adjacents: Vec<Vec<usize>>,
+
+    /// Zones (par index) auxquelles appartient chaque case (par index)
+    cell_zones: Vec<Vec<usize>>,
+
+    /// Cases (par index) de chaque zone
+    zone_cells: Vec<Vec<usize>>,
+
+    /// Nombre d'étoiles attendues dans chaque zone
+    zone_quotas: Vec<usize>,
+
+    /// Nombre d'étoiles courant dans chaque zone
+    zone_stars: Vec<usize>,
+
+    /// Nombre de cases non définies restantes dans chaque zone
+    zone_unknowns: Vec<usize>,
+
+    /// Nombre de façons de choisir `k` cases parmi `n` cases d'une zone : `combinations[n][k]`
+    combinations: Vec<Vec<u64>>,
+
+    /// Cases (par index) définies pendant la recherche, dans l'ordre où elles ont été définies
+    trail: Vec<usize>,
+
+    /// Nombre maximum de solutions recherchées
+    max_solutions: usize,
+
+    /// Solutions trouvées
+    solutions: Vec<Grid>,
+}
+
+impl<'a> BruteForce<'a> {
+    /// Constructeur du contexte de recherche
+    fn new(handler: &GridHandler, grid: &'a Grid, max_solutions: usize) -> Self {
+        let nb_columns = handler.nb_columns();
+        let index = |line_column: LineColumn| line_column.line * nb_columns + line_column.column;
+
+        let all_cells = handler.surfer(grid, &GridSurfer::AllCells);
+        let nb_cells = handler.nb_lines() * nb_columns;
+        let mut values = vec![CellValue::NoStar; nb_cells];
+        let mut adjacents = vec![Vec::new(); nb_cells];
+        for line_column in &all_cells {
+            values[index(*line_column)] = grid.cell(*line_column).value.clone();
+            adjacents[index(*line_column)] = handler
+                .adjacent_cells(*line_column)
+                .into_iter()
+                .map(index)
+                .collect();
+        }
+
+        // Zones à respecter : régions, lignes, colonnes et cages
+        let mut zones = handler.cages();
+        for region in handler.regions() {
+            zones.push(GridSurfer::Region(region));
+        }
+        for line in 0..handler.nb_lines() {
+            zones.push(GridSurfer::Line(line));
+        }
+        for column in 0..handler.nb_columns() {
+            zones.push(GridSurfer::Column(column));
+        }
+        // ainsi que les bandes de 2 lignes ou 2 colonnes consécutives, dont la capacité est souvent plus
+        // contraignante que celle de chacune de leurs lignes ou colonnes
+        for line in 1..handler.nb_lines() {
+            zones.push(GridSurfer::Lines(line - 1..=line));
+        }
+        for column in 1..handler.nb_columns() {
+            zones.push(GridSurfer::Columns(column - 1..=column));
+        }
+
+        let mut cell_zones = vec![Vec::new(); nb_cells];
+        let mut zone_cells = Vec::with_capacity(zones.len());
+        let mut zone_quotas = Vec::with_capacity(zones.len());
+        let mut zone_stars = Vec::with_capacity(zones.len());
+        let mut zone_unknowns = Vec::with_capacity(zones.len());
+        for (num_zone, zone) in zones.iter().enumerate() {
+            let mut cells = Vec::new();
+            let mut nb_stars = 0;
+            let mut nb_unknowns = 0;
+            for line_column in handler.surfer(grid, zone) {
+                cell_zones[index(line_column)].push(num_zone);
+                cells.push(index(line_column));
+                match grid.cell(line_column).value {
+                    CellValue::Star => nb_stars += 1,
+                    CellValue::Unknown => nb_unknowns += 1,
+                    CellValue::NoStar => (),
+                }
+            }
+            zone_cells.push(cells);
+            zone_quotas.push(handler.zone_nb_stars(zone));
+            zone_stars.push(nb_stars);
+            zone_unknowns.push(nb_unknowns);
+        }
+
+        let max_cells = zone_cells.iter().map(Vec::len).max().unwrap_or(0);
+        let max_stars = zone_quotas.iter().copied().max().unwrap_or(0);
+        Self {
+            grid,
+            nb_columns,
+            values,
+            adjacents,
+            cell_zones,
+            zone_cells,
+            zone_quotas,
+            zone_stars,
+            zone_unknowns,
+            combinations: combinations_table(max_cells, max_stars),
+            trail: Vec::new(),
+            max_solutions,
+            solutions: Vec::new(),
+        }
+    }
+
+    /// Propage les conséquences de la grille initiale.<br>
+    /// Retourne `false` si la grille initiale n'a pas de solution
+    fn propagate_initial(&mut self) -> bool {
+        let mut pending = Vec::new();
+        for cell in 0..self.values.len() {
+            if self.values[cell] == CellValue::Star {
+                for adjacent in &self.adjacents[cell] {
+                    pending.push((*adjacent, CellValue::NoStar));
+                }
+            }
+        }
+        for zone in 0..self.zone_cells.len() {
+            if !self.zone_consequences(zone, &mut pending) {
+                return false;
+            }
+        }
+        self.apply(pending)
+    }
+
+    /// Exploration récursive des cases non définies
+    fn explore(&mut self) {
+        if self.solutions.len() >= self.max_solutions {
+            return;
+        }
+
+        let Some(cell) = self.next_cell() else {
+            // Toutes les cases sont définies et toutes les zones ont exactement le nombre d'étoiles
+            // attendues (garanti par les contrôles faits à chaque étape)
+            self.push_solution();
+            return;
+        };
+
+        // Une étoile dans cette case ?
+        let mark = self.trail.len();
+        if self.apply(vec![(cell, CellValue::Star)]) {
+            self.explore();
+        }
+        self.undo(mark);
+
+        // Pas d'étoile dans cette case ?
+        if self.solutions.len() < self.max_solutions && self.apply(vec![(cell, CellValue::NoStar)])
+        {
+            self.explore();
+        }
+        self.undo(mark);
+    }
+
+    /// Prochaine case à examiner : la première case non définie de la zone incomplète qui a le moins de
+    /// façons de placer ses étoiles manquantes (ou, à défaut, la première case non définie de la grille)
+    fn next_cell(&self) -> Option<usize> {
+        let zone = (0..self.zone_cells.len())
+            .filter(|zone| {
+                self.zone_unknowns[*zone] > 0 && self.zone_stars[*zone] < self.zone_quotas[*zone]
+            })
+            .min_by_key(|zone| {
+                self.combinations[self.zone_unknowns[*zone]]
+                    [self.zone_quotas[*zone] - self.zone_stars[*zone]]
+            });
+        let cells = match zone {
+            Some(zone) => &self.zone_cells[zone][..],
+            None => &[],
+        };
+        cells
+            .iter()
+            .copied()
+            .find(|cell| self.values[*cell] == CellValue::Unknown)
+            .or_else(|| {
+                (0..self.values.len()).find(|cell| self.values[*cell] == CellValue::Unknown)
+            })
+    }
+
+    /// Définit la valeur de cases non définies et propage les conséquences de ces choix.<br>
+    /// Retourne `false` si ces choix conduisent à une grille invalide (les cases déjà définies restent
+    /// mémorisées dans `trail` pour être annulées)
+    fn apply(&mut self, mut pending: Vec<(usize, CellValue)>) -> bool {
+        while let Some((cell, value)) = pending.pop() {
+            if self.values[cell] != CellValue::Unknown {
+                if self.values[cell] == value {
+                    continue;
+                }
+                return false;
+            }
+            let is_star = value == CellValue::Star;
+            self.values[cell] = value;
+            self.trail.push(cell);
+            if is_star {
+                for adjacent in &self.adjacents[cell] {
+                    pending.push((*adjacent, CellValue::NoStar));
+                }
+            }
+            for zone in &self.cell_zones[cell] {
+                self.zone_unknowns[*zone] -= 1;
+                if is_star {
+                    self.zone_stars[*zone] += 1;
+                }
+            }
+            for zone in &self.cell_zones[cell] {
+                if !self.zone_consequences(*zone, &mut pending) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Contrôle le nombre d'étoiles d'une zone et ajoute à `pending` les cases non définies de la zone qui
+    /// ne peuvent plus être qu'une étoile ou que sans étoile.<br>
+    /// Retourne `false` si la zone ne peut pas avoir le nombre d'étoiles attendues
+    fn zone_consequences(&self, zone: usize, pending: &mut Vec<(usize, CellValue)>) -> bool {
+        let (nb_stars, nb_unknowns, quota) = (
+            self.zone_stars[zone],
+            self.zone_unknowns[zone],
+            self.zone_quotas[zone],
+        );
+        if nb_stars > quota || nb_stars + nb_unknowns < quota {
+            return false;
+        }
+        // Chaque bloc de 2x2 cases contient au plus une étoile. S'il y a juste assez de blocs pour les
+        // étoiles manquantes, chaque bloc contient une étoile : un bloc qui n'a qu'une seule case non
+        // définie de la zone est forcément une étoile. Il faut au moins `nb_unknowns / 4` blocs pour
+        // recouvrir la zone : inutile de les construire s'il y en a forcément plus que nécessaire
+        if nb_stars < quota && nb_unknowns <= 4 * (quota - nb_stars) {
+            let blocks = self.zone_blocks(zone, quota - nb_stars);
+            if nb_stars + blocks.len() < quota {
+                return false;
+            }
+            if nb_stars + blocks.len() == quota {
+                pending.extend(
+                    blocks
+                        .into_iter()
+                        .flatten()
+                        .map(|cell| (cell, CellValue::Star)),
+                );
+            }
+        }
+        if nb_unknowns > 0 && (nb_stars == quota || nb_stars + nb_unknowns == quota) {
+            let value = if nb_stars == quota {
+                CellValue::NoStar
+            } else {
+                CellValue::Star
+            };
+            for cell in &self.zone_cells[zone] {
+                if self.values[*cell] == CellValue::Unknown {
+                    pending.push((*cell, value.clone()));
+                }
+            }
+        }
+        true
+    }
+
+    /// Blocs de 2x2 cases qui recouvrent les cases non définies d'une zone. Pour chaque bloc, on retient
+    /// la case non définie de la zone qu'il contient s'il n'en contient qu'une seule.<br>
+    /// Un bloc de 2x2 cases ne peut pas contenir plus d'une étoile : le nombre de blocs est un majorant du
+    /// nombre d'étoiles qu'on peut encore poser dans la zone. La construction s'arrête dès qu'il y a plus
+    /// de `max_blocks` blocs.
+    fn zone_blocks(&self, zone: usize, max_blocks: usize) -> Vec<Option<usize>> {
+        let mut covered = Vec::with_capacity(4 * (max_blocks + 1));
+        let mut blocks = Vec::with_capacity(max_blocks + 1);
+        for cell in &self.zone_cells[zone] {
+            if self.values[*cell] == CellValue::Unknown && !covered.contains(cell) {
+                if blocks.len() == max_blocks + 1 {
+                    break;
+                }
+                let right = (cell % self.nb_columns + 1 < self.nb_columns).then_some(cell + 1);
+                let below = Some(cell + self.nb_columns).filter(|below| *below < self.values.len());
+                let below_right = right.and(below).map(|below| below + 1);
+                let mut zone_unknowns = [Some(*cell), right, below, below_right]
+                    .into_iter()
+                    .flatten()
+                    .inspect(|block_cell| covered.push(*block_cell))
+                    .filter(|block_cell| {
+                        self.values[*block_cell] == CellValue::Unknown
+                            && self.cell_zones[*block_cell].contains(&zone)
+                    })
+                    .collect::<Vec<_>>();
+                blocks.push(if zone_unknowns.len() == 1 {
+                    zone_unknowns.pop()
+                } else {
+                    None
+                });
+            }
+        }
+        blocks
+    }
+
+    /// Annule toutes les cases définies depuis que `trail` avait `mark` cases
+    fn undo(&mut self, mark: usize) {
+        while self.trail.len() > mark {
+            let cell = self
+                .trail
+                .pop()
+                .expect("Cases définies pendant la recherche");
+            let is_star = self.values[cell] == CellValue::Star;
+            self.values[cell] = CellValue::Unknown;
+            for zone in &self.cell_zones[cell] {
+                self.zone_unknowns[*zone] += 1;
+                if is_star {
+                    self.zone_stars[*zone] -= 1;
+                }
+            }
+        }
+    }
+
+    /// Mémorise la solution courante
+    fn push_solution(&mut self) {
+        let mut solution = self.grid.clone();
+        for (cell, value) in self.values.iter().enumerate() {
+            let line_column = LineColumn::new(cell / self.nb_columns, cell % self.nb_columns);
+            solution.cell_mut(line_column).value = value.clone();
+        }
+        self.solutions.push(solution);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridParser;
+
+    // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
+    fn get_test_grid() -> (GridHandler, Grid) {
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
+    }
+
+    #[test]
+    fn test_unique_solution() {
+        let (grid_handler, grid) = get_test_grid();
+
+        let solutions = find_solutions(&grid_handler, &grid, 2);
+        assert_eq!(solutions.len(), 1);
+        assert!(grid_handler.is_done(&solutions[0]));
+    }
+
+    #[test]
+    fn test_multiple_solutions() {
+        // Grille 4x4 en 4 régions carrées : les étoiles peuvent être placées de 2 façons
+        let (grid_handler, grid) = grid!["AABB", "AABB", "CCDD", "CCDD"; stars = 1];
+
+        assert_eq!(count_solutions(&grid_handler, &grid, 10), 2);
+        assert_eq!(count_solutions(&grid_handler, &grid, 1), 1);
+    }
+
+    #[test]
+    fn test_no_solution() {
+        let (grid_handler, mut grid) = get_test_grid();
+
+        // Une étoile dans la région 'B' sur la 1ere ligne empêche de placer l'étoile de la région 'A'
+        grid.cell_mut(LineColumn::new(1, 1)).value = CellValue::Star;
+
+        assert_eq!(count_solutions(&grid_handler, &grid, 2), 0);
+    }
+
+    #[test]
+    fn test_cage() {
+        // Grille 4x4 en 4 régions carrées : une cage des 2 premières cases de la 1ere ligne ne retient qu'une
+        // des 2 solutions
+        let grid_parser = GridParser::try_from(vec!["AABB", "AABB", "CCDD", "CCDD"]).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 1)
+            .with_cage(&[LineColumn::new(0, 0), LineColumn::new(0, 1)], 1);
+        let grid = Grid::from(&grid_handler);
+
+        let solutions = find_solutions(&grid_handler, &grid, 10);
+        assert_eq!(solutions.len(), 1);
+        assert!(solutions[0].cell(LineColumn::new(0, 1)).is_star());
+    }
+
+    #[test]
+    fn test_toroidal() {
+        // Grille 4x4 1★ sans région : 2 solutions, dont les étoiles des bords se touchent en mode 'torique'
+        let grid_parser = GridParser::try_from(vec!["AAAA", "AAAA", "AAAA", "AAAA"]).unwrap();
+        let grid_handler = GridHandler::without_regions(&grid_parser, 1);
+        let grid = Grid::from(&grid_handler);
+        assert_eq!(count_solutions(&grid_handler, &grid, 10), 2);
+
+        let grid_handler = grid_handler.with_toroidal(true);
+        assert_eq!(count_solutions(&grid_handler, &grid, 10), 0);
+    }
+
+    #[test]
+    fn test_large_grid() {
+        // Grille 17x17 3★ : la recherche doit aboutir rapidement sur les grandes grilles
+        let contents = std::fs::read_to_string("./test_grids/test17x17_3.txt").unwrap();
+        let grid_parser = GridParser::try_from(contents.as_str()).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 3);
+        let grid = Grid::from(&grid_handler);
+
+        let solutions = find_solutions(&grid_handler, &grid, 2);
+        assert_eq!(solutions.len(), 2);
+        assert!(solutions
+            .iter()
+            .all(|solution| grid_handler.is_done(solution)));
+    }
+}
@@ -6,6 +6,7 @@ use crate::Region;
 
 /// Case de la grille
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GridCell {
     /// Coordonnées de la case dans la grille
     pub line_column: LineColumn,
@@ -15,6 +16,10 @@ pub struct GridCell {
 
     /// Valeur de la case
     pub value: CellValue,
+
+    /// Étoile donnée par la définition de la grille (indice) : la valeur de la case ne peut pas être modifiée
+    /// par une [`crate::GridAction`]
+    pub given: bool,
 }
 
 impl GridCell {
@@ -35,4 +40,10 @@ impl GridCell {
     pub fn is_star(&self) -> bool {
         self.value == CellValue::Star
     }
+
+    /// Retourne `true` si la case est une étoile donnée par la définition de la grille
+    #[must_use]
+    pub const fn is_given(&self) -> bool {
+        self.given
+    }
 }
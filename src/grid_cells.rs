@@ -0,0 +1,218 @@
+//! Rangement des cases d'une grille.
+//!
+//! Les grilles d'au plus [`SMALL_GRID_SIZE`] x [`SMALL_GRID_SIZE`] cases (les tailles courantes des
+//! puzzles) rangent leurs cases dans un tableau de taille fixe ([`FixedCells`]) : la copie d'une telle
+//! grille, très fréquente pendant la recherche des règles de construction, ne nécessite aucune allocation.
+//! Les grilles plus grandes rangent chaque ligne de cases dans un vecteur.
+//!
+//! Le rangement est choisi automatiquement selon les dimensions de la grille.
+
+use crate::GridCell;
+use crate::LineColumn;
+
+/// Nombre maximum de lignes et de colonnes d'une grille dont les cases sont rangées dans un tableau de
+/// taille fixe
+pub const SMALL_GRID_SIZE: usize = 16;
+
+/// Cases d'une grille d'au plus `N` x `N` cases rangées dans un tableau de taille fixe
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FixedCells<const N: usize> {
+    /// Nombre de lignes de la grille
+    nb_lines: usize,
+
+    /// Nombre de colonnes de la grille
+    nb_columns: usize,
+
+    /// Cases de la grille (les cases au-delà des dimensions de la grille ne sont pas utilisées)
+    cells: [[GridCell; N]; N],
+}
+
+/// Cases d'une grille
+#[derive(Clone, PartialEq, Eq, Hash)]
+// Le tableau de taille fixe n'est volontairement pas alloué sur le tas
+#[allow(clippy::large_enum_variant)]
+pub enum GridCells {
+    /// Grille d'au plus [`SMALL_GRID_SIZE`] x [`SMALL_GRID_SIZE`] cases
+    Small(FixedCells<SMALL_GRID_SIZE>),
+
+    /// Grille plus grande : une ligne de cases par vecteur
+    Large(Vec<Vec<GridCell>>),
+}
+
+impl Default for GridCells {
+    fn default() -> Self {
+        Self::new(0, 0, |_| GridCell::default())
+    }
+}
+
+impl std::fmt::Debug for GridCells {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.lines()).finish()
+    }
+}
+
+impl From<Vec<Vec<GridCell>>> for GridCells {
+    /// Cases d'une grille rangées ligne par ligne (les lignes de longueurs différentes sont conservées
+    /// telles quelles)
+    fn from(lines: Vec<Vec<GridCell>>) -> Self {
+        let nb_columns = lines.first().map_or(0, Vec::len);
+        if lines.len() > SMALL_GRID_SIZE
+            || nb_columns > SMALL_GRID_SIZE
+            || lines
+                .iter()
+                .any(|cells_line| cells_line.len() != nb_columns)
+        {
+            return Self::Large(lines);
+        }
+        Self::new(lines.len(), nb_columns, |line_column| {
+            lines[line_column.line][line_column.column].clone()
+        })
+    }
+}
+
+impl GridCells {
+    /// Constructeur : cases d'une grille de `nb_lines` x `nb_columns` cases définies par `new_cell`
+    pub fn new(
+        nb_lines: usize,
+        nb_columns: usize,
+        mut new_cell: impl FnMut(LineColumn) -> GridCell,
+    ) -> Self {
+        if nb_lines <= SMALL_GRID_SIZE && nb_columns <= SMALL_GRID_SIZE {
+            let mut cells: [[GridCell; SMALL_GRID_SIZE]; SMALL_GRID_SIZE] = Default::default();
+            for (line, cells_line) in cells.iter_mut().enumerate().take(nb_lines) {
+                for (column, cell) in cells_line.iter_mut().enumerate().take(nb_columns) {
+                    *cell = new_cell(LineColumn::new(line, column));
+                }
+            }
+            Self::Small(FixedCells {
+                nb_lines,
+                nb_columns,
+                cells,
+            })
+        } else {
+            Self::Large(
+                (0..nb_lines)
+                    .map(|line| {
+                        (0..nb_columns)
+                            .map(|column| new_cell(LineColumn::new(line, column)))
+                            .collect()
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    /// Case en (line, column)
+    /// # Panics
+    /// Panique si la case est en dehors de la grille
+    pub fn cell(&self, line_column: LineColumn) -> &GridCell {
+        match self {
+            Self::Small(fixed) => {
+                &fixed.cells[..fixed.nb_lines][line_column.line][..fixed.nb_columns]
+                    [line_column.column]
+            }
+            Self::Large(lines) => &lines[line_column.line][line_column.column],
+        }
+    }
+
+    /// Case (mutable) en (line, column)
+    /// # Panics
+    /// Panique si la case est en dehors de la grille
+    pub fn cell_mut(&mut self, line_column: LineColumn) -> &mut GridCell {
+        match self {
+            Self::Small(fixed) => {
+                &mut fixed.cells[..fixed.nb_lines][line_column.line][..fixed.nb_columns]
+                    [line_column.column]
+            }
+            Self::Large(lines) => &mut lines[line_column.line][line_column.column],
+        }
+    }
+
+    /// Nombre de lignes de cases
+    pub fn nb_lines(&self) -> usize {
+        match self {
+            Self::Small(fixed) => fixed.nb_lines,
+            Self::Large(lines) => lines.len(),
+        }
+    }
+
+    /// Cases de la ligne `line`
+    /// # Panics
+    /// Panique si la ligne est en dehors de la grille
+    pub fn line(&self, line: usize) -> &[GridCell] {
+        match self {
+            Self::Small(fixed) => &fixed.cells[..fixed.nb_lines][line][..fixed.nb_columns],
+            Self::Large(lines) => &lines[line],
+        }
+    }
+
+    /// Itérateur sur les lignes de cases de la grille
+    pub fn lines(&self) -> impl Iterator<Item = &[GridCell]> {
+        (0..self.nb_lines()).map(|line| self.line(line))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GridCells {
+    /// Les cases sont sérialisées ligne par ligne, quel que soit leur rangement
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.lines())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GridCells {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Vec<GridCell>>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::CellValue;
+
+    /// Cases d'une grille de `nb_lines` x `nb_columns` cases avec une étoile en (1, 2)
+    fn test_cells(nb_lines: usize, nb_columns: usize) -> GridCells {
+        GridCells::new(nb_lines, nb_columns, |line_column| GridCell {
+            line_column,
+            value: if line_column == LineColumn::new(1, 2) {
+                CellValue::Star
+            } else {
+                CellValue::Unknown
+            },
+            ..GridCell::default()
+        })
+    }
+
+    #[test]
+    fn test_grid_cells() {
+        for (nb_lines, nb_columns, is_small) in [(5, 5, true), (16, 12, true), (17, 17, false)] {
+            let mut cells = test_cells(nb_lines, nb_columns);
+            assert_eq!(matches!(cells, GridCells::Small(_)), is_small);
+            assert_eq!(cells.lines().count(), nb_lines);
+            assert!(cells
+                .lines()
+                .all(|cells_line| cells_line.len() == nb_columns));
+            assert!(cells.cell(LineColumn::new(1, 2)).is_star());
+
+            let last = LineColumn::new(nb_lines - 1, nb_columns - 1);
+            cells.cell_mut(last).value = CellValue::NoStar;
+            assert_eq!(cells.cell(last).line_column, last);
+            assert_eq!(cells.cell(last).value, CellValue::NoStar);
+
+            // Même rangement depuis les lignes de cases
+            let lines: Vec<Vec<GridCell>> = cells.lines().map(<[GridCell]>::to_vec).collect();
+            assert_eq!(GridCells::from(lines), cells);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_grid_cells_outside() {
+        // Case en dehors de la grille mais dans le tableau de taille fixe
+        let cells = test_cells(5, 5);
+        let _ = cells.cell(LineColumn::new(2, 6));
+    }
+}
@@ -0,0 +1,394 @@
+//! Points de reprise d'une longue résolution.
+//!
+//! Pendant la résolution d'une grille, l'état de la grille et la trace des règles appliquées sont
+//! régulièrement écrits dans un fichier de reprise (voir [`CheckpointConfig`]). Après une interruption,
+//! la résolution reprend depuis ce fichier sans refaire les règles déjà appliquées.
+//!
+//! Le fichier de reprise est un fichier texte :
+//!
+//! ```text
+//! # Point de reprise star_battle
+//! stars: 2
+//! state: *-??-*...
+//! step: 0 Les cases adjacentes à l'étoile en A1 ne peuvent pas contenir une étoile : B1->Pas d'étoile
+//! grid:
+//! AABBCDDEE
+//! ...
+//! ```
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::grid_solve::{each_rule, solve_from};
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::SolveOutcome;
+use crate::SolverConfig;
+
+/// Intervalle par défaut entre 2 écritures du fichier de reprise
+pub const DEFAULT_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Entête d'un fichier de reprise
+const CHECKPOINT_HEADER: &str = "# Point de reprise star_battle";
+
+/// Erreur lors de la lecture ou de l'écriture d'un point de reprise
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CheckpointError {
+    /// Le fichier de reprise ne peut pas être lu
+    #[error("Erreur lecture du fichier {0}: {1}")]
+    Read(String, String),
+
+    /// Le fichier de reprise ne peut pas être écrit
+    #[error("Erreur écriture du fichier {0}: {1}")]
+    Write(String, String),
+
+    /// Le contenu n'est pas un point de reprise valide
+    #[error("Point de reprise invalide : {0}")]
+    Parse(String),
+}
+
+/// Configuration de l'écriture des points de reprise pendant une résolution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckpointConfig {
+    /// Fichier de reprise
+    path: PathBuf,
+
+    /// Intervalle minimum entre 2 écritures du fichier de reprise
+    interval: Duration,
+}
+
+impl CheckpointConfig {
+    /// Constructeur : points de reprise dans le fichier `path` toutes les
+    /// [`DEFAULT_CHECKPOINT_INTERVAL`]
+    #[must_use]
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            interval: DEFAULT_CHECKPOINT_INTERVAL,
+        }
+    }
+
+    /// Définit l'intervalle minimum entre 2 écritures du fichier de reprise (`Duration::ZERO` pour écrire
+    /// après chaque règle appliquée)
+    #[must_use]
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Fichier de reprise
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Intervalle minimum entre 2 écritures du fichier de reprise
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// Point de reprise d'une résolution : grille, état de la grille et trace des règles déjà appliquées
+#[derive(Clone, Debug)]
+pub struct Checkpoint {
+    /// Définition de la grille
+    pub parser: GridParser,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    pub nb_stars: usize,
+
+    /// État de la grille (voir [`Grid::encode`])
+    pub state: String,
+
+    /// Niveau et texte des règles déjà appliquées (dans la langue de la configuration de la résolution
+    /// qui les a appliquées, voir [`crate::SolverConfig::with_locale`])
+    pub trace: Vec<(usize, String)>,
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{CHECKPOINT_HEADER}")?;
+        writeln!(f, "stars: {}", self.nb_stars)?;
+        writeln!(f, "state: {}", self.state)?;
+        for (level, rule) in &self.trace {
+            writeln!(f, "step: {level} {rule}")?;
+        }
+        writeln!(f, "grid:")?;
+        writeln!(f, "{}", self.parser)
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = CheckpointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = |message: &str| CheckpointError::Parse(message.to_string());
+
+        let mut lines = s.lines();
+        if lines.next().map(str::trim) != Some(CHECKPOINT_HEADER) {
+            return Err(parse_error("entête absente"));
+        }
+        let (mut nb_stars, mut state, mut trace) = (None, None, Vec::new());
+        for line in lines.by_ref() {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(parse_error(&format!("ligne '{line}' inattendue")));
+            };
+            let value = value.trim();
+            match key {
+                "stars" => {
+                    nb_stars = Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| parse_error("nombre d'étoiles invalide"))?,
+                    );
+                }
+                "state" => state = Some(value.to_string()),
+                "step" => {
+                    let (level, rule) = value.split_once(' ').unwrap_or((value, ""));
+                    let level = level
+                        .parse::<usize>()
+                        .map_err(|_| parse_error(&format!("niveau de règle '{level}' invalide")))?;
+                    trace.push((level, rule.to_string()));
+                }
+                "grid" => break,
+                _ => return Err(parse_error(&format!("ligne '{line}' inattendue"))),
+            }
+        }
+        let parser = GridParser::try_from(lines.collect::<Vec<_>>().join("\n").as_str())
+            .map_err(CheckpointError::Parse)?;
+        let checkpoint = Self {
+            parser,
+            nb_stars: nb_stars.ok_or_else(|| parse_error("nombre d'étoiles absent"))?,
+            state: state.ok_or_else(|| parse_error("état de la grille absent"))?,
+            trace,
+        };
+        checkpoint.grid()?;
+        Ok(checkpoint)
+    }
+}
+
+impl Checkpoint {
+    /// Point de reprise au début de la résolution de la grille `parser` avec `nb_stars` étoiles par ligne,
+    /// colonne et région
+    /// # Panics
+    /// Panic si la grille est invalide avec ce nombre d'étoiles (voir [`Checkpoint::try_new`])
+    #[must_use]
+    pub fn new(parser: &GridParser, nb_stars: usize) -> Self {
+        Self::try_new(parser, nb_stars).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Point de reprise au début de la résolution de la grille `parser` avec `nb_stars` étoiles par ligne,
+    /// colonne et région
+    /// ### Errors
+    /// Retourne un [`CheckpointError::Parse`] si la grille est invalide avec ce nombre d'étoiles (voir
+    /// [`GridHandler::try_new`])
+    pub fn try_new(parser: &GridParser, nb_stars: usize) -> Result<Self, CheckpointError> {
+        let handler = GridHandler::try_new(parser, nb_stars).map_err(CheckpointError::Parse)?;
+        Ok(Self {
+            parser: parser.clone(),
+            nb_stars,
+            state: Grid::from(&handler).encode(),
+            trace: Vec::new(),
+        })
+    }
+
+    /// Grille et état de la grille du point de reprise
+    fn grid(&self) -> Result<(GridHandler, Grid), CheckpointError> {
+        let handler =
+            GridHandler::try_new(&self.parser, self.nb_stars).map_err(CheckpointError::Parse)?;
+        let grid = Grid::decode(&handler, &self.state).map_err(CheckpointError::Parse)?;
+        Ok((handler, grid))
+    }
+
+    /// Lecture du point de reprise du fichier `path`
+    /// ### Errors
+    /// Retourne un [`CheckpointError`] si le fichier ne peut pas être lu ou n'est pas un point de reprise
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        let path = path.as_ref();
+        std::fs::read_to_string(path)
+            .map_err(|e| CheckpointError::Read(path.display().to_string(), e.to_string()))?
+            .parse()
+    }
+
+    /// Écriture du point de reprise dans le fichier `path`.<br>
+    /// Le fichier est écrit dans un fichier temporaire puis renommé pour qu'une interruption pendant
+    /// l'écriture ne perde pas le point de reprise précédent
+    /// ### Errors
+    /// Retourne un [`CheckpointError`] si le fichier ne peut pas être écrit
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let path = path.as_ref();
+        let write_error =
+            |e: std::io::Error| CheckpointError::Write(path.display().to_string(), e.to_string());
+        let mut tmp_path = path.as_os_str().to_os_string();
+        tmp_path.push(".tmp");
+        std::fs::write(&tmp_path, self.to_string()).map_err(write_error)?;
+        std::fs::rename(&tmp_path, path).map_err(write_error)
+    }
+
+    /// Résolution de la grille à partir du point de reprise selon la configuration `config` en écrivant
+    /// régulièrement un nouveau point de reprise selon `checkpoint_config`.<br>
+    /// Le résultat ne contient que les règles appliquées depuis le point de reprise (voir
+    /// [`Checkpoint::trace`] pour les précédentes)
+    /// ### Errors
+    /// Retourne un [`CheckpointError`] si la grille ou son état dans le point de reprise ne sont pas valides
+    /// ou si le fichier de reprise ne peut pas être écrit
+    pub fn solve(
+        &self,
+        config: &SolverConfig,
+        checkpoint_config: &CheckpointConfig,
+    ) -> Result<SolveOutcome, CheckpointError> {
+        self.solve_with_steps(config, checkpoint_config, |_, _, _| {})
+    }
+
+    /// Reprise de la résolution (voir [`Checkpoint::solve`]) : `on_rule` est appelé dès qu'une règle est
+    /// trouvée, avec la grille à laquelle elle s'applique (avant son application)
+    /// ### Errors
+    /// Retourne un [`CheckpointError`] dans les mêmes cas que [`Checkpoint::solve`]
+    pub fn solve_with_steps(
+        &self,
+        config: &SolverConfig,
+        checkpoint_config: &CheckpointConfig,
+        on_rule: impl FnMut(&GridHandler, &Grid, &GoodRule),
+    ) -> Result<SolveOutcome, CheckpointError> {
+        let (handler, grid) = self.grid()?;
+
+        let mut on_step = each_rule(&handler, &grid, on_rule);
+        let mut last_save = Instant::now();
+        let mut save_error = None;
+        let outcome = solve_from(handler, grid, config, |grid, steps| {
+            on_step(grid, steps);
+            if last_save.elapsed() < checkpoint_config.interval() {
+                return true;
+            }
+            let mut checkpoint = self.clone();
+            checkpoint.state = grid.encode();
+            checkpoint.trace.extend(
+                steps
+                    .iter()
+                    .map(|(good_rule, level)| (*level, good_rule.text(config.locale()))),
+            );
+            last_save = Instant::now();
+            match checkpoint.save(checkpoint_config.path()) {
+                Ok(()) => true,
+                Err(e) => {
+                    save_error = Some(e);
+                    false
+                }
+            }
+        });
+        save_error.map_or(Ok(outcome), Err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::solve_grid;
+
+    #[test]
+    fn test_checkpoint_text() {
+        let parser =
+            GridParser::try_from("# title: Exemple\nABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED\n* A1")
+                .unwrap();
+        let mut checkpoint = Checkpoint::new(&parser, 1);
+        checkpoint
+            .trace
+            .push((3, "Règle : A2->Pas d'étoile".to_string()));
+        let text = checkpoint.to_string();
+        let parsed: Checkpoint = text.parse().unwrap();
+        assert_eq!(parsed.to_string(), text);
+        assert_eq!(parsed.nb_stars, 1);
+        assert_eq!(parsed.state, checkpoint.state);
+        assert_eq!(parsed.trace, checkpoint.trace);
+        assert_eq!(parsed.parser.given_stars(), parser.given_stars());
+        assert_eq!(parsed.parser.metadata(), parser.metadata());
+
+        assert!("stars: 1".parse::<Checkpoint>().is_err());
+        assert!(format!("{CHECKPOINT_HEADER}\nstars: x\ngrid:\nAB")
+            .parse::<Checkpoint>()
+            .is_err());
+        assert!(format!("{CHECKPOINT_HEADER}\nstars: 1\ngrid:\nABBBB")
+            .parse::<Checkpoint>()
+            .is_err());
+
+        // Nombre d'étoiles ou état incompatibles avec la grille (case bloquée en C1)
+        let grid = "grid:\nAB.BB\nABBBB\nCCBBB\nDDDDD\nDEEED";
+        let state = format!("??-{}", "?".repeat(22));
+        let unknown_blocked = "?".repeat(25);
+        for (nb_stars, state) in [(0, &state), (3, &state), (1, &unknown_blocked)] {
+            let text = format!("{CHECKPOINT_HEADER}\nstars: {nb_stars}\nstate: {state}\n{grid}");
+            assert!(matches!(
+                text.parse::<Checkpoint>(),
+                Err(CheckpointError::Parse(_))
+            ));
+        }
+        let text = format!("{CHECKPOINT_HEADER}\nstars: 1\nstate: {state}\n{grid}");
+        assert!(text.parse::<Checkpoint>().is_ok());
+        assert!(Checkpoint::try_new(&parser, 3).is_err());
+        let mut checkpoint = Checkpoint::new(&parser, 1);
+        checkpoint.nb_stars = 3;
+        let config = CheckpointConfig::new(std::env::temp_dir().join("star_battle_invalid_cp.txt"));
+        assert!(matches!(
+            checkpoint.solve(&SolverConfig::default(), &config),
+            Err(CheckpointError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_checkpoint_resume() {
+        let path = std::env::temp_dir().join("star_battle_test_checkpoint.txt");
+        let _ = std::fs::remove_file(&path);
+        let contents = std::fs::read_to_string("./test_grids/facile01_2.txt").unwrap();
+        let parser = GridParser::try_from(contents.as_str()).unwrap();
+        let config = SolverConfig::default();
+        let checkpoint_config = CheckpointConfig::new(&path).with_interval(Duration::ZERO);
+
+        // Résolution complète avec un point de reprise après chaque règle
+        let outcome = Checkpoint::new(&parser, 2)
+            .solve(&config, &checkpoint_config)
+            .unwrap();
+        let expected = solve_grid(&parser, 2, &config).unwrap();
+        assert!(outcome.is_solved());
+        assert_eq!(outcome.grid, expected.grid);
+        let checkpoint = Checkpoint::load(&path).unwrap();
+        assert_eq!(checkpoint.trace.len(), expected.steps.len());
+        assert_eq!(checkpoint.state, expected.grid.encode());
+
+        // Reprise à mi-parcours
+        let mut checkpoint = Checkpoint::new(&parser, 2);
+        let mut grid = Grid::from(&expected.handler);
+        let half = expected.steps.len() / 2;
+        for (good_rule, level) in &expected.steps[..half] {
+            grid.apply_good_rule(good_rule);
+            checkpoint.trace.push((*level, good_rule.to_string()));
+        }
+        checkpoint.state = grid.encode();
+        checkpoint.save(&path).unwrap();
+        let resumed = Checkpoint::load(&path)
+            .unwrap()
+            .solve(&config, &checkpoint_config)
+            .unwrap();
+        assert_eq!(resumed.initial_grid(), grid);
+        assert_eq!(resumed.grid, expected.grid);
+        assert_eq!(resumed.steps.len(), expected.steps.len() - half);
+        assert_eq!(
+            Checkpoint::load(&path).unwrap().trace.len(),
+            expected.steps.len()
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        // Fichier de reprise impossible à écrire
+        let bad_config = CheckpointConfig::new(std::env::temp_dir().join("inconnu").join("cp.txt"))
+            .with_interval(Duration::ZERO);
+        assert!(matches!(
+            Checkpoint::new(&parser, 2).solve(&config, &bad_config),
+            Err(CheckpointError::Write(..))
+        ));
+    }
+}
@@ -0,0 +1,135 @@
+//! Contrôle croisé des règles de construction avec la recherche exhaustive des solutions.
+//!
+//! Une règle de construction est une déduction : chacune de ses actions doit être vérifiée par toutes les
+//! solutions de la grille. Ce module résout une grille avec les règles de [`crate::get_good_rule`] et
+//! confronte chaque action à l'ensemble complet des solutions trouvées par 'force brute' (voir
+//! [`crate::find_solutions`]). C'est le filet de sécurité le plus sûr pour valider une nouvelle règle.
+
+use crate::find_solutions;
+use crate::get_good_rule;
+use crate::BadRuleError;
+use crate::CellValue;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+
+/// Incohérence entre les règles de construction et les solutions d'une grille
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CrossCheckError {
+    /// La grille n'a aucune solution : il n'y a rien à contrôler
+    #[error("La grille n'a aucune solution")]
+    NoSolution,
+
+    /// La grille a trop de solutions pour les examiner toutes
+    #[error("La grille a plus de {0} solutions")]
+    TooManySolutions(usize),
+
+    /// Les règles déclarent la grille invalide alors qu'elle a des solutions
+    #[error("Étape #{0} : grille déclarée invalide alors qu'elle a des solutions : {1}")]
+    BadRule(usize, BadRuleError),
+
+    /// Une action d'une règle de construction contredit une solution de la grille
+    #[error("Étape #{step} : l'action '{action}' de la règle '{rule}' contredit une solution de la grille")]
+    Unsound {
+        /// Numéro (à partir de 1) de la règle dans la résolution
+        step: usize,
+
+        /// Texte de la règle de construction
+        rule: String,
+
+        /// Action de la règle contredite par une solution
+        action: GridAction,
+    },
+}
+
+/// Résout la grille `grid` avec les règles de construction et vérifie que chaque action de chaque règle
+/// est conforme à toutes les solutions de la grille (au plus `max_solutions` solutions).<br>
+/// Retourne le nombre de règles appliquées et contrôlées.
+/// ### Errors
+/// Retourne un [`CrossCheckError`] si la grille n'a pas de solution, a plus de `max_solutions` solutions
+/// ou si une règle de construction contredit une solution
+pub fn cross_check_rules(
+    handler: &GridHandler,
+    grid: &Grid,
+    max_solutions: usize,
+) -> Result<usize, CrossCheckError> {
+    // Ensemble complet des solutions (inchangé par l'application de déductions correctes)
+    let solutions = find_solutions(handler, grid, max_solutions.saturating_add(1));
+    if solutions.is_empty() {
+        return Err(CrossCheckError::NoSolution);
+    }
+    if solutions.len() > max_solutions {
+        return Err(CrossCheckError::TooManySolutions(max_solutions));
+    }
+
+    let mut grid = grid.clone();
+    let mut nb_steps = 0;
+    loop {
+        let good_rule = match get_good_rule(handler, &grid) {
+            Ok(Some(good_rule)) => good_rule,
+            Ok(None) => return Ok(nb_steps),
+            Err(bad_rule) => return Err(CrossCheckError::BadRule(nb_steps + 1, bad_rule)),
+        };
+        nb_steps += 1;
+        for action in good_rule.actions() {
+            let is_sound = |solution: &Grid| match action {
+                GridAction::SetStar(line_column) => solution.cell(*line_column).is_star(),
+                GridAction::SetNoStar(line_column) => {
+                    solution.cell(*line_column).value == CellValue::NoStar
+                }
+                GridAction::SetUnknown(_) | GridAction::Toggle(_) => false,
+            };
+            if !solutions.iter().all(is_sound) {
+                return Err(CrossCheckError::Unsound {
+                    step: nb_steps,
+                    rule: good_rule.to_string(),
+                    action: action.clone(),
+                });
+            }
+        }
+        grid.apply_good_rule(&good_rule);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GeneratorConfig;
+    use crate::GridParser;
+    use crate::LineColumn;
+
+    #[test]
+    fn test_cross_check_generated_puzzles() {
+        for (size, nb_stars, seed) in [(5, 1, 1), (6, 1, 2), (7, 1, 3), (8, 1, 4), (9, 2, 5)] {
+            let puzzle = GeneratorConfig::new(size, nb_stars, seed)
+                .generate()
+                .unwrap();
+            let handler = GridHandler::new(&puzzle.parser, puzzle.nb_stars);
+            let nb_steps = cross_check_rules(&handler, &Grid::from(&handler), 1).unwrap();
+            assert!(nb_steps > 0);
+        }
+    }
+
+    #[test]
+    fn test_cross_check_several_solutions() {
+        // Grille sans région à plusieurs solutions : les déductions valent pour toutes les solutions
+        let grid_parser =
+            GridParser::try_from(vec!["AAAAA", "AAAAA", "AAAAA", "AAAAA", "AAAAA"]).unwrap();
+        let handler = GridHandler::without_regions(&grid_parser, 1);
+        let mut grid = Grid::from(&handler);
+        grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::Star;
+        assert!(cross_check_rules(&handler, &grid, 100).is_ok());
+        assert_eq!(
+            cross_check_rules(&handler, &Grid::from(&handler), 2),
+            Err(CrossCheckError::TooManySolutions(2))
+        );
+
+        // Grille sans solution
+        grid.cell_mut(LineColumn::new(1, 1)).value = CellValue::Star;
+        assert_eq!(
+            cross_check_rules(&handler, &grid, 100),
+            Err(CrossCheckError::NoSolution)
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Recherche des grilles en double dans un répertoire.
+//!
+//! Les recueils de grilles téléchargés contiennent souvent plusieurs fois la même grille, tournée, retournée
+//! ou avec des régions renommées. Chaque grille d'un répertoire est ramenée à sa forme canonique (voir
+//! [`GridParser::canonical`]) et les fichiers de même forme canonique sont regroupés.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::GridParser;
+
+/// Extension des fichiers de grilles examinés dans un répertoire
+pub const GRID_FILE_EXTENSION: &str = "txt";
+
+/// Erreur lors de la recherche des grilles en double dans un répertoire
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum DedupeError {
+    /// Le répertoire ne peut pas être lu
+    #[error("Erreur lecture du répertoire {0}: {1}")]
+    ReadDir(String, String),
+
+    /// Un fichier en double ne peut pas être supprimé
+    #[error("Erreur suppression du fichier {0}: {1}")]
+    Remove(String, String),
+}
+
+/// Résultat de la recherche des grilles en double dans un répertoire
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DedupeReport {
+    /// Nombre de grilles valides examinées
+    pub nb_grids: usize,
+
+    /// Groupes de fichiers (au moins 2) de grilles isomorphes, triés par nom de fichier
+    pub duplicates: Vec<Vec<PathBuf>>,
+
+    /// Fichiers qui ne contiennent pas une grille valide avec l'erreur correspondante
+    pub invalid: Vec<(PathBuf, String)>,
+}
+
+impl DedupeReport {
+    /// Fichiers en double : tous les fichiers de chaque groupe de grilles isomorphes sauf le premier
+    #[must_use]
+    pub fn redundant_files(&self) -> Vec<&PathBuf> {
+        self.duplicates
+            .iter()
+            .flat_map(|files| files.iter().skip(1))
+            .collect()
+    }
+
+    /// Supprime les fichiers en double (voir [`DedupeReport::redundant_files`]) et retourne leur nombre
+    /// ### Errors
+    /// Retourne un [`DedupeError`] si un fichier ne peut pas être supprimé
+    pub fn remove_redundant_files(&self) -> Result<usize, DedupeError> {
+        let files = self.redundant_files();
+        for file in &files {
+            std::fs::remove_file(file)
+                .map_err(|e| DedupeError::Remove(file.display().to_string(), e.to_string()))?;
+        }
+        Ok(files.len())
+    }
+}
+
+/// Liste triée des fichiers de grilles (extension [`GRID_FILE_EXTENSION`]) du répertoire `dir`
+/// ### Errors
+/// Retourne une erreur si le répertoire ne peut pas être lu
+pub fn list_grid_files(dir: impl AsRef<Path>) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|ext| ext == GRID_FILE_EXTENSION)
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Recherche des grilles isomorphes parmi les fichiers de grilles (extension [`GRID_FILE_EXTENSION`]) du
+/// répertoire `dir`
+/// ### Errors
+/// Retourne un [`DedupeError`] si le répertoire ne peut pas être lu
+pub fn find_duplicates(dir: impl AsRef<Path>) -> Result<DedupeReport, DedupeError> {
+    let dir = dir.as_ref();
+    let files = list_grid_files(dir)
+        .map_err(|e| DedupeError::ReadDir(dir.display().to_string(), e.to_string()))?;
+
+    let mut report = DedupeReport::default();
+    let mut groups: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for file in files {
+        let parser = std::fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|file_contents| GridParser::try_from(file_contents.as_str()));
+        match parser {
+            Ok(parser) => {
+                report.nb_grids += 1;
+                groups
+                    .entry(parser.canonical().to_string())
+                    .or_default()
+                    .push(file);
+            }
+            Err(e) => report.invalid.push((file, e)),
+        }
+    }
+    report.duplicates = groups
+        .into_values()
+        .filter(|files| files.len() > 1)
+        .collect();
+    report.duplicates.sort();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridTransform;
+
+    #[test]
+    fn test_find_duplicates() {
+        let dir = std::env::temp_dir().join("star_battle_test_dedupe");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let write = |name: &str, contents: &str| std::fs::write(dir.join(name), contents).unwrap();
+        write("a.txt", &parser.to_string());
+        write("b.txt", "xyyyy\nxyyyy\nzzyyy\nwwwww\nwvvvw");
+        write(
+            "c.txt",
+            &parser.transform(GridTransform::Rotate270).to_string(),
+        );
+        write("d.txt", "AABBB\nABBBB\nCCBBB\nDDDDD\nDEEED");
+        write("e.txt", "ABB\nAB");
+        write("f.md", &parser.to_string());
+
+        let report = find_duplicates(&dir).unwrap();
+        assert_eq!(report.nb_grids, 4);
+        assert_eq!(
+            report.duplicates,
+            vec![vec![
+                dir.join("a.txt"),
+                dir.join("b.txt"),
+                dir.join("c.txt")
+            ]]
+        );
+        assert_eq!(report.invalid.len(), 1);
+        assert_eq!(report.invalid[0].0, dir.join("e.txt"));
+
+        assert_eq!(report.remove_redundant_files(), Ok(2));
+        assert!(dir.join("a.txt").exists());
+        assert!(!dir.join("b.txt").exists());
+        assert!(find_duplicates(&dir).unwrap().duplicates.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(
+            find_duplicates(&dir),
+            Err(DedupeError::ReadDir(..))
+        ));
+    }
+}
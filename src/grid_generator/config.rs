@@ -0,0 +1,217 @@
+//! Paramètres de génération d'une grille.
+//!
+//! Une [`GeneratorConfig`] regroupe tous les paramètres d'une génération, y compris la 'graine' du
+//! générateur aléatoire : la même configuration produit toujours la même grille, ce qui permet de
+//! régénérer un ensemble de grilles à partir de leurs seules configurations.
+//!
+//! [`GeneratorConfig::generate_files`] écrit une série de grilles dans des fichiers numérotés dont les
+//! commentaires indiquent la graine de chaque grille.
+
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+use crate::rate_grid;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridTransform;
+use crate::PuzzleMetadata;
+
+use super::generator::{
+    GeneratedPuzzle, GeneratorError, GridGenerator, RegionStyle, DEFAULT_MAX_ATTEMPTS,
+};
+
+/// Paramètres de génération d'une grille
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratorConfig {
+    /// Nombre de lignes et de colonnes de la grille
+    pub size: usize,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    pub nb_stars: usize,
+
+    /// Graine du générateur aléatoire
+    pub seed: u64,
+
+    /// Style des régions
+    pub region_style: RegionStyle,
+
+    /// Nombre de cases minimum et maximum de chaque région
+    pub region_size_range: RangeInclusive<usize>,
+
+    /// Sinuosité (périmètre / aire) maximum de chaque région (si limitée)
+    pub max_snakiness: Option<f64>,
+
+    /// Symétrie de la grille ([`GridTransform::Identity`] pour une grille sans symétrie)
+    pub symmetry: GridTransform,
+
+    /// Score de qualité minimum de la grille (si demandé, voir [`crate::puzzle_quality`])
+    pub min_quality: Option<f64>,
+
+    /// Nombre maximum de tentatives pour générer la grille
+    pub max_attempts: usize,
+}
+
+impl GeneratorConfig {
+    /// Constructeur des paramètres de génération d'une grille de `size` x `size` cases avec `nb_stars`
+    /// étoiles à placer dans chaque ligne, colonne et région à partir de la graine `seed`.<br>
+    /// Les régions n'ont pas de contrainte de forme et la grille n'a pas de symétrie.
+    #[must_use]
+    pub const fn new(size: usize, nb_stars: usize, seed: u64) -> Self {
+        Self {
+            size,
+            nb_stars,
+            seed,
+            region_style: RegionStyle::StarBattle,
+            region_size_range: 1..=size * size,
+            max_snakiness: None,
+            symmetry: GridTransform::Identity,
+            min_quality: None,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Générateur correspondant à ces paramètres
+    #[must_use]
+    pub fn generator(&self) -> GridGenerator {
+        let generator = GridGenerator::new(self.size, self.nb_stars)
+            .with_max_attempts(self.max_attempts)
+            .with_symmetry(self.symmetry)
+            .with_region_style(self.region_style)
+            .with_region_size_range(self.region_size_range.clone());
+        let generator = match self.max_snakiness {
+            Some(max_snakiness) => generator.with_max_snakiness(max_snakiness),
+            None => generator,
+        };
+        match self.min_quality {
+            Some(min_quality) => generator.with_min_quality(min_quality),
+            None => generator,
+        }
+    }
+
+    /// Génère la grille correspondant à ces paramètres.<br>
+    /// Le résultat ne dépend que des paramètres : il est identique d'un appel à l'autre.
+    /// ### Errors
+    /// Retourne un [`GeneratorError`] si les paramètres sont incohérents ou si aucune grille n'a été
+    /// trouvée dans le nombre de tentatives autorisées
+    pub fn generate(&self) -> Result<GeneratedPuzzle, GeneratorError> {
+        self.generator().generate_with_seed(self.seed)
+    }
+
+    /// Génère `count` grilles dans les fichiers numérotés `grid_<numéro>.txt` du répertoire `dir` (créé si
+    /// nécessaire) : chaque grille est générée avec ces paramètres à partir de la graine `seed` + son rang
+    /// (voir [`GeneratorConfig::puzzle_file_contents`]).<br>
+    /// `on_file` est appelé dès qu'un fichier est écrit. Retourne la liste des fichiers écrits
+    /// ### Errors
+    /// Retourne un [`GeneratorError`] si une grille ne peut pas être générée ou si un fichier ne peut pas être
+    /// écrit
+    pub fn generate_files(
+        &self,
+        count: usize,
+        dir: impl AsRef<Path>,
+        mut on_file: impl FnMut(&Path),
+    ) -> Result<Vec<PathBuf>, GeneratorError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)
+            .map_err(|e| GeneratorError::Write(dir.display().to_string(), e.to_string()))?;
+
+        let width = count.to_string().len();
+        let mut files = Vec::with_capacity(count);
+        for index in 0..count {
+            let config = Self {
+                seed: self.seed.wrapping_add(index as u64),
+                ..self.clone()
+            };
+            let puzzle = config.generate()?;
+            let file_name = dir.join(format!("grid_{:0width$}.txt", index + 1));
+            std::fs::write(&file_name, config.puzzle_file_contents(&puzzle)).map_err(|e| {
+                GeneratorError::Write(file_name.display().to_string(), e.to_string())
+            })?;
+            on_file(&file_name);
+            files.push(file_name);
+        }
+        Ok(files)
+    }
+
+    /// Contenu du fichier de la grille `puzzle` générée avec ces paramètres : un commentaire avec la graine
+    /// (pour pouvoir régénérer la grille) suivi de la définition de la grille avec ses métadonnées (titre,
+    /// difficulté évaluée par [`rate_grid`] et nombre d'étoiles)
+    #[must_use]
+    pub fn puzzle_file_contents(&self, puzzle: &GeneratedPuzzle) -> String {
+        let handler = GridHandler::new(&puzzle.parser, puzzle.nb_stars);
+        let difficulty = match rate_grid(&handler, &Grid::from(&handler))
+            .ok()
+            .and_then(|rating| rating.difficulty())
+        {
+            Some(difficulty) => difficulty.to_string(),
+            None => "non résolue".to_string(),
+        };
+        let metadata = PuzzleMetadata {
+            title: Some(format!(
+                "Grille {size}x{size} {nb_stars}★ générée par star_battle",
+                size = self.size,
+                nb_stars = self.nb_stars,
+            )),
+            difficulty: Some(difficulty),
+            nb_stars: Some(self.nb_stars),
+            ..PuzzleMetadata::default()
+        };
+        format!(
+            "# seed: {seed}\n{parser}\n",
+            seed = self.seed,
+            parser = puzzle.parser.clone().with_metadata(metadata),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_deterministic() {
+        let mut config = GeneratorConfig::new(8, 1, 42);
+        config.region_size_range = 3..=16;
+        config.max_attempts = 1_000;
+        let puzzle = config.generate().unwrap();
+        assert_eq!(
+            puzzle.parser.list_cells(),
+            config.generate().unwrap().parser.list_cells()
+        );
+        for region in puzzle.parser.regions() {
+            assert!(config
+                .region_size_range
+                .contains(&puzzle.parser.region_cells(region).len()));
+        }
+
+        // Une autre graine donne (en principe) une autre grille
+        config.seed = 43;
+        assert_ne!(
+            puzzle.parser.list_cells(),
+            config.generate().unwrap().parser.list_cells()
+        );
+    }
+
+    #[test]
+    fn test_generate_files() {
+        let dir = std::env::temp_dir().join("star_battle_test_generate_files");
+        let mut written = Vec::new();
+        let files = GeneratorConfig::new(6, 1, 7)
+            .generate_files(2, &dir, |file| written.push(file.to_path_buf()))
+            .unwrap();
+        assert_eq!(files, vec![dir.join("grid_1.txt"), dir.join("grid_2.txt")]);
+        assert_eq!(written, files);
+
+        for (file, seed) in files.iter().zip([7, 8]) {
+            let file_contents = std::fs::read_to_string(file).unwrap();
+            let lines: Vec<&str> = file_contents.lines().collect();
+            assert!(lines.contains(&format!("# seed: {seed}").as_str()));
+            assert!(lines.contains(&"# stars: 1"));
+            let grid_parsed = crate::GridParser::try_from(file_contents.as_str()).unwrap();
+            assert_eq!(grid_parsed.metadata().nb_stars, Some(1));
+            assert!(grid_parsed.metadata().title.is_some());
+            let puzzle = GeneratorConfig::new(6, 1, seed).generate().unwrap();
+            assert_eq!(grid_parsed.list_cells(), puzzle.parser.list_cells());
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,1111 @@
+//! Générateur de grilles à solution unique.
+//!
+//! Chaque tentative de génération se déroule comme suit :
+//!
+//! * Placement aléatoire des étoiles de la 'solution' (`nb_stars` par ligne et colonne, sans adjacence)
+//! * Découpage aléatoire de la grille en régions qui contiennent chacune `nb_stars` étoiles
+//! * Comptage des solutions de la grille ainsi obtenue par recherche exhaustive
+//!
+//! Si la grille a plusieurs solutions, on tente de la 'réparer' : une case qui contient une étoile
+//! dans une autre solution (mais pas dans la solution initiale) est déplacée dans une région voisine.
+//! Cette autre solution devient alors invalide alors que la solution initiale reste valide.<br>
+//! Si la réparation n'aboutit pas, la grille est rejetée et une nouvelle tentative est faite.
+//!
+//! Avec une symétrie (voir [`GridGenerator::with_symmetry`]), le placement des étoiles, le découpage en
+//! régions et les réparations se font en même temps sur une case et toutes ses images.
+//!
+//! Le style des régions (voir [`GridGenerator::with_region_style`]) change la façon dont les régions
+//! grandissent autour des étoiles, par exemple pour obtenir des grilles à la façon du jeu Queens.
+//!
+//! Avec une difficulté cible (voir [`GridGenerator::with_target_difficulty`]), la grille à solution
+//! unique est ensuite 'affinée' : des cases en bordure de région sont déplacées dans une région voisine
+//! tant que la grille reste à solution unique et que sa résolution ne devient pas plus facile, jusqu'à
+//! atteindre la difficulté cible.
+//!
+//! Avec un palier de technique cible (voir [`GridGenerator::with_target_technique`]), la grille est affinée
+//! de la même façon jusqu'à ce que sa résolution nécessite une règle de ce palier sans nécessiter de
+//! règle d'un palier supérieur.
+//!
+//! Avec une qualité minimum (voir [`GridGenerator::with_min_quality`]), les grilles dont le score de
+//! [`puzzle_quality`] est insuffisant sont rejetées.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::grid_brute_force::find_solutions;
+use crate::line_column::{display_column, display_line};
+use crate::puzzle_quality;
+use crate::rate_grid;
+use crate::Difficulty;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::GridRating;
+use crate::GridSurfer;
+use crate::GridTransform;
+use crate::LineColumn;
+use crate::Region;
+use crate::Technique;
+
+use super::region_carver::{carve_regions, orthogonal_neighbors, RegionGrowth};
+use super::region_shape::{region_sizes, region_snakiness};
+use super::star_placement::random_star_placement;
+use super::symmetry::{image, orbit};
+
+/// Symboles utilisés pour identifier les régions d'une grille générée
+pub(crate) const REGION_CHARS: &str =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Nombre de tirages pour choisir chaque case qui agrandit un bloc
+const COMPACTNESS: usize = 3;
+
+/// Nombre de tirages pour choisir chaque case qui agrandit un bloc en 'bandes' (style Queens)
+const STRAIGHTNESS: usize = 4;
+
+/// Nombre maximum de tentatives par défaut
+pub const DEFAULT_MAX_ATTEMPTS: usize = 100;
+
+/// Erreur lors de la génération d'une grille
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GeneratorError {
+    /// Paramètres de génération incohérents
+    #[error("Paramètres de génération invalides : {0}")]
+    InvalidParameters(String),
+
+    /// Aucune grille à solution unique n'a été trouvée
+    #[error("Aucune grille à solution unique trouvée après {0} tentatives")]
+    TooManyAttempts(usize),
+
+    /// Le fichier d'une grille générée (ou son répertoire) ne peut pas être écrit (voir
+    /// [`crate::GeneratorConfig::generate_files`])
+    #[error("Erreur écriture du fichier {0}: {1}")]
+    Write(String, String),
+}
+
+/// Style des régions des grilles générées
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RegionStyle {
+    /// Régions plutôt 'compactes' comme celles des grilles Star Battle publiées
+    #[default]
+    StarBattle,
+
+    /// Régions en 'bandes' comme celles du jeu Queens (une seule étoile par ligne, colonne et région)
+    Queens,
+}
+
+/// Statistiques de génération
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GeneratorStats {
+    /// Nombre de tentatives de génération
+    pub attempts: usize,
+
+    /// Nombre de tentatives abandonnées faute de placement des étoiles
+    pub placement_failures: usize,
+
+    /// Nombre de tentatives abandonnées faute de découpage en régions (ou découpage avec des régions de
+    /// forme hors limites)
+    pub carving_failures: usize,
+
+    /// Nombre de grilles rejetées car elles ont plusieurs solutions (malgré les réparations)
+    pub rejected_grids: usize,
+
+    /// Nombre de réparations effectuées (cases déplacées dans une autre région)
+    pub repairs: usize,
+
+    /// Nombre de comptages de solutions effectués
+    pub solver_calls: usize,
+
+    /// Nombre de modifications retenues pour rendre une grille plus difficile
+    pub refinements: usize,
+
+    /// Nombre de grilles à solution unique rejetées car de qualité insuffisante
+    pub bland_grids: usize,
+}
+
+impl GeneratorStats {
+    /// Cumule des statistiques
+    pub fn add(&mut self, other: &Self) {
+        self.attempts += other.attempts;
+        self.placement_failures += other.placement_failures;
+        self.carving_failures += other.carving_failures;
+        self.rejected_grids += other.rejected_grids;
+        self.repairs += other.repairs;
+        self.solver_calls += other.solver_calls;
+        self.refinements += other.refinements;
+        self.bland_grids += other.bland_grids;
+    }
+}
+
+impl Display for GeneratorStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} tentative(s), {} échec(s) de placement, {} échec(s) de découpage, {} grille(s) rejetée(s), {} réparation(s), {} comptage(s) de solutions, {} affinage(s), {} grille(s) sans intérêt",
+            self.attempts,
+            self.placement_failures,
+            self.carving_failures,
+            self.rejected_grids,
+            self.repairs,
+            self.solver_calls,
+            self.refinements,
+            self.bland_grids
+        )
+    }
+}
+
+/// Grille générée.<br>
+/// L'unicité de la solution de chaque grille générée est vérifiée par la recherche exhaustive des
+/// solutions (voir [`crate::count_solutions`]) qui a validé son découpage en régions
+#[derive(Clone, Debug)]
+pub struct GeneratedPuzzle {
+    /// Définition de la grille générée
+    pub parser: GridParser,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    pub nb_stars: usize,
+
+    /// Étoiles de l'unique solution de la grille
+    pub solution: Vec<LineColumn>,
+
+    /// Nombre de solutions de la grille trouvées par la recherche exhaustive (limité à 2)
+    pub nb_solutions: usize,
+
+    /// Statistiques de la génération de cette grille
+    pub stats: GeneratorStats,
+}
+
+impl GeneratedPuzzle {
+    /// Retourne `true` si la grille a une solution unique (toujours le cas d'une grille générée)
+    #[must_use]
+    pub const fn is_unique(&self) -> bool {
+        self.nb_solutions == 1
+    }
+}
+
+/// Générateur de grilles carrées à solution unique
+#[derive(Debug)]
+pub struct GridGenerator {
+    /// Nombre de lignes et de colonnes des grilles générées
+    size: usize,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    nb_stars: usize,
+
+    /// Nombre maximum de tentatives pour générer une grille
+    max_attempts: usize,
+
+    /// Nombre maximum de réparations d'une grille qui a plusieurs solutions
+    max_repairs: usize,
+
+    /// Symétrie des grilles générées ([`GridTransform::Identity`] pour des grilles sans symétrie)
+    symmetry: GridTransform,
+
+    /// Style des régions des grilles générées
+    region_style: RegionStyle,
+
+    /// Nombre de cases minimum et maximum de chaque région
+    region_size_range: RangeInclusive<usize>,
+
+    /// Sinuosité (périmètre / aire) maximum de chaque région (si limitée)
+    max_snakiness: Option<f64>,
+
+    /// Difficulté minimum des grilles générées (si demandée)
+    target_difficulty: Option<Difficulty>,
+
+    /// Palier de technique le plus élevé nécessaire à la résolution des grilles générées (si demandé)
+    target_technique: Option<Technique>,
+
+    /// Nombre maximum de modifications examinées pour atteindre la difficulté ou le palier cible
+    max_refinements: usize,
+
+    /// Score de qualité minimum des grilles générées (si demandé)
+    min_quality: Option<f64>,
+
+    /// Statistiques cumulées de toutes les générations
+    stats: GeneratorStats,
+}
+
+impl GridGenerator {
+    /// Constructeur d'un générateur de grilles de `size` x `size` cases avec `nb_stars` étoiles à placer
+    /// dans chaque ligne, colonne et région
+    #[must_use]
+    pub const fn new(size: usize, nb_stars: usize) -> Self {
+        Self {
+            size,
+            nb_stars,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            max_repairs: size * size,
+            symmetry: GridTransform::Identity,
+            region_style: RegionStyle::StarBattle,
+            region_size_range: 1..=size * size,
+            max_snakiness: None,
+            target_difficulty: None,
+            target_technique: None,
+            max_refinements: 4 * size * size,
+            min_quality: None,
+            stats: GeneratorStats {
+                attempts: 0,
+                placement_failures: 0,
+                carving_failures: 0,
+                rejected_grids: 0,
+                repairs: 0,
+                solver_calls: 0,
+                refinements: 0,
+                bland_grids: 0,
+            },
+        }
+    }
+
+    /// Définit le nombre maximum de tentatives pour générer une grille
+    #[must_use]
+    pub const fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Définit le nombre maximum de réparations d'une grille qui a plusieurs solutions
+    #[must_use]
+    pub const fn with_max_repairs(mut self, max_repairs: usize) -> Self {
+        self.max_repairs = max_repairs;
+        self
+    }
+
+    /// Définit la symétrie des grilles générées : l'image de chaque région par `symmetry` est une région
+    /// de la grille.<br>
+    /// L'unique solution d'une grille symétrique est elle aussi symétrique. Une symétrie gauche/droite ou
+    /// haut/bas est donc impossible : la (ou les 2) colonne(s) ou ligne(s) centrale(s) ne pourraient pas
+    /// contenir le bon nombre d'étoiles.
+    #[must_use]
+    pub const fn with_symmetry(mut self, symmetry: GridTransform) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Définit le style des régions des grilles générées.<br>
+    /// Le style [`RegionStyle::Queens`] n'est possible qu'avec une seule étoile par ligne, colonne et région.
+    #[must_use]
+    pub const fn with_region_style(mut self, region_style: RegionStyle) -> Self {
+        self.region_style = region_style;
+        self
+    }
+
+    /// Définit le nombre de cases minimum et maximum de chaque région des grilles générées
+    #[must_use]
+    pub const fn with_region_size_range(
+        mut self,
+        region_size_range: RangeInclusive<usize>,
+    ) -> Self {
+        self.region_size_range = region_size_range;
+        self
+    }
+
+    /// Définit la sinuosité maximum de chaque région des grilles générées : rapport entre le périmètre
+    /// de la région (nombre de côtés de cases en bordure) et son aire (nombre de cases).<br>
+    /// Plus cette valeur est faible, plus les régions sont 'compactes' (1,33 pour un carré de 3x3 cases).
+    #[must_use]
+    pub const fn with_max_snakiness(mut self, max_snakiness: f64) -> Self {
+        self.max_snakiness = Some(max_snakiness);
+        self
+    }
+
+    /// Définit la difficulté minimum des grilles générées (évaluée par [`rate_grid`]).<br>
+    /// Une grille à solution unique qui n'atteint pas cette difficulté est modifiée (voir
+    /// [`GridGenerator::with_max_refinements`]) et rejetée si la difficulté n'est toujours pas atteinte.
+    #[must_use]
+    pub const fn with_target_difficulty(mut self, target_difficulty: Difficulty) -> Self {
+        self.target_difficulty = Some(target_difficulty);
+        self
+    }
+
+    /// Définit le palier de technique des grilles générées : leur résolution nécessite au moins une
+    /// règle de ce palier et aucune règle d'un palier supérieur (voir [`crate::GridRating::hardest_technique`]).<br>
+    /// Une grille à solution unique qui n'a pas ce palier est modifiée (voir
+    /// [`GridGenerator::with_max_refinements`]) et rejetée si le palier n'est toujours pas atteint.
+    #[must_use]
+    pub const fn with_target_technique(mut self, target_technique: Technique) -> Self {
+        self.target_technique = Some(target_technique);
+        self
+    }
+
+    /// Définit le nombre maximum de modifications examinées pour atteindre la difficulté cible (voir
+    /// [`GridGenerator::with_target_difficulty`]) et le palier de technique cible (voir
+    /// [`GridGenerator::with_target_technique`]) : ce nombre est commun aux 2 cibles, qui sont atteintes
+    /// ensemble (4 x `size` x `size` par défaut)
+    #[must_use]
+    pub const fn with_max_refinements(mut self, max_refinements: usize) -> Self {
+        self.max_refinements = max_refinements;
+        self
+    }
+
+    /// Définit le score de qualité minimum (évalué par [`puzzle_quality`], entre 0 et 100) des grilles
+    /// générées : les grilles à solution unique de score inférieur sont rejetées.
+    #[must_use]
+    pub const fn with_min_quality(mut self, min_quality: f64) -> Self {
+        self.min_quality = Some(min_quality);
+        self
+    }
+
+    /// Statistiques cumulées de toutes les générations effectuées par ce générateur
+    #[must_use]
+    pub const fn stats(&self) -> &GeneratorStats {
+        &self.stats
+    }
+
+    /// Génère une grille à solution unique
+    /// ### Errors
+    /// Retourne un [`GeneratorError`] si les paramètres sont incohérents ou si aucune grille n'a été
+    /// trouvée dans le nombre de tentatives autorisées
+    pub fn generate<R: Rng>(&mut self, rng: &mut R) -> Result<GeneratedPuzzle, GeneratorError> {
+        self.check_parameters()?;
+
+        let mut stats = GeneratorStats::default();
+        let result = self.try_generate(rng, None, &mut stats);
+        self.stats.add(&stats);
+        result.map(|(parser, solution, nb_solutions)| GeneratedPuzzle {
+            parser,
+            nb_stars: self.nb_stars,
+            solution,
+            nb_solutions,
+            stats,
+        })
+    }
+
+    /// Génère une grille à solution unique dont la solution est le placement d'étoiles `stars` (seules les
+    /// régions sont construites autour de ces étoiles).<br>
+    /// Avec une symétrie (voir [`GridGenerator::with_symmetry`]), le placement doit être invariant par
+    /// cette symétrie
+    /// ### Errors
+    /// Retourne un [`GeneratorError`] si les paramètres sont incohérents, si `stars` n'est pas une
+    /// solution possible d'une grille de ce générateur (`nb_stars` étoiles non adjacentes par ligne et
+    /// par colonne) ou si aucune grille n'a été trouvée dans le nombre de tentatives autorisées
+    pub fn generate_from_solution<R: Rng>(
+        &mut self,
+        stars: &[LineColumn],
+        rng: &mut R,
+    ) -> Result<GeneratedPuzzle, GeneratorError> {
+        self.check_parameters()?;
+        self.check_solution(stars)?;
+
+        let mut stats = GeneratorStats::default();
+        let result = self.try_generate(rng, Some(stars), &mut stats);
+        self.stats.add(&stats);
+        result.map(|(parser, solution, nb_solutions)| GeneratedPuzzle {
+            parser,
+            nb_stars: self.nb_stars,
+            solution,
+            nb_solutions,
+            stats,
+        })
+    }
+
+    /// Génère une grille à solution unique à partir de la graine `seed` : la même graine produit toujours
+    /// la même grille (pour des paramètres de génération identiques), quelle que soit la plateforme
+    /// ### Errors
+    /// Retourne un [`GeneratorError`] si les paramètres sont incohérents ou si aucune grille n'a été
+    /// trouvée dans le nombre de tentatives autorisées
+    pub fn generate_with_seed(&mut self, seed: u64) -> Result<GeneratedPuzzle, GeneratorError> {
+        self.generate(&mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// Vérifie la cohérence des paramètres de génération
+    fn check_parameters(&self) -> Result<(), GeneratorError> {
+        if self.nb_stars == 0 {
+            return Err(GeneratorError::InvalidParameters(
+                "Le nombre d'étoiles doit être > 0".to_string(),
+            ));
+        }
+        if self.size < 2 * self.nb_stars - 1 {
+            return Err(GeneratorError::InvalidParameters(format!(
+                "Trop d'étoiles à placer ({}) pour une grille de {} lignes",
+                self.nb_stars, self.size
+            )));
+        }
+        if self.size > REGION_CHARS.len() {
+            return Err(GeneratorError::InvalidParameters(format!(
+                "Trop de régions ({}) pour une grille (max. {})",
+                self.size,
+                REGION_CHARS.len()
+            )));
+        }
+        if matches!(
+            self.symmetry,
+            GridTransform::MirrorHorizontal | GridTransform::MirrorVertical
+        ) {
+            return Err(GeneratorError::InvalidParameters(format!(
+                "Symétrie {:?} impossible pour une grille à solution unique",
+                self.symmetry
+            )));
+        }
+        if self.region_style == RegionStyle::Queens && self.nb_stars != 1 {
+            return Err(GeneratorError::InvalidParameters(format!(
+                "Régions de style Queens impossibles avec {} étoiles",
+                self.nb_stars
+            )));
+        }
+        if self
+            .max_snakiness
+            .is_some_and(|max_snakiness| max_snakiness <= 0.0)
+        {
+            return Err(GeneratorError::InvalidParameters(
+                "La sinuosité maximum des régions doit être > 0".to_string(),
+            ));
+        }
+        // La taille moyenne d'une région est `size` cases
+        if !self.region_size_range.contains(&self.size) {
+            return Err(GeneratorError::InvalidParameters(format!(
+                "Taille des régions ({:?}) incompatible avec une grille de {} lignes",
+                self.region_size_range, self.size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Vérifie que le placement d'étoiles `stars` est une solution possible d'une grille de ce générateur
+    fn check_solution(&self, stars: &[LineColumn]) -> Result<(), GeneratorError> {
+        let invalid = |reason: String| {
+            Err(GeneratorError::InvalidParameters(format!(
+                "Placement d'étoiles invalide : {reason}"
+            )))
+        };
+        if let Some(star) = stars
+            .iter()
+            .find(|star| star.line >= self.size || star.column >= self.size)
+        {
+            return invalid(format!("{star} en dehors de la grille"));
+        }
+        for (index, star) in stars.iter().enumerate() {
+            if let Some(other) = stars[index + 1..].iter().find(|other| {
+                star.line.abs_diff(other.line) <= 1 && star.column.abs_diff(other.column) <= 1
+            }) {
+                return invalid(format!("{star} et {other} sont adjacentes ou identiques"));
+            }
+        }
+        for index in 0..self.size {
+            if stars.iter().filter(|star| star.line == index).count() != self.nb_stars {
+                return invalid(format!(
+                    "la ligne {} n'a pas {} étoile(s)",
+                    display_line(index),
+                    self.nb_stars
+                ));
+            }
+            if stars.iter().filter(|star| star.column == index).count() != self.nb_stars {
+                return invalid(format!(
+                    "la colonne {} n'a pas {} étoile(s)",
+                    display_column(index),
+                    self.nb_stars
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Boucle des tentatives de génération.<br>
+    /// Retourne la grille, sa solution et son nombre de solutions
+    fn try_generate<R: Rng>(
+        &self,
+        rng: &mut R,
+        solution: Option<&[LineColumn]>,
+        stats: &mut GeneratorStats,
+    ) -> Result<(GridParser, Vec<LineColumn>, usize), GeneratorError> {
+        for _ in 0..self.max_attempts {
+            stats.attempts += 1;
+
+            let Some(stars) = solution
+                .map(<[LineColumn]>::to_vec)
+                .or_else(|| random_star_placement(self.size, self.nb_stars, self.symmetry, rng))
+            else {
+                stats.placement_failures += 1;
+                continue;
+            };
+
+            let Some(mut regions) = carve_regions(
+                self.size,
+                self.nb_stars,
+                &stars,
+                self.symmetry,
+                *self.region_size_range.end(),
+                match self.region_style {
+                    RegionStyle::StarBattle => RegionGrowth::Compact(COMPACTNESS),
+                    RegionStyle::Queens => RegionGrowth::Stripes(STRAIGHTNESS),
+                },
+                rng,
+            )
+            .filter(|regions| self.are_region_shapes_valid(regions)) else {
+                stats.carving_failures += 1;
+                continue;
+            };
+
+            let Some(unique) = self.make_unique(&stars, &mut regions, rng, stats) else {
+                stats.rejected_grids += 1;
+                continue;
+            };
+            let unique = if self.target_difficulty.is_some() || self.target_technique.is_some() {
+                self.refine_difficulty(&stars, &mut regions, unique, rng, stats)
+            } else {
+                Some(unique)
+            };
+            // Nombre de solutions trouvées par la recherche exhaustive qui a validé le découpage final
+            let Some((parser, nb_solutions)) =
+                unique.filter(|(_, nb_solutions)| *nb_solutions == 1)
+            else {
+                stats.rejected_grids += 1;
+                continue;
+            };
+            if !self.is_quality_valid(&parser) {
+                stats.bland_grids += 1;
+                continue;
+            }
+            return Ok((parser, stars, nb_solutions));
+        }
+        Err(GeneratorError::TooManyAttempts(self.max_attempts))
+    }
+
+    /// Répare si nécessaire le découpage en régions pour que la grille n'ait qu'une seule solution.<br>
+    /// Retourne la grille et son nombre de solutions si `stars` est sa seule solution possible, `None`
+    /// sinon
+    fn make_unique<R: Rng>(
+        &self,
+        stars: &[LineColumn],
+        regions: &mut [usize],
+        rng: &mut R,
+        stats: &mut GeneratorStats,
+    ) -> Option<(GridParser, usize)> {
+        let stars: HashSet<LineColumn> = stars.iter().copied().collect();
+        let mut nb_repairs = 0;
+        loop {
+            let parser = regions_to_parser(self.size, regions);
+            let handler = GridHandler::new(&parser, self.nb_stars);
+            stats.solver_calls += 1;
+            // Grille à solution unique ?
+            let (nb_solutions, other_solution) = find_other_solution(&handler, &stars);
+            let Some(other_solution) = other_solution else {
+                return Some((parser, nb_solutions));
+            };
+
+            if nb_repairs >= self.max_repairs {
+                return None;
+            }
+
+            // Cases avec une étoile dans l'autre solution mais pas dans la solution initiale
+            let mut candidates: Vec<LineColumn> = handler
+                .surfer(&other_solution, &GridSurfer::AllCells)
+                .into_iter()
+                .filter(|line_column| {
+                    other_solution.cell(*line_column).is_star() && !stars.contains(line_column)
+                })
+                .collect();
+            candidates.shuffle(rng);
+
+            if !candidates
+                .iter()
+                .any(|line_column| self.move_cell(regions, *line_column, rng))
+            {
+                return None;
+            }
+            nb_repairs += 1;
+            stats.repairs += 1;
+        }
+    }
+
+    /// Modifie le découpage en régions d'une grille à solution unique jusqu'à atteindre la difficulté et
+    /// le palier de technique demandés.<br>
+    /// Seules les modifications qui conservent l'unicité de la solution sans rendre la grille plus facile
+    /// (ni nécessiter un palier de technique supérieur au palier demandé) sont retenues.<br>
+    /// `unique` est la grille du découpage `regions` et son nombre de solutions (voir
+    /// [`GridGenerator::make_unique`]).<br>
+    /// Retourne la grille et son nombre de solutions si elle atteint la difficulté et le palier demandés,
+    /// `None` sinon
+    fn refine_difficulty<R: Rng>(
+        &self,
+        stars: &[LineColumn],
+        regions: &mut [usize],
+        unique: (GridParser, usize),
+        rng: &mut R,
+        stats: &mut GeneratorStats,
+    ) -> Option<(GridParser, usize)> {
+        let stars: HashSet<LineColumn> = stars.iter().copied().collect();
+        let (mut parser, mut nb_solutions) = unique;
+        let mut rating = self.rate(&parser);
+        for _ in 0..self.max_refinements {
+            if self.is_target_reached(&rating) {
+                return Some((parser, nb_solutions));
+            }
+
+            // Case en bordure de région (sans étoile dans la solution) à déplacer
+            let candidates: Vec<usize> = (0..regions.len())
+                .filter(|cell| {
+                    !stars.contains(&LineColumn::new(cell / self.size, cell % self.size))
+                        && orthogonal_neighbors(self.size, *cell)
+                            .iter()
+                            .any(|neighbor| regions[*neighbor] != regions[*cell])
+                })
+                .collect();
+            let cell = candidates.choose(rng)?;
+            let saved_regions = regions.to_vec();
+            let line_column = LineColumn::new(cell / self.size, cell % self.size);
+            if !self.move_cell(regions, line_column, rng) {
+                continue;
+            }
+
+            let new_parser = regions_to_parser(self.size, regions);
+            let handler = GridHandler::new(&new_parser, self.nb_stars);
+            stats.solver_calls += 1;
+            let (new_nb_solutions, other_solution) = find_other_solution(&handler, &stars);
+            if other_solution.is_none() {
+                let new_rating = self.rate(&new_parser);
+                if new_rating.solved
+                    && self.is_technique_allowed(&new_rating)
+                    && (new_rating.score >= rating.score
+                        || !rating.solved
+                        || !self.is_technique_allowed(&rating))
+                {
+                    stats.refinements += 1;
+                    parser = new_parser;
+                    nb_solutions = new_nb_solutions;
+                    rating = new_rating;
+                    continue;
+                }
+            }
+            regions.copy_from_slice(&saved_regions);
+        }
+        self.is_target_reached(&rating)
+            .then_some((parser, nb_solutions))
+    }
+
+    /// Vérifie que la résolution d'une grille atteint la difficulté et le palier de technique demandés
+    fn is_target_reached(&self, rating: &GridRating) -> bool {
+        self.target_difficulty
+            .is_none_or(|target_difficulty| rating.difficulty() >= Some(target_difficulty))
+            && self.target_technique.is_none_or(|target_technique| {
+                rating.solved && rating.hardest_technique() == Some(target_technique)
+            })
+    }
+
+    /// Vérifie que la résolution d'une grille ne nécessite pas de palier de technique supérieur au palier
+    /// demandé
+    fn is_technique_allowed(&self, rating: &GridRating) -> bool {
+        self.target_technique
+            .is_none_or(|target_technique| rating.hardest_technique() <= Some(target_technique))
+    }
+
+    /// Évaluation de la difficulté d'une grille (à solution unique) générée
+    fn rate(&self, parser: &GridParser) -> GridRating {
+        let handler = GridHandler::new(parser, self.nb_stars);
+        rate_grid(&handler, &Grid::from(&handler)).unwrap_or_default()
+    }
+
+    /// Vérifie que la qualité d'une grille atteint le score minimum demandé
+    fn is_quality_valid(&self, parser: &GridParser) -> bool {
+        self.min_quality.is_none_or(|min_quality| {
+            puzzle_quality(&GridHandler::new(parser, self.nb_stars))
+                .is_ok_and(|quality| quality.score >= min_quality)
+        })
+    }
+
+    /// Vérifie que toutes les régions ont un nombre de cases et une sinuosité dans les limites demandées
+    fn are_region_shapes_valid(&self, regions: &[usize]) -> bool {
+        region_sizes(self.size, regions)
+            .iter()
+            .all(|region_size| self.region_size_range.contains(region_size))
+            && self.max_snakiness.is_none_or(|max_snakiness| {
+                (0..self.size)
+                    .all(|region| region_snakiness(self.size, regions, region) <= max_snakiness)
+            })
+    }
+
+    /// Déplace une case (et ses images) dans une région voisine (et ses images) si toutes les régions
+    /// concernées restent connexes et de forme valide.<br>
+    /// Retourne `true` si la case a été déplacée
+    fn move_cell<R: Rng>(
+        &self,
+        regions: &mut [usize],
+        line_column: LineColumn,
+        rng: &mut R,
+    ) -> bool {
+        let cell = line_column.line * self.size + line_column.column;
+        let region = regions[cell];
+        let mut neighbor_regions: Vec<usize> = orthogonal_neighbors(self.size, cell)
+            .into_iter()
+            .map(|neighbor| regions[neighbor])
+            .filter(|neighbor_region| *neighbor_region != region)
+            .collect();
+        neighbor_regions.sort_unstable();
+        neighbor_regions.dedup();
+        let Some(new_region) = neighbor_regions.choose(rng).copied() else {
+            return false;
+        };
+
+        // Région image de chaque région (le découpage est symétrique)
+        let mut region_images = vec![0; self.size];
+        for (cell, region) in regions.iter().enumerate() {
+            region_images[*region] = regions[image(self.symmetry, self.size, cell)];
+        }
+
+        let cells = orbit(self.symmetry, self.size, cell);
+        let mut new_regions = vec![new_region];
+        for _ in 1..cells.len() {
+            new_regions.push(region_images[new_regions[new_regions.len() - 1]]);
+        }
+        if region_images[new_regions[new_regions.len() - 1]] != new_region {
+            return false;
+        }
+
+        let old_regions: Vec<usize> = cells.iter().map(|cell| regions[*cell]).collect();
+        for (cell, new_region) in cells.iter().zip(&new_regions) {
+            regions[*cell] = *new_region;
+        }
+        if self.are_region_shapes_valid(regions)
+            && old_regions
+                .iter()
+                .chain(&new_regions)
+                .all(|region| is_region_connected(self.size, regions, *region))
+        {
+            true
+        } else {
+            for (cell, old_region) in cells.iter().zip(old_regions) {
+                regions[*cell] = old_region;
+            }
+            false
+        }
+    }
+}
+
+/// Recherche exhaustive des solutions d'une grille (2 au plus).<br>
+/// Retourne le nombre de solutions trouvées et une solution autre que la solution `stars` (`None` si
+/// `stars` est l'unique solution de la grille)
+fn find_other_solution(
+    handler: &GridHandler,
+    stars: &HashSet<LineColumn>,
+) -> (usize, Option<Grid>) {
+    let solutions = find_solutions(handler, &Grid::from(handler), 2);
+    let nb_solutions = solutions.len();
+    let other_solution = solutions.into_iter().find(|solution| {
+        stars
+            .iter()
+            .any(|line_column| !solution.cell(*line_column).is_star())
+    });
+    (nb_solutions, other_solution)
+}
+
+/// Construit une grille à solution unique dont la solution est le placement d'étoiles `stars` (`nb_stars`
+/// étoiles non adjacentes par ligne et par colonne d'une grille carrée) : les régions sont découpées
+/// autour de ces étoiles (voir [`GridGenerator::generate_from_solution`]).<br>
+/// Le découpage ne dépend que du placement : il est identique d'un appel à l'autre
+/// ### Errors
+/// Retourne un [`GeneratorError`] si `stars` n'est pas une solution possible ou si aucune grille n'a été
+/// trouvée dans le nombre de tentatives autorisées
+pub fn puzzle_from_solution(
+    stars: &[LineColumn],
+    nb_stars: usize,
+) -> Result<GridParser, GeneratorError> {
+    if nb_stars == 0 {
+        return Err(GeneratorError::InvalidParameters(
+            "Le nombre d'étoiles doit être > 0".to_string(),
+        ));
+    }
+    let size = stars.len() / nb_stars;
+    GridGenerator::new(size, nb_stars)
+        .generate_from_solution(stars, &mut ChaCha8Rng::seed_from_u64(0))
+        .map(|puzzle| puzzle.parser)
+}
+
+/// Vérifie qu'une région (non vide) est connexe
+fn is_region_connected(size: usize, regions: &[usize], region: usize) -> bool {
+    let cells: Vec<usize> = (0..regions.len())
+        .filter(|cell| regions[*cell] == region)
+        .collect();
+    let Some(first) = cells.first() else {
+        return false;
+    };
+    let mut visited = vec![false; regions.len()];
+    let mut to_visit = vec![*first];
+    visited[*first] = true;
+    let mut nb_visited = 0;
+    while let Some(cell) = to_visit.pop() {
+        nb_visited += 1;
+        for neighbor in orthogonal_neighbors(size, cell) {
+            if !visited[neighbor] && regions[neighbor] == region {
+                visited[neighbor] = true;
+                to_visit.push(neighbor);
+            }
+        }
+    }
+    nb_visited == cells.len()
+}
+
+/// Symbole d'une région selon son index
+pub(crate) fn region_char(index: usize) -> Region {
+    REGION_CHARS
+        .chars()
+        .nth(index)
+        .expect("Nombre de régions limité par la vérification des paramètres")
+}
+
+/// Construit la définition d'une grille à partir de la région (par index) de chaque case.<br>
+/// Les symboles des régions sont attribués dans l'ordre de leur première apparition dans la grille.
+fn regions_to_parser(size: usize, regions: &[usize]) -> GridParser {
+    let mut symbols: Vec<usize> = Vec::new();
+    let mut lines = Vec::with_capacity(size);
+    for line in regions.chunks(size) {
+        let mut text_line = String::with_capacity(size);
+        for region in line {
+            let index = symbols
+                .iter()
+                .position(|symbol| symbol == region)
+                .unwrap_or_else(|| {
+                    symbols.push(*region);
+                    symbols.len() - 1
+                });
+            text_line.push(region_char(index));
+        }
+        lines.push(text_line);
+    }
+    GridParser::try_from(lines).expect("Les régions générées sont connexes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::grid_brute_force::count_solutions;
+
+    /// Vérifie que la grille a pour seule solution les étoiles `stars`
+    fn is_unique_solution(handler: &GridHandler, stars: &[LineColumn]) -> bool {
+        let solutions = find_solutions(handler, &Grid::from(handler), 2);
+        solutions.len() == 1
+            && stars
+                .iter()
+                .all(|line_column| solutions[0].cell(*line_column).is_star())
+    }
+
+    #[test]
+    fn test_generate_unique() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (size, nb_stars) in [(5, 1), (8, 1), (8, 2)] {
+            let mut generator = GridGenerator::new(size, nb_stars);
+            let puzzle = generator.generate(&mut rng).unwrap();
+
+            assert_eq!(puzzle.parser.nb_lines(), size);
+            assert_eq!(puzzle.parser.nb_columns(), size);
+            assert_eq!(puzzle.parser.regions().len(), size);
+            assert_eq!(puzzle.solution.len(), size * nb_stars);
+
+            let handler = GridHandler::new(&puzzle.parser, nb_stars);
+            let grid = Grid::from(&handler);
+            assert_eq!(count_solutions(&handler, &grid, 2), 1);
+            assert!(puzzle.is_unique());
+            assert!(is_unique_solution(&handler, &puzzle.solution));
+            assert!(puzzle.stats.attempts >= 1);
+            assert_eq!(generator.stats(), &puzzle.stats);
+        }
+    }
+
+    #[test]
+    fn test_generate_with_seed() {
+        let puzzle = GridGenerator::new(6, 1).generate_with_seed(7).unwrap();
+        let other = GridGenerator::new(6, 1).generate_with_seed(7).unwrap();
+        assert_eq!(puzzle.parser.list_cells(), other.parser.list_cells());
+        assert_eq!(puzzle.solution, other.solution);
+    }
+
+    #[test]
+    fn test_generate_from_solution() {
+        // Diagonale 'en cavalier' d'une grille 5x5 1★
+        let stars: Vec<LineColumn> = [(0, 0), (1, 2), (2, 4), (3, 1), (4, 3)]
+            .into_iter()
+            .map(|(line, column)| LineColumn::new(line, column))
+            .collect();
+        let parser = puzzle_from_solution(&stars, 1).unwrap();
+        assert_eq!(
+            parser.list_cells(),
+            puzzle_from_solution(&stars, 1).unwrap().list_cells()
+        );
+        let handler = GridHandler::new(&parser, 1);
+        assert!(is_unique_solution(&handler, &stars));
+
+        // Placements invalides
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut generator = GridGenerator::new(5, 1);
+        assert!(generator
+            .generate_from_solution(&stars[..4], &mut rng)
+            .is_err());
+        let mut adjacent = stars.clone();
+        adjacent[1] = LineColumn::new(1, 1);
+        assert!(generator
+            .generate_from_solution(&adjacent, &mut rng)
+            .is_err());
+        assert!(puzzle_from_solution(&stars, 0).is_err());
+    }
+
+    #[test]
+    fn test_generate_symmetric() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (size, nb_stars, symmetry) in [
+            (8, 1, GridTransform::Rotate180),
+            (8, 2, GridTransform::Rotate90),
+            (9, 1, GridTransform::Transpose),
+        ] {
+            let mut generator = GridGenerator::new(size, nb_stars)
+                .with_symmetry(symmetry)
+                .with_max_attempts(1_000);
+            let puzzle = generator.generate(&mut rng).unwrap();
+
+            let handler = GridHandler::new(&puzzle.parser, nb_stars);
+            assert_eq!(count_solutions(&handler, &Grid::from(&handler), 2), 1);
+
+            // 2 cases d'une même région ont leurs images dans une même région
+            let transformed = puzzle.parser.transform(symmetry);
+            let cells = puzzle.parser.list_cells();
+            for cell in &cells {
+                for other in &cells {
+                    if cell.region == other.region {
+                        assert_eq!(
+                            transformed.cell_region(cell.line_column),
+                            transformed.cell_region(other.line_column)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_region_shapes() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut generator = GridGenerator::new(8, 1)
+            .with_region_size_range(3..=16)
+            .with_max_snakiness(2.5)
+            .with_max_attempts(1_000);
+        let puzzle = generator.generate(&mut rng).unwrap();
+        let handler = GridHandler::new(&puzzle.parser, 1);
+        for region in handler.regions() {
+            let nb_cells = handler.region_cells(region).len();
+            assert!((3..=16).contains(&nb_cells));
+            assert!(2 * handler.region_perimeter(region) <= 5 * nb_cells);
+        }
+    }
+
+    #[test]
+    fn test_generate_target_difficulty() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut generator = GridGenerator::new(8, 1)
+            .with_target_difficulty(Difficulty::Hard)
+            .with_max_refinements(200);
+        let puzzle = generator.generate(&mut rng).unwrap();
+
+        let handler = GridHandler::new(&puzzle.parser, 1);
+        let grid = Grid::from(&handler);
+        assert_eq!(count_solutions(&handler, &grid, 2), 1);
+        let rating = rate_grid(&handler, &grid).unwrap();
+        assert!(rating.difficulty() >= Some(Difficulty::Hard));
+    }
+
+    #[test]
+    fn test_generate_target_technique() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut generator = GridGenerator::new(8, 1)
+            .with_target_technique(Technique::Advanced)
+            .with_max_refinements(200);
+        let puzzle = generator.generate(&mut rng).unwrap();
+
+        let handler = GridHandler::new(&puzzle.parser, 1);
+        let grid = Grid::from(&handler);
+        assert_eq!(count_solutions(&handler, &grid, 2), 1);
+        let rating = rate_grid(&handler, &grid).unwrap();
+        assert_eq!(rating.hardest_technique(), Some(Technique::Advanced));
+
+        // Les 2 cibles partagent le même nombre de modifications, quel que soit l'ordre des appels
+        let generator = GridGenerator::new(8, 1)
+            .with_max_refinements(50)
+            .with_target_technique(Technique::Advanced)
+            .with_target_difficulty(Difficulty::Hard);
+        assert_eq!(generator.max_refinements, 50);
+    }
+
+    #[test]
+    fn test_generate_min_quality() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut generator = GridGenerator::new(8, 1).with_min_quality(40.0);
+        let puzzle = generator.generate(&mut rng).unwrap();
+
+        let handler = GridHandler::new(&puzzle.parser, 1);
+        assert!(puzzle_quality(&handler).unwrap().score >= 40.0);
+
+        // Score de qualité impossible à atteindre
+        let mut generator = GridGenerator::new(6, 1)
+            .with_min_quality(101.0)
+            .with_max_attempts(5);
+        assert!(generator.generate(&mut rng).is_err());
+        assert!(generator.stats().bland_grids > 0);
+    }
+
+    #[test]
+    fn test_generate_queens() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut generator = GridGenerator::new(8, 1).with_region_style(RegionStyle::Queens);
+        let puzzle = generator.generate(&mut rng).unwrap();
+
+        let handler = GridHandler::new(&puzzle.parser, 1);
+        assert_eq!(handler.regions().len(), 8);
+        assert_eq!(count_solutions(&handler, &Grid::from(&handler), 2), 1);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(matches!(
+            GridGenerator::new(5, 0).generate(&mut rng),
+            Err(GeneratorError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            GridGenerator::new(2, 2).generate(&mut rng),
+            Err(GeneratorError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            GridGenerator::new(8, 1)
+                .with_region_size_range(2..=6)
+                .generate(&mut rng),
+            Err(GeneratorError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            GridGenerator::new(8, 1)
+                .with_max_snakiness(0.0)
+                .generate(&mut rng),
+            Err(GeneratorError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            GridGenerator::new(10, 2)
+                .with_region_style(RegionStyle::Queens)
+                .generate(&mut rng),
+            Err(GeneratorError::InvalidParameters(_))
+        ));
+        assert!(matches!(
+            GridGenerator::new(8, 1)
+                .with_symmetry(GridTransform::MirrorVertical)
+                .generate(&mut rng),
+            Err(GeneratorError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_too_many_attempts() {
+        // Aucun placement d'étoiles possible dans une grille 3x3
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut generator = GridGenerator::new(3, 1).with_max_attempts(3);
+        assert_eq!(
+            generator.generate(&mut rng).unwrap_err(),
+            GeneratorError::TooManyAttempts(3)
+        );
+        assert_eq!(generator.stats().attempts, 3);
+        assert_eq!(generator.stats().placement_failures, 3);
+    }
+}
@@ -0,0 +1,19 @@
+//! Génération de grilles à résoudre
+
+mod config;
+mod generator;
+mod region_carver;
+mod region_partition;
+mod region_shape;
+mod star_configurations;
+mod star_placement;
+mod symmetry;
+
+pub use config::GeneratorConfig;
+pub use generator::{
+    puzzle_from_solution, GeneratedPuzzle, GeneratorError, GeneratorStats, GridGenerator,
+    RegionStyle,
+};
+pub(crate) use generator::{region_char, REGION_CHARS};
+pub use region_partition::generate_regions;
+pub use star_configurations::{StarConfigurations, StarConfigurationsIter};
@@ -0,0 +1,495 @@
+//! Découpage aléatoire d'une grille en régions autour d'un placement d'étoiles.
+//!
+//! Le découpage se fait en 2 temps :
+//!
+//! * Chaque étoile est le germe d'un 'bloc' qui grandit aléatoirement case par case (en horizontal ou
+//!   vertical) jusqu'à ce que toutes les cases de la grille appartiennent à un bloc
+//! * Pour les grilles à `nb_stars` étoiles, les blocs voisins sont ensuite regroupés par `nb_stars` pour
+//!   former des régions connexes qui contiennent chacune exactement `nb_stars` étoiles
+//!
+//! La façon dont les blocs grandissent (voir [`RegionGrowth`]) détermine l'allure des régions : plutôt
+//! 'compactes' comme celles des grilles Star Battle publiées ou en 'bandes' comme celles du jeu Queens.
+//!
+//! Le résultat est une région (par index) pour chaque case de la grille (par index ligne x colonne).
+//!
+//! Si une symétrie est demandée (le placement des étoiles étant lui-même symétrique), chaque case est
+//! attribuée en même temps que toutes ses images et chaque groupe de blocs est constitué en même temps
+//! que ses images : l'image d'une région est alors toujours une région.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::GridTransform;
+use crate::LineColumn;
+
+use super::symmetry::{image, orbit};
+
+/// Nombre maximum de regroupements examinés avant d'abandonner le regroupement des blocs
+const MAX_EXPLORED_GROUPS: usize = 10_000;
+
+/// Croissance des blocs autour de chaque étoile
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionGrowth {
+    /// Croissance 'compacte' : parmi le nombre de tirages indiqué, on retient la case qui a le plus de
+    /// cases voisines déjà dans le bloc (un seul tirage pour une croissance purement aléatoire)
+    Compact(usize),
+
+    /// Croissance en 'bandes' : parmi le nombre de tirages indiqué, on retient la case qui prolonge en
+    /// ligne droite le plus de segments du bloc
+    Stripes(usize),
+}
+
+/// Découpe une grille carrée de `size` x `size` cases en régions qui contiennent chacune `nb_stars` étoiles
+/// parmi les étoiles de `stars`, en respectant la symétrie `symmetry` (qui doit être celle du placement
+/// des étoiles).<br>
+/// Les blocs autour de chaque étoile grandissent selon `growth` sans dépasser `max_block_size` cases (pour
+/// les grilles à 1 étoile, chaque bloc est une région).<br>
+/// Retourne l'index de la région de chaque case (par index ligne x colonne) ou `None` si le découpage
+/// n'a pas abouti.
+pub fn carve_regions<R: Rng>(
+    size: usize,
+    nb_stars: usize,
+    stars: &[LineColumn],
+    symmetry: GridTransform,
+    max_block_size: usize,
+    growth: RegionGrowth,
+    rng: &mut R,
+) -> Option<Vec<usize>> {
+    // Bloc image de chaque bloc (index de l'étoile image de chaque étoile)
+    let star_cells: Vec<usize> = stars
+        .iter()
+        .map(|star| star.line * size + star.column)
+        .collect();
+    let block_images = star_cells
+        .iter()
+        .map(|cell| {
+            let image_cell = image(symmetry, size, *cell);
+            star_cells.iter().position(|other| *other == image_cell)
+        })
+        .collect::<Option<Vec<usize>>>()?;
+
+    let blocks = grow_blocks(
+        size,
+        &star_cells,
+        symmetry,
+        &block_images,
+        max_block_size,
+        growth,
+        rng,
+    )?;
+    if nb_stars == 1 {
+        return Some(blocks);
+    }
+    let groups = group_blocks(size, &blocks, &block_images, nb_stars, rng)?;
+    Some(blocks.iter().map(|block| groups[*block]).collect())
+}
+
+/// Liste des cases voisines (horizontalement ou verticalement) d'une case (par index)
+pub fn orthogonal_neighbors(size: usize, cell: usize) -> Vec<usize> {
+    let (line, column) = (cell / size, cell % size);
+    let mut neighbors = Vec::with_capacity(4);
+    if line > 0 {
+        neighbors.push(cell - size);
+    }
+    if line + 1 < size {
+        neighbors.push(cell + size);
+    }
+    if column > 0 {
+        neighbors.push(cell - 1);
+    }
+    if column + 1 < size {
+        neighbors.push(cell + 1);
+    }
+    neighbors
+}
+
+/// Fait grandir aléatoirement un bloc autour de chaque étoile (par index de case) jusqu'à couvrir toute
+/// la grille.<br>
+/// Une case est attribuée à un bloc en même temps que ses images sont attribuées aux blocs images.<br>
+/// Un bloc grandit selon `growth` et ne grandit plus lorsqu'il atteint `max_block_size` cases.<br>
+/// Retourne l'index du bloc (index de l'étoile dans `star_cells`) de chaque case ou `None` si certaines
+/// cases n'ont pu être attribuées à aucun bloc (de façon symétrique ou sans dépasser la taille maximale)
+fn grow_blocks<R: Rng>(
+    size: usize,
+    star_cells: &[usize],
+    symmetry: GridTransform,
+    block_images: &[usize],
+    max_block_size: usize,
+    growth: RegionGrowth,
+    rng: &mut R,
+) -> Option<Vec<usize>> {
+    let mut owners: Vec<Option<usize>> = vec![None; size * size];
+    let mut block_sizes = vec![1; star_cells.len()];
+    // Cases candidates pour agrandir un bloc : (case, bloc)
+    let mut frontier = Vec::new();
+    for (block, cell) in star_cells.iter().enumerate() {
+        owners[*cell] = Some(block);
+        for neighbor in orthogonal_neighbors(size, *cell) {
+            frontier.push((neighbor, block));
+        }
+    }
+
+    while !frontier.is_empty() {
+        let (cell, block) = pick_frontier(size, &mut frontier, &owners, growth, rng);
+        if owners[cell].is_some() {
+            continue;
+        }
+        // Blocs des images successives de la case
+        let cells = orbit(symmetry, size, cell);
+        let mut cell_blocks = vec![block];
+        for _ in 1..cells.len() {
+            cell_blocks.push(block_images[cell_blocks[cell_blocks.len() - 1]]);
+        }
+        if block_images[cell_blocks[cell_blocks.len() - 1]] != block {
+            // Cette case ne peut pas appartenir à ce bloc sans rompre la symétrie
+            continue;
+        }
+        if cell_blocks.iter().any(|block| {
+            block_sizes[*block] + cell_blocks.iter().filter(|other| *other == block).count()
+                > max_block_size
+        }) {
+            // Ce bloc (ou un bloc image) est déjà trop grand
+            continue;
+        }
+        for (cell, block) in cells.into_iter().zip(cell_blocks) {
+            owners[cell] = Some(block);
+            block_sizes[block] += 1;
+            for neighbor in orthogonal_neighbors(size, cell) {
+                if owners[neighbor].is_none() {
+                    frontier.push((neighbor, block));
+                }
+            }
+        }
+    }
+
+    owners.into_iter().collect()
+}
+
+/// Retire de `frontier` la case candidate suivante pour agrandir un bloc : (case, bloc).<br>
+/// On tire au hasard plusieurs candidats et on retient le meilleur selon `growth`.
+fn pick_frontier<R: Rng>(
+    size: usize,
+    frontier: &mut Vec<(usize, usize)>,
+    owners: &[Option<usize>],
+    growth: RegionGrowth,
+    rng: &mut R,
+) -> (usize, usize) {
+    let nb_draws = match growth {
+        RegionGrowth::Compact(nb_draws) | RegionGrowth::Stripes(nb_draws) => nb_draws,
+    };
+    let mut best_index = rng.gen_range(0..frontier.len());
+    let mut best_score = 0;
+    for draw in 0..nb_draws.max(1) {
+        let index = if draw == 0 {
+            best_index
+        } else {
+            rng.gen_range(0..frontier.len())
+        };
+        let (cell, block) = frontier[index];
+        let score = match growth {
+            RegionGrowth::Compact(_) => orthogonal_neighbors(size, cell)
+                .into_iter()
+                .filter(|neighbor| owners[*neighbor] == Some(block))
+                .count(),
+            RegionGrowth::Stripes(_) => straight_extensions(size, cell, block, owners),
+        };
+        if score > best_score {
+            best_index = index;
+            best_score = score;
+        }
+    }
+    frontier.swap_remove(best_index)
+}
+
+/// Nombre de segments de 2 cases du bloc `block` que la case `cell` prolonge en ligne droite
+fn straight_extensions(size: usize, cell: usize, block: usize, owners: &[Option<usize>]) -> usize {
+    orthogonal_neighbors(size, cell)
+        .into_iter()
+        .filter(|neighbor| {
+            // Case suivante dans la même direction
+            (2 * neighbor).checked_sub(cell).is_some_and(|beyond| {
+                owners[*neighbor] == Some(block)
+                    && orthogonal_neighbors(size, *neighbor).contains(&beyond)
+                    && owners[beyond] == Some(block)
+            })
+        })
+        .count()
+}
+
+/// Regroupe des blocs voisins par `group_size` blocs, l'image d'un groupe (selon `block_images`) étant
+/// toujours un groupe.<br>
+/// Retourne l'index du groupe de chaque bloc ou `None` si aucun regroupement n'a été trouvé
+fn group_blocks<R: Rng>(
+    size: usize,
+    blocks: &[usize],
+    block_images: &[usize],
+    group_size: usize,
+    rng: &mut R,
+) -> Option<Vec<usize>> {
+    let nb_blocks = block_images.len();
+    if !nb_blocks.is_multiple_of(group_size) {
+        return None;
+    }
+
+    // Blocs voisins de chaque bloc
+    let mut neighbors = vec![Vec::new(); nb_blocks];
+    for (cell, block) in blocks.iter().enumerate() {
+        for neighbor in orthogonal_neighbors(size, cell) {
+            let other = blocks[neighbor];
+            if other != *block && !neighbors[*block].contains(&other) {
+                neighbors[*block].push(other);
+            }
+        }
+    }
+
+    let mut grouping = BlockGrouping {
+        neighbors,
+        block_images: block_images.to_vec(),
+        group_size,
+        groups: vec![None; nb_blocks],
+        nb_groups: 0,
+        explored_groups: 0,
+    };
+    if grouping.group_next(rng) {
+        Some(grouping.groups.into_iter().flatten().collect())
+    } else {
+        None
+    }
+}
+
+/// Contexte de la recherche d'un regroupement des blocs
+struct BlockGrouping {
+    /// Blocs voisins de chaque bloc
+    neighbors: Vec<Vec<usize>>,
+
+    /// Bloc image de chaque bloc par symétrie
+    block_images: Vec<usize>,
+
+    /// Nombre de blocs par groupe
+    group_size: usize,
+
+    /// Groupe de chaque bloc (si déjà regroupé)
+    groups: Vec<Option<usize>>,
+
+    /// Nombre de groupes déjà constitués
+    nb_groups: usize,
+
+    /// Nombre de groupes examinés depuis le début de la recherche
+    explored_groups: usize,
+}
+
+impl BlockGrouping {
+    /// Constitue un groupe (et ses images) avec le premier bloc non regroupé et poursuit récursivement.<br>
+    /// Retourne `true` si tous les blocs ont pu être regroupés.
+    fn group_next<R: Rng>(&mut self, rng: &mut R) -> bool {
+        let Some(first) = self.groups.iter().position(Option::is_none) else {
+            return true;
+        };
+
+        let mut candidates = Vec::new();
+        self.connected_groups(&mut vec![first], &mut candidates);
+        candidates.shuffle(rng);
+
+        for group in candidates {
+            self.explored_groups += 1;
+            if self.explored_groups > MAX_EXPLORED_GROUPS {
+                return false;
+            }
+            let Some(images) = self.group_images(&group) else {
+                continue;
+            };
+            for image in &images {
+                for block in image {
+                    self.groups[*block] = Some(self.nb_groups);
+                }
+                self.nb_groups += 1;
+            }
+            if self.group_next(rng) {
+                return true;
+            }
+            for image in &images {
+                self.nb_groups -= 1;
+                for block in image {
+                    self.groups[*block] = None;
+                }
+            }
+        }
+        false
+    }
+
+    /// Liste du groupe et de ses images successives (toutes distinctes) jusqu'à retrouver le groupe.<br>
+    /// Retourne `None` si une image chevauche partiellement un autre groupe.
+    fn group_images(&self, group: &[usize]) -> Option<Vec<Vec<usize>>> {
+        let mut images = vec![group.to_vec()];
+        loop {
+            let mut image: Vec<usize> = images[images.len() - 1]
+                .iter()
+                .map(|block| self.block_images[*block])
+                .collect();
+            image.sort_unstable();
+            if image == images[0] {
+                return Some(images);
+            }
+            let overlaps = image.iter().any(|block| {
+                self.groups[*block].is_some() || images.iter().any(|other| other.contains(block))
+            });
+            if overlaps {
+                return None;
+            }
+            images.push(image);
+        }
+    }
+
+    /// Liste des groupes connexes de `group_size` blocs non regroupés qui complètent `current`
+    fn connected_groups(&self, current: &mut Vec<usize>, candidates: &mut Vec<Vec<usize>>) {
+        if current.len() == self.group_size {
+            let mut group = current.clone();
+            group.sort_unstable();
+            if !candidates.contains(&group) {
+                candidates.push(group);
+            }
+            return;
+        }
+        let mut extensions = Vec::new();
+        for block in current.iter() {
+            for neighbor in &self.neighbors[*block] {
+                if self.groups[*neighbor].is_none()
+                    && !current.contains(neighbor)
+                    && !extensions.contains(neighbor)
+                {
+                    extensions.push(*neighbor);
+                }
+            }
+        }
+        for neighbor in extensions {
+            current.push(neighbor);
+            self.connected_groups(current, candidates);
+            current.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::grid_generator::star_placement::random_star_placement;
+
+    // Vérifie que chaque région contient exactement `nb_stars` étoiles
+    fn assert_stars_per_region(
+        size: usize,
+        nb_stars: usize,
+        stars: &[LineColumn],
+        regions: &[usize],
+    ) {
+        assert_eq!(regions.len(), size * size);
+        for region in 0..size {
+            let nb = stars
+                .iter()
+                .filter(|star| regions[star.line * size + star.column] == region)
+                .count();
+            assert_eq!(nb, nb_stars);
+        }
+    }
+
+    #[test]
+    fn test_carve_regions() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (size, nb_stars) in [(6, 1), (10, 2)] {
+            let stars =
+                random_star_placement(size, nb_stars, GridTransform::Identity, &mut rng).unwrap();
+            let Some(regions) = carve_regions(
+                size,
+                nb_stars,
+                &stars,
+                GridTransform::Identity,
+                size * size,
+                RegionGrowth::Compact(1),
+                &mut rng,
+            ) else {
+                continue;
+            };
+            assert_stars_per_region(size, nb_stars, &stars, &regions);
+        }
+    }
+
+    #[test]
+    fn test_carve_stripes() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let size = 8;
+        let stars = random_star_placement(size, 1, GridTransform::Identity, &mut rng).unwrap();
+        let regions = carve_regions(
+            size,
+            1,
+            &stars,
+            GridTransform::Identity,
+            size * size,
+            RegionGrowth::Stripes(4),
+            &mut rng,
+        )
+        .unwrap();
+        assert_stars_per_region(size, 1, &stars, &regions);
+    }
+
+    #[test]
+    fn test_carve_max_block_size() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let size = 8;
+        let stars = random_star_placement(size, 1, GridTransform::Identity, &mut rng).unwrap();
+        let regions = (0..1_000)
+            .find_map(|_| {
+                carve_regions(
+                    size,
+                    1,
+                    &stars,
+                    GridTransform::Identity,
+                    10,
+                    RegionGrowth::Compact(1),
+                    &mut rng,
+                )
+            })
+            .unwrap();
+        for region in 0..size {
+            assert!(regions.iter().filter(|other| **other == region).count() <= 10);
+        }
+    }
+
+    #[test]
+    fn test_carve_symmetric_regions() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (size, nb_stars, symmetry) in [
+            (8, 1, GridTransform::Rotate180),
+            (10, 2, GridTransform::AntiTranspose),
+        ] {
+            let stars = random_star_placement(size, nb_stars, symmetry, &mut rng).unwrap();
+            let regions = (0..100)
+                .find_map(|_| {
+                    carve_regions(
+                        size,
+                        nb_stars,
+                        &stars,
+                        symmetry,
+                        size * size,
+                        RegionGrowth::Compact(1),
+                        &mut rng,
+                    )
+                })
+                .unwrap();
+            assert_stars_per_region(size, nb_stars, &stars, &regions);
+            // 2 cases d'une même région ont leurs images dans une même région
+            for cell in 0..size * size {
+                for other in 0..size * size {
+                    if regions[cell] == regions[other] {
+                        assert_eq!(
+                            regions[image(symmetry, size, cell)],
+                            regions[image(symmetry, size, other)]
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Découpage aléatoire d'une grille en régions connexes, sans placement d'étoiles.
+//!
+//! Chaque région grandit à partir d'une case 'germe' tirée au hasard. À chaque étape, la plus petite
+//! région qui peut encore grandir reçoit une case voisine (horizontalement ou verticalement) tirée au
+//! hasard : les régions obtenues sont connexes et de tailles voisines.<br>
+//! Ce découpage ne garantit pas qu'une grille ait une solution : il sert de point de départ pour des
+//! expérimentations ou un générateur de grilles.
+
+use rand::seq::index::sample;
+use rand::Rng;
+
+use crate::Region;
+
+use super::generator::{region_char, GeneratorError, REGION_CHARS};
+
+/// Découpe aléatoirement une grille de `nb_lines` x `nb_columns` cases en `nb_regions` régions connexes.<br>
+/// Retourne les régions de chaque ligne de la grille, dont les symboles sont attribués dans l'ordre de leur
+/// première apparition dans la grille (voir [`crate::GridParser`])
+/// ### Errors
+/// Retourne un [`GeneratorError::InvalidParameters`] s'il n'y a pas de région, plus de régions que de
+/// cases ou plus de régions que de symboles disponibles
+pub fn generate_regions<R: Rng>(
+    nb_lines: usize,
+    nb_columns: usize,
+    nb_regions: usize,
+    rng: &mut R,
+) -> Result<Vec<Vec<Region>>, GeneratorError> {
+    let nb_cells = nb_lines * nb_columns;
+    if nb_regions == 0 || nb_regions > nb_cells {
+        return Err(GeneratorError::InvalidParameters(format!(
+            "Nombre de régions ({nb_regions}) invalide pour une grille de {nb_cells} cases"
+        )));
+    }
+    if nb_regions > REGION_CHARS.len() {
+        return Err(GeneratorError::InvalidParameters(format!(
+            "Trop de régions ({nb_regions}) pour une grille (max. {})",
+            REGION_CHARS.len()
+        )));
+    }
+
+    // Région (par index) de chaque case (par index ligne x colonne)
+    let mut owners: Vec<Option<usize>> = vec![None; nb_cells];
+    let mut region_sizes = vec![1; nb_regions];
+    // Cases candidates pour agrandir chaque région
+    let mut frontiers = vec![Vec::new(); nb_regions];
+    for (region, cell) in sample(rng, nb_cells, nb_regions).into_iter().enumerate() {
+        owners[cell] = Some(region);
+        frontiers[region] = neighbors(nb_lines, nb_columns, cell);
+    }
+
+    loop {
+        // Plus petite région qui peut encore grandir
+        for frontier in &mut frontiers {
+            frontier.retain(|cell| owners[*cell].is_none());
+        }
+        let Some(region) = (0..nb_regions)
+            .filter(|region| !frontiers[*region].is_empty())
+            .min_by_key(|region| region_sizes[*region])
+        else {
+            break;
+        };
+        let frontier = &mut frontiers[region];
+        let cell = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+        owners[cell] = Some(region);
+        region_sizes[region] += 1;
+        frontier.extend(
+            neighbors(nb_lines, nb_columns, cell)
+                .into_iter()
+                .filter(|neighbor| owners[*neighbor].is_none()),
+        );
+    }
+
+    // Symboles des régions dans l'ordre de leur première apparition
+    let mut symbols: Vec<usize> = Vec::with_capacity(nb_regions);
+    Ok(owners
+        .chunks(nb_columns)
+        .map(|line| {
+            line.iter()
+                .map(|owner| {
+                    let region = owner.expect("Toutes les cases d'une grille sont accessibles");
+                    let index = symbols
+                        .iter()
+                        .position(|symbol| *symbol == region)
+                        .unwrap_or_else(|| {
+                            symbols.push(region);
+                            symbols.len() - 1
+                        });
+                    region_char(index)
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Cases voisines (horizontalement ou verticalement) d'une case (par index) d'une grille de `nb_lines` x
+/// `nb_columns` cases
+fn neighbors(nb_lines: usize, nb_columns: usize, cell: usize) -> Vec<usize> {
+    let (line, column) = (cell / nb_columns, cell % nb_columns);
+    let mut neighbors = Vec::with_capacity(4);
+    if line > 0 {
+        neighbors.push(cell - nb_columns);
+    }
+    if line + 1 < nb_lines {
+        neighbors.push(cell + nb_columns);
+    }
+    if column > 0 {
+        neighbors.push(cell - 1);
+    }
+    if column + 1 < nb_columns {
+        neighbors.push(cell + 1);
+    }
+    neighbors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::GridParser;
+
+    #[test]
+    fn test_generate_regions() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (nb_lines, nb_columns, nb_regions) in [(5, 5, 5), (10, 10, 10), (6, 9, 12), (3, 3, 9)] {
+            let regions = generate_regions(nb_lines, nb_columns, nb_regions, &mut rng).unwrap();
+            assert_eq!(regions.len(), nb_lines);
+            assert!(regions.iter().all(|line| line.len() == nb_columns));
+
+            // Régions connexes (vérifiées par la lecture de la grille)
+            let lines: Vec<String> = regions.iter().map(|line| line.iter().collect()).collect();
+            let parser = GridParser::try_from(lines).unwrap();
+            assert_eq!(parser.regions().len(), nb_regions);
+        }
+
+        assert!(generate_regions(5, 5, 0, &mut rng).is_err());
+        assert!(generate_regions(2, 2, 5, &mut rng).is_err());
+        assert!(generate_regions(10, 10, REGION_CHARS.len() + 1, &mut rng).is_err());
+    }
+}
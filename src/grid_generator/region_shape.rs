@@ -0,0 +1,49 @@
+//! Mesures de la forme des régions d'une grille générée.
+//!
+//! Les régions des grilles publiées sont en général assez 'compactes'. Pour s'en approcher, le générateur
+//! peut limiter la taille des régions et leur 'sinuosité' : le rapport entre le périmètre d'une région
+//! (nombre de côtés de cases en bordure de la région) et son aire (nombre de cases).<br>
+//! Un carré de 3x3 cases a une sinuosité de 12 / 9 = 1,33 alors qu'une ligne de 9 cases a une
+//! sinuosité de 20 / 9 = 2,22.
+
+use super::region_carver::orthogonal_neighbors;
+
+/// Nombre de cases de chacune des `nb_regions` régions d'un découpage (région par index de case)
+pub fn region_sizes(nb_regions: usize, regions: &[usize]) -> Vec<usize> {
+    let mut sizes = vec![0; nb_regions];
+    for region in regions {
+        sizes[*region] += 1;
+    }
+    sizes
+}
+
+/// Sinuosité (périmètre / aire) d'une région (non vide) d'un découpage d'une grille de `size` x `size`
+/// cases
+#[allow(clippy::cast_precision_loss)]
+pub fn region_snakiness(size: usize, regions: &[usize], region: usize) -> f64 {
+    let mut area = 0;
+    let mut perimeter = 0;
+    for cell in (0..regions.len()).filter(|cell| regions[*cell] == region) {
+        area += 1;
+        let same_region_neighbors = orthogonal_neighbors(size, cell)
+            .into_iter()
+            .filter(|neighbor| regions[*neighbor] == region)
+            .count();
+        perimeter += 4 - same_region_neighbors;
+    }
+    perimeter as f64 / area as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_shape() {
+        // Grille 3x3 : une région en 'L' (0) et une région de 2x2 cases (1)
+        let regions = [0, 0, 0, 0, 1, 1, 0, 1, 1];
+        assert_eq!(region_sizes(2, &regions), vec![5, 4]);
+        assert!((region_snakiness(3, &regions, 0) - 12.0 / 5.0).abs() < f64::EPSILON);
+        assert!((region_snakiness(3, &regions, 1) - 8.0 / 4.0).abs() < f64::EPSILON);
+    }
+}
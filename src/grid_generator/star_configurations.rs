@@ -0,0 +1,326 @@
+//! Énumération des placements d'étoiles valides d'une grille vide.
+//!
+//! Un placement est valide s'il y a `nb_stars` étoiles dans chaque ligne et chaque colonne d'une grille
+//! carrée sans que 2 étoiles soient adjacentes. Les régions ne sont pas prises en compte.
+//!
+//! Les placements sont construits ligne par ligne. Le nombre de façons de compléter un placement ne
+//! dépend que de la ligne courante, des colonnes des étoiles de la ligne précédente et du nombre
+//! d'étoiles déjà placées dans chaque colonne : ces dénombrements partiels sont mémorisés, ce qui permet
+//! de compter les placements et d'en tirer un au hasard de façon uniforme sans tous les énumérer.
+
+use std::collections::HashMap;
+
+use rand::Rng;
+
+use crate::LineColumn;
+
+use super::star_placement::combine_columns;
+
+/// État d'un placement partiel : (ligne à compléter, colonnes des étoiles de la ligne précédente, nombre
+/// d'étoiles déjà placées dans chaque colonne)
+type PartialPlacement = (usize, Vec<usize>, Vec<usize>);
+
+/// Combinaison de colonnes des étoiles d'une ligne et nombre d'étoiles de chaque colonne qui en résulte
+type LineCandidate = (Vec<usize>, Vec<usize>);
+
+/// Placements d'étoiles valides d'une grille carrée de `size` x `size` cases avec `nb_stars` étoiles par
+/// ligne et par colonne
+#[derive(Debug)]
+pub struct StarConfigurations {
+    /// Nombre de lignes et de colonnes de la grille
+    size: usize,
+
+    /// Nombre d'étoiles par ligne et par colonne
+    nb_stars: usize,
+
+    /// Nombre de façons de compléter chaque placement partiel déjà examiné
+    counts: HashMap<PartialPlacement, u128>,
+}
+
+impl StarConfigurations {
+    /// Constructeur des placements d'étoiles d'une grille de `size` x `size` cases avec `nb_stars` étoiles
+    /// par ligne et par colonne
+    #[must_use]
+    pub fn new(size: usize, nb_stars: usize) -> Self {
+        Self {
+            size,
+            nb_stars,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Nombre de placements d'étoiles valides.<br>
+    /// Ce nombre croît très vite avec la taille de la grille : le calcul n'est envisageable que pour des
+    /// grilles de taille raisonnable (jusqu'à 10x10 environ).
+    pub fn count(&mut self) -> u128 {
+        if self.size == 0 || self.nb_stars == 0 {
+            return 0;
+        }
+        self.count_from(0, &[], &vec![0; self.size])
+    }
+
+    /// Tire au hasard un placement d'étoiles valide, tous les placements ayant la même probabilité.<br>
+    /// Retourne la liste des étoiles (triées par ligne puis par colonne) ou `None` s'il n'existe aucun
+    /// placement valide.
+    pub fn sample<R: Rng>(&mut self, rng: &mut R) -> Option<Vec<LineColumn>> {
+        if self.count() == 0 {
+            return None;
+        }
+        let mut stars = Vec::with_capacity(self.size * self.nb_stars);
+        let mut previous = Vec::new();
+        let mut column_stars = vec![0; self.size];
+        for line in 0..self.size {
+            let weighted: Vec<(Vec<usize>, Vec<usize>, u128)> =
+                line_candidates(self.size, self.nb_stars, line, &previous, &column_stars)
+                    .into_iter()
+                    .map(|(columns, new_column_stars)| {
+                        let count = self.count_from(line + 1, &columns, &new_column_stars);
+                        (columns, new_column_stars, count)
+                    })
+                    .collect();
+            let total: u128 = weighted.iter().map(|(_, _, count)| count).sum();
+            let mut draw = rng.gen_range(0..total);
+            let (columns, new_column_stars, _) = weighted
+                .into_iter()
+                .find(|(_, _, count)| {
+                    if draw < *count {
+                        true
+                    } else {
+                        draw -= count;
+                        false
+                    }
+                })
+                .expect("Tirage dans le total des dénombrements");
+            stars.extend(columns.iter().map(|column| LineColumn::new(line, *column)));
+            previous = columns;
+            column_stars = new_column_stars;
+        }
+        Some(stars)
+    }
+
+    /// Itérateur sur tous les placements d'étoiles valides (chaque placement est la liste de ses étoiles,
+    /// triées par ligne puis par colonne)
+    #[must_use]
+    pub fn iter(&self) -> StarConfigurationsIter {
+        let mut iter = StarConfigurationsIter {
+            size: self.size,
+            nb_stars: self.nb_stars,
+            stack: Vec::new(),
+            lines: Vec::new(),
+            column_stars: vec![0; self.size],
+        };
+        if self.size > 0 && self.nb_stars > 0 {
+            let candidates = iter.line_candidates(0);
+            iter.stack.push((candidates, 0));
+        }
+        iter
+    }
+
+    /// Nombre de façons de compléter le placement à partir de la ligne `line`
+    fn count_from(&mut self, line: usize, previous: &[usize], column_stars: &[usize]) -> u128 {
+        if line == self.size {
+            return 1;
+        }
+        let key = (line, previous.to_vec(), column_stars.to_vec());
+        if let Some(count) = self.counts.get(&key) {
+            return *count;
+        }
+        let count = line_candidates(self.size, self.nb_stars, line, previous, column_stars)
+            .into_iter()
+            .map(|(columns, new_column_stars)| {
+                self.count_from(line + 1, &columns, &new_column_stars)
+            })
+            .sum();
+        self.counts.insert(key, count);
+        count
+    }
+}
+
+/// Itérateur sur les placements d'étoiles valides (voir [`StarConfigurations::iter`])
+#[derive(Debug)]
+pub struct StarConfigurationsIter {
+    /// Nombre de lignes et de colonnes de la grille
+    size: usize,
+
+    /// Nombre d'étoiles par ligne et par colonne
+    nb_stars: usize,
+
+    /// Pour chaque ligne en cours, combinaisons de colonnes possibles et index de la prochaine à examiner
+    stack: Vec<(Vec<LineCandidate>, usize)>,
+
+    /// Colonnes des étoiles des lignes déjà placées
+    lines: Vec<Vec<usize>>,
+
+    /// Nombre d'étoiles placées dans chaque colonne
+    column_stars: Vec<usize>,
+}
+
+impl StarConfigurationsIter {
+    /// Combinaisons de colonnes possibles pour la ligne `line` compte tenu des lignes déjà placées
+    fn line_candidates(&self, line: usize) -> Vec<LineCandidate> {
+        let previous = self.lines.last().map_or(&[][..], Vec::as_slice);
+        line_candidates(self.size, self.nb_stars, line, previous, &self.column_stars)
+    }
+}
+
+impl Iterator for StarConfigurationsIter {
+    type Item = Vec<LineColumn>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.stack.is_empty() {
+            let line = self.stack.len() - 1;
+            if self.lines.len() > line {
+                for column in self.lines.pop().unwrap_or_default() {
+                    self.column_stars[column] -= 1;
+                }
+            }
+            let (candidates, next) = &mut self.stack[line];
+            let Some((columns, _)) = candidates.get(*next) else {
+                self.stack.pop();
+                continue;
+            };
+            let columns = columns.clone();
+            *next += 1;
+            for column in &columns {
+                self.column_stars[*column] += 1;
+            }
+            self.lines.push(columns);
+
+            if line + 1 == self.size {
+                let stars = self
+                    .lines
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(line, columns)| {
+                        columns
+                            .iter()
+                            .map(move |column| LineColumn::new(line, *column))
+                    })
+                    .collect();
+                return Some(stars);
+            }
+            let candidates = self.line_candidates(line + 1);
+            self.stack.push((candidates, 0));
+        }
+        None
+    }
+}
+
+/// Combinaisons de `nb_stars` colonnes non adjacentes possibles pour les étoiles de la ligne `line` d'une
+/// grille de `size` x `size` cases, sachant les colonnes `previous` des étoiles de la ligne précédente et
+/// le nombre `column_stars` d'étoiles déjà placées dans chaque colonne.<br>
+/// Seules les combinaisons qui laissent assez de lignes pour compléter chaque colonne sont retenues (avec
+/// le nombre d'étoiles de chaque colonne qui en résulte).
+fn line_candidates(
+    size: usize,
+    nb_stars: usize,
+    line: usize,
+    previous: &[usize],
+    column_stars: &[usize],
+) -> Vec<LineCandidate> {
+    let allowed: Vec<usize> = (0..size)
+        .filter(|column| {
+            column_stars[*column] < nb_stars
+                && previous
+                    .iter()
+                    .all(|previous_column| previous_column.abs_diff(*column) > 1)
+        })
+        .collect();
+    let mut combinations = Vec::new();
+    combine_columns(
+        &allowed,
+        0,
+        nb_stars,
+        &mut Vec::with_capacity(nb_stars),
+        &mut combinations,
+    );
+
+    // 2 étoiles d'une même colonne ne peuvent pas être sur des lignes consécutives
+    let lines_left = size - line - 1;
+    combinations
+        .into_iter()
+        .filter_map(|columns| {
+            let mut new_column_stars = column_stars.to_vec();
+            for column in &columns {
+                new_column_stars[*column] += 1;
+            }
+            let feasible = (0..size).all(|column| {
+                let max_possible = if columns.contains(&column) {
+                    lines_left / 2
+                } else {
+                    lines_left.div_ceil(2)
+                };
+                nb_stars - new_column_stars[column] <= max_possible
+            });
+            feasible.then_some((columns, new_column_stars))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_count() {
+        // Nombre de permutations sans éléments consécutifs adjacents (OEIS A002464)
+        for (size, expected) in [(3, 0), (4, 2), (5, 14), (6, 90), (7, 646), (8, 5242)] {
+            assert_eq!(StarConfigurations::new(size, 1).count(), expected);
+        }
+        assert_eq!(StarConfigurations::new(0, 1).count(), 0);
+        assert_eq!(StarConfigurations::new(5, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_iter() {
+        for (size, nb_stars) in [(4, 1), (6, 1), (7, 2)] {
+            let mut configurations = StarConfigurations::new(size, nb_stars);
+            let all: Vec<Vec<LineColumn>> = configurations.iter().collect();
+            assert_eq!(all.len() as u128, configurations.count());
+            for stars in &all {
+                assert_eq!(stars.len(), size * nb_stars);
+                for index in 0..size {
+                    assert_eq!(stars.iter().filter(|s| s.column == index).count(), nb_stars);
+                }
+            }
+        }
+        assert_eq!(
+            StarConfigurations::new(4, 1).iter().collect::<Vec<_>>(),
+            vec![
+                vec![
+                    LineColumn::new(0, 1),
+                    LineColumn::new(1, 3),
+                    LineColumn::new(2, 0),
+                    LineColumn::new(3, 2)
+                ],
+                vec![
+                    LineColumn::new(0, 2),
+                    LineColumn::new(1, 0),
+                    LineColumn::new(2, 3),
+                    LineColumn::new(3, 1)
+                ]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut configurations = StarConfigurations::new(5, 1);
+        let all: Vec<Vec<LineColumn>> = configurations.iter().collect();
+
+        // Chacun des 14 placements est tiré environ 100 fois sur 1400 tirages
+        let mut draws = vec![0; all.len()];
+        for _ in 0..1_400 {
+            let stars = configurations.sample(&mut rng).unwrap();
+            let index = all.iter().position(|other| *other == stars).unwrap();
+            draws[index] += 1;
+        }
+        assert!(draws.iter().all(|nb| (50..150).contains(nb)));
+
+        assert!(StarConfigurations::new(3, 1).sample(&mut rng).is_none());
+    }
+}
@@ -0,0 +1,312 @@
+//! Placement aléatoire d'étoiles dans une grille vide.
+//!
+//! On cherche ici une façon de placer `nb_stars` étoiles dans chaque ligne et chaque colonne d'une grille
+//! sans que 2 étoiles soient adjacentes. Les régions ne sont pas prises en compte : c'est le placement
+//! des étoiles qui sert ensuite de 'solution' autour de laquelle les régions sont construites.
+//!
+//! Si une symétrie est demandée, le placement est invariant par cette symétrie : placer (ou exclure) une
+//! étoile dans une case place (ou exclut) également une étoile dans toutes les images de cette case.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::GridTransform;
+use crate::LineColumn;
+
+use super::symmetry::orbit;
+
+/// Nombre maximum de lignes examinées avant d'abandonner une recherche de placement
+const MAX_EXPLORED_LINES: usize = 10_000;
+
+/// Recherche aléatoire d'un placement de `nb_stars` étoiles par ligne et par colonne dans une grille carrée
+/// de `size` x `size` cases, sans étoiles adjacentes et invariant par `symmetry`.<br>
+/// Retourne la liste des étoiles placées (triées par ligne puis par colonne) ou `None` si aucun placement
+/// n'a été trouvé.
+pub fn random_star_placement<R: Rng>(
+    size: usize,
+    nb_stars: usize,
+    symmetry: GridTransform,
+    rng: &mut R,
+) -> Option<Vec<LineColumn>> {
+    if size == 0 || nb_stars == 0 {
+        return None;
+    }
+    let mut placement = StarPlacement {
+        size,
+        nb_stars,
+        symmetry,
+        state: PlacementState {
+            values: vec![None; size * size],
+            line_stars: vec![0; size],
+            column_stars: vec![0; size],
+        },
+        explored_lines: 0,
+    };
+    if placement.place_line(0, rng) {
+        let stars = placement
+            .state
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value == Some(true))
+            .map(|(cell, _)| LineColumn::new(cell / size, cell % size))
+            .collect();
+        Some(stars)
+    } else {
+        None
+    }
+}
+
+/// État courant d'un placement d'étoiles
+#[derive(Clone)]
+struct PlacementState {
+    /// Contenu de chaque case (par index) : `Some(true)` pour une étoile, `Some(false)` pour une case sans
+    /// étoile et `None` pour une case pas encore décidée
+    values: Vec<Option<bool>>,
+
+    /// Nombre d'étoiles déjà placées dans chaque ligne
+    line_stars: Vec<usize>,
+
+    /// Nombre d'étoiles déjà placées dans chaque colonne
+    column_stars: Vec<usize>,
+}
+
+/// Contexte de la recherche d'un placement d'étoiles
+struct StarPlacement {
+    /// Taille de la grille
+    size: usize,
+
+    /// Nombre d'étoiles par ligne et par colonne
+    nb_stars: usize,
+
+    /// Symétrie du placement
+    symmetry: GridTransform,
+
+    /// État courant du placement
+    state: PlacementState,
+
+    /// Nombre de lignes examinées depuis le début de la recherche
+    explored_lines: usize,
+}
+
+impl StarPlacement {
+    /// Place les étoiles de la ligne `line` et poursuit récursivement avec les lignes suivantes.<br>
+    /// Retourne `true` si toutes les lignes ont pu être complétées.
+    fn place_line<R: Rng>(&mut self, line: usize, rng: &mut R) -> bool {
+        if line == self.size {
+            return true;
+        }
+        self.explored_lines += 1;
+        if self.explored_lines > MAX_EXPLORED_LINES {
+            return false;
+        }
+
+        let mut candidates = self.line_candidates(line);
+        candidates.shuffle(rng);
+        for columns in candidates {
+            let saved_state = self.state.clone();
+            if self.apply_line(line, &columns)
+                && self.columns_feasible(line)
+                && self.place_line(line + 1, rng)
+            {
+                return true;
+            }
+            self.state = saved_state;
+        }
+        false
+    }
+
+    /// Liste des combinaisons de colonnes possibles pour les étoiles de la ligne `line`.<br>
+    /// Chaque combinaison comprend les étoiles déjà imposées sur cette ligne par symétrie.
+    fn line_candidates(&self, line: usize) -> Vec<Vec<usize>> {
+        let mut forced = Vec::new();
+        let mut allowed = Vec::new();
+        for column in 0..self.size {
+            let cell = line * self.size + column;
+            match self.state.values[cell] {
+                Some(true) => forced.push(column),
+                Some(false) => (),
+                None => {
+                    if self.state.column_stars[column] < self.nb_stars
+                        && !self.is_star_adjacent(cell)
+                    {
+                        allowed.push(column);
+                    }
+                }
+            }
+        }
+        if forced.len() > self.nb_stars {
+            return Vec::new();
+        }
+
+        let mut candidates = Vec::new();
+        let mut current = Vec::with_capacity(self.nb_stars);
+        combine_columns(
+            &allowed,
+            0,
+            self.nb_stars - forced.len(),
+            &mut current,
+            &mut candidates,
+        );
+        candidates
+            .into_iter()
+            .map(|mut columns| {
+                columns.extend(&forced);
+                columns.sort_unstable();
+                columns
+            })
+            .filter(|columns| columns.windows(2).all(|pair| pair[1] > pair[0] + 1))
+            .collect()
+    }
+
+    /// Retourne `true` si une case voisine (y compris en diagonale) d'une case est une étoile
+    fn is_star_adjacent(&self, cell: usize) -> bool {
+        let (line, column) = (cell / self.size, cell % self.size);
+        for adjacent_line in line.saturating_sub(1)..=(line + 1).min(self.size - 1) {
+            for adjacent_column in column.saturating_sub(1)..=(column + 1).min(self.size - 1) {
+                let adjacent = adjacent_line * self.size + adjacent_column;
+                if adjacent != cell && self.state.values[adjacent] == Some(true) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Décide du contenu de toutes les cases de la ligne `line` (étoiles dans les colonnes `columns`).<br>
+    /// Retourne `false` si ce contenu (ou celui des images de ces cases) n'est pas possible.
+    fn apply_line(&mut self, line: usize, columns: &[usize]) -> bool {
+        (0..self.size)
+            .all(|column| self.set_value(line * self.size + column, columns.contains(&column)))
+    }
+
+    /// Décide du contenu d'une case et de toutes ses images par symétrie.<br>
+    /// Retourne `false` si ce contenu n'est pas possible.
+    fn set_value(&mut self, cell: usize, is_star: bool) -> bool {
+        for image in orbit(self.symmetry, self.size, cell) {
+            match self.state.values[image] {
+                Some(value) if value != is_star => return false,
+                Some(_) => (),
+                None => {
+                    if is_star {
+                        let (line, column) = (image / self.size, image % self.size);
+                        if self.is_star_adjacent(image)
+                            || self.state.line_stars[line] >= self.nb_stars
+                            || self.state.column_stars[column] >= self.nb_stars
+                        {
+                            return false;
+                        }
+                        self.state.line_stars[line] += 1;
+                        self.state.column_stars[column] += 1;
+                    }
+                    self.state.values[image] = Some(is_star);
+                }
+            }
+        }
+        true
+    }
+
+    /// Vérifie qu'il reste assez de lignes après la ligne `line` pour compléter chaque colonne, sachant
+    /// que 2 étoiles d'une même colonne ne peuvent pas être sur des lignes consécutives
+    fn columns_feasible(&self, line: usize) -> bool {
+        let lines_left = self.size - line - 1;
+        (0..self.size).all(|column| {
+            let needed = self.nb_stars - self.state.column_stars[column];
+            let max_possible = if self.state.values[line * self.size + column] == Some(true) {
+                lines_left / 2
+            } else {
+                lines_left.div_ceil(2)
+            };
+            needed <= max_possible
+        })
+    }
+}
+
+/// Construit toutes les combinaisons de `nb` colonnes non adjacentes parmi `allowed[from..]`
+pub(super) fn combine_columns(
+    allowed: &[usize],
+    from: usize,
+    nb: usize,
+    current: &mut Vec<usize>,
+    candidates: &mut Vec<Vec<usize>>,
+) {
+    if current.len() == nb {
+        candidates.push(current.clone());
+        return;
+    }
+    for index in from..allowed.len() {
+        let column = allowed[index];
+        if current.last().is_none_or(|last| column > last + 1) {
+            current.push(column);
+            combine_columns(allowed, index + 1, nb, current, candidates);
+            current.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::grid_generator::symmetry::image;
+
+    // Vérifie qu'un placement d'étoiles respecte les règles du jeu (hors régions)
+    fn assert_valid_placement(size: usize, nb_stars: usize, stars: &[LineColumn]) {
+        assert_eq!(stars.len(), size * nb_stars);
+        for index in 0..size {
+            assert_eq!(stars.iter().filter(|s| s.line == index).count(), nb_stars);
+            assert_eq!(stars.iter().filter(|s| s.column == index).count(), nb_stars);
+        }
+        for star in stars {
+            for other in stars {
+                if star != other {
+                    assert!(
+                        star.line.abs_diff(other.line) > 1
+                            || star.column.abs_diff(other.column) > 1
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_star_placement() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (size, nb_stars) in [(5, 1), (8, 1), (10, 2)] {
+            let stars =
+                random_star_placement(size, nb_stars, GridTransform::Identity, &mut rng).unwrap();
+            assert_valid_placement(size, nb_stars, &stars);
+        }
+    }
+
+    #[test]
+    fn test_symmetric_star_placement() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for (size, nb_stars, symmetry) in [
+            (8, 1, GridTransform::Rotate180),
+            (9, 1, GridTransform::Rotate90),
+            (10, 2, GridTransform::Rotate180),
+            (10, 2, GridTransform::Transpose),
+        ] {
+            let stars = random_star_placement(size, nb_stars, symmetry, &mut rng).unwrap();
+            assert_valid_placement(size, nb_stars, &stars);
+            for star in &stars {
+                let cell = image(symmetry, size, star.line * size + star.column);
+                assert!(stars.contains(&LineColumn::new(cell / size, cell % size)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_impossible_star_placement() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Impossible de placer une étoile par ligne et colonne sans adjacence dans une grille 3x3
+        assert!(random_star_placement(3, 1, GridTransform::Identity, &mut rng).is_none());
+        // Avec une symétrie gauche/droite, la (ou les) colonne(s) centrale(s) ne peuvent pas être complétées
+        assert!(random_star_placement(5, 1, GridTransform::MirrorHorizontal, &mut rng).is_none());
+        assert!(random_star_placement(10, 2, GridTransform::MirrorHorizontal, &mut rng).is_none());
+    }
+}
@@ -0,0 +1,44 @@
+//! Symétrie des grilles générées.
+//!
+//! Une grille symétrique est invariante par une [`GridTransform`] : l'image d'une case d'une région est une
+//! case de la région 'image' et l'image d'une étoile de la solution est une étoile de la solution.
+//!
+//! Les fonctions de ce module travaillent sur les cases d'une grille carrée repérées par leur index
+//! (ligne x colonne).
+
+use crate::GridTransform;
+use crate::LineColumn;
+
+/// Image d'une case (par index) d'une grille carrée de `size` x `size` cases
+pub fn image(symmetry: GridTransform, size: usize, cell: usize) -> usize {
+    let line_column = symmetry.apply(LineColumn::new(cell / size, cell % size), size, size);
+    line_column.line * size + line_column.column
+}
+
+/// Orbite d'une case (par index) : la case suivie de ses images successives jusqu'à retrouver la case
+pub fn orbit(symmetry: GridTransform, size: usize, cell: usize) -> Vec<usize> {
+    let mut cells = vec![cell];
+    let mut current = image(symmetry, size, cell);
+    while current != cell {
+        cells.push(current);
+        current = image(symmetry, size, current);
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orbit() {
+        // Grille 4x4 : la case 0 (A1) a 4 images par rotation d'un quart de tour
+        assert_eq!(orbit(GridTransform::Rotate90, 4, 0), vec![0, 3, 15, 12]);
+        assert_eq!(orbit(GridTransform::Rotate180, 4, 0), vec![0, 15]);
+        assert_eq!(orbit(GridTransform::MirrorHorizontal, 4, 1), vec![1, 2]);
+        assert_eq!(orbit(GridTransform::Identity, 4, 5), vec![5]);
+
+        // Grille 5x5 : la case centrale est sa propre image
+        assert_eq!(orbit(GridTransform::Rotate90, 5, 12), vec![12]);
+    }
+}
@@ -1,12 +1,103 @@
 //! Examine toutes les possibilités pour poser les étoiles manquantes dans une zone et recherche
 //! si des cases sont invariantes pour toutes ces possibilités.<br>
 
+use std::collections::HashSet;
+
 use crate::check_bad_rules;
 use crate::CellValue;
 use crate::Grid;
 use crate::GridHandler;
 use crate::LineColumn;
 
+use super::SolverLimits;
+
+/// Nombre maximum de grilles possibles collectées par défaut pour une zone : toutes les grilles possibles
+/// sont collectées.<br>
+/// Un budget plus faible (voir [`crate::SolverConfig::with_max_possible_grids`]) abandonne l'examen des
+/// zones qui ont trop de grilles possibles pour accélérer la résolution des grandes grilles (17x17 et plus),
+/// au risque de ne plus trouver certaines règles.
+pub const MAX_POSSIBLE_GRIDS: usize = usize::MAX;
+
+/// Nombre maximum de combinaisons des étoiles manquantes d'une zone pour retenir automatiquement la
+/// stratégie [`CollectorStrategy::BruteForce`] (voir [`CollectorStrategy::for_zone`])
+pub const BRUTE_FORCE_MAX_COMBINATIONS: usize = 64;
+
+/// Nombre maximum de combinaisons des étoiles manquantes construites par une recherche en 'force brute'
+/// (`collect_possible_grids` ou [`CollectorStrategy::BruteForce`]).<br>
+/// Au-delà, l'examen de la zone est abandonné comme s'il y avait trop de grilles possibles : le nombre
+/// de combinaisons d'une grande zone (plusieurs lignes d'une grande grille) se compte en milliards.
+pub const MAX_EXPLORED_COMBINATIONS: usize = 1_000_000;
+
+/// Stratégie de recherche récursive des grilles possibles d'une zone (voir
+/// `Collector::collect_recursive_possible_grids`).<br>
+/// Les 2 stratégies trouvent exactement les mêmes grilles possibles, dans le même ordre : seule la durée
+/// de la recherche diffère
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CollectorStrategy {
+    /// Toutes les combinaisons des étoiles manquantes sont construites puis la viabilité de chaque grille
+    /// obtenue est vérifiée : efficace s'il y a peu de combinaisons
+    BruteForce,
+
+    /// Les étoiles sont posées une par une et la viabilité de la grille est vérifiée après chacune d'elles
+    /// pour écarter au plus tôt les combinaisons impossibles : efficace s'il y a beaucoup de combinaisons
+    Recursive,
+}
+
+impl CollectorStrategy {
+    /// Stratégie retenue pour une zone avec `nb_cells` cases non définies et `nb_missing_stars` étoiles
+    /// manquantes : [`CollectorStrategy::BruteForce`] s'il y a au plus [`BRUTE_FORCE_MAX_COMBINATIONS`]
+    /// combinaisons possibles de ces étoiles, sinon [`CollectorStrategy::Recursive`]
+    #[must_use]
+    pub const fn for_zone(nb_cells: usize, nb_missing_stars: usize) -> Self {
+        if nb_missing_stars > nb_cells {
+            return Self::BruteForce;
+        }
+        // Nombre de combinaisons de `nb_missing_stars` cases parmi `nb_cells` (sans dépasser le seuil)
+        let mut nb_combinations: usize = 1;
+        let mut index = 0;
+        while index < nb_missing_stars {
+            nb_combinations = match nb_combinations.checked_mul(nb_cells - index) {
+                Some(product) => product / (index + 1),
+                None => return Self::Recursive,
+            };
+            if nb_combinations > BRUTE_FORCE_MAX_COMBINATIONS {
+                return Self::Recursive;
+            }
+            index += 1;
+        }
+        Self::BruteForce
+    }
+}
+
+/// Grille possible d'une zone, mémorisée par les seules cases modifiées par rapport à la grille examinée
+/// (triées dans l'ordre des cases de la grille) : la recherche des grilles possibles n'a pas à copier
+/// toute la grille pour chacune d'elles
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PossibleGrid {
+    /// Cases modifiées avec leur valeur dans cette grille possible
+    changes: Vec<(LineColumn, CellValue)>,
+}
+
+impl PossibleGrid {
+    /// Valeur d'une case dans cette grille possible de la grille examinée `grid`
+    pub fn value(&self, grid: &Grid, line_column: LineColumn) -> CellValue {
+        match self
+            .changes
+            .binary_search_by_key(&line_column, |(line_column, _)| *line_column)
+        {
+            Ok(index) => self.changes[index].1.clone(),
+            Err(_) => grid.cell(line_column).value.clone(),
+        }
+    }
+
+    /// Mémoire occupée par cette grille possible (en octets)
+    pub fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.changes.capacity() * std::mem::size_of::<(LineColumn, CellValue)>()
+    }
+}
+
 /// Structure pour la recherche des combinaisons possibles qui positionnent
 /// le nombre attendu d'étoiles dans une zone.<br>
 ///
@@ -16,7 +107,9 @@ use crate::LineColumn;
 ///
 /// * `collect_possible_grids` : Recherche les combinaisons possibles dans la zone uniquement
 /// * `collect_recursive_possible_grids` : Recherche les combinaison de manière récursive en
-///   examinant les autres cases des grilles possibles
+///   examinant les autres cases des grilles possibles. La stratégie de cette recherche
+///   ([`CollectorStrategy`]) est choisie pour chaque zone selon son nombre de cases non définies et
+///   d'étoiles manquantes (ou imposée par `with_strategy`)
 ///
 /// Pour cela, cette structure `Collector` s'utilise comme suit :
 ///
@@ -27,6 +120,10 @@ use crate::LineColumn;
 ///
 /// Ensuite, la fonction `Variant::check_for_invariants` permet examiner les différentes grilles possibles
 /// pour en extraire d'éventuelles cases invariantes dans toutes les combinaisons
+///
+/// La recherche modifie une unique grille de travail : chaque modification est mémorisée pour être
+/// annulée au retour d'une branche de la recherche et seules les cases modifiées d'une grille possible
+/// sont conservées ([`PossibleGrid`])
 pub struct Collector<'a> {
     /// Handler de la grille à étudier
     handler: &'a GridHandler,
@@ -41,12 +138,43 @@ pub struct Collector<'a> {
     nb_stars: usize,
 
     /// Liste des combinaisons de grilles possibles pour placer le nombre d'étoiles demandés dans la zone
-    pub possible_grids: Vec<Grid>,
+    /// (sans doublon, dans l'ordre de leur découverte)
+    pub possible_grids: Vec<PossibleGrid>,
+
+    /// Grilles possibles déjà collectées (pour écarter les doublons)
+    known_possible_grids: HashSet<PossibleGrid>,
+
+    /// Nombre maximum de grilles possibles collectées (voir [`MAX_POSSIBLE_GRIDS`])
+    max_possible_grids: usize,
+
+    /// Nombre maximum de combinaisons construites par une recherche en 'force brute' (voir
+    /// [`MAX_EXPLORED_COMBINATIONS`])
+    max_explored_combinations: usize,
+
+    /// Nombre de combinaisons déjà construites par la recherche en 'force brute'
+    nb_explored_combinations: usize,
+
+    /// Indique que la recherche a été abandonnée car il y a plus de `max_possible_grids` grilles
+    /// possibles ou de `max_explored_combinations` combinaisons (`possible_grids` est alors incomplet)
+    is_overflow: bool,
+
+    /// Stratégie imposée pour la recherche récursive (choisie pour la zone si None)
+    strategy: Option<CollectorStrategy>,
+
+    /// Limites de la recherche (aucune si None)
+    limits: Option<&'a SolverLimits>,
+
+    /// Grille de travail : grille à étudier modifiée par la branche en cours de la recherche
+    work_grid: Grid,
+
+    /// Cases modifiées dans la grille de travail avec leur valeur précédente, dans l'ordre des
+    /// modifications (pour les annuler)
+    trail: Vec<(LineColumn, CellValue)>,
 }
 
 impl<'a> Collector<'a> {
     /// Constructeur d'une zone à examiner
-    pub const fn new(
+    pub fn new(
         handler: &'a GridHandler,
         grid: &'a Grid,
         zone: &'a Vec<LineColumn>,
@@ -58,30 +186,170 @@ impl<'a> Collector<'a> {
             zone,
             nb_stars,
             possible_grids: Vec::new(),
+            known_possible_grids: HashSet::new(),
+            max_possible_grids: MAX_POSSIBLE_GRIDS,
+            max_explored_combinations: MAX_EXPLORED_COMBINATIONS,
+            nb_explored_combinations: 0,
+            is_overflow: false,
+            strategy: None,
+            limits: None,
+            // Une copie de la grille n'est pas observée
+            work_grid: grid.clone(),
+            trail: Vec::new(),
         }
     }
 
+    /// Impose la stratégie de la recherche récursive (choix automatique pour la zone si None)
+    pub const fn with_strategy(mut self, strategy: Option<CollectorStrategy>) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Définit le nombre maximum de grilles possibles à collecter avant d'abandonner la recherche
+    pub const fn with_max_possible_grids(mut self, max_possible_grids: usize) -> Self {
+        self.max_possible_grids = max_possible_grids;
+        self
+    }
+
+    /// Définit les limites de la recherche : la recherche est abandonnée dès qu'elles sont atteintes
+    pub const fn with_limits(mut self, limits: &'a SolverLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Indique si la recherche a été abandonnée car il y a trop de grilles possibles (ou de combinaisons
+    /// à construire) dans la zone ou car les limites de la recherche sont atteintes
+    pub const fn is_overflow(&self) -> bool {
+        self.is_overflow
+    }
+
+    /// Indique si la recherche a été abandonnée car le nombre maximum de grilles possibles est atteint
+    /// (voir [`Collector::with_max_possible_grids`])
+    pub fn is_possible_grids_overflow(&self) -> bool {
+        self.is_overflow && self.possible_grids.len() >= self.max_possible_grids
+    }
+
+    /// Modifie la valeur d'une case de la grille de travail (modification annulée par `undo`)
+    fn set_value(&mut self, line_column: LineColumn, value: CellValue) {
+        let old = std::mem::replace(&mut self.work_grid.cell_mut(line_column).value, value);
+        self.trail.push((line_column, old));
+    }
+
+    /// Annule les modifications de la grille de travail postérieures au repère `mark` (taille de `trail`)
+    fn undo(&mut self, mark: usize) {
+        while self.trail.len() > mark {
+            if let Some((line_column, old)) = self.trail.pop() {
+                self.work_grid.cell_mut(line_column).value = old;
+            }
+        }
+    }
+
+    /// Complète les cases non définies de la zone dans la grille de travail par des cases sans étoile
+    fn set_zone_unknown_no_star(&mut self) {
+        let zone = self.zone;
+        for line_column in zone {
+            if self.work_grid.cell(*line_column).is_unknown() {
+                self.set_value(*line_column, CellValue::NoStar);
+            }
+        }
+    }
+
+    /// Ajoute la grille de travail aux grilles possibles si elle n'y est pas déjà (ou abandonne la recherche
+    /// s'il y en a déjà trop)
+    fn push_possible_grid(&mut self) {
+        if self.possible_grids.len() < self.max_possible_grids {
+            // Chaque case n'est modifiée qu'une fois dans une branche de la recherche
+            let mut changes: Vec<(LineColumn, CellValue)> = self
+                .trail
+                .iter()
+                .map(|(line_column, _)| {
+                    (
+                        *line_column,
+                        self.work_grid.cell(*line_column).value.clone(),
+                    )
+                })
+                .collect();
+            changes.sort_unstable_by_key(|(line_column, _)| *line_column);
+            // Les cases modifiées ne le sont qu'à partir d'une case non définie : deux grilles possibles
+            // identiques ont les mêmes cases modifiées
+            let possible_grid = PossibleGrid { changes };
+            if self.known_possible_grids.insert(possible_grid.clone()) {
+                self.possible_grids.push(possible_grid);
+            }
+        } else {
+            #[cfg(feature = "tracing")]
+            if !self.is_overflow {
+                tracing::trace!(
+                    max_possible_grids = self.max_possible_grids,
+                    "trop de grilles possibles, examen de la zone abandonné"
+                );
+            }
+            self.is_overflow = true;
+        }
+    }
+
+    /// Décompte une nouvelle combinaison examinée dans les limites de la recherche.<br>
+    /// Retourne `false` (et abandonne la recherche) si ces limites sont atteintes
+    fn count_combination(&mut self) -> bool {
+        if self
+            .limits
+            .is_some_and(|limits| !limits.count_combination())
+        {
+            #[cfg(feature = "tracing")]
+            if !self.is_overflow {
+                tracing::trace!("limites de la recherche atteintes, examen de la zone abandonné");
+            }
+            self.is_overflow = true;
+        }
+        !self.is_overflow
+    }
+
+    /// Décompte une nouvelle combinaison construite par la recherche en 'force brute'.<br>
+    /// Retourne `false` (et abandonne la recherche) si le nombre maximum de combinaisons ou les limites de
+    /// la recherche sont atteints
+    fn explore_combination(&mut self) -> bool {
+        if !self.count_combination() {
+            return false;
+        }
+        if self.nb_explored_combinations >= self.max_explored_combinations {
+            #[cfg(feature = "tracing")]
+            if !self.is_overflow {
+                tracing::trace!(
+                    max_explored_combinations = self.max_explored_combinations,
+                    "trop de combinaisons, examen de la zone abandonné"
+                );
+            }
+            self.is_overflow = true;
+            return false;
+        }
+        self.nb_explored_combinations += 1;
+        true
+    }
+
     /// Cherche les combinaisons possibles qui positionnent le nombre attendu d'étoiles dans la zone.
     ///
     /// On utilise ici la 'force brute' pour tester toutes les façons de poser les étoiles manquantes
     /// dans la zone.
     ///
-    /// S'il y a n étoiles à placer (n > 0) dans les m cases non définies d'une zone,
-    /// on explore tous les nombres de 1 à 2**m -1 qui ont n bits à 1 et on positionne des étoiles
-    /// dans tous les i-eme cases si me i-eme bit est 1.
-    /// Si la grille obtenue est 'viable', on la retient comme combinaison possible.
+    /// S'il y a n étoiles à placer (n > 0) dans les m cases non définies d'une zone, on explore toutes les
+    /// combinaisons de n cases parmi ces m cases en écartant au plus tôt les cases adjacentes à une étoile
+    /// déjà posée. Si la grille obtenue est 'viable', on la retient comme combinaison possible.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            skip_all,
+            fields(nb_stars = self.nb_stars, nb_cells = self.zone.len())
+        )
+    )]
     pub fn collect_possible_grids(&mut self) {
         let mut cur_nb_stars = 0; // Nombre d'étoiles déjà placées dans la région
-        let mut cur_nb_unknown = 0; // Nombre de cases non définies dans la grille
         let mut cur_line_column_unknown = Vec::new(); // Coordonnées des cases non définies dans la région
         for line_column in self.zone {
             match self.grid.cell(*line_column).value {
                 CellValue::Star => cur_nb_stars += 1,
                 CellValue::NoStar => (),
-                CellValue::Unknown => {
-                    cur_nb_unknown += 1;
-                    cur_line_column_unknown.push(*line_column);
-                }
+                CellValue::Unknown => cur_line_column_unknown.push(*line_column),
             }
         }
 
@@ -95,35 +363,69 @@ impl<'a> Collector<'a> {
         let nb_to_do_star = self.nb_stars - cur_nb_stars;
 
         assert!(
-            nb_to_do_star <= cur_nb_unknown,
+            nb_to_do_star <= cur_line_column_unknown.len(),
             "Situation inattendue lors de l'examen de la région !"
         );
 
-        // Boucle sur toutes les façons de poser `nb_to_do_star` étoiles dans les
-        // `cur_nb_unknown` cases non définies.
-        for combinaison in 1..usize::pow(
-            2,
-            u32::try_from(cur_nb_unknown).expect("Région trop grande (32 cases inconnues max) !"),
-        ) {
-            // On a besoin d'autant de bits à 1 dans combinaison qu'on d'étoiles à placer
-            if count_ones(combinaison) == nb_to_do_star {
-                // On crée un nouvelle grille possible avec toutes les étoiles positionnées dans la région
-                let mut new_grid = self.grid.clone();
-                for (i, line_column) in cur_line_column_unknown.iter().enumerate() {
-                    new_grid.cell_mut(*line_column).value = {
-                        if combinaison & (1 << i) == 0 {
-                            CellValue::NoStar
-                        } else {
-                            CellValue::Star
-                        }
-                    }
-                }
+        // Exploration de toutes les façons de poser `nb_to_do_star` étoiles dans les cases non définies
+        self.collect_combinations(
+            &cur_line_column_unknown,
+            nb_to_do_star,
+            &mut Vec::with_capacity(nb_to_do_star),
+        );
+    }
 
-                // Si cette nouvelle grille est viable... on l'ajoute à la liste des grilles possibles
-                if check_bad_rules(self.handler, &new_grid).is_ok() {
-                    self.possible_grids.push(new_grid);
+    /// Complète récursivement la combinaison `stars` avec des cases de `candidates` jusqu'à avoir
+    /// `nb_to_do_star` étoiles et retient les grilles viables ainsi obtenues
+    fn collect_combinations(
+        &mut self,
+        candidates: &[LineColumn],
+        nb_to_do_star: usize,
+        stars: &mut Vec<LineColumn>,
+    ) {
+        if self.is_overflow {
+            return;
+        }
+        if stars.len() == nb_to_do_star {
+            if !self.explore_combination() {
+                return;
+            }
+            // On complète la grille de travail avec toutes les étoiles positionnées dans la région
+            let mark = self.trail.len();
+            let zone = self.zone;
+            for line_column in zone {
+                if self.work_grid.cell(*line_column).is_unknown() {
+                    let value = if stars.contains(line_column) {
+                        CellValue::Star
+                    } else {
+                        CellValue::NoStar
+                    };
+                    self.set_value(*line_column, value);
                 }
             }
+
+            // Si cette nouvelle grille est viable... on l'ajoute à la liste des grilles possibles
+            if check_bad_rules(self.handler, &self.work_grid).is_ok() {
+                self.push_possible_grid();
+            }
+            self.undo(mark);
+            return;
+        }
+
+        // Pas assez de cases restantes pour placer les étoiles manquantes
+        if candidates.len() < nb_to_do_star - stars.len() {
+            return;
+        }
+
+        for (index, line_column) in candidates.iter().enumerate() {
+            // Une étoile adjacente à une étoile déjà posée n'est pas possible
+            let adjacent_cells = self.handler.adjacent_cells(*line_column);
+            if stars.iter().any(|star| adjacent_cells.contains(star)) {
+                continue;
+            }
+            stars.push(*line_column);
+            self.collect_combinations(&candidates[index + 1..], nb_to_do_star, stars);
+            stars.pop();
         }
     }
 
@@ -137,106 +439,315 @@ impl<'a> Collector<'a> {
     /// - Puis, on définit qu'il n'y a pas d'étoile dans cette case et on recherche à nouveau les grilles possibles
     ///   avec cette combinaison. Cette recherche se fait en appelant à nouveau le même algorithme de recherche
     /// - En final, toutes les grilles possibles collectées 'récursivement' sont des grilles possibles pour la zone
-    pub fn collect_recursive_possible_grids(&mut self) {
-        // Décompte du nombre d'étoiles qui restent à placer dans la zone
-        let nb_current_stars = self
-            .zone
+    ///
+    /// Retourne la stratégie de recherche utilisée (voir [`CollectorStrategy`])
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            skip_all,
+            fields(nb_stars = self.nb_stars, nb_cells = self.zone.len())
+        )
+    )]
+    pub fn collect_recursive_possible_grids(&mut self) -> CollectorStrategy {
+        let grid = self.grid;
+        let nb_current_stars = self.zone_nb_stars(grid);
+        let strategy = self.strategy.unwrap_or_else(|| {
+            let nb_cells = self
+                .zone
+                .iter()
+                .filter(|line_column| grid.cell(**line_column).is_unknown())
+                .count();
+            CollectorStrategy::for_zone(nb_cells, self.nb_stars.saturating_sub(nb_current_stars))
+        });
+        match strategy {
+            CollectorStrategy::Recursive => self.collect_recursive(),
+            CollectorStrategy::BruteForce => {
+                // Une zone qui a déjà trop d'étoiles n'a pas de grille possible
+                if nb_current_stars <= self.nb_stars {
+                    self.collect_brute_force(0, self.nb_stars - nb_current_stars, &mut Vec::new());
+                }
+            }
+        }
+        strategy
+    }
+
+    /// Nombre d'étoiles de la zone dans la grille `grid`
+    fn zone_nb_stars(&self, grid: &Grid) -> usize {
+        self.zone
             .iter()
-            .filter(|line_column| self.grid.cell(**line_column).value == CellValue::Star)
-            .count();
+            .filter(|line_column| grid.cell(**line_column).value == CellValue::Star)
+            .count()
+    }
+
+    /// Recherche des grilles possibles par la stratégie [`CollectorStrategy::BruteForce`] : complète la
+    /// combinaison `stars` avec des cases de la zone à partir de l'index `start` jusqu'à avoir
+    /// `nb_to_do_star` étoiles.<br>
+    /// Les grilles retenues sont celles de [`CollectorStrategy::Recursive`] : les cases adjacentes aux
+    /// étoiles posées sont sans étoile et la viabilité de la grille est vérifiée telle qu'elle est lorsque
+    /// la recherche récursive pose la dernière étoile
+    fn collect_brute_force(&mut self, start: usize, nb_to_do_star: usize, stars: &mut Vec<usize>) {
+        if self.is_overflow {
+            return;
+        }
+        let zone = self.zone;
+        if stars.len() == nb_to_do_star {
+            if !self.explore_combination() {
+                return;
+            }
+            let mark = self.trail.len();
+            let mut previous = 0;
+            for index in stars.iter() {
+                // Cases de la zone écartées avant cette étoile par la recherche récursive
+                for line_column in &zone[previous..*index] {
+                    if self.is_possible_star(&self.work_grid, *line_column) {
+                        self.set_value(*line_column, CellValue::NoStar);
+                    }
+                }
+                self.set_star(zone[*index]);
+                previous = index + 1;
+            }
+            if stars.is_empty() || check_bad_rules(self.handler, &self.work_grid).is_ok() {
+                self.set_zone_unknown_no_star();
+                self.push_possible_grid();
+            }
+            self.undo(mark);
+            return;
+        }
+
+        for index in start..zone.len() {
+            let line_column = zone[index];
+            // Case non définie sans étoile adjacente (dans la grille ou dans la combinaison)
+            if !self.is_possible_star(self.grid, line_column)
+                || stars.iter().any(|star| {
+                    self.handler
+                        .adjacent_cells(zone[*star])
+                        .contains(&line_column)
+                })
+            {
+                continue;
+            }
+            stars.push(index);
+            self.collect_brute_force(index + 1, nb_to_do_star, stars);
+            stars.pop();
+        }
+    }
+
+    /// Recherche récursive des grilles possibles à partir de la grille de travail
+    fn collect_recursive(&mut self) {
+        if self.is_overflow {
+            return;
+        }
+
+        // Décompte du nombre d'étoiles qui restent à placer dans la zone
+        let nb_current_stars = self.zone_nb_stars(&self.work_grid);
 
         if nb_current_stars == self.nb_stars {
             // Toutes les étoiles sont placées dans la zone
             // La grille courante est la seule possibilité dans ce cas...
             // On complète les cases non définies de cette zone par des cases sans étoile
-            let mut new_grid = self.grid.clone();
-            for line_column in self.zone {
-                if new_grid.cell(*line_column).value == CellValue::Unknown {
-                    new_grid.cell_mut(*line_column).value = CellValue::NoStar;
-                }
-            }
-            self.possible_grids.push(new_grid);
-            // ...qu'on retourne
+            let mark = self.trail.len();
+            self.set_zone_unknown_no_star();
+            // ...qu'on retient
+            self.push_possible_grid();
+            self.undo(mark);
             return;
         }
 
         // Au moins une étoile est à placer. On cherche la première case possible dans la zone pour cela
-        if let Some(line_column) = self.first_possible_line_column_for_a_star() {
-            // On construit alors une nouvelle grille possible
-            // Et on pose une étoile dans cette case dans une nouvelle grille possible
+        if let Some(line_column) = self.first_possible_line_column_for_a_star(&self.work_grid) {
+            // On pose une étoile dans cette case de la grille de travail
             // et on invalide la possibilité d'une étoile pour toutes les cases adjacentes
-            let mut new_grid = self.grid.clone();
-            self.set_star(&mut new_grid, line_column);
+            let mark = self.trail.len();
+            self.set_star(line_column);
             // Si cette nouvelle grille est viable...
-            if check_bad_rules(self.handler, &new_grid).is_ok() {
+            if self.count_combination() && check_bad_rules(self.handler, &self.work_grid).is_ok() {
                 // ...on recherche les grilles possibles pour cette nouvelle grille
-                let mut new_collector =
-                    Collector::new(self.handler, &new_grid, self.zone, self.nb_stars);
-                new_collector.collect_recursive_possible_grids();
-                // Toutes les grilles trouvées par ce nouveau collector sont des grilles possibles pour la grille courante
-                self.possible_grids.extend(new_collector.possible_grids);
+                self.collect_recursive();
             }
+            self.undo(mark);
 
-            //  Puis on construit une autre grille possible pour la zone sans une étoile dans cette case
-            let mut new_grid = self.grid.clone();
-            new_grid.cell_mut(line_column).value = CellValue::NoStar;
-            // On recherche les grilles possibles pour cette nouvelle grille
-            let mut new_collector =
-                Collector::new(self.handler, &new_grid, self.zone, self.nb_stars);
-            new_collector.collect_recursive_possible_grids();
-            // Toutes les grilles trouvées par ce nouveau collector sont des grilles possibles pour la grille courante
-            self.possible_grids.extend(new_collector.possible_grids);
+            //  Puis on recherche les grilles possibles pour la zone sans une étoile dans cette case
+            self.set_value(line_column, CellValue::NoStar);
+            self.collect_recursive();
+            self.undo(mark);
         }
 
-        // On retourne les grilles trouvées jusqu'ici
+        // Les grilles trouvées sont collectées dans `possible_grids`
     }
 
     /// Recherche la première case possible pour poser une étoile dans la zone
-    fn first_possible_line_column_for_a_star(&self) -> Option<LineColumn> {
-        for line_column in self.zone {
-            // Case possible pour poser une étoile ?
-            if self.grid.cell(*line_column).is_unknown() {
-                // Il ne faut pas d'étoiles dans les cases adjacentes à cette case
-                if self
-                    .handler
-                    .adjacent_cells(*line_column)
-                    .iter()
-                    .filter(|line_column| self.grid.cell(**line_column).value == CellValue::Star)
-                    .count()
-                    == 0
-                {
-                    return Some(*line_column);
-                }
-            }
-        }
-        None
+    fn first_possible_line_column_for_a_star(&self, grid: &Grid) -> Option<LineColumn> {
+        self.zone
+            .iter()
+            .copied()
+            .find(|line_column| self.is_possible_star(grid, *line_column))
+    }
+
+    /// Indique si une étoile peut être posée dans la case : case non définie et pas d'étoile dans les
+    /// cases adjacentes
+    fn is_possible_star(&self, grid: &Grid, line_column: LineColumn) -> bool {
+        grid.cell(line_column).is_unknown()
+            && !self
+                .handler
+                .adjacent_cells(line_column)
+                .iter()
+                .any(|line_column| grid.cell(*line_column).is_star())
     }
 
-    /// Pose une étoile sur une grille possible et indique que toutes les cases autour de cette étoile
+    /// Pose une étoile dans la grille de travail et indique que toutes les cases autour de cette étoile
     /// ne peuvent pas être une étoile
-    fn set_star(&self, new_grid: &mut Grid, line_column: LineColumn) {
-        // Pose une étoile dans cette case dans une nouvelle grille possible
-        new_grid.cell_mut(line_column).value = CellValue::Star;
+    fn set_star(&mut self, line_column: LineColumn) {
+        // Pose une étoile dans cette case de la grille de travail
+        self.set_value(line_column, CellValue::Star);
         // On indique que toutes les cases autour de cette étoile ne peuvent pas être une étoile
         for adjacent_line_column in self.handler.adjacent_cells(line_column) {
-            match self.grid.cell(adjacent_line_column).value {
+            match self.work_grid.cell(adjacent_line_column).value {
                 CellValue::Star => panic!("Bug dans l'algo !!! La case {adjacent_line_column} ne devrait pas être une étoile"),
                 CellValue::NoStar => (),
-                CellValue::Unknown => new_grid.cell_mut(adjacent_line_column).value = CellValue::NoStar,
+                CellValue::Unknown => self.set_value(adjacent_line_column, CellValue::NoStar),
             }
         }
     }
 }
 
-/// Compte le nombre de bits à 1 dans un usize
-const fn count_ones(n: usize) -> usize {
-    let mut count = 0;
-    let mut num = n;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::get_good_rule;
+    use crate::GridParser;
+    use crate::GridSurfer;
+
+    #[test]
+    fn test_strategy_for_zone() {
+        assert_eq!(
+            CollectorStrategy::for_zone(5, 1),
+            CollectorStrategy::BruteForce
+        );
+        // 10 parmi 5 : 252 combinaisons
+        assert_eq!(
+            CollectorStrategy::for_zone(10, 5),
+            CollectorStrategy::Recursive
+        );
+        // 8 parmi 2 : 28 combinaisons
+        assert_eq!(
+            CollectorStrategy::for_zone(8, 2),
+            CollectorStrategy::BruteForce
+        );
+        assert_eq!(
+            CollectorStrategy::for_zone(100, 3),
+            CollectorStrategy::Recursive
+        );
+        assert_eq!(
+            CollectorStrategy::for_zone(2, 3),
+            CollectorStrategy::BruteForce
+        );
+    }
+
+    #[test]
+    fn test_max_explored_combinations() {
+        // Pas de dépassement de capacité pour les très grandes zones
+        assert_eq!(
+            CollectorStrategy::for_zone(usize::MAX, 3),
+            CollectorStrategy::Recursive
+        );
+        assert_eq!(
+            CollectorStrategy::for_zone(200, 100),
+            CollectorStrategy::Recursive
+        );
+
+        // 4 combinaisons pour placer l'étoile de la région A : la recherche est abandonnée au-delà de 3
+        let (handler, grid) = grid!["AABB", "AABB", "CCDD", "CCDD"; stars = 1];
+        let zone = handler.surfer(&grid, &GridSurfer::Region('A'));
+        for (max_explored_combinations, is_overflow) in [(3, true), (4, false)] {
+            let mut collector = Collector::new(&handler, &grid, &zone, 1);
+            collector.max_explored_combinations = max_explored_combinations;
+            collector.collect_possible_grids();
+            assert_eq!(collector.is_overflow(), is_overflow);
+
+            let mut collector = Collector::new(&handler, &grid, &zone, 1)
+                .with_strategy(Some(CollectorStrategy::BruteForce));
+            collector.max_explored_combinations = max_explored_combinations;
+            collector.collect_recursive_possible_grids();
+            assert_eq!(collector.is_overflow(), is_overflow);
+        }
+    }
+
+    #[test]
+    fn test_possible_grid_value() {
+        let (handler, grid) = grid!["AABB", "AABB", "CCDD", "CCDD"; stars = 1];
+        let zone = handler.surfer(&grid, &GridSurfer::Region('A'));
+        let mut collector = Collector::new(&handler, &grid, &zone, 1);
+        collector.collect_recursive_possible_grids();
+        assert_eq!(collector.possible_grids.len(), 4);
+
+        // 1ère grille possible : étoile en A1, les cases adjacentes sont sans étoile
+        let possible_grid = &collector.possible_grids[0];
+        assert_eq!(
+            possible_grid.value(&grid, LineColumn::new(0, 0)),
+            CellValue::Star
+        );
+        assert_eq!(
+            possible_grid.value(&grid, LineColumn::new(1, 1)),
+            CellValue::NoStar
+        );
+        assert_eq!(
+            possible_grid.value(&grid, LineColumn::new(3, 3)),
+            CellValue::Unknown
+        );
+        // La grille examinée n'est pas modifiée
+        assert!(grid.cell(LineColumn::new(0, 0)).is_unknown());
 
-    while num > 0 {
-        count += num & 1; // Ajoute 1 si le bit de poids faible est 1
-        num >>= 1; // Décale num vers la droite
+        // Une même grille possible n'est collectée qu'une seule fois
+        collector.set_star(LineColumn::new(0, 0));
+        collector.set_zone_unknown_no_star();
+        collector.push_possible_grid();
+        assert_eq!(collector.possible_grids.len(), 4);
     }
 
-    count
+    #[test]
+    fn test_strategies_same_possible_grids() {
+        let parser = GridParser::try_from(
+            std::fs::read_to_string("./test_grids/moyen01_2.txt")
+                .unwrap()
+                .as_str(),
+        )
+        .unwrap();
+        let handler = GridHandler::new(&parser, 2);
+        let mut grid = Grid::from(&handler);
+        let mut zones: Vec<GridSurfer> = handler
+            .regions()
+            .into_iter()
+            .map(GridSurfer::Region)
+            .collect();
+        zones.extend((0..handler.nb_lines()).map(GridSurfer::Line));
+        zones.extend(
+            (0..handler.nb_columns() - 1).map(|column| GridSurfer::Columns(column..=column + 1)),
+        );
+
+        // Mêmes grilles possibles (dans le même ordre) à chaque étape de la résolution
+        loop {
+            for zone in &zones {
+                let surfer = handler.surfer(&grid, zone);
+                let nb_stars = handler.zone_nb_stars(zone);
+                let collect = |strategy| {
+                    let mut collector = Collector::new(&handler, &grid, &surfer, nb_stars)
+                        .with_strategy(Some(strategy));
+                    assert_eq!(collector.collect_recursive_possible_grids(), strategy);
+                    (collector.is_overflow(), collector.possible_grids)
+                };
+                assert_eq!(
+                    collect(CollectorStrategy::BruteForce),
+                    collect(CollectorStrategy::Recursive),
+                    "zone {zone}"
+                );
+            }
+            match get_good_rule(&handler, &grid).unwrap() {
+                Some(good_rule) => grid.apply_good_rule(&good_rule),
+                None => break,
+            }
+        }
+        assert!(handler.is_done(&grid));
+    }
 }
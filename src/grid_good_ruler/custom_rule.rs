@@ -0,0 +1,245 @@
+//! Règles de construction/résolution définies par l'utilisateur.
+//!
+//! Une application peut ajouter ses propres règles ([`Rule`]) aux règles intégrées (voir
+//! [`crate::SolverConfig::with_rule`]) : une règle est examinée juste après les règles intégrées de son
+//! niveau de coût et elle est trouvée avec ce niveau.
+//!
+//! Les actions d'une règle définie par l'utilisateur sont vérifiées avant que la règle ne soit retenue :
+//! une règle qui ne modifie aucune case non définie ou qui rend la grille invalide est ignorée.
+
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridHandler;
+
+use super::NB_GOOD_RULE_LEVELS;
+
+/// Règle de construction définie par l'utilisateur
+pub trait Rule: Send + Sync {
+    /// Recherche de la règle sur la grille : retourne une règle applicable si trouvée. None sinon.<br>
+    /// Les actions de la règle doivent porter sur des cases non définies de la grille et la laisser
+    /// valide : sinon, ou si la règle ne modifie aucune case, la règle retournée est ignorée (voir
+    /// [`Grid::apply_good_rule_checked`])
+    fn apply(&self, handler: &GridHandler, grid: &Grid) -> Option<GoodRule>;
+
+    /// Nom de la règle
+    fn name(&self) -> &str;
+
+    /// Niveau de coût de la règle (entre 0 et [`NB_GOOD_RULE_LEVELS`] - 1, ramené au dernier niveau
+    /// au-delà) : la règle est examinée après les règles intégrées de ce niveau. Dernier niveau par défaut
+    fn cost(&self) -> usize {
+        NB_GOOD_RULE_LEVELS - 1
+    }
+}
+
+/// Règles définies par l'utilisateur d'une configuration, dans l'ordre de leur ajout
+#[derive(Clone, Default)]
+pub(crate) struct CustomRules {
+    rules: Vec<Arc<dyn Rule>>,
+}
+
+impl CustomRules {
+    /// Constructeur : aucune règle
+    pub(crate) const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Ajoute une règle
+    pub(crate) fn push(&mut self, rule: Arc<dyn Rule>) {
+        self.rules.push(rule);
+    }
+
+    /// Règles dans l'ordre de leur ajout
+    pub(crate) fn rules(&self) -> &[Arc<dyn Rule>] {
+        &self.rules
+    }
+
+    /// Règles examinées après les règles intégrées du niveau `level`
+    pub(crate) fn with_level(&self, level: usize) -> impl Iterator<Item = &dyn Rule> {
+        self.rules
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(move |rule| rule_level(*rule) == level)
+    }
+}
+
+/// Recherche de la règle définie par l'utilisateur `rule` sur la grille.<br>
+/// La règle trouvée n'est retenue que si ses actions modifient la grille et la laissent valide (voir
+/// [`Grid::apply_good_rule_checked`]) : la résolution ne peut pas boucler sur une règle sans effet
+pub(crate) fn apply_custom_rule(
+    rule: &dyn Rule,
+    handler: &GridHandler,
+    grid: &Grid,
+) -> Option<GoodRule> {
+    rule.apply(handler, grid).filter(|good_rule| {
+        let mut checked_grid = grid.clone();
+        checked_grid
+            .apply_good_rule_checked(handler, good_rule)
+            .is_ok()
+            && checked_grid != *grid
+    })
+}
+
+/// Niveau d'une règle définie par l'utilisateur (voir [`Rule::cost`])
+pub(crate) fn rule_level(rule: &dyn Rule) -> usize {
+    rule.cost().min(NB_GOOD_RULE_LEVELS - 1)
+}
+
+impl Debug for CustomRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.rules.iter().map(|rule| rule.name()))
+            .finish()
+    }
+}
+
+// Deux listes de règles sont égales si elles contiennent les mêmes règles (partagées) dans le même ordre
+impl PartialEq for CustomRules {
+    fn eq(&self, other: &Self) -> bool {
+        self.rules.len() == other.rules.len()
+            && self
+                .rules
+                .iter()
+                .zip(&other.rules)
+                .all(|(rule, other_rule)| Arc::ptr_eq(rule, other_rule))
+    }
+}
+
+impl Eq for CustomRules {}
+
+impl Hash for CustomRules {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for rule in &self.rules {
+            rule.name().hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::get_good_rule_with_config;
+    use crate::CellValue;
+    use crate::GridAction;
+    use crate::GridSurfer;
+    use crate::LineColumn;
+    use crate::SolverConfig;
+
+    /// Règle qui connaît la solution de la grille : la première case non définie sans étoile dans la
+    /// solution est sans étoile
+    struct SolutionRule(Grid);
+
+    impl Rule for SolutionRule {
+        fn apply(&self, _handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
+            let cell = grid
+                .iter_with_value(CellValue::Unknown)
+                .find(|cell| self.0.cell(cell.line_column).value == CellValue::NoStar)?;
+            Some(GoodRule::InvariantWithZone(
+                GridSurfer::Line(cell.line_column.line),
+                vec![GridAction::SetNoStar(cell.line_column)],
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "Solution"
+        }
+
+        fn cost(&self) -> usize {
+            0
+        }
+    }
+
+    /// Règle de niveau 0 qui retourne toujours les mêmes actions
+    struct FixedRule(Vec<GridAction>);
+
+    impl Rule for FixedRule {
+        fn apply(&self, _handler: &GridHandler, _grid: &Grid) -> Option<GoodRule> {
+            Some(GoodRule::InvariantWithZone(
+                GridSurfer::Line(0),
+                self.0.clone(),
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "Fixe"
+        }
+
+        fn cost(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn test_invalid_custom_rule() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let a1 = LineColumn::new(0, 0);
+        grid.apply_action(&GridAction::SetNoStar(a1)).unwrap();
+
+        // Règles sans effet, sur une case définie, en dehors de la grille ou qui rendent la grille
+        // invalide : ignorées
+        for actions in [
+            vec![],
+            vec![GridAction::SetUnknown(LineColumn::new(1, 1))],
+            vec![GridAction::SetNoStar(a1)],
+            vec![GridAction::SetStar(LineColumn::new(9, 9))],
+            vec![
+                GridAction::SetStar(LineColumn::new(1, 1)),
+                GridAction::SetStar(LineColumn::new(1, 3)),
+            ],
+        ] {
+            let config = SolverConfig::new()
+                .with_max_level(0)
+                .with_rule(FixedRule(actions));
+            assert!(get_good_rule_with_config(&grid_handler, &grid, &config)
+                .unwrap()
+                .is_none());
+        }
+
+        // La résolution s'arrête avec une règle sans effet
+        let config = SolverConfig::default().with_rule(FixedRule(vec![]));
+        let parser =
+            crate::GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        assert!(crate::solve_grid(&parser, 1, &config).unwrap().is_solved());
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let mut solution = grid.clone();
+        while let Some((good_rule, _)) =
+            get_good_rule_with_config(&grid_handler, &solution, &SolverConfig::default()).unwrap()
+        {
+            solution.apply_good_rule(&good_rule);
+        }
+
+        // Aucune règle intégrée de niveau 0 sur la grille initiale
+        let config = SolverConfig::new().with_max_level(0);
+        assert!(get_good_rule_with_config(&grid_handler, &grid, &config)
+            .unwrap()
+            .is_none());
+
+        // La règle définie par l'utilisateur est trouvée avec son niveau
+        let config = config.with_rule(SolutionRule(solution));
+        assert_eq!(config.custom_rules().len(), 1);
+        assert_eq!(config.custom_rules()[0].name(), "Solution");
+        let (good_rule, level) = get_good_rule_with_config(&grid_handler, &grid, &config)
+            .unwrap()
+            .unwrap();
+        assert_eq!(level, 0);
+        assert!(matches!(good_rule, GoodRule::InvariantWithZone(..)));
+        assert_eq!(config.clone(), config);
+        assert!(format!("{config:?}").contains("[\"Solution\"]"));
+
+        // Niveau de coût non examiné
+        let config = config.with_max_level(1).with_levels(&[1]);
+        assert!(!matches!(
+            get_good_rule_with_config(&grid_handler, &grid, &config),
+            Ok(Some((_, 0)))
+        ));
+    }
+}
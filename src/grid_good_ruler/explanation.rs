@@ -0,0 +1,331 @@
+//! Explications des règles de construction/résolution d'une grille.
+//!
+//! Une règle peut être expliquée de façon concise (une ligne, identique à son affichage) ou de façon
+//! pédagogique en détaillant le raisonnement qui conduit à ses actions.
+//!
+//! Expliquée dans le contexte d'une grille, une règle indique aussi la région de chaque case concernée
+//! (`B4 (région C)`) et le nombre d'étoiles qui restent à placer dans la zone examinée : l'explication se
+//! suffit alors à elle-même, sans se reporter à l'affichage de la grille.
+
+use crate::grid_action::display_vec_actions;
+use crate::CellValue;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::LineColumn;
+use crate::Locale;
+use crate::Region;
+
+/// Niveau de détail de l'explication d'une règle de construction (voir [`GoodRule::explain`])
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Verbosity {
+    /// Explication concise en une ligne (affichage de la règle)
+    #[default]
+    Terse,
+
+    /// Explication pédagogique qui détaille le raisonnement de la règle
+    Pedagogical,
+}
+
+impl GoodRule {
+    /// Explication de la règle selon le niveau de détail `verbosity`
+    #[must_use]
+    pub fn explain(&self, verbosity: Verbosity) -> String {
+        match verbosity {
+            Verbosity::Terse => self.to_string(),
+            Verbosity::Pedagogical => format!(
+                "{} Par conséquent : {}",
+                self.reasoning(Locale::French),
+                display_vec_actions(self.actions(), Locale::French)
+            ),
+        }
+    }
+
+    /// Explication de la règle selon le niveau de détail `verbosity` dans le contexte de la grille `grid`
+    /// (état de la grille avant l'application de la règle) : chaque case est annotée de sa région et le
+    /// nombre d'étoiles qui restent à placer dans la zone examinée par la règle est précisé
+    #[must_use]
+    pub fn explain_in(&self, handler: &GridHandler, grid: &Grid, verbosity: Verbosity) -> String {
+        self.explain_in_locale(handler, grid, verbosity, Locale::French)
+    }
+
+    /// Explication de la règle dans le contexte de la grille `grid` (voir [`GoodRule::explain_in`]) dans
+    /// la langue `locale`
+    #[must_use]
+    pub fn explain_in_locale(
+        &self,
+        handler: &GridHandler,
+        grid: &Grid,
+        verbosity: Verbosity,
+        locale: Locale,
+    ) -> String {
+        let actions = self
+            .actions()
+            .iter()
+            .map(|action| display_annotated_action(handler, action, locale))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let quota = self.zone().map(|zone| {
+            let nb_stars = handler.zone_nb_stars(zone);
+            let nb_remaining = nb_stars.saturating_sub(handler.surfer_cells_with_value_count(
+                grid,
+                zone,
+                &CellValue::Star,
+            ));
+            match locale {
+                Locale::French => {
+                    format!("{zone} : reste {nb_remaining} étoile(s) à placer sur {nb_stars}")
+                }
+                Locale::English => format!(
+                    "{}: {nb_remaining} star(s) left to place out of {nb_stars}",
+                    zone.text(locale)
+                ),
+            }
+        });
+        let summary = self.summary(locale);
+        let reasoning = self.reasoning(locale);
+        let consequently = match locale {
+            Locale::French => "Par conséquent",
+            Locale::English => "Therefore",
+        };
+        match (verbosity, quota) {
+            (Verbosity::Terse, None) => format!("{summary} : {actions}"),
+            (Verbosity::Terse, Some(quota)) => format!("{summary} ({quota}) : {actions}"),
+            (Verbosity::Pedagogical, None) => format!("{reasoning} {consequently} : {actions}"),
+            (Verbosity::Pedagogical, Some(quota)) => {
+                format!("{reasoning} {quota}. {consequently} : {actions}")
+            }
+        }
+    }
+
+    /// Raisonnement qui justifie les actions de la règle dans la langue `locale`
+    fn reasoning(&self, locale: Locale) -> String {
+        // Texte d'une liste de régions
+        fn display_regions(regions: &[Region]) -> String {
+            regions
+                .iter()
+                .map(|region| format!("'{region}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+
+        if locale == Locale::English {
+            return self.english_reasoning();
+        }
+        match self {
+            Self::NoStarAdjacentToStar(line_column, _) => format!(
+                "Une étoile est placée en {line_column}. Deux étoiles ne peuvent pas se toucher, même en \
+                 diagonale : aucune des cases qui entourent {line_column} ne peut donc contenir une étoile."
+            ),
+            Self::ZoneNoStarCompleted(grid_surfer, _) => format!(
+                "La zone {grid_surfer} contient déjà toutes ses étoiles : ses autres cases ne peuvent donc \
+                 plus contenir une étoile."
+            ),
+            Self::ZoneExclusions(regions, grid_surfer, _) => format!(
+                "Les cases restantes de {grid_surfer} n'appartiennent qu'aux régions {} : toutes les étoiles \
+                 de {grid_surfer} sont donc placées dans ces régions, qui ne peuvent pas en contenir d'autres. \
+                 Les cases de ces régions en dehors de {grid_surfer} ne peuvent donc pas contenir une étoile.",
+                display_regions(regions)
+            ),
+            Self::ZoneCombinations(regions, grid_surfer, _) => format!(
+                "Les cases restantes des régions {} sont toutes dans {grid_surfer} : les étoiles de ces \
+                 régions sont donc placées dans {grid_surfer}, qui ne peut pas en contenir d'autres. Les \
+                 autres cases de {grid_surfer} ne peuvent donc pas contenir une étoile.",
+                display_regions(regions)
+            ),
+            Self::ZoneStarCompleted(grid_surfer, _) => format!(
+                "Il reste dans la zone {grid_surfer} autant de cases possibles que d'étoiles à placer : \
+                 chacune de ces cases contient donc une étoile."
+            ),
+            Self::InvariantWithZone(grid_surfer, _) => format!(
+                "Quelle que soit la façon de placer les étoiles manquantes de la zone {grid_surfer}, \
+                 certaines cases ont toujours le même contenu : ce contenu est donc certain."
+            ),
+            Self::Contradiction(line_column, _) => format!(
+                "Supposons une étoile en {line_column} : ses cases adjacentes ne peuvent plus contenir \
+                 d'étoile et les zones complétées en découlent. La grille obtenue n'est plus valide : \
+                 {line_column} ne peut donc pas contenir une étoile."
+            ),
+            Self::BlockCounting(grid_surfer, _) => format!(
+                "Deux étoiles ne peuvent pas se toucher : un bloc de 2x2 cases contient au plus une étoile. \
+                 {grid_surfer} est découpé en autant de blocs possibles que d'étoiles à y placer : chacun \
+                 de ces blocs contient donc exactement une étoile."
+            ),
+            Self::LineSegments(grid_surfer, _) => format!(
+                "Deux étoiles ne peuvent pas se toucher : un segment de n cases consécutives de \
+                 {grid_surfer} contient au plus (n + 1) / 2 étoiles. La somme de ces maximums est égale au \
+                 nombre d'étoiles de {grid_surfer} : chaque segment contient donc son maximum d'étoiles."
+            ),
+        }
+    }
+
+    /// Raisonnement qui justifie les actions de la règle en anglais
+    fn english_reasoning(&self) -> String {
+        // Texte d'une liste de régions
+        fn display_regions(regions: &[Region]) -> String {
+            regions
+                .iter()
+                .map(|region| format!("'{region}'"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+
+        let zone = self
+            .zone()
+            .map(|zone| zone.text(Locale::English))
+            .unwrap_or_default();
+        match self {
+            Self::NoStarAdjacentToStar(line_column, _) => format!(
+                "A star is placed in {line_column}. Two stars cannot touch each other, not even \
+                 diagonally: none of the cells around {line_column} can therefore contain a star."
+            ),
+            Self::ZoneNoStarCompleted(..) => format!(
+                "The zone {zone} already contains all its stars: its other cells can therefore no \
+                 longer contain a star."
+            ),
+            Self::ZoneExclusions(regions, ..) => format!(
+                "The remaining cells of {zone} only belong to regions {}: all the stars of {zone} are \
+                 therefore placed in these regions, which cannot contain any other star. The cells of \
+                 these regions outside {zone} can therefore not contain a star.",
+                display_regions(regions)
+            ),
+            Self::ZoneCombinations(regions, ..) => format!(
+                "The remaining cells of regions {} are all in {zone}: the stars of these regions are \
+                 therefore placed in {zone}, which cannot contain any other star. The other cells of \
+                 {zone} can therefore not contain a star.",
+                display_regions(regions)
+            ),
+            Self::ZoneStarCompleted(..) => format!(
+                "The zone {zone} has as many possible cells left as stars to place: each of these \
+                 cells therefore contains a star."
+            ),
+            Self::InvariantWithZone(..) => format!(
+                "Whatever the way to place the missing stars of the zone {zone}, some cells always \
+                 have the same content: this content is therefore certain."
+            ),
+            Self::Contradiction(line_column, _) => format!(
+                "Suppose a star in {line_column}: its adjacent cells can no longer contain a star and \
+                 the completed zones follow. The resulting grid is no longer valid: {line_column} can \
+                 therefore not contain a star."
+            ),
+            Self::BlockCounting(..) => format!(
+                "Two stars cannot touch each other: a 2x2 block of cells contains at most one star. \
+                 {zone} is divided into as many possible blocks as stars to place: each of these \
+                 blocks therefore contains exactly one star."
+            ),
+            Self::LineSegments(..) => format!(
+                "Two stars cannot touch each other: a segment of n consecutive cells of {zone} \
+                 contains at most (n + 1) / 2 stars. The sum of these maximums is equal to the number \
+                 of stars of {zone}: each segment therefore contains its maximum of stars."
+            ),
+        }
+    }
+}
+
+/// Texte d'une case annotée de sa région (`B4 (région C)`) pour une grille avec des régions dans la
+/// langue `locale`
+fn display_annotated_line_column(
+    handler: &GridHandler,
+    line_column: LineColumn,
+    locale: Locale,
+) -> String {
+    if handler.has_regions() {
+        let region = handler.cell_region(line_column);
+        match locale {
+            Locale::French => format!("{line_column} (région {region})"),
+            Locale::English => format!("{line_column} (region {region})"),
+        }
+    } else {
+        line_column.to_string()
+    }
+}
+
+/// Texte d'une action dont la case est annotée de sa région dans la langue `locale`
+fn display_annotated_action(handler: &GridHandler, action: &GridAction, locale: Locale) -> String {
+    let line_column = display_annotated_line_column(handler, action.line_column(), locale);
+    action.text_with_cell(&line_column, locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridSurfer;
+
+    #[test]
+    fn test_explain() {
+        let good_rule = GoodRule::ZoneCombinations(
+            vec!['C'],
+            GridSurfer::Line(2),
+            vec![GridAction::SetNoStar(LineColumn::new(2, 3))],
+        );
+        assert_eq!(good_rule.explain(Verbosity::Terse), good_rule.to_string());
+
+        let explanation = good_rule.explain(Verbosity::Pedagogical);
+        assert!(
+            explanation.starts_with("Les cases restantes des régions 'C' sont toutes dans Ligne 3")
+        );
+        assert!(explanation.ends_with("Par conséquent : D3->Pas d'étoile"));
+    }
+
+    #[test]
+    fn test_explain_in() {
+        let (grid_handler, grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1; no_star = "A4"];
+        let good_rule = GoodRule::ZoneExclusions(
+            vec!['D'],
+            GridSurfer::Line(3),
+            vec![
+                GridAction::SetNoStar(LineColumn::new(4, 0)),
+                GridAction::SetNoStar(LineColumn::new(4, 4)),
+            ],
+        );
+        assert_eq!(
+            good_rule.explain_in(&grid_handler, &grid, Verbosity::Terse),
+            "Les cases restantes des regions D qui ne sont pas dans Ligne 4 ne peuvent être une étoile \
+             (Ligne 4 : reste 1 étoile(s) à placer sur 1) : A5 (région D)->Pas d'étoile, E5 (région D)->Pas d'étoile"
+        );
+        let explanation = good_rule.explain_in(&grid_handler, &grid, Verbosity::Pedagogical);
+        assert!(explanation.contains(
+            "Ligne 4 : reste 1 étoile(s) à placer sur 1. Par conséquent : A5 (région D)->Pas d'étoile"
+        ));
+
+        // Pas de zone examinée pour les cases adjacentes à une étoile
+        let good_rule = GoodRule::NoStarAdjacentToStar(
+            LineColumn::new(0, 0),
+            vec![GridAction::SetNoStar(LineColumn::new(1, 1))],
+        );
+        assert_eq!(
+            good_rule.explain_in(&grid_handler, &grid, Verbosity::Terse),
+            "Les cases adjacentes à l'étoile en A1 ne peuvent pas contenir une étoile : B2 (région B)->Pas d'étoile"
+        );
+    }
+
+    #[test]
+    fn test_explain_in_english() {
+        let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let good_rule = GoodRule::ZoneStarCompleted(
+            GridSurfer::Region('A'),
+            vec![GridAction::SetStar(LineColumn::new(0, 0))],
+        );
+        assert_eq!(
+            good_rule.explain_in_locale(&grid_handler, &grid, Verbosity::Terse, Locale::English),
+            "The remaining cells of Region 'A' can only be stars (Region 'A': 1 star(s) left to place \
+             out of 1) : A1 (region A)->Star"
+        );
+        assert!(good_rule
+            .explain_in_locale(
+                &grid_handler,
+                &grid,
+                Verbosity::Pedagogical,
+                Locale::English
+            )
+            .ends_with("Therefore : A1 (region A)->Star"));
+        assert_eq!(
+            good_rule.explain_in_locale(&grid_handler, &grid, Verbosity::Terse, Locale::French),
+            good_rule.explain_in(&grid_handler, &grid, Verbosity::Terse)
+        );
+    }
+}
@@ -5,34 +5,48 @@
 use std::fmt::Display;
 
 use crate::check_bad_rules;
-use crate::grid_action::display_vec_actions;
+use crate::grid_action::{check_action_cell, display_vec_actions};
+use crate::solve_metrics::{record_rule, record_rule_search};
 use crate::BadRuleError;
 use crate::Grid;
 use crate::GridAction;
+use crate::GridActionError;
 use crate::GridHandler;
 use crate::GridSurfer;
 use crate::LineColumn;
+use crate::Locale;
 use crate::Region;
 
+use super::custom_rule::apply_custom_rule;
+use super::rule_block_counting::rule_block_counting;
+use super::rule_candidates_intersection::rule_candidates_intersection;
+use super::rule_contradiction::rule_contradiction;
+use super::rule_line_segments::rule_line_segments;
 use super::rule_no_star_adjacent_to_star::rule_no_star_adjacent_to_star;
 use super::rule_region_combinations::{
     rule_region_1_combinations, rule_region_2_combinations, rule_region_3_combinations,
-    rule_region_4_combinations,
+    rule_region_4_combinations, rule_region_n_combinations,
 };
 use super::rule_region_exclusions::{
     rule_region_1_exclusions, rule_region_2_exclusions, rule_region_3_exclusions,
-    rule_region_4_exclusions,
+    rule_region_4_exclusions, rule_region_n_exclusions,
 };
 use super::rule_region_possible_stars::rule_region_possible_stars;
+use super::rule_strategy::{FixedOrder, RuleStrategy};
 use super::rule_value_completed::rule_value_completed;
 use super::rule_zone_possible_stars::{
     rule_line_column_recursive_possible_stars, rule_multi_2_lines_columns_recursive_possible_stars,
     rule_multi_3_lines_columns_recursive_possible_stars,
     rule_multi_4_lines_columns_recursive_possible_stars, rule_region_recursive_possible_stars,
 };
+use super::Interruption;
+use super::SolverConfig;
 
-/// Énumération des règles applicables à la construction/résolution d'une grille
-#[derive(Clone, Debug)]
+/// Énumération des règles applicables à la construction/résolution d'une grille.<br>
+/// Deux règles identiques peuvent différer par l'ordre de leurs actions ou la représentation de leur zone :
+/// elles sont égales une fois normalisées (voir [`GoodRule::normalize`])
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GoodRule {
     /// Indique les cases adjacentes à une étoile qui ne peuvent pas contenir une étoile
     NoStarAdjacentToStar(LineColumn, Vec<GridAction>),
@@ -54,10 +68,39 @@ pub enum GoodRule {
     /// Indique que quelle que soit la façon de placer les étoiles dans une zone, des cases n'ont
     /// toujours qu'une seule et même possibilité
     InvariantWithZone(GridSurfer, Vec<GridAction>),
+
+    /// Indique qu'une étoile dans une case et ses conséquences immédiates rendent la grille invalide : la
+    /// case ne peut pas contenir une étoile
+    Contradiction(LineColumn, Vec<GridAction>),
+
+    /// Indique que chaque bloc de 2x2 cases qui peut encore recevoir une étoile dans 2 lignes ou colonnes
+    /// consécutives en contient forcément une
+    BlockCounting(GridSurfer, Vec<GridAction>),
+
+    /// Indique que chaque segment de cases possibles d'une ligne ou colonne contient le maximum d'étoiles
+    /// qu'il peut recevoir
+    LineSegments(GridSurfer, Vec<GridAction>),
 }
 
 impl Display for GoodRule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text(Locale::French))
+    }
+}
+
+impl GoodRule {
+    /// Texte de la règle et de ses actions dans la langue `locale`
+    #[must_use]
+    pub fn text(&self, locale: Locale) -> String {
+        format!(
+            "{} : {}",
+            self.summary(locale),
+            display_vec_actions(self.actions(), locale)
+        )
+    }
+
+    /// Texte de la règle sans ses actions dans la langue `locale`
+    pub(crate) fn summary(&self, locale: Locale) -> String {
         // Texte pour une ligne de régions
         fn display_vec_regions(regions: &[Region]) -> String {
             let mut str_regions = String::new();
@@ -70,47 +113,146 @@ impl Display for GoodRule {
             str_regions
         }
 
-        match self {
-            Self::NoStarAdjacentToStar(line_column, actions) => {
-                write!(f, "Les cases adjacentes à l'étoile en {line_column} ne peuvent pas contenir une étoile : {}", display_vec_actions(actions))
-            }
-            Self::ZoneNoStarCompleted(grid_surfer, actions) => {
-                write!(
-                    f,
-                    "Les cases restantes pour {grid_surfer} ne peuvent pas contenir une étoile : {}",
-                    display_vec_actions(actions)
-                )
-            }
-            Self::ZoneExclusions(regions, grid_surfer, actions) => {
-                let str_regions = display_vec_regions(regions);
-                write!(
-                    f,
-                    "Les cases restantes des regions {str_regions} qui ne sont pas dans {grid_surfer} ne peuvent être une étoile : {}",
-                    display_vec_actions(actions)
-                )
-            }
-            Self::ZoneCombinations(regions, grid_surfer, actions) => {
-                let str_regions = display_vec_regions(regions);
-                write!(
-                    f,
-                    "Les cases restantes sur {grid_surfer} qui ne sont pas dans les régions {str_regions} ne peuvent être une étoile : {}",
-                    display_vec_actions(actions)
-                )
+        match locale {
+            Locale::French => {
+                match self {
+                    Self::NoStarAdjacentToStar(line_column, _) => {
+                        format!("Les cases adjacentes à l'étoile en {line_column} ne peuvent pas contenir une étoile")
+                    }
+                    Self::ZoneNoStarCompleted(grid_surfer, _) => {
+                        format!("Les cases restantes pour {grid_surfer} ne peuvent pas contenir une étoile")
+                    }
+                    Self::ZoneExclusions(regions, grid_surfer, _) => {
+                        let str_regions = display_vec_regions(regions);
+                        format!("Les cases restantes des regions {str_regions} qui ne sont pas dans {grid_surfer} ne peuvent être une étoile")
+                    }
+                    Self::ZoneCombinations(regions, grid_surfer, _) => {
+                        let str_regions = display_vec_regions(regions);
+                        format!("Les cases restantes sur {grid_surfer} qui ne sont pas dans les régions {str_regions} ne peuvent être une étoile")
+                    }
+                    Self::ZoneStarCompleted(grid_surfer, _) => {
+                        format!("Les cases restantes pour {grid_surfer} peuvent être qu'une étoile")
+                    }
+                    Self::InvariantWithZone(surfer, _) => {
+                        format!(
+                            "Toutes les possibilités pour {surfer} impliquent la seule possibilité"
+                        )
+                    }
+                    Self::Contradiction(line_column, _) => {
+                        format!("Une étoile en {line_column} conduit à une contradiction")
+                    }
+                    Self::BlockCounting(grid_surfer, _) => {
+                        format!("Chaque bloc de 2x2 cases possible pour {grid_surfer} contient une étoile")
+                    }
+                    Self::LineSegments(grid_surfer, _) => {
+                        format!("Chaque segment de cases possibles pour {grid_surfer} contient son maximum d'étoiles")
+                    }
+                }
             }
-            Self::ZoneStarCompleted(grid_surfer, actions) => {
-                write!(
-                    f,
-                    "Les cases restantes pour {grid_surfer} peuvent être qu'une étoile : {}",
-                    display_vec_actions(actions)
-                )
+            Locale::English => match self {
+                Self::NoStarAdjacentToStar(line_column, _) => {
+                    format!("The cells adjacent to the star in {line_column} cannot contain a star")
+                }
+                Self::ZoneNoStarCompleted(grid_surfer, _) => format!(
+                    "The remaining cells of {} cannot contain a star",
+                    grid_surfer.text(locale)
+                ),
+                Self::ZoneExclusions(regions, grid_surfer, _) => format!(
+                    "The remaining cells of regions {} outside {} cannot be a star",
+                    display_vec_regions(regions),
+                    grid_surfer.text(locale)
+                ),
+                Self::ZoneCombinations(regions, grid_surfer, _) => format!(
+                    "The remaining cells of {} outside regions {} cannot be a star",
+                    grid_surfer.text(locale),
+                    display_vec_regions(regions)
+                ),
+                Self::ZoneStarCompleted(grid_surfer, _) => format!(
+                    "The remaining cells of {} can only be stars",
+                    grid_surfer.text(locale)
+                ),
+                Self::InvariantWithZone(surfer, _) => format!(
+                    "All the possibilities for {} lead to the only possibility",
+                    surfer.text(locale)
+                ),
+                Self::Contradiction(line_column, _) => {
+                    format!("A star in {line_column} leads to a contradiction")
+                }
+                Self::BlockCounting(grid_surfer, _) => format!(
+                    "Each possible 2x2 block of {} contains a star",
+                    grid_surfer.text(locale)
+                ),
+                Self::LineSegments(grid_surfer, _) => format!(
+                    "Each segment of possible cells of {} contains its maximum of stars",
+                    grid_surfer.text(locale)
+                ),
+            },
+        }
+    }
+
+    /// Zone de la grille examinée par la règle (None pour les cases adjacentes à une étoile)
+    #[must_use]
+    pub const fn zone(&self) -> Option<&GridSurfer> {
+        match self {
+            Self::NoStarAdjacentToStar(..) | Self::Contradiction(..) => None,
+            Self::ZoneNoStarCompleted(grid_surfer, _)
+            | Self::ZoneExclusions(_, grid_surfer, _)
+            | Self::ZoneCombinations(_, grid_surfer, _)
+            | Self::ZoneStarCompleted(grid_surfer, _)
+            | Self::InvariantWithZone(grid_surfer, _)
+            | Self::BlockCounting(grid_surfer, _)
+            | Self::LineSegments(grid_surfer, _) => Some(grid_surfer),
+        }
+    }
+
+    /// Normalise la règle : actions et régions triées sans doublon, zone sous sa forme canonique (voir
+    /// [`GridSurfer::normalized`])
+    pub fn normalize(&mut self) {
+        let (regions, zone, actions) = match self {
+            Self::NoStarAdjacentToStar(_, actions) | Self::Contradiction(_, actions) => {
+                (None, None, actions)
             }
-            Self::InvariantWithZone(surfer, actions) => {
-                write!(
-                    f,
-                    "Toutes les possibilités pour {surfer} impliquent la seule possibilité : {}",
-                    display_vec_actions(actions)
-                )
+            Self::ZoneNoStarCompleted(zone, actions)
+            | Self::ZoneStarCompleted(zone, actions)
+            | Self::InvariantWithZone(zone, actions)
+            | Self::BlockCounting(zone, actions)
+            | Self::LineSegments(zone, actions) => (None, Some(zone), actions),
+            Self::ZoneExclusions(regions, zone, actions)
+            | Self::ZoneCombinations(regions, zone, actions) => {
+                (Some(regions), Some(zone), actions)
             }
+        };
+        actions.sort();
+        actions.dedup();
+        if let Some(regions) = regions {
+            regions.sort_unstable();
+            regions.dedup();
+        }
+        if let Some(zone) = zone {
+            *zone = zone.normalized();
+        }
+    }
+
+    /// Règle normalisée (voir [`GoodRule::normalize`])
+    #[must_use]
+    pub fn normalized(mut self) -> Self {
+        self.normalize();
+        self
+    }
+
+    /// Liste des actions de la règle sur la grille
+    #[must_use]
+    pub fn actions(&self) -> &[GridAction] {
+        match self {
+            Self::NoStarAdjacentToStar(_, actions)
+            | Self::ZoneNoStarCompleted(_, actions)
+            | Self::ZoneExclusions(_, _, actions)
+            | Self::ZoneCombinations(_, _, actions)
+            | Self::ZoneStarCompleted(_, actions)
+            | Self::InvariantWithZone(_, actions)
+            | Self::Contradiction(_, actions)
+            | Self::BlockCounting(_, actions)
+            | Self::LineSegments(_, actions) => actions,
         }
     }
 }
@@ -118,18 +260,40 @@ impl Display for GoodRule {
 impl Grid {
     /// Application d'une règle de construction sur une grille
     pub fn apply_good_rule(&mut self, rule: &GoodRule) {
-        match rule {
-            GoodRule::NoStarAdjacentToStar(_, actions)
-            | GoodRule::ZoneNoStarCompleted(_, actions)
-            | GoodRule::ZoneExclusions(_, _, actions)
-            | GoodRule::ZoneCombinations(_, _, actions)
-            | GoodRule::ZoneStarCompleted(_, actions)
-            | GoodRule::InvariantWithZone(_, actions) => {
-                for action in actions {
-                    self.apply_action(action);
-                }
-            }
+        for action in rule.actions() {
+            // Les règles ne portent que sur des cases non définies, jamais sur une étoile donnée
+            let result = self.apply_action(action);
+            debug_assert!(
+                result.is_ok(),
+                "Action {action:?} de la règle invalide : {result:?}"
+            );
+        }
+    }
+
+    /// Application vérifiée d'une règle de construction sur une grille (par exemple une règle relue
+    /// depuis une trace).<br>
+    /// Les actions de la règle doivent porter sur des cases non définies de la grille et laisser la grille
+    /// valide.
+    /// Sinon, aucune action n'est appliquée (voir [`Grid::apply_actions_checked`])
+    /// ### Errors
+    /// Retourne un [`BadRuleError`] si une action porte sur une case déjà définie, si une action est
+    /// impossible ou si la grille obtenue n'est pas valide
+    pub fn apply_good_rule_checked(
+        &mut self,
+        handler: &GridHandler,
+        rule: &GoodRule,
+    ) -> Result<(), BadRuleError> {
+        for action in rule.actions() {
+            check_action_cell(handler, action.line_column())?;
         }
+        if let Some(action) = rule
+            .actions()
+            .iter()
+            .find(|action| !self.cell(action.line_column()).is_unknown())
+        {
+            return Err(GridActionError::CellAlreadyDefined(action.line_column()).into());
+        }
+        self.apply_actions_checked(handler, rule.actions())
     }
 }
 
@@ -138,8 +302,98 @@ impl Grid {
 /// ### Errors
 /// Retourne un [`BadRuleError`] si la grille n'est pas valide
 #[allow(clippy::module_name_repetitions)]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn get_good_rule(handler: &GridHandler, grid: &Grid) -> Result<Option<GoodRule>, BadRuleError> {
-    // Grille viable ?
+    Ok(get_good_rule_with_level(handler, grid)?.map(|(good_rule, _)| good_rule))
+}
+
+/// Nombre de niveaux de règles de construction (voir [`get_good_rule_with_level`])
+pub const NB_GOOD_RULE_LEVELS: usize = 21;
+
+/// Identification d'une règle de construction applicable à la grille avec son niveau.<br>
+/// Les règles sont examinées de la plus simple à la plus complexe : le niveau est le rang (à partir de 0)
+/// de la règle trouvée dans cet ordre (entre 0 et [`NB_GOOD_RULE_LEVELS`] - 1).<br>
+/// Retourne la règle et son niveau si trouvé. None sinon.
+/// ### Errors
+/// Retourne un [`BadRuleError`] si la grille n'est pas valide
+pub fn get_good_rule_with_level(
+    handler: &GridHandler,
+    grid: &Grid,
+) -> Result<Option<(GoodRule, usize)>, BadRuleError> {
+    get_good_rule_with_config(handler, grid, &SolverConfig::default())
+}
+
+/// Recherche d'une règle de construction applicable à la grille
+type RuleFn<'a> = dyn Fn(&GridHandler, &Grid) -> Option<GoodRule> + 'a;
+
+/// Identification d'une règle de construction applicable à la grille avec son niveau selon la
+/// configuration `config` (voir [`SolverConfig`]).<br>
+/// Seules les règles des niveaux [`SolverConfig::levels`] sont examinées, dans cet ordre.<br>
+/// Retourne la règle et son niveau si trouvé. None sinon (ou si la recherche est interrompue par les
+/// limites de la configuration, voir [`try_get_good_rule`]).
+/// ### Errors
+/// Retourne un [`BadRuleError`] si la grille n'est pas valide
+pub fn get_good_rule_with_config(
+    handler: &GridHandler,
+    grid: &Grid,
+    config: &SolverConfig,
+) -> Result<Option<(GoodRule, usize)>, BadRuleError> {
+    match try_get_good_rule(handler, grid, config) {
+        Ok(good_rule) => Ok(good_rule),
+        Err(GoodRuleError::BadRule(bad_rule)) => Err(bad_rule),
+        Err(GoodRuleError::Interrupted(_)) => Ok(None),
+    }
+}
+
+/// Erreur lors de la recherche d'une règle de construction (voir [`try_get_good_rule`])
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GoodRuleError {
+    /// La grille n'est pas valide
+    #[error(transparent)]
+    BadRule(#[from] BadRuleError),
+
+    /// La recherche a été interrompue par les limites de la configuration
+    #[error(transparent)]
+    Interrupted(#[from] Interruption),
+}
+
+/// Identification d'une règle de construction applicable à la grille avec son niveau selon la
+/// configuration `config`, comme [`get_good_rule_with_config`], en signalant une recherche interrompue
+/// par les limites de la configuration (voir [`SolverConfig::with_limits`]).<br>
+/// Les limites sont vérifiées entre les niveaux de règles et pendant les recherches les plus coûteuses
+/// (grilles possibles des zones, raisonnement par l'absurde) : une règle trouvée malgré l'interruption
+/// reste valide.
+/// ### Errors
+/// Retourne un [`GoodRuleError`] si la grille n'est pas valide ou si la recherche est interrompue avant
+/// de trouver une règle
+pub fn try_get_good_rule(
+    handler: &GridHandler,
+    grid: &Grid,
+    config: &SolverConfig,
+) -> Result<Option<(GoodRule, usize)>, GoodRuleError> {
+    get_good_rule_with_strategy(handler, grid, config, None)
+}
+
+/// Identification d'une règle de construction applicable à la grille avec son niveau selon la
+/// configuration `config`, comme [`try_get_good_rule`], en examinant les niveaux de règles dans l'ordre
+/// de la stratégie `strategy` (voir [`RuleStrategy`], ordre des niveaux de la configuration si None).<br>
+/// Le niveau retourné est celui de la règle trouvée, quel que soit son rang dans l'ordre d'examen.
+/// ### Errors
+/// Retourne un [`GoodRuleError`] si la grille n'est pas valide ou si la recherche est interrompue avant
+/// de trouver une règle
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(handler, grid, strategy), err)
+)]
+pub fn get_good_rule_with_strategy(
+    handler: &GridHandler,
+    grid: &Grid,
+    config: &SolverConfig,
+    strategy: Option<&mut dyn RuleStrategy>,
+) -> Result<Option<(GoodRule, usize)>, GoodRuleError> {
+    // Grille cohérente avec sa définition (en mode debug) et viable ?
+    grid.debug_check_consistency_with(handler);
     check_bad_rules(handler, grid)?;
 
     // Grille terminée ?
@@ -147,28 +401,69 @@ pub fn get_good_rule(handler: &GridHandler, grid: &Grid) -> Result<Option<GoodRu
         return Ok(None);
     }
 
-    for f in [
-        rule_no_star_adjacent_to_star,
-        rule_value_completed,
-        rule_region_1_exclusions,
-        rule_region_1_combinations,
-        rule_region_possible_stars,
-        rule_region_2_exclusions,
-        rule_region_2_combinations,
-        rule_region_recursive_possible_stars,
-        rule_region_3_exclusions,
-        rule_region_3_combinations,
-        rule_line_column_recursive_possible_stars,
-        rule_region_4_exclusions,
-        rule_region_4_combinations,
-        rule_multi_2_lines_columns_recursive_possible_stars,
-        rule_multi_3_lines_columns_recursive_possible_stars,
-        rule_multi_4_lines_columns_recursive_possible_stars,
-    ] {
-        if let Some(rule) = f(handler, grid) {
-            return Ok(Some(rule));
+    let rules: [&RuleFn; NB_GOOD_RULE_LEVELS] = [
+        &rule_no_star_adjacent_to_star,
+        &rule_value_completed,
+        &rule_region_1_exclusions,
+        &rule_region_1_combinations,
+        &|handler, grid| rule_region_possible_stars(handler, grid, config),
+        &rule_region_2_exclusions,
+        &rule_region_2_combinations,
+        &|handler, grid| rule_region_recursive_possible_stars(handler, grid, config),
+        &rule_region_3_exclusions,
+        &rule_region_3_combinations,
+        &|handler, grid| rule_line_column_recursive_possible_stars(handler, grid, config),
+        &rule_region_4_exclusions,
+        &rule_region_4_combinations,
+        &|handler, grid| rule_multi_2_lines_columns_recursive_possible_stars(handler, grid, config),
+        &|handler, grid| rule_multi_3_lines_columns_recursive_possible_stars(handler, grid, config),
+        &|handler, grid| rule_multi_4_lines_columns_recursive_possible_stars(handler, grid, config),
+        // Règle ajoutée après les autres pour ne pas modifier le niveau des règles existantes (et donc
+        // l'évaluation de la difficulté des grilles)
+        &rule_candidates_intersection,
+        &rule_block_counting,
+        &rule_line_segments,
+        // Exclusions et combinaisons de 5 lignes ou colonnes et plus (aucune par défaut)
+        &|handler, grid| {
+            (5..=config.max_band_size()).find_map(|n| {
+                rule_region_n_exclusions(handler, grid, n)
+                    .or_else(|| rule_region_n_combinations(handler, grid, n))
+            })
+        },
+        // Raisonnement par l'absurde, le plus coûteux, examiné en dernier
+        &|handler, grid| rule_contradiction(handler, grid, config),
+    ];
+    let levels = config.levels();
+    let mut fixed_order = FixedOrder;
+    let strategy = strategy.unwrap_or(&mut fixed_order);
+    for level in strategy.order(&levels) {
+        if !levels.contains(&level) {
+            continue;
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("level", level).entered();
+        if let Some(interruption) = config.limits().interruption() {
+            return Err(interruption.into());
+        }
+        // Règle intégrée du niveau puis règles définies par l'utilisateur de ce niveau
+        if let Some(rule) = record_rule_search(level, || {
+            rules[level](handler, grid).or_else(|| {
+                config
+                    .custom_rules_with_level(level)
+                    .find_map(|rule| apply_custom_rule(rule, handler, grid))
+            })
+        }) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(level, kind = %rule.kind(), "règle trouvée");
+            record_rule(level);
+            strategy.rule_found(level);
+            return Ok(Some((rule, level)));
         }
     }
+    // Aucune règle trouvée par une recherche éventuellement écourtée
+    if let Some(interruption) = config.limits().interruption() {
+        return Err(interruption.into());
+    }
 
     Ok(None)
 }
@@ -181,6 +476,7 @@ mod tests {
     use std::io::Read;
 
     use crate::GridParser;
+    use crate::RuleKind;
 
     // Liste des grilles d'exemple
     const TEST_GRIDS_FILENAME_AND_NB_STARS: &[(&str, usize)] = &[
@@ -233,6 +529,274 @@ mod tests {
         test_all_test_grids("expert");
     }
 
+    #[test]
+    fn test_large_grid() {
+        // Grille 17x17 3★ à solution unique : les règles doivent la résoudre entièrement avec un budget
+        // de grilles possibles par zone adapté aux grandes grilles
+        let contents = std::fs::read_to_string("./test_grids/test17x17_3_unique.txt").unwrap();
+        let grid_parser = GridParser::try_from(contents.as_str()).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 3);
+        let config = SolverConfig::new().with_max_possible_grids(200);
+        let mut grid = Grid::from(&grid_handler);
+        let mut rule_kinds = Vec::new();
+        while let Some((good_rule, _)) =
+            get_good_rule_with_config(&grid_handler, &grid, &config).unwrap()
+        {
+            rule_kinds.push(good_rule.kind());
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        check_golden_trace("./test_grids/test17x17_3_unique.txt", &rule_kinds);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+
+        let good_rule = get_good_rule(&grid_handler, &grid).unwrap().unwrap();
+        let json = serde_json::to_string(&good_rule).unwrap();
+        let good_rule_copy: GoodRule = serde_json::from_str(&json).unwrap();
+        assert_eq!(good_rule_copy.to_string(), good_rule.to_string());
+
+        grid.cell_mut(LineColumn::new(0, 0)).value = crate::CellValue::Star;
+        grid.cell_mut(LineColumn::new(1, 1)).value = crate::CellValue::Star;
+        let bad_rule = get_good_rule(&grid_handler, &grid).unwrap_err();
+        let json = serde_json::to_string(&bad_rule).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BadRuleError>(&json).unwrap(),
+            bad_rule
+        );
+
+        // Règles ajoutées après les autres et erreurs d'une recherche limitée
+        for good_rule in [
+            GoodRule::BlockCounting(
+                GridSurfer::Lines(0..=1),
+                vec![GridAction::SetStar(LineColumn::new(1, 3))],
+            ),
+            GoodRule::LineSegments(
+                GridSurfer::Column(2),
+                vec![GridAction::SetNoStar(LineColumn::new(0, 2))],
+            ),
+            GoodRule::Contradiction(
+                LineColumn::new(4, 4),
+                vec![GridAction::SetNoStar(LineColumn::new(4, 4))],
+            ),
+        ] {
+            let json = serde_json::to_string(&good_rule).unwrap();
+            assert_eq!(serde_json::from_str::<GoodRule>(&json).unwrap(), good_rule);
+        }
+        for error in [
+            GoodRuleError::BadRule(bad_rule),
+            GoodRuleError::Interrupted(Interruption::Timeout),
+        ] {
+            let json = serde_json::to_string(&error).unwrap();
+            assert_eq!(serde_json::from_str::<GoodRuleError>(&json).unwrap(), error);
+        }
+    }
+
+    #[test]
+    fn test_zone_stars() {
+        // Régions 'A' et 'C' regroupées dans une région 'A' de 2 étoiles
+        let grid_parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "AABBB", "DDDDD", "DEEED"]).unwrap();
+        let grid_handler =
+            GridHandler::with_zone_stars(&grid_parser, 1, &[(GridSurfer::Region('A'), 2)]);
+        let mut grid = Grid::from(&grid_handler);
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        for star in [(0, 0), (1, 3), (2, 1), (3, 4), (4, 2)] {
+            assert!(grid.cell(LineColumn::new(star.0, star.1)).is_star());
+        }
+    }
+
+    #[test]
+    fn test_cage() {
+        // Grille 4x4 en 4 régions carrées qui n'a une solution unique qu'avec la cage des 2 premières cases
+        // de la 1ere ligne
+        let grid_parser = GridParser::try_from(vec!["AABB", "AABB", "CCDD", "CCDD"]).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 1)
+            .with_cage(&[LineColumn::new(0, 0), LineColumn::new(0, 1)], 1);
+        let mut grid = Grid::from(&grid_handler);
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        for star in [(0, 1), (1, 3), (2, 0), (3, 2)] {
+            assert!(grid.cell(LineColumn::new(star.0, star.1)).is_star());
+        }
+    }
+
+    #[test]
+    fn test_given_stars() {
+        // Grille 4x4 en 4 régions carrées qui n'a une solution unique qu'avec l'étoile donnée en B1
+        let (grid_handler, mut grid) = grid!["AABB", "AABB", "CCDD", "CCDD", "* B1"; stars = 1];
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        for star in [(0, 1), (1, 3), (2, 0), (3, 2)] {
+            assert!(grid.cell(LineColumn::new(star.0, star.1)).is_star());
+        }
+    }
+
+    #[test]
+    fn test_queens() {
+        // Grille du jeu Queens : 1 reine par ligne, colonne et région de couleur
+        let grid_parser = GridParser::try_from(vec![
+            "AAAABBB", "AACCBAA", "AAAAAAA", "AAADDDA", "AAADEEE", "AAADFFF", "GGGGFFF",
+        ])
+        .unwrap();
+        let grid_handler = GridHandler::queens(&grid_parser);
+        let mut grid = Grid::from(&grid_handler);
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        for queen in [(0, 5), (1, 2), (2, 0), (3, 3), (4, 6), (5, 4), (6, 1)] {
+            assert!(grid.cell(LineColumn::new(queen.0, queen.1)).is_star());
+        }
+    }
+
+    #[test]
+    fn test_without_regions() {
+        // Grille 5x5 1★ sans région avec 2 étoiles déjà placées
+        let grid_parser =
+            GridParser::try_from(vec!["AAAAA", "AAAAA", "AAAAA", "AAAAA", "AAAAA"]).unwrap();
+        let grid_handler = GridHandler::without_regions(&grid_parser, 1);
+        let mut grid = Grid::from(&grid_handler);
+        grid.cell_mut(LineColumn::new(0, 0)).value = crate::CellValue::Star;
+        grid.cell_mut(LineColumn::new(1, 2)).value = crate::CellValue::Star;
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        for star in [(0, 0), (1, 2), (2, 4), (3, 1), (4, 3)] {
+            assert!(grid.cell(LineColumn::new(star.0, star.1)).is_star());
+        }
+    }
+
+    #[test]
+    fn test_rectangular() {
+        // Grille de 5 lignes et 10 colonnes avec 2 étoiles par ligne et 1 étoile par colonne et par région
+        let grid_parser = GridParser::try_from(vec![
+            "DDDDDEAAHB",
+            "CGDGEEAAHH",
+            "GGGGEEFFHH",
+            "GGGIFFFFHH",
+            "GGGIJJJHHH",
+        ])
+        .unwrap();
+        let grid_handler = GridHandler::with_stars_per_zone(&grid_parser, 2, 1, 1);
+        let mut grid = Grid::from(&grid_handler);
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        for star in [
+            (0, 7),
+            (0, 9),
+            (1, 0),
+            (1, 2),
+            (2, 4),
+            (2, 6),
+            (3, 1),
+            (3, 8),
+            (4, 3),
+            (4, 5),
+        ] {
+            assert!(grid.cell(LineColumn::new(star.0, star.1)).is_star());
+        }
+    }
+
+    #[test]
+    fn test_blocked_cells() {
+        // Grille 5x5 1★ avec 3 cases bloquées
+        let (grid_handler, mut grid) =
+            grid!["AB.BB", "ABBBB", "CCB.B", "DDDDD", ".EEED"; stars = 1];
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+        for star in [(0, 0), (1, 3), (2, 1), (3, 4), (4, 2)] {
+            assert!(grid.cell(LineColumn::new(star.0, star.1)).is_star());
+        }
+    }
+
+    #[test]
+    fn test_solver_config() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+
+        // Seules les règles les plus simples sont examinées
+        let config = SolverConfig::new().with_max_level(1);
+        assert!(get_good_rule_with_config(&grid_handler, &grid, &config)
+            .unwrap()
+            .is_none());
+
+        // Sans budget, les règles sur les grilles possibles d'une zone ne s'appliquent plus
+        let config = SolverConfig::new().with_max_possible_grids(0);
+        let (_, level) = get_good_rule_with_config(&grid_handler, &grid, &config)
+            .unwrap()
+            .unwrap();
+        assert!(level != 4);
+
+        // Configuration par défaut : la grille est résolue
+        let config = SolverConfig::default();
+        while let Some((good_rule, _)) =
+            get_good_rule_with_config(&grid_handler, &grid, &config).unwrap()
+        {
+            grid.apply_good_rule(&good_rule);
+        }
+        assert!(grid_handler.is_done(&grid));
+    }
+
+    #[test]
+    fn test_rule_strategies() {
+        use crate::{CheapestFirst, LastSuccessfulFirst, RegionFirst};
+
+        let contents = std::fs::read_to_string("./test_grids/moyen01_2.txt").unwrap();
+        let grid_parser = GridParser::try_from(contents.as_str()).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 2);
+        let config = SolverConfig::default();
+        let solve = |strategy: &mut dyn RuleStrategy| {
+            let mut grid = Grid::from(&grid_handler);
+            while let Some((good_rule, _)) =
+                get_good_rule_with_strategy(&grid_handler, &grid, &config, Some(&mut *strategy))
+                    .unwrap()
+            {
+                grid.apply_good_rule(&good_rule);
+            }
+            grid
+        };
+
+        // Toutes les stratégies résolvent la grille (à solution unique)
+        let solution = solve(&mut FixedOrder);
+        assert!(grid_handler.is_done(&solution));
+        assert_eq!(solve(&mut CheapestFirst), solution);
+        assert_eq!(solve(&mut RegionFirst), solution);
+        let mut strategy = LastSuccessfulFirst::new();
+        assert_eq!(solve(&mut strategy), solution);
+        assert!(strategy.last_level().is_some());
+
+        // Le niveau retourné est celui de la règle trouvée
+        let grid = Grid::from(&grid_handler);
+        let (good_rule, level) =
+            get_good_rule_with_strategy(&grid_handler, &grid, &config, Some(&mut CheapestFirst))
+                .unwrap()
+                .unwrap();
+        let expected = get_good_rule_with_config(
+            &grid_handler,
+            &grid,
+            &SolverConfig::new().with_levels(&[level]),
+        )
+        .unwrap();
+        assert_eq!(expected, Some((good_rule, level)));
+    }
+
     /// Primitive générique qui teste les grilles de tests dont le nom de leur fichier contient
     /// la chaîne `filename_part`
     /// (Evite de tout tester silencieusement car c'est un peu long...)
@@ -249,12 +813,13 @@ mod tests {
                 let grid_parser = GridParser::try_from(file_contents.as_str()).unwrap();
                 let grid_handler = GridHandler::new(&grid_parser, *nb_stars);
                 let mut grid = Grid::from(&grid_handler);
+                let mut rule_kinds = Vec::new();
                 // Boucle de résolution
                 loop {
                     match get_good_rule(&grid_handler, &grid) {
                         Ok(option_good_rule) => {
-                            if option_good_rule.is_some() {
-                                let good_rule = option_good_rule.unwrap();
+                            if let Some(good_rule) = option_good_rule {
+                                rule_kinds.push(good_rule.kind());
                                 grid.apply_good_rule(&good_rule);
                             } else {
                                 break;
@@ -271,7 +836,153 @@ mod tests {
                     grid_handler.display(&grid, true)
                 );
                 assert!(grid_handler.is_done(&grid));
+                check_golden_trace(grid_file_name, &rule_kinds);
             }
         }
     }
+
+    /// Vérifie que la suite des catégories de règles appliquées pour résoudre la grille du fichier
+    /// `grid_file_name` est celle enregistrée dans le fichier de référence `test_grids/golden/<grille>.trace`
+    /// (une catégorie par ligne).<br>
+    /// Toute modification de l'ordre ou du comportement des règles apparaît ainsi dans ces fichiers : après
+    /// vérification, ils sont mis à jour en lançant les tests avec la variable d'environnement
+    /// `UPDATE_GOLDEN=1`
+    fn check_golden_trace(grid_file_name: &str, rule_kinds: &[RuleKind]) {
+        let grid_name = std::path::Path::new(grid_file_name)
+            .file_stem()
+            .unwrap()
+            .to_string_lossy();
+        let golden_file_name = format!("./test_grids/golden/{grid_name}.trace");
+        let trace: String = rule_kinds
+            .iter()
+            .map(|rule_kind| format!("{rule_kind:?}\n"))
+            .collect();
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&golden_file_name, &trace).unwrap();
+            return;
+        }
+        let golden_trace = std::fs::read_to_string(&golden_file_name)
+            .unwrap_or_else(|e| panic!("Fichier de référence {golden_file_name} illisible: {e}"));
+        assert!(
+            trace == golden_trace,
+            "La suite des règles appliquées à la grille {grid_file_name} ne correspond plus à \
+             {golden_file_name} (UPDATE_GOLDEN=1 pour mettre à jour ce fichier)"
+        );
+    }
+
+    #[test]
+    fn test_rule_display_snapshot() {
+        // Textes des règles appliquées pour résoudre des grilles qui utilisent toutes les catégories de règles
+        for (grid_name, nb_stars) in [("test01", 1), ("facile01_2", 2)] {
+            let contents =
+                std::fs::read_to_string(format!("./test_grids/{grid_name}.txt")).unwrap();
+            let grid_parser = GridParser::try_from(contents.as_str()).unwrap();
+            let grid_handler = GridHandler::new(&grid_parser, nb_stars);
+            let mut grid = Grid::from(&grid_handler);
+            let mut rule_texts = String::new();
+            while let Some((good_rule, level)) =
+                get_good_rule_with_level(&grid_handler, &grid).unwrap()
+            {
+                rule_texts.push_str(&format!("[{level}] {good_rule}\n"));
+                grid.apply_good_rule(&good_rule);
+            }
+            insta::assert_snapshot!(format!("rules_{grid_name}"), rule_texts);
+        }
+    }
+
+    #[test]
+    fn test_text_locale() {
+        let good_rule = GoodRule::ZoneExclusions(
+            vec!['D'],
+            GridSurfer::Lines(3..=4),
+            vec![GridAction::SetNoStar(LineColumn::new(2, 0))],
+        );
+        assert_eq!(good_rule.text(Locale::French), good_rule.to_string());
+        assert_eq!(
+            good_rule.text(Locale::English),
+            "The remaining cells of regions D outside Lines 4-5 cannot be a star : A3->No star"
+        );
+
+        let bad_rule = BadRuleError::NotEnoughStarsInZone(GridSurfer::Column(1));
+        assert_eq!(bad_rule.text(Locale::French), bad_rule.to_string());
+        assert_eq!(
+            bad_rule.text(Locale::English),
+            "Unable to place all the stars in 'Column B'"
+        );
+    }
+
+    #[test]
+    fn test_apply_good_rule_checked() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let good_rule = get_good_rule(&grid_handler, &grid).unwrap().unwrap();
+        let saved_grid = grid.clone();
+        assert!(grid
+            .apply_good_rule_checked(&grid_handler, &good_rule)
+            .is_ok());
+
+        // La même règle rejouée porte sur des cases déjà définies : rien n'est appliqué
+        let mut replayed_grid = grid.clone();
+        let line_column = good_rule.actions()[0].line_column();
+        assert_eq!(
+            replayed_grid
+                .apply_good_rule_checked(&grid_handler, &good_rule)
+                .unwrap_err(),
+            BadRuleError::GridAction(GridActionError::CellAlreadyDefined(line_column))
+        );
+        assert_eq!(replayed_grid, grid);
+
+        // Une règle qui rend la grille invalide n'est pas appliquée
+        let mut grid = saved_grid.clone();
+        let bad_rule = GoodRule::ZoneStarCompleted(
+            GridSurfer::Line(0),
+            vec![
+                GridAction::SetStar(LineColumn::new(0, 0)),
+                GridAction::SetStar(LineColumn::new(0, 2)),
+            ],
+        );
+        assert_eq!(
+            grid.apply_good_rule_checked(&grid_handler, &bad_rule)
+                .unwrap_err(),
+            BadRuleError::TooManyStarsInZone(GridSurfer::Line(0))
+        );
+        assert_eq!(grid, saved_grid);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let a1 = LineColumn::new(0, 0);
+        let b1 = LineColumn::new(0, 1);
+        let rule = GoodRule::ZoneExclusions(
+            vec!['B', 'A', 'B'],
+            GridSurfer::Lines(2..=2),
+            vec![
+                GridAction::SetNoStar(b1),
+                GridAction::SetNoStar(a1),
+                GridAction::SetNoStar(b1),
+            ],
+        );
+        let same_rule = GoodRule::ZoneExclusions(
+            vec!['A', 'B'],
+            GridSurfer::Line(2),
+            vec![GridAction::SetNoStar(a1), GridAction::SetNoStar(b1)],
+        );
+        assert_ne!(rule, same_rule);
+        assert_eq!(rule.clone().normalized(), same_rule);
+        assert_eq!(rule.normalized().to_string(), same_rule.to_string());
+
+        // Règles triées et sans doublon
+        let mut rules = vec![
+            GoodRule::ZoneStarCompleted(GridSurfer::Column(1), vec![GridAction::SetStar(b1)]),
+            GoodRule::NoStarAdjacentToStar(a1, vec![GridAction::SetNoStar(b1)]),
+            GoodRule::ZoneStarCompleted(GridSurfer::Columns(1..=1), vec![GridAction::SetStar(b1)]),
+        ];
+        for rule in &mut rules {
+            rule.normalize();
+        }
+        rules.sort();
+        rules.dedup();
+        assert_eq!(rules.len(), 2);
+        assert!(matches!(rules[0], GoodRule::NoStarAdjacentToStar(..)));
+    }
 }
@@ -6,6 +6,8 @@ use crate::GridAction;
 use crate::GridHandler;
 use crate::GridSurfer;
 
+use super::collector::PossibleGrid;
+
 /// Énumération de la situation pour les cases possiblement variantes dans toutes les
 /// combinaisons possibles de grilles
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -48,7 +50,7 @@ impl Variant {
     pub fn check_for_invariants(
         handler: &GridHandler,
         grid: &Grid,
-        possible_grids: &Vec<Grid>,
+        possible_grids: &[PossibleGrid],
     ) -> Vec<GridAction> {
         // Liste des cases non déterminées dans la grille initiale
         let mut cells = Vec::new();
@@ -62,15 +64,16 @@ impl Variant {
         }
 
         // Parcours de toutes les grilles possibles collectées
-        for grid in possible_grids {
+        for possible_grid in possible_grids {
             // On combine toutes les cases à examiner avec ce qu'on a déjà observé
             for (line_column, variant) in cells.iter().zip(variants.iter_mut()) {
                 let prev_variant = *variant;
-                let new_variant = prev_variant.combine(match grid.cell(*line_column).value {
-                    CellValue::Star => Self::Star,
-                    CellValue::NoStar => Self::NoStar,
-                    CellValue::Unknown => Self::Unknown,
-                });
+                let new_variant =
+                    prev_variant.combine(match possible_grid.value(grid, *line_column) {
+                        CellValue::Star => Self::Star,
+                        CellValue::NoStar => Self::NoStar,
+                        CellValue::Unknown => Self::Unknown,
+                    });
                 *variant = new_variant;
             }
         }
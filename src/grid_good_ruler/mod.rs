@@ -1,16 +1,42 @@
 //! Gestion des règles de construction/résolution d'une grille
 
 mod collector;
+mod custom_rule;
+mod explanation;
 mod good_rule;
 mod invariant;
+mod rule_block_counting;
+mod rule_candidates_intersection;
+mod rule_contradiction;
 mod rule_generic_possible_stars;
+mod rule_kind;
+mod rule_line_segments;
 mod rule_no_star_adjacent_to_star;
 mod rule_region_combinations;
 mod rule_region_exclusions;
 mod rule_region_possible_stars;
+mod rule_strategy;
 mod rule_value_completed;
 mod rule_zone_possible_stars;
+mod solver_config;
+mod solver_limits;
 mod star_adjacent;
 
-pub use good_rule::{get_good_rule, GoodRule};
+pub use collector::{CollectorStrategy, BRUTE_FORCE_MAX_COMBINATIONS, MAX_EXPLORED_COMBINATIONS};
+pub use custom_rule::Rule;
+pub use explanation::Verbosity;
+pub use good_rule::{
+    get_good_rule, get_good_rule_with_config, get_good_rule_with_level,
+    get_good_rule_with_strategy, try_get_good_rule, GoodRule, GoodRuleError, NB_GOOD_RULE_LEVELS,
+};
 use rule_generic_possible_stars::{rule_generic_possible_stars, ZoneToExamine};
+pub use rule_kind::{RuleKind, Technique};
+pub use rule_region_combinations::rule_region_n_combinations;
+pub use rule_region_exclusions::rule_region_n_exclusions;
+pub use rule_strategy::{
+    CheapestFirst, FixedOrder, LastSuccessfulFirst, RegionFirst, RuleStrategy,
+};
+pub use solver_config::SolverConfig;
+#[cfg(feature = "config")]
+pub use solver_config::SolverConfigError;
+pub use solver_limits::{Interruption, SolverLimits};
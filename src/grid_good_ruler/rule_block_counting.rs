@@ -0,0 +1,213 @@
+//! Règle de construction/résolution d'une grille.
+//!
+//! Deux étoiles ne peuvent pas se toucher : un bloc de 2x2 cases contient donc au plus une étoile.<br>
+//! Deux lignes (ou colonnes) consécutives sont découpées en blocs de 2x2 cases (un bloc de 2x1 cases
+//! complète le découpage si nécessaire). Si le nombre de blocs qui peuvent encore recevoir une étoile est
+//! égal au nombre d'étoiles attendues dans ces 2 lignes, chacun de ces blocs contient exactement une
+//! étoile :
+//! * Si un bloc n'a qu'une seule case candidate, cette case est une étoile
+//! * Les cases adjacentes à toutes les cases candidates d'un bloc ne peuvent pas être des étoiles
+//!
+//! Pour une grille 1★ de 4 colonnes ou 2★ de 8 colonnes par exemple, chaque bloc contient une étoile.
+
+use crate::CellValue;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::GridSurfer;
+use crate::LineColumn;
+
+/// Cherche 2 lignes (ou colonnes) consécutives dont chaque bloc de 2x2 cases contient forcément une étoile
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn rule_block_counting(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
+    let nb_lines = handler.nb_lines();
+    let nb_columns = handler.nb_columns();
+    let line_strips = (0..nb_lines.saturating_sub(1)).map(|line| {
+        let blocks = strip_blocks(nb_columns, |column, offset| {
+            LineColumn::new(line + offset, column)
+        });
+        (GridSurfer::Lines(line..=line + 1), blocks)
+    });
+    let column_strips = (0..nb_columns.saturating_sub(1)).map(|column| {
+        let blocks = strip_blocks(nb_lines, |line, offset| {
+            LineColumn::new(line, column + offset)
+        });
+        (GridSurfer::Columns(column..=column + 1), blocks)
+    });
+    for (zone, tilings) in line_strips.chain(column_strips) {
+        for blocks in tilings {
+            if let Some(actions) = try_block_counting(handler, grid, &zone, &blocks) {
+                return Some(GoodRule::BlockCounting(zone, actions));
+            }
+        }
+    }
+    None
+}
+
+/// Découpages en blocs de 2 lignes (ou colonnes) consécutives de `length` cases : blocs à partir de la
+/// première case puis à partir de la deuxième case (la première case forme alors un bloc à elle seule).<br>
+/// `cell(index, offset)` est la case d'index `index` dans la ligne (ou colonne) `offset` (0 ou 1) des 2
+/// lignes (ou colonnes)
+fn strip_blocks(
+    length: usize,
+    cell: impl Fn(usize, usize) -> LineColumn,
+) -> Vec<Vec<Vec<LineColumn>>> {
+    [0, 1]
+        .into_iter()
+        .filter(|first| *first < length)
+        .map(|first| {
+            let mut starts = if first == 1 { vec![0] } else { Vec::new() };
+            starts.extend((first..length).step_by(2));
+            starts
+                .iter()
+                .map(|start| {
+                    let end = if *start == 0 && first == 1 {
+                        1
+                    } else {
+                        (start + 2).min(length)
+                    };
+                    (*start..end)
+                        .flat_map(|index| [cell(index, 0), cell(index, 1)])
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Retourne `true` si une étoile peut encore être placée dans la case `line_column` : case non définie,
+/// sans étoile adjacente, dont aucune zone n'a déjà toutes ses étoiles
+pub(super) fn is_candidate(handler: &GridHandler, grid: &Grid, line_column: LineColumn) -> bool {
+    if !grid.cell(line_column).is_unknown() || handler.is_blocked(line_column) {
+        return false;
+    }
+    let mut zones = vec![
+        GridSurfer::Line(line_column.line),
+        GridSurfer::Column(line_column.column),
+    ];
+    if handler.has_regions() {
+        zones.push(GridSurfer::Region(handler.cell_region(line_column)));
+    }
+    !handler.is_star_adjacent(grid, line_column)
+        && zones.iter().all(|zone| {
+            handler.surfer_cells_with_value_count(grid, zone, &CellValue::Star)
+                < handler.zone_nb_stars(zone)
+        })
+}
+
+/// Actions déduites d'un découpage en blocs `blocks` de la zone `zone` (2 lignes ou colonnes
+/// consécutives) si chaque bloc qui peut encore recevoir une étoile en contient forcément une
+fn try_block_counting(
+    handler: &GridHandler,
+    grid: &Grid,
+    zone: &GridSurfer,
+    blocks: &[Vec<LineColumn>],
+) -> Option<Vec<GridAction>> {
+    // Blocs qui peuvent encore recevoir une étoile : cases candidates des blocs sans étoile
+    let mut nb_available_blocks = 0;
+    let mut free_blocks = Vec::new();
+    for block in blocks {
+        if block
+            .iter()
+            .any(|line_column| grid.cell(*line_column).is_star())
+        {
+            nb_available_blocks += 1;
+        } else {
+            let candidates: Vec<LineColumn> = block
+                .iter()
+                .copied()
+                .filter(|line_column| is_candidate(handler, grid, *line_column))
+                .collect();
+            if !candidates.is_empty() {
+                nb_available_blocks += 1;
+                free_blocks.push(candidates);
+            }
+        }
+    }
+    if free_blocks.is_empty() || nb_available_blocks != handler.zone_nb_stars(zone) {
+        return None;
+    }
+
+    // Chaque bloc sans étoile contient une étoile dans une de ses cases candidates
+    for candidates in free_blocks {
+        if let [line_column] = candidates[..] {
+            return Some(vec![GridAction::SetStar(line_column)]);
+        }
+        let mut common_neighbors = handler.adjacent_cells(candidates[0]);
+        for candidate in &candidates[1..] {
+            let adjacent_cells = handler.adjacent_cells(*candidate);
+            common_neighbors.retain(|line_column| adjacent_cells.contains(line_column));
+        }
+        let actions: Vec<GridAction> = common_neighbors
+            .into_iter()
+            .filter(|line_column| grid.cell(*line_column).is_unknown())
+            .map(GridAction::SetNoStar)
+            .collect();
+        if !actions.is_empty() {
+            return Some(actions);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_blocks() {
+        let tilings = strip_blocks(3, LineColumn::new);
+        assert_eq!(tilings.len(), 2);
+        assert_eq!(
+            tilings[0].iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![4, 2]
+        );
+        assert_eq!(
+            tilings[1].iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+    }
+
+    #[test]
+    fn test_rule_block_counting() {
+        // Grille 1★ de 4x4 cases : les 2 premières lignes sont découpées en 2 blocs qui contiennent
+        // chacun une étoile. Seule la case D2 reste candidate dans le bloc de droite
+        let (grid_handler, grid) = grid![
+            "AABB", "AABB", "CCDD", "CCDD";
+            stars = 1;
+            no_star = "C1 D1 C2"
+        ];
+        match rule_block_counting(&grid_handler, &grid) {
+            Some(GoodRule::BlockCounting(zone, actions)) => {
+                assert_eq!(zone, GridSurfer::Lines(0..=1));
+                assert_eq!(actions, vec![GridAction::SetStar(LineColumn::new(1, 3))]);
+            }
+            _ => panic!("La règle n'est pas détectée"),
+        }
+
+        // Bloc dont les cases candidates ont des cases adjacentes communes en dehors du bloc
+        let (grid_handler, grid) = grid![
+            "AABB", "AABB", "CCDD", "CCDD";
+            stars = 1;
+            no_star = "C1 D1"
+        ];
+        match rule_block_counting(&grid_handler, &grid) {
+            Some(GoodRule::BlockCounting(zone, actions)) => {
+                assert_eq!(zone, GridSurfer::Lines(0..=1));
+                assert_eq!(
+                    actions,
+                    vec![
+                        GridAction::SetNoStar(LineColumn::new(2, 2)),
+                        GridAction::SetNoStar(LineColumn::new(2, 3))
+                    ]
+                );
+            }
+            _ => panic!("La règle n'est pas détectée"),
+        }
+
+        // Rien à déduire d'une grille 1★ de 5x5 cases (3 blocs pour 2 étoiles)
+        let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        assert!(rule_block_counting(&grid_handler, &grid).is_none());
+    }
+}
@@ -0,0 +1,172 @@
+//! Règle de construction/résolution d'une grille.
+//!
+//! Examine les cases candidates de chaque zone (voir [`GridHandler::star_candidates`]) :
+//! * Si les cases candidates d'une ligne (ou colonne) sont toutes dans une même région, les étoiles
+//!   manquantes de cette ligne sont placées dans cette région. Si elles suffisent à compléter la région,
+//!   les autres cases de la région (en dehors de la ligne) ne peuvent pas être des étoiles.
+//! * Si les cases candidates d'une région sont toutes dans une même ligne (ou colonne), les étoiles
+//!   manquantes de cette région sont placées dans cette ligne. Si elles suffisent à compléter la ligne,
+//!   les autres cases de la ligne (en dehors de la région) ne peuvent pas être des étoiles.
+//!
+//! Contrairement aux règles [`rule_region_exclusions`] et [`rule_region_combinations`] (avec n = 1), la
+//! règle reste applicable si des étoiles sont déjà placées dans la ligne ou la région : seules les étoiles
+//! manquantes sont prises en compte.
+//!
+//! [`rule_region_exclusions`]: super::rule_region_exclusions
+//! [`rule_region_combinations`]: super::rule_region_combinations
+
+use crate::CellValue;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::GridSurfer;
+
+/// Cherche une ligne (ou colonne) dont les cases candidates sont toutes dans une même région ou une
+/// région dont les cases candidates sont toutes dans une même ligne (ou colonne)
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn rule_candidates_intersection(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
+    // Règle sans objet pour une grille sans contrainte de région
+    if !handler.has_regions() {
+        return None;
+    }
+
+    // Lignes et colonnes dont les cases candidates sont dans une seule région
+    let lines = (0..handler.nb_lines()).map(GridSurfer::Line);
+    let columns = (0..handler.nb_columns()).map(GridSurfer::Column);
+    for zone in lines.chain(columns) {
+        let candidates = handler.star_candidates(grid, &zone);
+        let Some(first_candidate) = candidates.first() else {
+            continue;
+        };
+        let region = handler.cell_region(*first_candidate);
+        if candidates
+            .iter()
+            .any(|line_column| handler.cell_region(*line_column) != region)
+        {
+            continue;
+        }
+        let region_zone = GridSurfer::Region(region);
+        if missing_stars(handler, grid, &zone) < missing_stars(handler, grid, &region_zone) {
+            continue;
+        }
+        let actions = no_star_actions(handler, grid, &region_zone, &zone);
+        if !actions.is_empty() {
+            return Some(GoodRule::ZoneExclusions(vec![region], zone, actions));
+        }
+    }
+
+    // Régions dont les cases candidates sont dans une seule ligne ou une seule colonne
+    for region in handler.regions() {
+        let region_zone = GridSurfer::Region(region);
+        let candidates = handler.star_candidates(grid, &region_zone);
+        let Some(first_candidate) = candidates.first() else {
+            continue;
+        };
+        let mut zones = Vec::new();
+        if candidates
+            .iter()
+            .all(|line_column| line_column.line == first_candidate.line)
+        {
+            zones.push(GridSurfer::Line(first_candidate.line));
+        }
+        if candidates
+            .iter()
+            .all(|line_column| line_column.column == first_candidate.column)
+        {
+            zones.push(GridSurfer::Column(first_candidate.column));
+        }
+        for zone in zones {
+            if missing_stars(handler, grid, &region_zone) < missing_stars(handler, grid, &zone) {
+                continue;
+            }
+            let actions = no_star_actions(handler, grid, &zone, &region_zone);
+            if !actions.is_empty() {
+                return Some(GoodRule::ZoneCombinations(vec![region], zone, actions));
+            }
+        }
+    }
+    None
+}
+
+/// Nombre d'étoiles qui restent à placer dans une zone
+fn missing_stars(handler: &GridHandler, grid: &Grid, zone: &GridSurfer) -> usize {
+    handler
+        .zone_nb_stars(zone)
+        .saturating_sub(handler.surfer_cells_with_value_count(grid, zone, &CellValue::Star))
+}
+
+/// Actions 'pas d'étoile' pour les cases non définies de la zone `zone` qui ne sont pas dans la zone
+/// `excluded`
+fn no_star_actions(
+    handler: &GridHandler,
+    grid: &Grid,
+    zone: &GridSurfer,
+    excluded: &GridSurfer,
+) -> Vec<GridAction> {
+    let excluded_cells = handler.surfer_set(grid, excluded);
+    handler
+        .surfer(grid, zone)
+        .into_iter()
+        .filter(|line_column| {
+            grid.cell(*line_column).is_unknown() && !excluded_cells.contains(line_column)
+        })
+        .map(GridAction::SetNoStar)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::LineColumn;
+
+    #[test]
+    fn test_candidates_intersection() {
+        // La seule case candidate de la ligne 3 (A3) est dans la région 'A'
+        let (grid_handler, grid) = grid![
+            "AABBB", "ABBBB", "ACCCC", "DDDEE", "DDDEE"; stars = 1; no_star = "B3 C3 D3 E3"
+        ];
+        let good_rule = rule_candidates_intersection(&grid_handler, &grid).unwrap();
+        let GoodRule::ZoneExclusions(regions, zone, actions) = &good_rule else {
+            panic!("Règle inattendue : {good_rule}");
+        };
+        assert_eq!(regions, &vec!['A']);
+        assert_eq!(zone, &GridSurfer::Line(2));
+        assert_eq!(
+            actions,
+            &vec![
+                GridAction::SetNoStar(LineColumn::new(0, 0)),
+                GridAction::SetNoStar(LineColumn::new(0, 1)),
+                GridAction::SetNoStar(LineColumn::new(1, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_candidates_intersection_region() {
+        // Les cases candidates de la région 'C' sont toutes dans la ligne 3
+        let (grid_handler, grid) = grid![
+            "AABBB", "AABBB", "DCCCE", "DDDEE", "DDDEE"; stars = 1
+        ];
+        let good_rule = rule_candidates_intersection(&grid_handler, &grid).unwrap();
+        assert!(matches!(
+            good_rule,
+            GoodRule::ZoneCombinations(_, GridSurfer::Line(2), _)
+        ));
+        assert_eq!(
+            good_rule.actions(),
+            &[
+                GridAction::SetNoStar(LineColumn::new(2, 0)),
+                GridAction::SetNoStar(LineColumn::new(2, 4)),
+            ]
+        );
+
+        // Règle sans objet pour une grille sans contrainte de région
+        let parser =
+            crate::GridParser::try_from(vec!["AABBB", "AABBB", "DCCCE", "DDDEE", "DDDEE"]).unwrap();
+        let grid_handler = GridHandler::without_regions(&parser, 1);
+        let grid = Grid::from(&grid_handler);
+        assert!(rule_candidates_intersection(&grid_handler, &grid).is_none());
+    }
+}
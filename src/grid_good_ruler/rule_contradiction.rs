@@ -0,0 +1,109 @@
+//! Règle de construction/résolution d'une grille.
+//!
+//! Raisonnement par l'absurde ('what-if') : une étoile est placée par hypothèse dans une case inconnue,
+//! puis les conséquences immédiates de cette étoile sont propagées avec les règles les plus simples (cases
+//! adjacentes à une étoile, zones complètes ou dont les cases restantes sont des étoiles).<br>
+//! Si la grille obtenue n'est plus valide (voir [`check_bad_rules`]), l'hypothèse est fausse : la case ne
+//! peut pas contenir une étoile.
+//!
+//! Cette règle, coûteuse, n'est examinée qu'après toutes les autres. Avec la feature `parallel`, les cases
+//! sont examinées en parallèle (la case retenue reste la première dans l'ordre de la grille), sauf si la
+//! configuration impose une recherche séquentielle (voir [`SolverConfig::is_parallel`]).
+
+use crate::check_bad_rules;
+use crate::parallel::find_map_first;
+use crate::CellValue;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::LineColumn;
+
+use super::rule_no_star_adjacent_to_star::rule_no_star_adjacent_to_star;
+use super::rule_value_completed::rule_value_completed;
+use super::SolverConfig;
+
+/// Cherche une case inconnue dans laquelle une étoile rend la grille invalide.<br>
+/// Les cases restantes ne sont plus examinées dès que les limites de la recherche de la configuration
+/// `config` sont atteintes
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn rule_contradiction(
+    handler: &GridHandler,
+    grid: &Grid,
+    config: &SolverConfig,
+) -> Option<GoodRule> {
+    let limits = config.limits();
+    let cells: Vec<LineColumn> = grid
+        .iter_with_value(CellValue::Unknown)
+        .map(|cell| cell.line_column)
+        .filter(|line_column| !handler.is_blocked(*line_column))
+        .collect();
+    let line_column = find_map_first(&cells, config.is_parallel(), |line_column| {
+        (limits.interruption().is_none() && is_star_contradiction(handler, grid, *line_column))
+            .then_some(*line_column)
+    })?;
+    Some(GoodRule::Contradiction(
+        line_column,
+        vec![GridAction::SetNoStar(line_column)],
+    ))
+}
+
+/// Retourne `true` si une étoile dans la case `line_column` et ses conséquences immédiates rendent la
+/// grille invalide
+fn is_star_contradiction(handler: &GridHandler, grid: &Grid, line_column: LineColumn) -> bool {
+    let mut grid = grid.clone();
+    // Pas d'observateur de la grille pour une hypothèse
+    grid.set_observer(None);
+    if grid
+        .apply_action(&GridAction::SetStar(line_column))
+        .is_err()
+    {
+        return false;
+    }
+    loop {
+        if check_bad_rules(handler, &grid).is_err() {
+            return true;
+        }
+        let Some(good_rule) = rule_no_star_adjacent_to_star(handler, &grid)
+            .or_else(|| rule_value_completed(handler, &grid))
+        else {
+            return false;
+        };
+        grid.apply_good_rule(&good_rule);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_contradiction() {
+        // Une étoile en B1 interdit les 2 cases de la région A (A1, A2) : la région A ne peut plus
+        // recevoir son étoile
+        let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let b1 = LineColumn::new(0, 1);
+        assert!(is_star_contradiction(&grid_handler, &grid, b1));
+
+        // Aucune contradiction pour les étoiles de la solution
+        let mut solved_grid = grid.clone();
+        for step in grid_handler.steps(&mut solved_grid) {
+            step.unwrap();
+        }
+        for cell in solved_grid.iter_with_value(CellValue::Star) {
+            assert!(!is_star_contradiction(
+                &grid_handler,
+                &grid,
+                cell.line_column
+            ));
+        }
+
+        match rule_contradiction(&grid_handler, &grid, &SolverConfig::new()) {
+            Some(GoodRule::Contradiction(line_column, actions)) => {
+                assert_eq!(actions, vec![GridAction::SetNoStar(line_column)]);
+                assert!(is_star_contradiction(&grid_handler, &grid, line_column));
+            }
+            _ => panic!("La règle n'est pas détectée"),
+        }
+    }
+}
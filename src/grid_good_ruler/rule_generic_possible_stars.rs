@@ -2,17 +2,29 @@
 //!
 //! Recherche générique des cases invariantes pour toutes les combinaisons possibles d'une zone.
 //!
+//! Avec la feature `parallel`, les zones sont examinées en parallèle : la zone retenue (celle qui a le
+//! moins de grilles possibles, la première dans l'ordre d'examen en cas d'égalité) est la même qu'en mode
+//! séquentiel. La configuration peut imposer un examen séquentiel (voir [`SolverConfig::is_parallel`]).
 
+#[cfg(feature = "parallel")]
+use crate::parallel::map_ordered;
+#[cfg(feature = "parallel")]
+use crate::solve_metrics::record_metrics;
+use crate::solve_metrics::record_zone;
+use crate::solve_metrics::ZoneExamination;
 use crate::CellValue;
 use crate::GoodRule;
 use crate::Grid;
 use crate::GridAction;
 use crate::GridHandler;
 use crate::GridSurfer;
+#[cfg(feature = "parallel")]
+use crate::SolveMetrics;
 
-use super::collector::Collector;
+use super::collector::{Collector, PossibleGrid};
 use super::invariant::Variant;
 use super::star_adjacent::StarAdjacent;
+use super::SolverConfig;
 
 /// Énumération des différentes zones possibles pour être examinées
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -23,26 +35,23 @@ pub enum ZoneToExamine {
 }
 
 /// Méthode générique qui cherche toutes les combinaisons possibles dans les différentes zones ou régions
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(handler, grid, config))
+)]
 pub fn rule_generic_possible_stars(
     handler: &GridHandler,
     grid: &Grid,
     zone_to_examine: ZoneToExamine,
     recursive: bool,
+    config: &SolverConfig,
 ) -> Option<GoodRule> {
-    // Pour simplifier la règle présentée à un humain, on retient la région qui génère un minimum
-    // de grilles pour placer toutes les étoiles
-    #[derive(Debug, Default)]
-    struct BestCollector {
-        grid_surfer: Option<GridSurfer>,
-        nb_possible_grids: usize,
-        invariant_actions: Vec<GridAction>,
-    }
-
     // zones: [(GridSurfer, nb_stars, combinaisons_count)]
     let mut zones = Vec::new();
 
     // Closure pour compléter la liste des zones à examiner (évite les répétitions de paramètres)
-    let mut add_zone = |grid_surfer: GridSurfer, nb_stars: usize| {
+    let mut add_zone = |grid_surfer: GridSurfer| {
+        let nb_stars = handler.zone_nb_stars(&grid_surfer);
         let nb_combinaisons = combinaisons_count(handler, grid, &grid_surfer, nb_stars);
         zones.push((grid_surfer, nb_stars, nb_combinaisons));
     };
@@ -51,59 +60,54 @@ pub fn rule_generic_possible_stars(
         ZoneToExamine::Region => {
             // Parcours de toutes les régions
             for region in handler.regions() {
-                add_zone(GridSurfer::Region(region), handler.nb_stars());
+                add_zone(GridSurfer::Region(region));
+            }
+            // ... ainsi que toutes les cages
+            for cage in handler.cages() {
+                add_zone(cage);
             }
         }
         ZoneToExamine::LineAndColumn => {
             // Parcours de toutes les lignes
             for line in 0..handler.nb_lines() {
-                add_zone(GridSurfer::Line(line), handler.nb_stars());
+                add_zone(GridSurfer::Line(line));
             }
             // Parcours de toutes les colonnes
             for column in 0..handler.nb_columns() {
-                add_zone(GridSurfer::Column(column), handler.nb_stars());
+                add_zone(GridSurfer::Column(column));
             }
         }
         ZoneToExamine::MultipleLinesAndColumns(2) => {
             // Double-lignes
             for line in 0..handler.nb_lines() - 1 {
-                add_zone(GridSurfer::Lines(line..=line + 1), 2 * handler.nb_stars());
+                add_zone(GridSurfer::Lines(line..=line + 1));
             }
 
             // Double-colonnes
             for column in 0..handler.nb_columns() - 1 {
-                add_zone(
-                    GridSurfer::Columns(column..=column + 1),
-                    2 * handler.nb_stars(),
-                );
+                add_zone(GridSurfer::Columns(column..=column + 1));
             }
         }
         ZoneToExamine::MultipleLinesAndColumns(3) => {
             // Double-lignes
             for line in 0..handler.nb_lines() - 2 {
-                add_zone(GridSurfer::Lines(line..=line + 2), 3 * handler.nb_stars());
+                add_zone(GridSurfer::Lines(line..=line + 2));
             }
 
             // Double-colonnes
             for column in 0..handler.nb_columns() - 2 {
-                add_zone(
-                    GridSurfer::Columns(column..=column + 2),
-                    3 * handler.nb_stars(),
-                );
+                add_zone(GridSurfer::Columns(column..=column + 2));
             }
         }
         ZoneToExamine::MultipleLinesAndColumns(4) => {
             // Double-lignes
             for line in 0..handler.nb_lines() - 3 {
-                add_zone(GridSurfer::Lines(line..=line + 3), 4 * handler.nb_stars());
+                add_zone(GridSurfer::Lines(line..=line + 3));
             }
 
             // Double-colonnes
             for column in 0..handler.nb_columns() - 3 {
-                add_zone(
-                    GridSurfer::Columns(column..=column + 3),
-                    4 * handler.nb_stars(),
-                );
+                add_zone(GridSurfer::Columns(column..=column + 3));
             }
         }
         ZoneToExamine::MultipleLinesAndColumns(_) => {
@@ -114,13 +118,70 @@ pub fn rule_generic_possible_stars(
     }
 
     // Tri des différentes zones par ordre croissant de combinaisons possible
-    zones.sort_by(|a, b| a.2.cmp(&b.2));
+    zones.sort_by_key(|a| a.2);
+
+    let best_collector = best_zone(handler, grid, zones, recursive, config);
+    // Règle trouvée ?
+    best_collector.grid_surfer.map(|grid_surfer| {
+        GoodRule::InvariantWithZone(grid_surfer, best_collector.invariant_actions)
+    })
+}
 
+/// Pour simplifier la règle présentée à un humain, on retient la région qui génère un minimum
+/// de grilles pour placer toutes les étoiles
+#[derive(Debug, Default)]
+struct BestCollector {
+    grid_surfer: Option<GridSurfer>,
+    nb_possible_grids: usize,
+    invariant_actions: Vec<GridAction>,
+}
+
+/// Examine les zones `zones` (zone, nombre d'étoiles, nombre de combinaisons) et retient la première zone
+/// applicable (dans l'ordre des zones) qui a le moins de grilles possibles.<br>
+/// Les zones sont examinées en parallèle avec la feature `parallel`, sauf si la configuration impose un
+/// examen séquentiel
+fn best_zone(
+    handler: &GridHandler,
+    grid: &Grid,
+    zones: Vec<(GridSurfer, usize, usize)>,
+    recursive: bool,
+    config: &SolverConfig,
+) -> BestCollector {
+    #[cfg(feature = "parallel")]
+    if config.is_parallel() {
+        return best_zone_parallel(handler, grid, zones, recursive, config);
+    }
+    best_zone_sequential(handler, grid, zones, recursive, config)
+}
+
+/// Examine les zones `zones` (zone, nombre d'étoiles, nombre de combinaisons) dans leur ordre et retient
+/// la première zone applicable qui a le moins de grilles possibles
+fn best_zone_sequential(
+    handler: &GridHandler,
+    grid: &Grid,
+    zones: Vec<(GridSurfer, usize, usize)>,
+    recursive: bool,
+    config: &SolverConfig,
+) -> BestCollector {
     let mut best_collector = BestCollector::default();
     // Examine les différentes zones
     for (grid_surfer, nb_stars, _) in zones {
-        let (invariant_actions, nb_possible_grids) =
-            try_star_complete(handler, grid, &grid_surfer, nb_stars, recursive);
+        // Une zone qui a au moins autant de grilles possibles que la meilleure zone déjà trouvée ne sera
+        // pas retenue : inutile de collecter plus de grilles
+        let max_possible_grids = if best_collector.grid_surfer.is_some() {
+            best_collector.nb_possible_grids.saturating_sub(1)
+        } else {
+            config.max_possible_grids()
+        };
+        let (invariant_actions, nb_possible_grids) = try_star_complete(
+            handler,
+            grid,
+            &grid_surfer,
+            nb_stars,
+            recursive,
+            max_possible_grids,
+            config,
+        );
         if !invariant_actions.is_empty()
         // La règle s'applique pour cette zone...
             && (best_collector.grid_surfer.is_none()
@@ -135,15 +196,60 @@ pub fn rule_generic_possible_stars(
             };
         }
     }
-    // Règle trouvée ?
-    if best_collector.grid_surfer.is_some() {
-        Some(GoodRule::InvariantWithZone(
-            best_collector.grid_surfer.unwrap(),
-            best_collector.invariant_actions,
-        ))
-    } else {
-        None
+    best_collector
+}
+
+/// Examine en parallèle les zones `zones` (zone, nombre d'étoiles, nombre de combinaisons) et retient la
+/// première zone applicable (dans l'ordre des zones) qui a le moins de grilles possibles.<br>
+/// Chaque zone est examinée avec le budget complet de la configuration : une zone qui aurait été
+/// écartée en mode séquentiel (au moins autant de grilles possibles que la meilleure zone) n'est de
+/// toute façon pas retenue et elle est relevée comme écartée (voir [`SolveMetrics::nb_pruned_zones`])
+#[cfg(feature = "parallel")]
+fn best_zone_parallel(
+    handler: &GridHandler,
+    grid: &Grid,
+    zones: Vec<(GridSurfer, usize, usize)>,
+    recursive: bool,
+    config: &SolverConfig,
+) -> BestCollector {
+    // Les mesures relevées par chaque thread sont reportées sur le thread courant
+    let results = map_ordered(&zones, |(grid_surfer, nb_stars, _)| {
+        SolveMetrics::measure(|| {
+            try_star_complete(
+                handler,
+                grid,
+                grid_surfer,
+                *nb_stars,
+                recursive,
+                config.max_possible_grids(),
+                config,
+            )
+        })
+    });
+    let mut best_collector = BestCollector::default();
+    for ((grid_surfer, _, _), ((invariant_actions, nb_possible_grids), mut metrics)) in
+        zones.into_iter().zip(results)
+    {
+        // Zone écartée en mode séquentiel : trop de grilles possibles pour faire mieux que la meilleure zone
+        if best_collector.grid_surfer.is_some()
+            && nb_possible_grids >= best_collector.nb_possible_grids.max(1)
+        {
+            metrics.nb_overflow_zones = 0;
+            metrics.nb_pruned_zones = 1;
+        }
+        record_metrics(&metrics);
+        if !invariant_actions.is_empty()
+            && (best_collector.grid_surfer.is_none()
+                || nb_possible_grids < best_collector.nb_possible_grids)
+        {
+            best_collector = BestCollector {
+                grid_surfer: Some(grid_surfer),
+                nb_possible_grids,
+                invariant_actions,
+            };
+        }
     }
+    best_collector
 }
 
 /// Calcul le nombre de combinaisons possible pour placer toutes les étoiles dans une zone
@@ -170,7 +276,8 @@ fn combinaisons_count(
     let mut nb_combinaisons = 1;
     for _ in 0..nb_stars_left {
         // Pour chaque étoile restant à placer, on ajoute le nombre de combinaisons possible
-        nb_combinaisons *= nb_cells;
+        // (sans dépasser `usize::MAX` pour les grandes zones)
+        nb_combinaisons = usize::saturating_mul(nb_combinaisons, nb_cells);
         nb_cells -= 1;
     }
     nb_combinaisons
@@ -178,20 +285,74 @@ fn combinaisons_count(
 
 /// Vérifie si la règle est applicable sur la région définie.<br>
 /// Si applicable, retourne la liste des actions déduites par la règle et le nombre de grilles possibles
-/// qui ont été examinées pour ces actions
+/// qui ont été examinées pour ces actions.<br>
+/// La zone n'est pas examinée (aucune action) s'il y a plus de `max_possible_grids` grilles possibles.<br>
+/// La stratégie de la recherche récursive est celle imposée par la configuration `config` ou choisie
+/// pour la zone (voir [`crate::CollectorStrategy::for_zone`])
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip(handler, grid, grid_surfer, config),
+        fields(zone = %grid_surfer)
+    )
+)]
 fn try_star_complete(
     handler: &GridHandler,
     grid: &Grid,
     grid_surfer: &GridSurfer,
     nb_stars: usize,
     recursive: bool,
+    max_possible_grids: usize,
+    config: &SolverConfig,
 ) -> (Vec<GridAction>, usize) {
+    // Plus aucune zone n'est examinée dès que les limites de la recherche sont atteintes
+    if config.limits().interruption().is_some() {
+        return (Vec::new(), 0);
+    }
     let surfer = handler.surfer(grid, grid_surfer);
-    let mut collector = Collector::new(handler, grid, &surfer, nb_stars);
-    if recursive {
-        collector.collect_recursive_possible_grids();
+    let mut collector = Collector::new(handler, grid, &surfer, nb_stars)
+        .with_max_possible_grids(max_possible_grids)
+        .with_strategy(config.collector_strategy())
+        .with_limits(config.limits());
+    // Stratégie de la recherche récursive (None pour la recherche limitée au contenu de la zone)
+    let strategy = if recursive {
+        Some(collector.collect_recursive_possible_grids())
     } else {
         collector.collect_possible_grids();
+        None
+    };
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        nb_possible_grids = collector.possible_grids.len(),
+        is_overflow = collector.is_overflow(),
+        strategy = ?strategy,
+        "grilles possibles collectées"
+    );
+    // Un budget inférieur à celui de la configuration écarte les zones qui ne peuvent pas faire mieux que
+    // la meilleure zone déjà trouvée : elles ne sont pas abandonnées
+    let examination = if !collector.is_overflow() {
+        ZoneExamination::Complete
+    } else if collector.is_possible_grids_overflow()
+        && max_possible_grids < config.max_possible_grids()
+    {
+        ZoneExamination::Pruned
+    } else {
+        ZoneExamination::Overflow
+    };
+    record_zone(
+        strategy,
+        collector.possible_grids.len(),
+        collector
+            .possible_grids
+            .iter()
+            .map(PossibleGrid::memory_size)
+            .sum(),
+        examination,
+    );
+    if collector.is_overflow() {
+        // Trop de grilles possibles : la zone n'est pas examinée
+        return (Vec::new(), collector.possible_grids.len());
     }
     // Liste des invariants dans la région pour toutes les grilles possibles
     let mut invariants = Variant::check_for_invariants(handler, grid, &collector.possible_grids);
@@ -211,16 +372,11 @@ fn try_star_complete(
 mod tests {
     use super::*;
 
-    use crate::GridParser;
     use crate::LineColumn;
 
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -0,0 +1,242 @@
+//! Catégories des règles de construction/résolution d'une grille.
+
+use std::fmt::Display;
+
+use crate::Difficulty;
+use crate::GoodRule;
+
+/// Catégorie d'une règle de construction [`GoodRule`].<br>
+/// Permet de regrouper, filtrer ou libeller les règles sans analyser leur affichage textuel.<br>
+/// Les noms des variantes sont stables d'une version à l'autre (ils peuvent être enregistrés dans des
+/// traces ou des statistiques) : une variante n'est jamais renommée ni supprimée, de nouvelles variantes
+/// peuvent être ajoutées avec de nouvelles règles
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum RuleKind {
+    /// Voir [`GoodRule::NoStarAdjacentToStar`]
+    NoStarAdjacentToStar,
+
+    /// Voir [`GoodRule::ZoneNoStarCompleted`]
+    ZoneNoStarCompleted,
+
+    /// Voir [`GoodRule::ZoneExclusions`]
+    ZoneExclusions,
+
+    /// Voir [`GoodRule::ZoneCombinations`]
+    ZoneCombinations,
+
+    /// Voir [`GoodRule::ZoneStarCompleted`]
+    ZoneStarCompleted,
+
+    /// Voir [`GoodRule::InvariantWithZone`]
+    InvariantWithZone,
+
+    /// Voir [`GoodRule::Contradiction`]
+    Contradiction,
+
+    /// Voir [`GoodRule::BlockCounting`]
+    BlockCounting,
+
+    /// Voir [`GoodRule::LineSegments`]
+    LineSegments,
+}
+
+/// Palier de technique d'une règle de construction, de la plus élémentaire à la plus laborieuse.<br>
+/// Permet de filtrer les règles (par exemple n'indiquer que des règles élémentaires) ou de les regrouper
+/// dans une interface
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Technique {
+    /// Conséquences directes d'une étoile ou du nombre de cases restantes d'une zone
+    Basic,
+
+    /// Décompte des étoiles de plusieurs zones (régions, lignes, blocs ou segments)
+    Intermediate,
+
+    /// Examen de toutes les façons de placer les étoiles d'une zone
+    Advanced,
+
+    /// Essai d'une étoile jusqu'à une contradiction
+    BruteForce,
+}
+
+impl Display for Technique {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Basic => write!(f, "élémentaire"),
+            Self::Intermediate => write!(f, "intermédiaire"),
+            Self::Advanced => write!(f, "avancée"),
+            Self::BruteForce => write!(f, "force brute"),
+        }
+    }
+}
+
+impl Display for RuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl RuleKind {
+    /// Nom court de la règle
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::NoStarAdjacentToStar => "Étoile adjacente",
+            Self::ZoneNoStarCompleted => "Zone complète",
+            Self::ZoneExclusions => "Exclusions",
+            Self::ZoneCombinations => "Combinaisons",
+            Self::ZoneStarCompleted => "Étoiles restantes",
+            Self::InvariantWithZone => "Invariants",
+            Self::Contradiction => "Contradiction",
+            Self::BlockCounting => "Blocs 2x2",
+            Self::LineSegments => "Segments",
+        }
+    }
+
+    /// Difficulté pour un humain d'appliquer la règle
+    #[must_use]
+    pub const fn difficulty(&self) -> Difficulty {
+        match self {
+            Self::NoStarAdjacentToStar | Self::ZoneNoStarCompleted | Self::ZoneStarCompleted => {
+                Difficulty::Easy
+            }
+            Self::ZoneExclusions
+            | Self::ZoneCombinations
+            | Self::BlockCounting
+            | Self::LineSegments => Difficulty::Medium,
+            Self::InvariantWithZone => Difficulty::Hard,
+            Self::Contradiction => Difficulty::Expert,
+        }
+    }
+
+    /// Palier de technique de la règle
+    #[must_use]
+    pub const fn technique(&self) -> Technique {
+        match self {
+            Self::NoStarAdjacentToStar | Self::ZoneNoStarCompleted | Self::ZoneStarCompleted => {
+                Technique::Basic
+            }
+            Self::ZoneExclusions
+            | Self::ZoneCombinations
+            | Self::BlockCounting
+            | Self::LineSegments => Technique::Intermediate,
+            Self::InvariantWithZone => Technique::Advanced,
+            Self::Contradiction => Technique::BruteForce,
+        }
+    }
+
+    /// Description de la règle
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::NoStarAdjacentToStar => {
+                "Une case adjacente à une étoile ne peut pas contenir une étoile"
+            }
+            Self::ZoneNoStarCompleted => {
+                "Les cases restantes d'une zone qui a déjà toutes ses étoiles ne peuvent pas contenir une étoile"
+            }
+            Self::ZoneExclusions => {
+                "Les étoiles de régions contenues dans des lignes ou colonnes excluent les autres cases de ces régions"
+            }
+            Self::ZoneCombinations => {
+                "Des régions qui occupent entièrement des lignes ou colonnes excluent les autres cases de ces lignes ou colonnes"
+            }
+            Self::ZoneStarCompleted => {
+                "Les cases restantes d'une zone sont des étoiles s'il reste autant de cases que d'étoiles à placer"
+            }
+            Self::InvariantWithZone => {
+                "Une case a toujours la même valeur quelle que soit la façon de placer les étoiles dans une zone"
+            }
+            Self::Contradiction => {
+                "Une case ne peut pas contenir une étoile si cette étoile et ses conséquences immédiates rendent la grille invalide"
+            }
+            Self::BlockCounting => {
+                "Un bloc de 2x2 cases contient au plus une étoile : s'il y a autant de blocs possibles que d'étoiles dans 2 lignes ou colonnes, chaque bloc en contient une"
+            }
+            Self::LineSegments => {
+                "Un segment de n cases consécutives d'une ligne ou colonne contient au plus (n + 1) / 2 étoiles : si ces maximums suffisent tout juste, chaque segment contient son maximum"
+            }
+        }
+    }
+}
+
+impl GoodRule {
+    /// Catégorie de la règle
+    #[must_use]
+    pub const fn kind(&self) -> RuleKind {
+        match self {
+            Self::NoStarAdjacentToStar(..) => RuleKind::NoStarAdjacentToStar,
+            Self::ZoneNoStarCompleted(..) => RuleKind::ZoneNoStarCompleted,
+            Self::ZoneExclusions(..) => RuleKind::ZoneExclusions,
+            Self::ZoneCombinations(..) => RuleKind::ZoneCombinations,
+            Self::ZoneStarCompleted(..) => RuleKind::ZoneStarCompleted,
+            Self::InvariantWithZone(..) => RuleKind::InvariantWithZone,
+            Self::Contradiction(..) => RuleKind::Contradiction,
+            Self::BlockCounting(..) => RuleKind::BlockCounting,
+            Self::LineSegments(..) => RuleKind::LineSegments,
+        }
+    }
+
+    /// Palier de technique de la règle (voir [`RuleKind::technique`])
+    #[must_use]
+    pub const fn technique(&self) -> Technique {
+        self.kind().technique()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::get_good_rule;
+    use crate::GridAction;
+    use crate::LineColumn;
+
+    #[test]
+    fn test_kind() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        grid.apply_action(&GridAction::SetStar(LineColumn::new(2, 2)))
+            .unwrap();
+
+        let good_rule = get_good_rule(&grid_handler, &grid).unwrap().unwrap();
+        let kind = good_rule.kind();
+        assert_eq!(kind, RuleKind::NoStarAdjacentToStar);
+        assert_eq!(kind.difficulty(), Difficulty::Easy);
+        assert_eq!(good_rule.technique(), Technique::Basic);
+        assert_eq!(kind.to_string(), kind.name());
+        assert!(!kind.description().is_empty());
+    }
+
+    #[test]
+    fn test_technique() {
+        assert!(Technique::Basic < Technique::Intermediate);
+        assert!(Technique::Advanced < Technique::BruteForce);
+        assert_eq!(RuleKind::LineSegments.technique(), Technique::Intermediate);
+        assert_eq!(RuleKind::InvariantWithZone.technique(), Technique::Advanced);
+        assert_eq!(RuleKind::Contradiction.technique(), Technique::BruteForce);
+        assert_eq!(Technique::BruteForce.to_string(), "force brute");
+    }
+
+    #[test]
+    fn test_stable_names() {
+        // Ces noms sont enregistrés par les utilisateurs de la bibliothèque : ils ne doivent pas changer
+        for (kind, name) in [
+            (RuleKind::NoStarAdjacentToStar, "NoStarAdjacentToStar"),
+            (RuleKind::ZoneNoStarCompleted, "ZoneNoStarCompleted"),
+            (RuleKind::ZoneExclusions, "ZoneExclusions"),
+            (RuleKind::ZoneCombinations, "ZoneCombinations"),
+            (RuleKind::ZoneStarCompleted, "ZoneStarCompleted"),
+            (RuleKind::InvariantWithZone, "InvariantWithZone"),
+            (RuleKind::Contradiction, "Contradiction"),
+            (RuleKind::BlockCounting, "BlockCounting"),
+            (RuleKind::LineSegments, "LineSegments"),
+        ] {
+            assert_eq!(format!("{kind:?}"), name);
+            #[cfg(feature = "serde")]
+            assert_eq!(serde_json::to_string(&kind).unwrap(), format!("\"{name}\""));
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Règle de construction/résolution d'une grille.
+//!
+//! Une ligne (ou colonne) est découpée en segments : suites maximales de cases consécutives qui
+//! contiennent une étoile ou qui peuvent encore en recevoir une. Deux étoiles ne pouvant pas se toucher, un
+//! segment de `n` cases contient au plus `(n + 1) / 2` étoiles.<br>
+//! Si la somme de ces maximums est égale au nombre d'étoiles de la ligne, chaque segment contient
+//! exactement son maximum d'étoiles :
+//! * Un segment d'un nombre impair de cases a ses étoiles dans sa première case, sa troisième case, etc.
+//! * Un segment de 2 cases contient une étoile : les cases adjacentes à ses 2 cases ne peuvent pas être
+//!   des étoiles
+//!
+//! Cette règle ne fait que compter des cases : elle est bien moins coûteuse que la recherche des
+//! combinaisons possibles d'une ligne.
+
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::GridSurfer;
+use crate::LineColumn;
+
+use super::rule_block_counting::is_candidate;
+
+/// Cherche une ligne ou colonne dont chaque segment contient son maximum d'étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn rule_line_segments(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
+    let nb_lines = handler.nb_lines();
+    let nb_columns = handler.nb_columns();
+    let lines = (0..nb_lines).map(|line| {
+        let cells: Vec<LineColumn> = (0..nb_columns)
+            .map(|column| LineColumn::new(line, column))
+            .collect();
+        (GridSurfer::Line(line), cells)
+    });
+    let columns = (0..nb_columns).map(|column| {
+        let cells: Vec<LineColumn> = (0..nb_lines)
+            .map(|line| LineColumn::new(line, column))
+            .collect();
+        (GridSurfer::Column(column), cells)
+    });
+    for (zone, cells) in lines.chain(columns) {
+        let actions = line_segments_actions(handler, grid, &zone, &cells);
+        if !actions.is_empty() {
+            return Some(GoodRule::LineSegments(zone, actions));
+        }
+    }
+    None
+}
+
+/// Segments des cases `cells` (dans l'ordre de la ligne ou colonne) : suites maximales de cases qui
+/// contiennent une étoile ou qui peuvent encore en recevoir une
+fn segments(handler: &GridHandler, grid: &Grid, cells: &[LineColumn]) -> Vec<Vec<LineColumn>> {
+    let mut segments = Vec::new();
+    let mut segment = Vec::new();
+    for line_column in cells {
+        if grid.cell(*line_column).is_star() || is_candidate(handler, grid, *line_column) {
+            segment.push(*line_column);
+        } else if !segment.is_empty() {
+            segments.push(std::mem::take(&mut segment));
+        }
+    }
+    if !segment.is_empty() {
+        segments.push(segment);
+    }
+    segments
+}
+
+/// Actions déduites des segments de la ligne ou colonne `zone` de cases `cells`
+fn line_segments_actions(
+    handler: &GridHandler,
+    grid: &Grid,
+    zone: &GridSurfer,
+    cells: &[LineColumn],
+) -> Vec<GridAction> {
+    let segments = segments(handler, grid, cells);
+    let nb_max_stars: usize = segments
+        .iter()
+        .map(|segment| segment.len().div_ceil(2))
+        .sum();
+    if nb_max_stars != handler.zone_nb_stars(zone) {
+        return Vec::new();
+    }
+
+    // Chaque segment contient son maximum d'étoiles
+    let mut actions = Vec::new();
+    for segment in segments {
+        if segment.len() % 2 == 1 {
+            for (index, line_column) in segment.iter().enumerate() {
+                if grid.cell(*line_column).is_unknown() {
+                    actions.push(if index % 2 == 0 {
+                        GridAction::SetStar(*line_column)
+                    } else {
+                        GridAction::SetNoStar(*line_column)
+                    });
+                }
+            }
+        } else if let [first, second] = segment[..] {
+            let second_adjacent_cells = handler.adjacent_cells(second);
+            actions.extend(
+                handler
+                    .adjacent_cells(first)
+                    .into_iter()
+                    .filter(|line_column| {
+                        second_adjacent_cells.contains(line_column)
+                            && grid.cell(*line_column).is_unknown()
+                    })
+                    .map(GridAction::SetNoStar),
+            );
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::set_cells;
+    use crate::CellValue;
+    use crate::GridParser;
+
+    #[test]
+    fn test_rule_line_segments() {
+        // Grille 2★ : la 1ère ligne n'a plus qu'un segment de 3 cases (A1, B1, C1) pour 2 étoiles
+        let contents = std::fs::read_to_string("./test_grids/facile01_2.txt").unwrap();
+        let grid_parser = GridParser::try_from(contents.as_str()).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 2);
+        let mut grid = Grid::from(&grid_handler);
+        set_cells(&mut grid, "D1 E1 F1 G1 H1 I1", CellValue::NoStar);
+        match rule_line_segments(&grid_handler, &grid) {
+            Some(GoodRule::LineSegments(zone, actions)) => {
+                assert_eq!(zone, GridSurfer::Line(0));
+                assert_eq!(
+                    actions,
+                    vec![
+                        GridAction::SetStar(LineColumn::new(0, 0)),
+                        GridAction::SetNoStar(LineColumn::new(0, 1)),
+                        GridAction::SetStar(LineColumn::new(0, 2)),
+                    ]
+                );
+            }
+            _ => panic!("La règle n'est pas détectée"),
+        }
+
+        // Grille 1★ : segment de 2 cases (D1, E1) dont les cases adjacentes communes sont D2 et E2
+        let (grid_handler, grid) = grid![
+            "ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED";
+            stars = 1;
+            no_star = "A1 B1 C1"
+        ];
+        match rule_line_segments(&grid_handler, &grid) {
+            Some(GoodRule::LineSegments(zone, actions)) => {
+                assert_eq!(zone, GridSurfer::Line(0));
+                assert_eq!(
+                    actions,
+                    vec![
+                        GridAction::SetNoStar(LineColumn::new(1, 3)),
+                        GridAction::SetNoStar(LineColumn::new(1, 4)),
+                    ]
+                );
+            }
+            _ => panic!("La règle n'est pas détectée"),
+        }
+
+        // Rien à déduire de la grille initiale
+        let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        assert!(rule_line_segments(&grid_handler, &grid).is_none());
+    }
+}
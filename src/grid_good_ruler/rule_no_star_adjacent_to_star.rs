@@ -2,29 +2,28 @@
 //!
 //! Recherche les cases adjacentes à une étoile qui ne peuvent pas contenir une étoile.
 
+use crate::CellValue;
 use crate::GoodRule;
 use crate::Grid;
 use crate::GridAction;
 use crate::GridHandler;
-use crate::GridSurfer;
 
 /// Cherche si une étoile déjà placée à des cases adjacentes non définies.
 /// Si oui, ces cases peuvent être définie comme `NoStar`
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_no_star_adjacent_to_star(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    for line_column in handler.surfer(grid, &GridSurfer::AllCells) {
-        if grid.cell(line_column).is_star() {
-            let unknown_adjacent_cells: Vec<GridAction> = handler
-                .adjacent_cells(line_column)
-                .iter()
-                .filter(|line_column| grid.cell(**line_column).is_unknown())
-                .map(|line_column| GridAction::SetNoStar(*line_column))
-                .collect();
-            if !unknown_adjacent_cells.is_empty() {
-                return Some(GoodRule::NoStarAdjacentToStar(
-                    line_column,
-                    unknown_adjacent_cells,
-                ));
-            }
+    for cell in grid.iter_with_value(CellValue::Star) {
+        let unknown_adjacent_cells: Vec<GridAction> = handler
+            .adjacent_cells(cell.line_column)
+            .iter()
+            .filter(|line_column| grid.cell(**line_column).is_unknown())
+            .map(|line_column| GridAction::SetNoStar(*line_column))
+            .collect();
+        if !unknown_adjacent_cells.is_empty() {
+            return Some(GoodRule::NoStarAdjacentToStar(
+                cell.line_column,
+                unknown_adjacent_cells,
+            ));
         }
     }
     None
@@ -34,16 +33,12 @@ pub fn rule_no_star_adjacent_to_star(handler: &GridHandler, grid: &Grid) -> Opti
 mod tests {
     use super::*;
 
-    use crate::GridParser;
+    use crate::GridSurfer;
     use crate::LineColumn;
 
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -52,7 +47,8 @@ mod tests {
 
         // On place volontairement 1 étoile au centre de la grille
         let center_line_column = LineColumn::new(2, 2);
-        grid.apply_action(&GridAction::SetStar(center_line_column));
+        grid.apply_action(&GridAction::SetStar(center_line_column))
+            .unwrap();
 
         // Les 8 cases adjacentes ne peuvent pas contenir une étoile
         let good_rule = rule_no_star_adjacent_to_star(&grid_handler, &grid);
@@ -8,6 +8,9 @@
 //! et il ne peut donc pas y avoir d'autres étoiles sur ce 'n' lignes.<br>
 //! Idem pour les colonnes.
 //!
+//! Si le nombre d'étoiles à placer n'est pas le même dans toutes les zones, la règle s'applique lorsque
+//! les régions doivent contenir autant d'étoiles que les lignes (ou colonnes) qu'elles occupent.
+//!
 //! //! Cette règle est l'opposée de la règle [`rule_region_exclusions`]
 
 /// Crate qui recherche n combinaisons possibles dans un vecteur d'elements
@@ -22,36 +25,71 @@ use crate::LineColumn;
 
 /// Recherche les régions de 1 ligne ou 1 colonne. Les autres cases de cette ligne ou colonne
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_1_combinations(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_combinations(handler, grid, 1)
+    rule_region_n_combinations(handler, grid, 1)
 }
 
 /// Recherche les couples de régions sur 2 ligne ou 2 colonne. Les autres cases de ces lignes ou colonnes
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_2_combinations(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_combinations(handler, grid, 2)
+    rule_region_n_combinations(handler, grid, 2)
 }
 
 /// Recherche les triplets de régions sur 3 ligne ou 3 colonne. Les autres cases de ces lignes ou colonnes
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_3_combinations(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_combinations(handler, grid, 3)
+    rule_region_n_combinations(handler, grid, 3)
 }
 
 /// Recherche les quadruplets de régions sur 4 ligne ou 4 colonne. Les autres cases de ces lignes ou colonnes
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_4_combinations(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_combinations(handler, grid, 4)
+    rule_region_n_combinations(handler, grid, 4)
+}
+
+/// Nombre maximum de combinaisons de régions examinées par [`rule_region_n_combinations`]
+const MAX_REGION_COMBINATIONS: usize = 200_000;
+
+/// Nombre de combinaisons de `n` éléments parmi `nb` (saturé à `usize::MAX`)
+fn nb_combinations(nb: usize, n: usize) -> usize {
+    let n = n.min(nb - n);
+    let mut nb_combinations = 1_usize;
+    for index in 0..n {
+        match nb_combinations.checked_mul(nb - index) {
+            Some(product) => nb_combinations = product / (index + 1),
+            None => return usize::MAX,
+        }
+    }
+    nb_combinations
 }
 
 /// Cherche les combinaisons de 'n' régions occupent exactement 'n' lignes ou 'n' colonnes.<br>
 /// Si des cases appartement à d'autres régions sont dans ces lignes ou colonnes, elles ne peuvent
-/// pas être des étoiles
-fn rule_region_generic_combinations(
+/// pas être des étoiles.<br>
+/// 'n' peut aller jusqu'au nombre de régions : la règle n'est pas examinée s'il y a plus de
+/// 200 000 combinaisons de 'n' régions (voir [`crate::SolverConfig::with_max_band_size`])
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(handler, grid))
+)]
+pub fn rule_region_n_combinations(
     handler: &GridHandler,
     grid: &Grid,
     n: usize,
 ) -> Option<GoodRule> {
+    // Règle sans objet pour une grille sans contrainte de région
+    if !handler.has_regions() {
+        return None;
+    }
+    // Garde sur le coût de la recherche
+    let nb_regions = handler.regions().len();
+    if n == 0 || n > nb_regions || nb_combinations(nb_regions, n) > MAX_REGION_COMBINATIONS {
+        return None;
+    }
     // On utilise le crate 'combination' pour trouver toutes les combinaisons possibles
     for vec_regions in combine::from_vec_at(&handler.regions(), n) {
         // On cherche les cases qui sont dans la combinaison et on détermine les lignes/colonnes minimales/maximales
@@ -80,7 +118,12 @@ fn rule_region_generic_combinations(
             }
         }
 
-        if (max_line - min_line + 1) == n {
+        let regions_nb_stars: usize = vec_regions
+            .iter()
+            .map(|region| handler.zone_nb_stars(&GridSurfer::Region(*region)))
+            .sum();
+
+        if handler.zone_nb_stars(&GridSurfer::Lines(min_line..=max_line)) == regions_nb_stars {
             // Les 'n' régions occupent exactement 'n' lignes
             // Existe-t-il des cases dans ces lignes qui n'appartiennent pas à ces régions et qui sont indéfinies ?
             let grid_surfer = GridSurfer::Lines(min_line..=max_line);
@@ -106,7 +149,8 @@ fn rule_region_generic_combinations(
             }
         }
 
-        if (max_column - min_column + 1) == n {
+        if handler.zone_nb_stars(&GridSurfer::Columns(min_column..=max_column)) == regions_nb_stars
+        {
             // Les 'n' regions occupent exactement 'n'
             // Existe-t-il des cases dans ces colonnes qui n'appartiennent pas à ces régions et qui sont indéfinies ?
             let grid_surfer = GridSurfer::Columns(min_column..=max_column);
@@ -140,15 +184,9 @@ fn rule_region_generic_combinations(
 mod tests {
     use super::*;
 
-    use crate::GridParser;
-
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -161,6 +199,14 @@ mod tests {
         let good_rule = option_good_rule.unwrap();
         grid.apply_good_rule(&good_rule);
 
+        // 'n' nul ou plus grand que le nombre de régions
+        assert!(rule_region_n_combinations(&grid_handler, &grid, 0).is_none());
+        assert!(rule_region_n_combinations(&grid_handler, &grid, 6).is_none());
+
+        assert_eq!(nb_combinations(5, 2), 10);
+        assert_eq!(nb_combinations(25, 6), 177_100);
+        assert_eq!(nb_combinations(200, 100), usize::MAX);
+
         // println!("Rule: {}", &good_rule);
         // println!("Grid :\n{}", grid_handler.display(&grid, true));
         // panic!("stop test")
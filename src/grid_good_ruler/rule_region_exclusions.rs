@@ -16,6 +16,10 @@
 //! cette région sont sur cette ligne (ou colonne) et les cases des cette région qui ne sont pas dans cette
 //! ligne (ou colonne) ne peuvent pas être des étoiles.
 //!
+//! Si le nombre d'étoiles à placer n'est pas le même dans toutes les zones, la règle s'applique lorsque
+//! les régions présentes dans les lignes (ou colonnes) ne doivent pas contenir plus d'étoiles que ces
+//! lignes (ou colonnes).
+//!
 //! Cette règle est l'opposée de la règle [`rule_region_combinations`]
 
 use crate::CellValue;
@@ -29,38 +33,48 @@ use crate::Region;
 
 /// Recherche les régions de 1 ligne ou 1 colonne. Les autres cases de cette ligne ou colonne
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_1_exclusions(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_exclusions(handler, grid, 1)
+    rule_region_n_exclusions(handler, grid, 1)
 }
 
 /// Recherche les couples de régions sur 2 ligne ou 2 colonne. Les autres cases de ces lignes ou colonnes
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_2_exclusions(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_exclusions(handler, grid, 2)
+    rule_region_n_exclusions(handler, grid, 2)
 }
 
 /// Recherche les triplets de régions sur 3 ligne ou 3 colonne. Les autres cases de ces lignes ou colonnes
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_3_exclusions(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_exclusions(handler, grid, 3)
+    rule_region_n_exclusions(handler, grid, 3)
 }
 
 /// Recherche les quadruplets de régions sur 4 ligne ou 4 colonne. Les autres cases de ces lignes ou colonnes
 /// ne peuvent pas être des étoiles
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_4_exclusions(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_region_generic_exclusions(handler, grid, 4)
+    rule_region_n_exclusions(handler, grid, 4)
 }
 
 /// Cherche les combinaisons de 'n' lignes ou colonnes qui contiennent exactement 'n' régions.<br>
 /// S'il existe des cases appartement à ces régions dans d'autres lignes ou colonnes, elles ne peuvent
-/// pas être des étoiles
+/// pas être des étoiles.<br>
+/// 'n' peut aller jusqu'à la taille de la grille : les grandes grilles nécessitent parfois 5 ou 6 lignes
+/// ou colonnes (voir [`crate::SolverConfig::with_max_band_size`])
 #[allow(clippy::range_minus_one)]
-fn rule_region_generic_exclusions(
-    handler: &GridHandler,
-    grid: &Grid,
-    n: usize,
-) -> Option<GoodRule> {
-    for line in 0..=handler.nb_lines() - n {
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(handler, grid))
+)]
+pub fn rule_region_n_exclusions(handler: &GridHandler, grid: &Grid, n: usize) -> Option<GoodRule> {
+    // Règle sans objet pour une grille sans contrainte de région
+    if !handler.has_regions() || n == 0 {
+        return None;
+    }
+    for line in 0..(handler.nb_lines() + 1).saturating_sub(n) {
         let grid_surfer = GridSurfer::Lines(line..=line + n - 1);
         if let Some((vec_regions, candidates)) =
             rule_region_more_generic_exclusions(handler, grid, n, &grid_surfer)
@@ -72,7 +86,7 @@ fn rule_region_generic_exclusions(
             return Some(GoodRule::ZoneExclusions(vec_regions, grid_surfer, actions));
         }
     }
-    for column in 0..=handler.nb_columns() - n {
+    for column in 0..(handler.nb_columns() + 1).saturating_sub(n) {
         let grid_surfer = GridSurfer::Columns(column..=column + n - 1);
         if let Some((vec_regions, candidates)) =
             rule_region_more_generic_exclusions(handler, grid, n, &grid_surfer)
@@ -87,7 +101,7 @@ fn rule_region_generic_exclusions(
     None
 }
 
-/// Spécialisation de `rule_region_generic_exclusions` pour 'n' lignes ou 'n' colonnes.<br>
+/// Spécialisation de `rule_region_n_exclusions` pour 'n' lignes ou 'n' colonnes.<br>
 /// Compte combien de régions différentes sont présentes dans le `grid_surfer`. Si 'n' régions alors
 /// recherche des cases candidates qui ne sont pas définies pour ces régions en dehors de `grid_surfer`
 fn rule_region_more_generic_exclusions(
@@ -96,10 +110,12 @@ fn rule_region_more_generic_exclusions(
     n: usize,
     grid_surfer: &GridSurfer,
 ) -> Option<(Vec<Region>, Vec<LineColumn>)> {
-    let surfer = handler.surfer(grid, grid_surfer);
+    let surfer = handler.surfer_set(grid, grid_surfer);
+    let zone_nb_stars = handler.zone_nb_stars(grid_surfer);
     let mut vec_regions = Vec::new();
-    for line_column in &surfer {
-        match grid.cell(*line_column).value {
+    let mut regions_nb_stars = 0;
+    for line_column in surfer.iter() {
+        match grid.cell(line_column).value {
             // S'il existe déjà des étoiles dans les n lignes ou colonnes, on abandonne la recherche
             // (la règle n'est pas applicable)
             CellValue::Star => return None,
@@ -108,10 +124,11 @@ fn rule_region_more_generic_exclusions(
             CellValue::NoStar => continue,
             // Case non définie, on comptabilise sa région
             CellValue::Unknown => {
-                let region = grid.cell(*line_column).region;
+                let region = grid.cell(line_column).region;
                 if !vec_regions.contains(&region) {
                     vec_regions.push(region);
-                    if vec_regions.len() > n {
+                    regions_nb_stars += handler.zone_nb_stars(&GridSurfer::Region(region));
+                    if vec_regions.len() > n || regions_nb_stars > zone_nb_stars {
                         return None;
                     }
                 }
@@ -141,15 +158,9 @@ fn rule_region_more_generic_exclusions(
 mod tests {
     use super::*;
 
-    use crate::GridParser;
-
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -162,6 +173,10 @@ mod tests {
         let good_rule = option_good_rule.unwrap();
         grid.apply_good_rule(&good_rule);
 
+        // 'n' nul ou plus grand que la grille
+        assert!(rule_region_n_exclusions(&grid_handler, &grid, 0).is_none());
+        assert!(rule_region_n_exclusions(&grid_handler, &grid, 6).is_none());
+
         // println!("Rule: {}", &good_rule);
         // println!("Grid :\n{}", grid_handler.display(&grid, true));
         // panic!("stop test")
@@ -12,28 +12,28 @@ use crate::Grid;
 use crate::GridHandler;
 
 use super::rule_generic_possible_stars;
+use super::SolverConfig;
 use super::ZoneToExamine;
 
 /// Cherche toutes les combinaisons d'étoiles possibles dans les différentes régions.
 /// Version simplifiée de `rule_region_recursive_possible_stars` qui se limite au contenu des
 /// différentes régions pour une compréhension plus aisées pour un humain
-pub fn rule_region_possible_stars(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
-    rule_generic_possible_stars(handler, grid, ZoneToExamine::Region, false)
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn rule_region_possible_stars(
+    handler: &GridHandler,
+    grid: &Grid,
+    config: &SolverConfig,
+) -> Option<GoodRule> {
+    rule_generic_possible_stars(handler, grid, ZoneToExamine::Region, false, config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::GridParser;
-
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -44,13 +44,15 @@ mod tests {
 
         // Cette règle s'applique sur la région 'CC' dans la 3eme ligne : Les cases adjacentes ne peuvent
         // pas être une étoile...
-        let option_good_rule = rule_region_possible_stars(&grid_handler, &grid);
+        let option_good_rule =
+            rule_region_possible_stars(&grid_handler, &grid, &SolverConfig::default());
         assert!(option_good_rule.is_some());
         grid.apply_good_rule(&option_good_rule.unwrap());
 
         // Cette règle s'applique sur l'avant dernière ligne de 'DDDDD' : On doit mettre une étoile
         // sur cette ligne donc les D sur la ligne suivante ne peuvent pas être une étoile...
-        let option_good_rule = rule_region_possible_stars(&grid_handler, &grid);
+        let option_good_rule =
+            rule_region_possible_stars(&grid_handler, &grid, &SolverConfig::default());
         assert!(option_good_rule.is_some());
         grid.apply_good_rule(&option_good_rule.unwrap());
     }
@@ -0,0 +1,134 @@
+//! Stratégies d'ordre d'examen des règles de construction/résolution d'une grille.
+//!
+//! Par défaut, les règles sont examinées par ordre de niveau, de la plus simple à la plus complexe
+//! ([`FixedOrder`]). Une autre stratégie ([`RuleStrategy`]) permet d'expérimenter d'autres ordres (voir
+//! [`crate::get_good_rule_with_strategy`]) : le niveau d'une règle trouvée reste celui de sa règle, quel
+//! que soit son rang dans l'ordre d'examen.
+
+use super::NB_GOOD_RULE_LEVELS;
+
+/// Stratégie d'ordre d'examen des niveaux de règles
+pub trait RuleStrategy {
+    /// Niveaux de règles `levels` (voir [`crate::SolverConfig::levels`]) dans l'ordre de leur examen.<br>
+    /// Les niveaux retournés qui ne sont pas dans `levels` sont ignorés
+    fn order(&mut self, levels: &[usize]) -> Vec<usize>;
+
+    /// Informe la stratégie qu'une règle de niveau `level` a été trouvée
+    fn rule_found(&mut self, _level: usize) {}
+}
+
+/// Ordre fixe : les niveaux sont examinés dans l'ordre de la configuration (stratégie par défaut)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FixedOrder;
+
+impl RuleStrategy for FixedOrder {
+    fn order(&mut self, levels: &[usize]) -> Vec<usize> {
+        levels.to_vec()
+    }
+}
+
+/// Coût estimé de la recherche de chaque niveau de règle (du moins coûteux au plus coûteux)
+const LEVEL_COSTS: [u8; NB_GOOD_RULE_LEVELS] = [
+    1, 1, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 7, 8, 2, 1, 1, 9, 10,
+];
+
+/// Les moins coûteuses d'abord : les niveaux sont examinés par coût estimé croissant (dans l'ordre de la
+/// configuration pour un même coût).<br>
+/// Les règles de comptage ajoutées après les autres (blocs 2x2, segments) sont ainsi examinées avant la
+/// recherche des grilles possibles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CheapestFirst;
+
+impl RuleStrategy for CheapestFirst {
+    fn order(&mut self, levels: &[usize]) -> Vec<usize> {
+        let mut levels = levels.to_vec();
+        levels.sort_by_key(|level| LEVEL_COSTS[*level]);
+        levels
+    }
+}
+
+/// Niveaux des règles qui examinent les régions (exclusions, combinaisons et grilles possibles des
+/// régions)
+const REGION_LEVELS: [usize; 10] = [2, 3, 4, 5, 6, 7, 8, 9, 11, 12];
+
+/// Les régions d'abord : les niveaux des règles qui examinent les régions sont examinés avant les autres
+/// (dans l'ordre de la configuration)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RegionFirst;
+
+impl RuleStrategy for RegionFirst {
+    fn order(&mut self, levels: &[usize]) -> Vec<usize> {
+        let (mut region_levels, other_levels): (Vec<usize>, Vec<usize>) = levels
+            .iter()
+            .partition(|level| REGION_LEVELS.contains(level));
+        region_levels.extend(other_levels);
+        region_levels
+    }
+}
+
+/// La dernière trouvée d'abord : le niveau de la dernière règle trouvée est examiné avant les autres
+/// (dans l'ordre de la configuration).<br>
+/// Une règle s'applique souvent plusieurs fois de suite pendant une résolution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LastSuccessfulFirst {
+    /// Niveau de la dernière règle trouvée
+    last_level: Option<usize>,
+}
+
+impl LastSuccessfulFirst {
+    /// Constructeur : aucune règle trouvée
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { last_level: None }
+    }
+
+    /// Niveau de la dernière règle trouvée
+    #[must_use]
+    pub const fn last_level(&self) -> Option<usize> {
+        self.last_level
+    }
+}
+
+impl RuleStrategy for LastSuccessfulFirst {
+    fn order(&mut self, levels: &[usize]) -> Vec<usize> {
+        let mut levels = levels.to_vec();
+        if let Some(index) = self
+            .last_level
+            .and_then(|last_level| levels.iter().position(|level| *level == last_level))
+        {
+            let last_level = levels.remove(index);
+            levels.insert(0, last_level);
+        }
+        levels
+    }
+
+    fn rule_found(&mut self, level: usize) {
+        self.last_level = Some(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategies_order() {
+        let levels: Vec<usize> = (0..NB_GOOD_RULE_LEVELS).collect();
+        assert_eq!(FixedOrder.order(&levels), levels);
+
+        let order = CheapestFirst.order(&levels);
+        assert_eq!(order[..4], [0, 1, 17, 18]);
+        assert_eq!(order.last(), Some(&20));
+
+        let order = RegionFirst.order(&[0, 1, 2, 4, 10, 11]);
+        assert_eq!(order, vec![2, 4, 11, 0, 1, 10]);
+
+        let mut strategy = LastSuccessfulFirst::new();
+        assert_eq!(strategy.order(&[0, 1, 2]), vec![0, 1, 2]);
+        strategy.rule_found(2);
+        assert_eq!(strategy.last_level(), Some(2));
+        assert_eq!(strategy.order(&[0, 1, 2]), vec![2, 0, 1]);
+        // Niveau absent de la configuration
+        assert_eq!(strategy.order(&[0, 1]), vec![0, 1]);
+    }
+}
@@ -11,9 +11,10 @@ use crate::GridAction;
 use crate::GridHandler;
 use crate::GridSurfer;
 
-/// Cherche dans les régions, les lignes et les colonnes s'il y a des contenus de cases 'évidents :
+/// Cherche dans les régions, les lignes, les colonnes et les cages s'il y a des contenus de cases 'évidents :
 /// * Pas d'étoile si toutes les étoiles sont déjà placées dans la zone
 /// * Une étoile si une seule possibilité pour la zone
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_value_completed(handler: &GridHandler, grid: &Grid) -> Option<GoodRule> {
     let mut zones = Vec::new();
 
@@ -32,9 +33,14 @@ pub fn rule_value_completed(handler: &GridHandler, grid: &Grid) -> Option<GoodRu
         zones.push(GridSurfer::Column(column));
     }
 
+    // Parcours de toutes les cages
+    zones.extend(handler.cages());
+
     // Examine toutes les zones prévues
     for zone in zones {
-        if let Some(good_rule) = try_value_completed(handler, grid, &zone, handler.nb_stars()) {
+        if let Some(good_rule) =
+            try_value_completed(handler, grid, &zone, handler.zone_nb_stars(&zone))
+        {
             return Some(good_rule);
         }
     }
@@ -98,16 +104,11 @@ fn try_value_completed(
 mod tests {
     use super::*;
 
-    use crate::GridParser;
     use crate::LineColumn;
 
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -119,7 +120,9 @@ mod tests {
                 let mut test_grid = grid.clone();
 
                 // On place volontairement 1 étoile dans la grille
-                test_grid.apply_action(&GridAction::SetStar(LineColumn::new(line, column)));
+                test_grid
+                    .apply_action(&GridAction::SetStar(LineColumn::new(line, column)))
+                    .unwrap();
 
                 // La règle doit détecter une région qui doit être complétée avec des cases sans étoile
                 let good_rule = rule_value_completed(&grid_handler, &test_grid);
@@ -136,7 +139,8 @@ mod tests {
         let (grid_handler, mut grid) = get_test_grid();
 
         // On place volontairement 1 case sans étoile dans la zone A de 2 cases
-        grid.apply_action(&GridAction::SetNoStar(LineColumn::new(1, 0)));
+        grid.apply_action(&GridAction::SetNoStar(LineColumn::new(1, 0)))
+            .unwrap();
 
         // La règle doit détecter une région qui doit être complétée avec des cases avec étoile
         let good_rule = rule_value_completed(&grid_handler, &grid);
@@ -7,60 +7,74 @@ use crate::Grid;
 use crate::GridHandler;
 
 use super::rule_generic_possible_stars;
+use super::SolverConfig;
 use super::ZoneToExamine;
 
 /// Cherche toutes les combinaisons possibles dans les différentes régions.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_region_recursive_possible_stars(
     handler: &GridHandler,
     grid: &Grid,
+    config: &SolverConfig,
 ) -> Option<GoodRule> {
-    rule_generic_possible_stars(handler, grid, ZoneToExamine::Region, true)
+    rule_generic_possible_stars(handler, grid, ZoneToExamine::Region, true, config)
 }
 
 /// Cherche toutes les combinaisons possibles dans les différentes ligne ou colonne.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_line_column_recursive_possible_stars(
     handler: &GridHandler,
     grid: &Grid,
+    config: &SolverConfig,
 ) -> Option<GoodRule> {
-    rule_generic_possible_stars(handler, grid, ZoneToExamine::LineAndColumn, true)
+    rule_generic_possible_stars(handler, grid, ZoneToExamine::LineAndColumn, true, config)
 }
 
 /// Cherche toutes les combinaisons possibles dans les groupes de 2 lignes ou 2 colonnes
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_multi_2_lines_columns_recursive_possible_stars(
     handler: &GridHandler,
     grid: &Grid,
+    config: &SolverConfig,
 ) -> Option<GoodRule> {
     rule_generic_possible_stars(
         handler,
         grid,
         ZoneToExamine::MultipleLinesAndColumns(2),
         true,
+        config,
     )
 }
 
 /// Cherche toutes les combinaisons possibles dans les groupes de 3 lignes ou 3 colonnes
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_multi_3_lines_columns_recursive_possible_stars(
     handler: &GridHandler,
     grid: &Grid,
+    config: &SolverConfig,
 ) -> Option<GoodRule> {
     rule_generic_possible_stars(
         handler,
         grid,
         ZoneToExamine::MultipleLinesAndColumns(3),
         true,
+        config,
     )
 }
 
 /// Cherche toutes les combinaisons possibles dans les groupes de 4 lignes ou 3 colonnes
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
 pub fn rule_multi_4_lines_columns_recursive_possible_stars(
     handler: &GridHandler,
     grid: &Grid,
+    config: &SolverConfig,
 ) -> Option<GoodRule> {
     rule_generic_possible_stars(
         handler,
         grid,
         ZoneToExamine::MultipleLinesAndColumns(4),
         true,
+        config,
     )
 }
 
@@ -68,15 +82,9 @@ pub fn rule_multi_4_lines_columns_recursive_possible_stars(
 mod tests {
     use super::*;
 
-    use crate::GridParser;
-
     // Construction d'un objet GridHandler et d'un Grid à partir d'une grille de test
     fn get_test_grid() -> (GridHandler, Grid) {
-        let grid_parser =
-            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
-        let grid_handler = GridHandler::new(&grid_parser, 1);
-        let grid = Grid::from(&grid_handler);
-        (grid_handler, grid)
+        grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1]
     }
 
     #[test]
@@ -87,9 +95,12 @@ mod tests {
         println!("Grille initiale :\n{}", grid_handler.display(&grid, true));
 
         loop {
-            let option_good_rule = rule_line_column_recursive_possible_stars(&grid_handler, &grid);
-            if option_good_rule.is_some() {
-                let good_rule = option_good_rule.unwrap();
+            let option_good_rule = rule_line_column_recursive_possible_stars(
+                &grid_handler,
+                &grid,
+                &SolverConfig::default(),
+            );
+            if let Some(good_rule) = option_good_rule {
                 println!("{good_rule}");
                 grid.apply_good_rule(&good_rule);
 
@@ -0,0 +1,511 @@
+//! Configuration de la recherche des règles de construction/résolution d'une grille.
+//!
+//! Avec la feature `config`, la configuration peut être lue dans un fichier TOML (voir
+//! [`SolverConfig::from_path`]) :
+//!
+//! ```toml
+//! # Règles examinées dans cet ordre (par niveau)
+//! levels = [0, 1, 2, 3, 4, 5, 6, 7]
+//! max_level = 10
+//! max_possible_grids = 500
+//! verbosity = "Pedagogical"
+//! locale = "English"
+//! # Stratégie imposée pour la recherche des grilles possibles (choisie pour chaque zone si absent)
+//! collector_strategy = "Recursive"
+//! # Nombre maximum de lignes ou colonnes des règles d'exclusions et de combinaisons de régions
+//! max_band_size = 6
+//! # Recherche déterministe (durée maximum ignorée, examen séquentiel)
+//! deterministic = true
+//! # Examen des zones et des cases en parallèle (feature `parallel`)
+//! parallel = false
+//! ```
+
+use std::sync::Arc;
+
+use crate::Locale;
+
+use super::collector::MAX_POSSIBLE_GRIDS;
+use super::custom_rule::CustomRules;
+use super::CollectorStrategy;
+use super::Rule;
+use super::SolverLimits;
+use super::Verbosity;
+use super::NB_GOOD_RULE_LEVELS;
+
+/// Configuration de la recherche des règles de construction (voir [`crate::get_good_rule_with_config`])
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverConfig {
+    /// Niveau maximum des règles examinées (entre 0 et [`NB_GOOD_RULE_LEVELS`] - 1)
+    max_level: usize,
+
+    /// Nombre maximum de grilles possibles examinées pour une zone avant d'abandonner l'examen de
+    /// cette zone
+    max_possible_grids: usize,
+
+    /// Niveau de détail des explications des règles trouvées (voir [`crate::GoodRule::explain`])
+    verbosity: Verbosity,
+
+    /// Langue des textes des règles trouvées (voir [`crate::GoodRule::text`])
+    #[cfg_attr(feature = "serde", serde(default))]
+    locale: Locale,
+
+    /// Niveaux des règles examinées dans l'ordre de leur examen (toutes les règles par ordre de niveau
+    /// si None)
+    levels: Option<Vec<usize>>,
+
+    /// Stratégie de la recherche des grilles possibles d'une zone (choisie pour chaque zone si None)
+    collector_strategy: Option<CollectorStrategy>,
+
+    /// Nombre maximum de lignes ou colonnes examinées par les règles d'exclusions et de combinaisons de
+    /// régions
+    max_band_size: usize,
+
+    /// Recherche déterministe : les règles trouvées ne dépendent que de la grille et de la configuration
+    #[cfg_attr(feature = "serde", serde(default))]
+    deterministic: bool,
+
+    /// Examen des zones et des cases en parallèle (avec la feature `parallel`)
+    #[cfg_attr(feature = "serde", serde(default = "default_parallel"))]
+    parallel: bool,
+
+    /// Limites de la recherche (durée, combinaisons examinées, annulation), sans limite par défaut
+    #[cfg_attr(feature = "serde", serde(skip))]
+    limits: SolverLimits,
+
+    /// Règles définies par l'utilisateur examinées avec les règles intégrées
+    #[cfg_attr(feature = "serde", serde(skip))]
+    custom_rules: CustomRules,
+}
+
+/// Nombre maximum de lignes ou colonnes par défaut des règles d'exclusions et de combinaisons de régions
+const DEFAULT_MAX_BAND_SIZE: usize = 4;
+
+/// Examen en parallèle par défaut (configuration sérialisée sans ce paramètre)
+#[cfg(feature = "serde")]
+const fn default_parallel() -> bool {
+    true
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SolverConfig {
+    /// Constructeur : toutes les règles sont examinées avec le budget par défaut
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_level: NB_GOOD_RULE_LEVELS - 1,
+            max_possible_grids: MAX_POSSIBLE_GRIDS,
+            verbosity: Verbosity::Terse,
+            locale: Locale::French,
+            levels: None,
+            collector_strategy: None,
+            max_band_size: DEFAULT_MAX_BAND_SIZE,
+            deterministic: false,
+            parallel: true,
+            limits: SolverLimits::new(),
+            custom_rules: CustomRules::new(),
+        }
+    }
+
+    /// Limite les règles examinées à celles dont le niveau est au plus `max_level`.<br>
+    /// Les règles les plus complexes ne sont ainsi pas utilisées
+    #[must_use]
+    pub const fn with_max_level(mut self, max_level: usize) -> Self {
+        self.max_level = if max_level < NB_GOOD_RULE_LEVELS {
+            max_level
+        } else {
+            NB_GOOD_RULE_LEVELS - 1
+        };
+        self
+    }
+
+    /// Définit le nombre maximum de grilles possibles examinées pour une zone.<br>
+    /// Un budget plus élevé permet de trouver des règles sur les grandes grilles au prix d'une recherche plus longue
+    #[must_use]
+    pub const fn with_max_possible_grids(mut self, max_possible_grids: usize) -> Self {
+        self.max_possible_grids = max_possible_grids;
+        self
+    }
+
+    /// Définit le niveau de détail des explications des règles trouvées
+    #[must_use]
+    pub const fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Définit la langue des textes des règles trouvées
+    #[must_use]
+    pub const fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Limite les règles examinées à celles des niveaux `levels`, examinées dans l'ordre de cette liste.<br>
+    /// Les niveaux invalides (au moins [`NB_GOOD_RULE_LEVELS`]) et les doublons sont ignorés
+    #[must_use]
+    pub fn with_levels(mut self, levels: &[usize]) -> Self {
+        let mut valid_levels = Vec::new();
+        for level in levels {
+            if *level < NB_GOOD_RULE_LEVELS && !valid_levels.contains(level) {
+                valid_levels.push(*level);
+            }
+        }
+        self.levels = Some(valid_levels);
+        self
+    }
+
+    /// Impose la stratégie de la recherche des grilles possibles d'une zone (choisie pour chaque zone
+    /// selon son nombre de cases non définies et d'étoiles manquantes si None).<br>
+    /// Les grilles possibles trouvées, et donc les règles, ne dépendent pas de la stratégie
+    #[must_use]
+    pub const fn with_collector_strategy(
+        mut self,
+        collector_strategy: Option<CollectorStrategy>,
+    ) -> Self {
+        self.collector_strategy = collector_strategy;
+        self
+    }
+
+    /// Définit le nombre maximum de lignes ou colonnes examinées par les règles d'exclusions et de
+    /// combinaisons de régions (4 par défaut).<br>
+    /// Au-delà de 4, ces règles sont examinées pour 5 lignes ou colonnes et plus après les règles des
+    /// niveaux précédents : les grandes grilles nécessitent parfois ces raisonnements, plus coûteux
+    #[must_use]
+    pub const fn with_max_band_size(mut self, max_band_size: usize) -> Self {
+        self.max_band_size = max_band_size;
+        self
+    }
+
+    /// Définit les limites de la recherche : une recherche interrompue par ces limites est signalée par
+    /// [`crate::try_get_good_rule`] (voir [`SolverLimits`])
+    #[must_use]
+    pub fn with_limits(mut self, limits: SolverLimits) -> Self {
+        self.limits = limits.with_deadline_ignored(self.deterministic);
+        self
+    }
+
+    /// Impose (ou non) une recherche déterministe : la durée maximum des limites de la recherche est
+    /// ignorée et les zones et les cases sont examinées séquentiellement (le décompte des combinaisons
+    /// examinées ne dépend alors pas de la répartition entre les threads).<br>
+    /// Les règles trouvées ne dépendent alors que de la grille et de la configuration, pas de la machine
+    #[must_use]
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self.limits = self.limits.with_deadline_ignored(deterministic);
+        self
+    }
+
+    /// Autorise (ou non) l'examen des zones et des cases en parallèle avec la feature `parallel` (autorisé
+    /// par défaut).<br>
+    /// Les règles trouvées sont les mêmes : un examen séquentiel laisse les autres threads à l'application
+    #[must_use]
+    pub const fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Ajoute une règle définie par l'utilisateur : elle est examinée juste après les règles intégrées de
+    /// son niveau de coût (voir [`Rule::cost`]), si ce niveau fait partie des niveaux examinés
+    #[must_use]
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.custom_rules.push(Arc::new(rule));
+        self
+    }
+
+    /// Niveau maximum des règles examinées
+    #[must_use]
+    pub const fn max_level(&self) -> usize {
+        self.max_level
+    }
+
+    /// Nombre maximum de grilles possibles examinées pour une zone
+    #[must_use]
+    pub const fn max_possible_grids(&self) -> usize {
+        self.max_possible_grids
+    }
+
+    /// Niveau de détail des explications des règles trouvées
+    #[must_use]
+    pub const fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// Langue des textes des règles trouvées
+    #[must_use]
+    pub const fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    /// Niveaux des règles examinées dans l'ordre de leur examen (niveaux au plus [`Self::max_level`])
+    #[must_use]
+    pub fn levels(&self) -> Vec<usize> {
+        match &self.levels {
+            Some(levels) => levels
+                .iter()
+                .copied()
+                .filter(|level| *level <= self.max_level)
+                .collect(),
+            None => (0..=self.max_level).collect(),
+        }
+    }
+
+    /// Stratégie imposée pour la recherche des grilles possibles d'une zone (None si choisie pour
+    /// chaque zone)
+    #[must_use]
+    pub const fn collector_strategy(&self) -> Option<CollectorStrategy> {
+        self.collector_strategy
+    }
+
+    /// Nombre maximum de lignes ou colonnes examinées par les règles d'exclusions et de combinaisons de
+    /// régions
+    #[must_use]
+    pub const fn max_band_size(&self) -> usize {
+        self.max_band_size
+    }
+
+    /// Retourne `true` si la recherche est déterministe (voir [`Self::with_deterministic`])
+    #[must_use]
+    pub const fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Retourne `true` si les zones et les cases peuvent être examinées en parallèle (recherche non
+    /// déterministe qui autorise le parallélisme, avec la feature `parallel`)
+    #[must_use]
+    pub const fn is_parallel(&self) -> bool {
+        self.parallel && !self.deterministic
+    }
+
+    /// Limites de la recherche
+    #[must_use]
+    pub const fn limits(&self) -> &SolverLimits {
+        &self.limits
+    }
+
+    /// Règles définies par l'utilisateur dans l'ordre de leur ajout
+    #[must_use]
+    pub fn custom_rules(&self) -> &[Arc<dyn Rule>] {
+        self.custom_rules.rules()
+    }
+
+    /// Règles définies par l'utilisateur examinées après les règles intégrées du niveau `level`
+    pub(crate) fn custom_rules_with_level(&self, level: usize) -> impl Iterator<Item = &dyn Rule> {
+        self.custom_rules.with_level(level)
+    }
+}
+
+/// Erreur lors de la lecture d'une configuration dans un fichier TOML
+#[cfg(feature = "config")]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum SolverConfigError {
+    /// Le fichier ne peut pas être lu
+    #[error("Erreur lecture du fichier {0}: {1}")]
+    Read(String, String),
+
+    /// Le contenu n'est pas une configuration TOML valide
+    #[error("Configuration invalide : {0}")]
+    Parse(String),
+
+    /// Niveau de règle en dehors des niveaux existants
+    #[error("Niveau de règle {0} invalide (entre 0 et {max})", max = NB_GOOD_RULE_LEVELS - 1)]
+    Level(usize),
+}
+
+/// Contenu d'un fichier de configuration : les paramètres absents gardent leur valeur par défaut
+#[cfg(feature = "config")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SolverConfigFile {
+    max_level: Option<usize>,
+    max_possible_grids: Option<usize>,
+    verbosity: Option<Verbosity>,
+    locale: Option<Locale>,
+    levels: Option<Vec<usize>>,
+    collector_strategy: Option<CollectorStrategy>,
+    max_band_size: Option<usize>,
+    deterministic: Option<bool>,
+    parallel: Option<bool>,
+}
+
+#[cfg(feature = "config")]
+impl SolverConfig {
+    /// Lecture d'une configuration dans le fichier TOML `path` (feature `config`).<br>
+    /// Les paramètres absents du fichier gardent leur valeur par défaut
+    /// ### Errors
+    /// Retourne un [`SolverConfigError`] si le fichier ne peut pas être lu ou ne contient pas une
+    /// configuration valide
+    pub fn from_path(path: impl AsRef<std::path::Path>) -> Result<Self, SolverConfigError> {
+        let path = path.as_ref();
+        let file_contents = std::fs::read_to_string(path)
+            .map_err(|e| SolverConfigError::Read(path.display().to_string(), e.to_string()))?;
+        Self::from_toml(&file_contents)
+    }
+
+    /// Lecture d'une configuration dans le texte TOML `text` (feature `config`)
+    /// ### Errors
+    /// Retourne un [`SolverConfigError`] si le texte n'est pas une configuration valide
+    pub fn from_toml(text: &str) -> Result<Self, SolverConfigError> {
+        let file: SolverConfigFile =
+            toml::from_str(text).map_err(|e| SolverConfigError::Parse(e.message().to_string()))?;
+
+        let mut config = Self::new();
+        if let Some(max_level) = file.max_level {
+            if max_level >= NB_GOOD_RULE_LEVELS {
+                return Err(SolverConfigError::Level(max_level));
+            }
+            config = config.with_max_level(max_level);
+        }
+        if let Some(max_possible_grids) = file.max_possible_grids {
+            config = config.with_max_possible_grids(max_possible_grids);
+        }
+        if let Some(verbosity) = file.verbosity {
+            config = config.with_verbosity(verbosity);
+        }
+        if let Some(locale) = file.locale {
+            config = config.with_locale(locale);
+        }
+        if let Some(levels) = file.levels {
+            if let Some(level) = levels.iter().find(|level| **level >= NB_GOOD_RULE_LEVELS) {
+                return Err(SolverConfigError::Level(*level));
+            }
+            config = config.with_levels(&levels);
+        }
+        if file.collector_strategy.is_some() {
+            config = config.with_collector_strategy(file.collector_strategy);
+        }
+        if let Some(max_band_size) = file.max_band_size {
+            config = config.with_max_band_size(max_band_size);
+        }
+        if let Some(deterministic) = file.deterministic {
+            config = config.with_deterministic(deterministic);
+        }
+        if let Some(parallel) = file.parallel {
+            config = config.with_parallel(parallel);
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let config = SolverConfig::default();
+        assert_eq!(config.max_level(), NB_GOOD_RULE_LEVELS - 1);
+        assert_eq!(config.max_possible_grids(), MAX_POSSIBLE_GRIDS);
+        assert_eq!(config.verbosity(), Verbosity::Terse);
+        assert_eq!(config.locale(), Locale::French);
+        assert_eq!(config.collector_strategy(), None);
+        assert_eq!(config.max_band_size(), DEFAULT_MAX_BAND_SIZE);
+        assert!(!config.is_deterministic());
+        assert!(config.is_parallel());
+
+        let config = SolverConfig::new()
+            .with_max_level(3)
+            .with_max_possible_grids(50)
+            .with_verbosity(Verbosity::Pedagogical)
+            .with_locale(Locale::English)
+            .with_max_band_size(6);
+        assert_eq!(config.max_band_size(), 6);
+        assert_eq!(config.max_level(), 3);
+        assert_eq!(config.max_possible_grids(), 50);
+        assert_eq!(config.verbosity(), Verbosity::Pedagogical);
+        assert_eq!(config.locale(), Locale::English);
+
+        // Le niveau maximum est borné par le nombre de niveaux de règles
+        let config = SolverConfig::new().with_max_level(100);
+        assert_eq!(config.max_level(), NB_GOOD_RULE_LEVELS - 1);
+
+        assert!(!SolverConfig::new().with_parallel(false).is_parallel());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        // La durée maximum est ignorée quel que soit l'ordre des paramètres
+        let limits = SolverLimits::new().with_max_duration(std::time::Duration::ZERO);
+        let config = SolverConfig::new().with_limits(limits.clone());
+        assert!(config.limits().interruption().is_some());
+        for config in [
+            config.clone().with_deterministic(true),
+            SolverConfig::new()
+                .with_deterministic(true)
+                .with_limits(limits),
+        ] {
+            assert!(config.is_deterministic());
+            assert!(!config.is_parallel());
+            assert_eq!(config.limits().interruption(), None);
+            assert!(config
+                .with_deterministic(false)
+                .limits()
+                .interruption()
+                .is_some());
+        }
+    }
+
+    #[test]
+    fn test_levels() {
+        let config = SolverConfig::new();
+        assert_eq!(
+            config.levels(),
+            (0..NB_GOOD_RULE_LEVELS).collect::<Vec<_>>()
+        );
+        assert_eq!(config.with_max_level(2).levels(), vec![0, 1, 2]);
+
+        // Ordre conservé, niveaux invalides et doublons ignorés, niveau maximum respecté
+        let config = SolverConfig::new().with_levels(&[3, 0, 100, 3, 1, 7]);
+        assert_eq!(config.levels(), vec![3, 0, 1, 7]);
+        assert_eq!(config.with_max_level(3).levels(), vec![3, 0, 1]);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_from_toml() {
+        let config = SolverConfig::from_toml(
+            "levels = [1, 0, 2]\nmax_possible_grids = 50\nverbosity = \"Pedagogical\"\n\
+             locale = \"English\"\ncollector_strategy = \"BruteForce\"\nmax_band_size = 5\n\
+             deterministic = true\nparallel = false\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.collector_strategy(),
+            Some(CollectorStrategy::BruteForce)
+        );
+        assert_eq!(config.levels(), vec![1, 0, 2]);
+        assert_eq!(config.max_level(), NB_GOOD_RULE_LEVELS - 1);
+        assert_eq!(config.max_possible_grids(), 50);
+        assert_eq!(config.verbosity(), Verbosity::Pedagogical);
+        assert_eq!(config.locale(), Locale::English);
+        assert_eq!(config.max_band_size(), 5);
+        assert!(config.is_deterministic());
+        assert!(!config.is_parallel());
+
+        assert_eq!(
+            SolverConfig::from_toml("").unwrap(),
+            SolverConfig::default()
+        );
+        assert_eq!(
+            SolverConfig::from_toml(&format!("max_level = {NB_GOOD_RULE_LEVELS}")),
+            Err(SolverConfigError::Level(NB_GOOD_RULE_LEVELS))
+        );
+        assert_eq!(
+            SolverConfig::from_toml("levels = [0, 100]"),
+            Err(SolverConfigError::Level(100))
+        );
+        assert!(matches!(
+            SolverConfig::from_toml("max_levels = 3"),
+            Err(SolverConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            SolverConfig::from_path("./test_grids/inconnu.toml"),
+            Err(SolverConfigError::Read(..))
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+//! Limites de la recherche des règles de construction/résolution d'une grille.
+//!
+//! Une application (interface graphique, serveur) peut interrompre une recherche trop longue sur une grille
+//! difficile : durée maximum, nombre maximum de combinaisons d'étoiles examinées ou annulation demandée
+//! par un autre thread (voir [`SolverLimits`]).
+
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cause de l'interruption d'une recherche par ses limites (voir [`SolverLimits`])
+#[derive(thiserror::Error, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interruption {
+    /// La recherche a été annulée
+    #[error("Recherche annulée")]
+    Cancelled,
+
+    /// La durée maximum de la recherche est dépassée
+    #[error("Durée maximum de la recherche dépassée")]
+    Timeout,
+
+    /// Le nombre maximum de combinaisons examinées est dépassé
+    #[error("Nombre maximum de combinaisons examinées dépassé")]
+    TooManyCombinations,
+}
+
+/// Limites de la recherche des règles (voir [`crate::SolverConfig::with_limits`]).<br>
+/// Les limites sont partagées par les copies d'une configuration : la durée est décomptée à partir de
+/// [`SolverLimits::with_max_duration`] et les combinaisons examinées par toutes les recherches qui
+/// utilisent ces limites sont décomptées ensemble
+#[derive(Clone, Debug, Default)]
+pub struct SolverLimits {
+    /// Instant au-delà duquel la recherche est interrompue
+    deadline: Option<Instant>,
+
+    /// Nombre maximum de combinaisons d'étoiles examinées
+    max_combinations: Option<usize>,
+
+    /// Nombre de combinaisons d'étoiles déjà examinées (décompte uniquement avec un nombre maximum de
+    /// combinaisons)
+    nb_combinations: Option<Arc<AtomicUsize>>,
+
+    /// Indicateur d'annulation de la recherche (positionné par un autre thread)
+    cancel_flag: Option<Arc<AtomicBool>>,
+
+    /// La durée maximum est ignorée (recherche déterministe, voir [`crate::SolverConfig::with_deterministic`])
+    ignore_deadline: bool,
+}
+
+impl SolverLimits {
+    /// Constructeur : aucune limite
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            deadline: None,
+            max_combinations: None,
+            nb_combinations: None,
+            cancel_flag: None,
+            ignore_deadline: false,
+        }
+    }
+
+    /// Interrompt la recherche au-delà de la durée `max_duration` à partir de maintenant
+    #[must_use]
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.deadline = Instant::now().checked_add(max_duration);
+        self
+    }
+
+    /// Interrompt la recherche au-delà de `max_combinations` combinaisons d'étoiles examinées par la
+    /// recherche des grilles possibles des zones
+    #[must_use]
+    pub fn with_max_combinations(mut self, max_combinations: usize) -> Self {
+        self.max_combinations = Some(max_combinations);
+        self.nb_combinations = Some(Arc::new(AtomicUsize::new(0)));
+        self
+    }
+
+    /// Interrompt la recherche dès que l'indicateur `cancel_flag` est positionné à `true`
+    #[must_use]
+    pub fn with_cancel_flag(mut self, cancel_flag: Arc<AtomicBool>) -> Self {
+        self.cancel_flag = Some(cancel_flag);
+        self
+    }
+
+    /// Ignore (ou non) la durée maximum de la recherche : une recherche déterministe ne dépend pas de la
+    /// rapidité de la machine
+    #[must_use]
+    pub(crate) const fn with_deadline_ignored(mut self, ignore_deadline: bool) -> Self {
+        self.ignore_deadline = ignore_deadline;
+        self
+    }
+
+    /// Nombre de combinaisons d'étoiles déjà examinées
+    #[must_use]
+    pub fn nb_combinations(&self) -> usize {
+        self.nb_combinations
+            .as_ref()
+            .map_or(0, |nb_combinations| nb_combinations.load(Ordering::Relaxed))
+    }
+
+    /// Cause de l'interruption de la recherche si une limite est atteinte. None sinon
+    #[must_use]
+    pub fn interruption(&self) -> Option<Interruption> {
+        if self
+            .cancel_flag
+            .as_ref()
+            .is_some_and(|cancel_flag| cancel_flag.load(Ordering::Relaxed))
+        {
+            return Some(Interruption::Cancelled);
+        }
+        if !self.ignore_deadline
+            && self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Some(Interruption::Timeout);
+        }
+        if self
+            .max_combinations
+            .is_some_and(|max_combinations| self.nb_combinations() > max_combinations)
+        {
+            return Some(Interruption::TooManyCombinations);
+        }
+        None
+    }
+
+    /// Décompte une combinaison d'étoiles examinée.<br>
+    /// Retourne `false` si la recherche doit être interrompue
+    pub(crate) fn count_combination(&self) -> bool {
+        if let Some(nb_combinations) = &self.nb_combinations {
+            nb_combinations.fetch_add(1, Ordering::Relaxed);
+        }
+        self.interruption().is_none()
+    }
+}
+
+// Deux limites sont égales si elles partagent les mêmes décomptes et indicateur d'annulation
+impl PartialEq for SolverLimits {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+            && self.ignore_deadline == other.ignore_deadline
+            && self.max_combinations == other.max_combinations
+            && same_arc(&self.nb_combinations, &other.nb_combinations)
+            && same_arc(&self.cancel_flag, &other.cancel_flag)
+    }
+}
+
+impl Eq for SolverLimits {}
+
+impl Hash for SolverLimits {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deadline.hash(state);
+        self.ignore_deadline.hash(state);
+        self.max_combinations.hash(state);
+    }
+}
+
+/// Retourne `true` si les 2 valeurs partagées sont les mêmes (ou absentes toutes les deux)
+fn same_arc<T>(arc: &Option<Arc<T>>, other: &Option<Arc<T>>) -> bool {
+    match (arc, other) {
+        (Some(arc), Some(other)) => Arc::ptr_eq(arc, other),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interruption() {
+        assert_eq!(SolverLimits::new().interruption(), None);
+
+        let cancel_flag = Arc::new(AtomicBool::new(false));
+        let limits = SolverLimits::new().with_cancel_flag(Arc::clone(&cancel_flag));
+        assert_eq!(limits.interruption(), None);
+        cancel_flag.store(true, Ordering::Relaxed);
+        assert_eq!(limits.interruption(), Some(Interruption::Cancelled));
+
+        let limits = SolverLimits::new().with_max_duration(Duration::ZERO);
+        assert_eq!(limits.interruption(), Some(Interruption::Timeout));
+        assert_eq!(limits.with_deadline_ignored(true).interruption(), None);
+
+        // Décompte partagé par les copies des limites
+        let limits = SolverLimits::new().with_max_combinations(2);
+        let copy = limits.clone();
+        assert!(limits.count_combination());
+        assert!(copy.count_combination());
+        assert!(!limits.count_combination());
+        assert_eq!(copy.nb_combinations(), 3);
+        assert_eq!(copy.interruption(), Some(Interruption::TooManyCombinations));
+        assert_eq!(limits, copy);
+        assert_ne!(limits, SolverLimits::new().with_max_combinations(2));
+    }
+}
@@ -6,6 +6,8 @@ use crate::GridAction;
 use crate::GridHandler;
 use crate::GridSurfer;
 
+use super::collector::PossibleGrid;
+
 /// Énumération de la situation pour les cases possiblement toujours adjacentes à une étoile
 /// dans toutes les combinaisons possibles de grilles
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -26,7 +28,7 @@ impl StarAdjacent {
     pub fn check_for_star_adjacents(
         handler: &GridHandler,
         grid: &Grid,
-        possible_grids: &Vec<Grid>,
+        possible_grids: &[PossibleGrid],
     ) -> Vec<GridAction> {
         // Liste des cases non déterminées dans la grille initiale
         let mut cells = Vec::new();
@@ -40,22 +42,21 @@ impl StarAdjacent {
         }
 
         // Parcours de toutes les grilles possibles collectées
-        for grid in possible_grids {
+        for possible_grid in possible_grids {
             // On combine toutes les cases à examiner avec ce qu'on a déjà observé
             for (line_column, variant) in cells.iter().zip(star_adjacents.iter_mut()) {
                 // Seules les cases avec une situation différente de `CellValue::Star` peuvent prétendre
                 // à être toujours adjacentes à une étoile
-                if grid.cell(*line_column).value == CellValue::Star {
+                if possible_grid.value(grid, *line_column) == CellValue::Star {
                     *variant = Self::Variable;
                 } else {
                     // Et qu'elles n'ont pas été déjà identifiées comme StarAdjacent::Variable
                     if *variant != Self::Variable {
                         // Liste des cases adjacentes
                         let adjacents = handler.adjacent_cells(*line_column);
-                        if adjacents
-                            .iter()
-                            .any(|line_column| grid.cell(*line_column).value == CellValue::Star)
-                        {
+                        if adjacents.iter().any(|line_column| {
+                            possible_grid.value(grid, *line_column) == CellValue::Star
+                        }) {
                             *variant = Self::Always;
                         } else {
                             *variant = Self::Variable;
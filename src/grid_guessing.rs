@@ -0,0 +1,217 @@
+//! Classification des grilles qui nécessitent des hypothèses.
+//!
+//! Une grille 'logique' est entièrement résolue par les règles de construction (voir
+//! [`crate::get_good_rule_with_config`]). Sinon, les règles s'arrêtent sur une grille bloquée : il faut
+//! alors faire une hypothèse sur une case (étoile ou pas), poursuivre avec les règles et revenir en
+//! arrière en cas d'incohérence.
+//!
+//! [`classify_guessing`] indique où se produit la première hypothèse inévitable ([`Bifurcation`]) et la
+//! profondeur de la recherche nécessaire (nombre maximum d'hypothèses imbriquées) : une grille générée
+//! de bonne qualité ne nécessite aucune hypothèse.
+
+use crate::BadRuleError;
+use crate::CellValue;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::GridSurfer;
+use crate::LineColumn;
+use crate::SolverConfig;
+
+/// Première hypothèse inévitable de la résolution d'une grille
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bifurcation {
+    /// Grille bloquée : aucune règle de construction n'est plus applicable
+    pub grid: Grid,
+
+    /// Zone de la grille bloquée qui a le moins de cases candidates pour ses étoiles manquantes
+    pub zone: GridSurfer,
+
+    /// Case de cette zone retenue pour l'hypothèse (étoile ou pas)
+    pub line_column: LineColumn,
+}
+
+/// Résultat de la classification d'une grille selon la nécessité de faire des hypothèses
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuessingReport {
+    /// Nombre de règles de construction appliquées avant la première hypothèse (ou jusqu'à la solution)
+    pub nb_logic_steps: usize,
+
+    /// Première hypothèse inévitable (`None` si les règles de construction suffisent)
+    pub bifurcation: Option<Bifurcation>,
+
+    /// Nombre maximum d'hypothèses imbriquées pendant la recherche de la solution (0 si les règles de
+    /// construction suffisent)
+    pub search_depth: usize,
+
+    /// Une solution a été trouvée (par les règles seules ou avec des hypothèses)
+    pub solved: bool,
+}
+
+impl GuessingReport {
+    /// Retourne `true` si la grille est résolue par les règles de construction seules
+    #[must_use]
+    pub const fn is_logical(&self) -> bool {
+        self.solved && self.bifurcation.is_none()
+    }
+}
+
+/// Classification de la grille `grid` selon la nécessité de faire des hypothèses pour la résoudre avec
+/// les règles de construction de la configuration `config`
+/// ### Errors
+/// Retourne un [`BadRuleError`] si la grille n'est pas valide
+pub fn classify_guessing(
+    handler: &GridHandler,
+    grid: &Grid,
+    config: &SolverConfig,
+) -> Result<GuessingReport, BadRuleError> {
+    let mut grid = grid.clone();
+    let nb_logic_steps = apply_logic(handler, &mut grid, config)?;
+    if handler.is_done(&grid) {
+        return Ok(GuessingReport {
+            nb_logic_steps,
+            bifurcation: None,
+            search_depth: 0,
+            solved: true,
+        });
+    }
+
+    let bifurcation = choose_bifurcation(handler, &grid).map(|(zone, line_column)| Bifurcation {
+        grid: grid.clone(),
+        zone,
+        line_column,
+    });
+    let mut search = GuessSearch {
+        handler,
+        config,
+        search_depth: 0,
+    };
+    let solved = bifurcation.is_some() && search.explore(grid, 0);
+    Ok(GuessingReport {
+        nb_logic_steps,
+        bifurcation,
+        search_depth: search.search_depth,
+        solved,
+    })
+}
+
+/// Applique les règles de construction à la grille tant que possible et retourne leur nombre
+fn apply_logic(
+    handler: &GridHandler,
+    grid: &mut Grid,
+    config: &SolverConfig,
+) -> Result<usize, BadRuleError> {
+    let mut nb_steps = 0;
+    for step in handler.steps_with_config(grid, config) {
+        step?;
+        nb_steps += 1;
+    }
+    Ok(nb_steps)
+}
+
+/// Zone qui a le moins de cases candidates pour ses étoiles manquantes (voir
+/// [`GridHandler::star_candidates`]) et première de ces cases.<br>
+/// Retourne `None` si une zone ne peut plus recevoir ses étoiles manquantes
+fn choose_bifurcation(handler: &GridHandler, grid: &Grid) -> Option<(GridSurfer, LineColumn)> {
+    let mut zones = handler.cages();
+    zones.extend(handler.regions().into_iter().map(GridSurfer::Region));
+    zones.extend((0..handler.nb_lines()).map(GridSurfer::Line));
+    zones.extend((0..handler.nb_columns()).map(GridSurfer::Column));
+
+    let mut best: Option<(GridSurfer, Vec<LineColumn>)> = None;
+    for zone in zones {
+        let candidates = handler.star_candidates(grid, &zone);
+        if candidates.is_empty() {
+            if handler.surfer_cells_with_value_count(grid, &zone, &CellValue::Star)
+                < handler.zone_nb_stars(&zone)
+            {
+                return None;
+            }
+            continue;
+        }
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_candidates)| candidates.len() < best_candidates.len())
+        {
+            best = Some((zone, candidates));
+        }
+    }
+    best.map(|(zone, candidates)| (zone, candidates[0]))
+}
+
+/// Contexte de la recherche de la solution avec des hypothèses
+struct GuessSearch<'a> {
+    /// Handler de la grille
+    handler: &'a GridHandler,
+
+    /// Configuration des règles de construction
+    config: &'a SolverConfig,
+
+    /// Nombre maximum d'hypothèses imbriquées atteint
+    search_depth: usize,
+}
+
+impl GuessSearch<'_> {
+    /// Recherche d'une solution à partir de la grille bloquée `grid` après `depth` hypothèses
+    /// imbriquées.<br>
+    /// Retourne `true` si une solution est trouvée
+    fn explore(&mut self, grid: Grid, depth: usize) -> bool {
+        let Some((_, line_column)) = choose_bifurcation(self.handler, &grid) else {
+            return false;
+        };
+        let depth = depth + 1;
+        self.search_depth = self.search_depth.max(depth);
+        for action in [
+            GridAction::SetStar(line_column),
+            GridAction::SetNoStar(line_column),
+        ] {
+            let mut grid = grid.clone();
+            if grid.apply_action(&action).is_err()
+                || apply_logic(self.handler, &mut grid, self.config).is_err()
+            {
+                continue;
+            }
+            if self.handler.is_done(&grid) || self.explore(grid, depth) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridParser;
+
+    #[test]
+    fn test_classify_guessing() {
+        let parser = GridParser::try_from(
+            std::fs::read_to_string("./test_grids/facile01_2.txt")
+                .unwrap()
+                .as_str(),
+        )
+        .unwrap();
+        let handler = GridHandler::new(&parser, 2);
+        let grid = Grid::from(&handler);
+
+        // Grille résolue par les règles de construction
+        let report = classify_guessing(&handler, &grid, &SolverConfig::default()).unwrap();
+        assert!(report.is_logical());
+        assert!(report.nb_logic_steps > 0);
+        assert_eq!(report.search_depth, 0);
+
+        // Avec les seules règles les plus simples, des hypothèses sont nécessaires
+        let config = SolverConfig::default().with_levels(&[0, 1]);
+        let report = classify_guessing(&handler, &grid, &config).unwrap();
+        assert!(!report.is_logical());
+        assert!(report.solved);
+        assert!(report.search_depth >= 1);
+        let bifurcation = report.bifurcation.unwrap();
+        assert!(bifurcation.grid.cell(bifurcation.line_column).is_unknown());
+        assert!(handler
+            .star_candidates(&bifurcation.grid, &bifurcation.zone)
+            .contains(&bifurcation.line_column));
+    }
+}
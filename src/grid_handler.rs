@@ -1,43 +1,326 @@
 //! Structure d'une grille en cours de résolution.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use crate::check_bad_rules;
+use crate::grid_parser::BLOCKED_CELL_CHAR;
 use crate::CellValue;
 use crate::Grid;
 use crate::GridParser;
+use crate::GridSurfer;
 use crate::LineColumn;
+use crate::PuzzleMetadata;
 use crate::Region;
 use crate::{display_column, display_line};
 
-/// Description d'une grille en cours de résolution
-#[derive(Debug)]
+/// Description d'une grille en cours de résolution.<br>
+/// Les listes précalculées (régions, cases des régions, région de chaque case et cases adjacentes) sont
+/// partagées entre les copies d'un handler : une copie ne les duplique pas (résolutions en parallèle,
+/// essais successifs du générateur de grilles, ...)
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SerializedGridHandler", into = "SerializedGridHandler")
+)]
 pub struct GridHandler {
     /// Taille de la grille
     size: LineColumn,
 
-    /// Nombre d'étoiles à placer dans chaque ligne, colonne ou région dans la grille
+    /// Nombre d'étoiles à placer par défaut dans chaque ligne (et dans chaque colonne et région d'une grille
+    /// 'classique') de la grille
     nb_stars: usize,
 
+    /// Nombre d'étoiles à placer dans chaque ligne
+    line_stars: Vec<usize>,
+
+    /// Nombre d'étoiles à placer dans chaque colonne
+    column_stars: Vec<usize>,
+
+    /// Nombre d'étoiles à placer dans chaque région (dans l'ordre de `regions`)
+    region_stars: Vec<usize>,
+
     /// Liste des régions de la grille
-    regions: Vec<Region>,
+    regions: Arc<[Region]>,
+
+    /// Liste des cases de chaque région (dans l'ordre de `regions`)
+    region_cells: Arc<[Vec<LineColumn>]>,
 
     /// Liste des lignes avec la région correspondant à chaque case de la ligne
-    cells_region: Vec<Vec<Region>>,
+    cells_region: Arc<[Vec<Region>]>,
+
+    /// Liste des cases (non bloquées) de chaque ligne
+    line_cells: Arc<[Vec<LineColumn>]>,
+
+    /// Liste des cases (non bloquées) de chaque colonne
+    column_cells: Arc<[Vec<LineColumn>]>,
+
+    /// Cases adjacentes de chaque case de la grille (ligne par ligne)
+    adjacent_cells: Arc<[Vec<LineColumn>]>,
+
+    /// Étoiles données par la grille (voir [`GridParser::given_stars`])
+    given_stars: Vec<LineColumn>,
+
+    /// Zones supplémentaires de la grille (cases de la zone et nombre d'étoiles à y placer)
+    cages: Vec<(Vec<LineColumn>, usize)>,
+
+    /// Grille 'torique' : les bords opposés de la grille sont adjacents (voir [`GridHandler::with_toroidal`])
+    toroidal: bool,
+
+    /// Métadonnées de la grille (voir [`GridParser::metadata`])
+    metadata: PuzzleMetadata,
+}
+
+/// Représentation sérialisée d'un [`GridHandler`] : définition de la grille, nombre d'étoiles de chaque zone,
+/// cages et mode 'torique'.<br>
+/// Les listes précalculées sont reconstruites (et la grille contrôlée) à la désérialisation par les mêmes
+/// constructeurs qu'une grille lue dans un fichier
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedGridHandler {
+    /// Définition de la grille (régions, étoiles données et métadonnées)
+    parser: crate::grid_parser::SerializedGridParser,
+
+    /// Nombre d'étoiles à placer par défaut dans chaque ligne
+    nb_stars: usize,
+
+    /// Nombre d'étoiles à placer dans chaque ligne
+    line_stars: Vec<usize>,
+
+    /// Nombre d'étoiles à placer dans chaque colonne
+    column_stars: Vec<usize>,
+
+    /// Nombre d'étoiles à placer dans chaque région (aucune pour une grille sans contrainte de région)
+    region_stars: Vec<(Region, usize)>,
+
+    /// Zones supplémentaires de la grille
+    #[serde(default)]
+    cages: Vec<(Vec<LineColumn>, usize)>,
+
+    /// Grille 'torique'
+    #[serde(default)]
+    toroidal: bool,
+}
+
+#[cfg(feature = "serde")]
+impl From<GridHandler> for SerializedGridHandler {
+    fn from(handler: GridHandler) -> Self {
+        let parser = crate::grid_parser::SerializedGridParser {
+            lines: handler
+                .cells_region
+                .iter()
+                .map(|line| line.iter().collect())
+                .collect(),
+            given_stars: handler.given_stars,
+            metadata: handler.metadata,
+        };
+        Self {
+            parser,
+            nb_stars: handler.nb_stars,
+            line_stars: handler.line_stars,
+            column_stars: handler.column_stars,
+            region_stars: handler
+                .regions
+                .iter()
+                .copied()
+                .zip(handler.region_stars)
+                .collect(),
+            cages: handler.cages,
+            toroidal: handler.toroidal,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedGridHandler> for GridHandler {
+    type Error = String;
+
+    fn try_from(value: SerializedGridHandler) -> Result<Self, Self::Error> {
+        let parser = GridParser::try_from(value.parser)?;
+        if value.line_stars.len() != parser.nb_lines()
+            || value.column_stars.len() != parser.nb_columns()
+        {
+            return Err(format!(
+                "Le nombre d'étoiles des lignes et des colonnes ne correspond pas à une grille de {} lignes et {} \
+                 colonnes",
+                parser.nb_lines(),
+                parser.nb_columns()
+            ));
+        }
+        let zone_stars: Vec<(GridSurfer, usize)> = value
+            .line_stars
+            .iter()
+            .enumerate()
+            .map(|(line, nb_stars)| (GridSurfer::Line(line), *nb_stars))
+            .chain(
+                value
+                    .column_stars
+                    .iter()
+                    .enumerate()
+                    .map(|(column, nb_stars)| (GridSurfer::Column(column), *nb_stars)),
+            )
+            .chain(
+                value
+                    .region_stars
+                    .iter()
+                    .map(|(region, nb_stars)| (GridSurfer::Region(*region), *nb_stars)),
+            )
+            .collect();
+        let nb_stars = value.nb_stars;
+        let mut handler = Self::try_build(
+            &parser,
+            (nb_stars, nb_stars, nb_stars),
+            &zone_stars,
+            !value.region_stars.is_empty(),
+        )?;
+        for (cells, nb_stars) in &value.cages {
+            let mut cage_cells = cells.clone();
+            cage_cells.sort_unstable();
+            cage_cells.dedup();
+            let nb_cells = cage_cells.len();
+            if *nb_stars == 0
+                || nb_cells < (2 * nb_stars) - 1
+                || cells.iter().any(|cell| {
+                    cell.line >= handler.nb_lines()
+                        || cell.column >= handler.nb_columns()
+                        || handler.is_blocked(*cell)
+                })
+            {
+                return Err(format!(
+                    "Cage de {nb_cells} cases et {nb_stars} étoiles invalide dans la grille"
+                ));
+            }
+            handler = handler.with_cage(cells, *nb_stars);
+        }
+        Ok(handler.with_toroidal(value.toroidal))
+    }
 }
 
 impl GridHandler {
-    /// Constructeur selon un grid parser et le nombre d'étoiles à placer dans la grille
+    /// Constructeur selon un grid parser et le nombre d'étoiles à placer dans chaque ligne, colonne et
+    /// région de la grille
     /// # Panics
     /// Panic si la taille de la grille est <= 0 ou qu'il y a trop d'étoiles à placer selon la taille de la grille
     #[must_use]
     pub fn new(parser: &GridParser, nb_stars: usize) -> Self {
+        Self::with_zone_stars(parser, nb_stars, &[])
+    }
+
+    /// Constructeur selon un grid parser, le nombre d'étoiles à placer par défaut dans chaque ligne, colonne
+    /// et région de la grille et le nombre d'étoiles à placer dans certaines lignes ([`GridSurfer::Line`]),
+    /// colonnes ([`GridSurfer::Column`]) ou régions ([`GridSurfer::Region`]) de la grille
+    /// # Panics
+    /// Panic si la taille de la grille est <= 0, qu'il y a trop d'étoiles à placer selon la taille d'une zone
+    /// de la grille, qu'une zone n'existe pas dans la grille ou que le nombre total d'étoiles à placer dans
+    /// les lignes, les colonnes et les régions n'est pas le même
+    #[must_use]
+    pub fn with_zone_stars(
+        parser: &GridParser,
+        nb_stars: usize,
+        zone_stars: &[(GridSurfer, usize)],
+    ) -> Self {
+        Self::build(parser, (nb_stars, nb_stars, nb_stars), zone_stars, true)
+    }
+
+    /// Constructeur d'une grille (éventuellement rectangulaire) selon un grid parser et le nombre d'étoiles
+    /// à placer dans chaque ligne (`line_nb_stars`), chaque colonne (`column_nb_stars`) et chaque région
+    /// (`region_nb_stars`) de la grille.<br>
+    /// Par exemple, une grille de 5 lignes et 10 colonnes peut avoir 2 étoiles par ligne et 1 étoile par
+    /// colonne.
+    /// # Panics
+    /// Panic si la taille de la grille est <= 0, qu'il y a trop d'étoiles à placer selon la taille d'une zone
+    /// de la grille ou que le nombre total d'étoiles à placer dans les lignes, les colonnes et les régions
+    /// n'est pas le même
+    #[must_use]
+    pub fn with_stars_per_zone(
+        parser: &GridParser,
+        line_nb_stars: usize,
+        column_nb_stars: usize,
+        region_nb_stars: usize,
+    ) -> Self {
+        Self::build(
+            parser,
+            (line_nb_stars, column_nb_stars, region_nb_stars),
+            &[],
+            true,
+        )
+    }
+
+    /// Constructeur d'une grille sans contrainte de région (variantes où seules les lignes, les colonnes
+    /// et l'adjacence des étoiles comptent) selon un grid parser et le nombre d'étoiles à placer dans chaque
+    /// ligne et colonne de la grille.<br>
+    /// Les régions du grid parser ne servent alors qu'à l'affichage de la grille.
+    /// # Panics
+    /// Panic si la taille de la grille est <= 0 ou qu'il y a trop d'étoiles à placer selon la taille de la grille
+    #[must_use]
+    pub fn without_regions(parser: &GridParser, nb_stars: usize) -> Self {
+        Self::build(parser, (nb_stars, nb_stars, 0), &[], false)
+    }
+
+    /// Constructeur d'une grille selon les règles du jeu Queens : une seule 'reine' (étoile) par ligne,
+    /// colonne et région (de couleur) et 2 reines ne peuvent pas se toucher, même en diagonale.<br>
+    /// Comme 2 reines d'une même ligne ou colonne sont déjà exclues, ces règles sont celles d'une grille
+    /// Star Battle 1★ : une grille Queens importée (une lettre par couleur de région) se résout directement.
+    /// # Panics
+    /// Panic si la grille n'a pas autant de régions que de lignes et de colonnes
+    #[must_use]
+    pub fn queens(parser: &GridParser) -> Self {
+        Self::new(parser, 1)
+    }
+
+    /// Constructeur selon un grid parser et le nombre d'étoiles à placer dans chaque ligne, colonne et
+    /// région de la grille (voir [`GridHandler::new`]) pour une grille qui provient de l'extérieur (fichier,
+    /// requête, ...)
+    /// ### Errors
+    /// Retourne une erreur si le nombre d'étoiles est nul, qu'il y a trop d'étoiles à placer selon la taille
+    /// d'une zone de la grille ou que la grille n'a pas autant de régions que de lignes et de colonnes (le
+    /// nombre total d'étoiles à placer dans les lignes, les colonnes et les régions n'est pas le même)
+    pub fn try_new(parser: &GridParser, nb_stars: usize) -> Result<Self, String> {
+        Self::try_build(parser, (nb_stars, nb_stars, nb_stars), &[], true)
+    }
+
+    /// Construction d'une grille avec ou sans contrainte de région selon le nombre d'étoiles à placer par
+    /// défaut dans chaque ligne, colonne et région (voir [`GridHandler::with_zone_stars`])
+    /// # Panics
+    /// Panic si la grille est invalide (voir [`GridHandler::try_build`])
+    fn build(
+        parser: &GridParser,
+        nb_zone_stars: (usize, usize, usize),
+        zone_stars: &[(GridSurfer, usize)],
+        with_regions: bool,
+    ) -> Self {
+        Self::try_build(parser, nb_zone_stars, zone_stars, with_regions)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Construction d'une grille avec ou sans contrainte de région selon le nombre d'étoiles à placer par
+    /// défaut dans chaque ligne, colonne et région (voir [`GridHandler::with_zone_stars`])
+    /// ### Errors
+    /// Retourne une erreur si la taille de la grille est nulle, qu'il y a trop d'étoiles à placer selon la
+    /// taille d'une zone de la grille, qu'une zone n'existe pas dans la grille ou que le nombre total
+    /// d'étoiles à placer dans les lignes, les colonnes et les régions n'est pas le même
+    fn try_build(
+        parser: &GridParser,
+        (line_nb_stars, column_nb_stars, region_nb_stars): (usize, usize, usize),
+        zone_stars: &[(GridSurfer, usize)],
+        with_regions: bool,
+    ) -> Result<Self, String> {
         let nb_lines = parser.nb_lines();
         let nb_columns = parser.nb_columns();
-        assert!(nb_lines > 0, "nb_lines doit être > 0");
-        assert!(nb_columns > 0, "nb_columns doit être > 0");
-        assert!(nb_stars > 0, "nb_stars doit être > 0");
+        if nb_lines == 0 || nb_columns == 0 {
+            return Err("La grille doit avoir au moins une ligne et une colonne".to_string());
+        }
+        if line_nb_stars == 0 || column_nb_stars == 0 || (with_regions && region_nb_stars == 0) {
+            return Err("Le nombre d'étoiles doit être > 0".to_string());
+        }
 
-        // Liste des regions de la grille
-        let mut regions: Vec<char> = parser.regions();
+        // Liste des regions de la grille (aucune si les régions ne sont pas une contrainte)
+        let mut regions: Vec<char> = if with_regions {
+            parser.regions()
+        } else {
+            Vec::new()
+        };
         // Tri par taille de la region (en nombre de cases)
         regions.sort_by(|a, b| {
             parser
@@ -46,22 +329,79 @@ impl GridHandler {
                 .cmp(&parser.region_cells(*b).len())
         });
 
+        // Nombre d'étoiles à placer dans chaque zone
+        let mut line_stars = vec![line_nb_stars; nb_lines];
+        let mut column_stars = vec![column_nb_stars; nb_columns];
+        let mut region_stars = vec![region_nb_stars; regions.len()];
+        for (zone, zone_nb_stars) in zone_stars {
+            match zone {
+                GridSurfer::Line(line) if *line < nb_lines => line_stars[*line] = *zone_nb_stars,
+                GridSurfer::Column(column) if *column < nb_columns => {
+                    column_stars[*column] = *zone_nb_stars;
+                }
+                GridSurfer::Region(region) if regions.contains(region) => {
+                    let index = regions.iter().position(|other| other == region).unwrap();
+                    region_stars[index] = *zone_nb_stars;
+                }
+                _ => {
+                    return Err(format!(
+                        "Zone '{zone}' invalide pour définir un nombre d'étoiles"
+                    ))
+                }
+            }
+        }
+        let nb_total_stars: usize = line_stars.iter().sum();
+        let nb_region_stars: usize = region_stars.iter().sum();
+        if column_stars.iter().sum::<usize>() != nb_total_stars
+            || (with_regions && nb_region_stars != nb_total_stars)
+        {
+            return Err(format!(
+                "Le nombre total d'étoiles à placer dans les lignes ({nb_total_stars}), les colonnes ({}) et les \
+                 régions ({nb_region_stars} pour {} régions) doit être le même",
+                column_stars.iter().sum::<usize>(),
+                regions.len()
+            ));
+        }
+
         // Pour mettre nb_stars sans qu'elles se touchent, il faut au moins ((2 * nb_stars) - 1) cases...
-        let min_nb_cells = (2 * nb_stars) - 1;
-        assert!(
-            nb_lines >= min_nb_cells,
-            "Trop d'étoiles à placer ({nb_stars}) pour une grille de {nb_lines} lignes"
-        );
-        assert!(
-            nb_columns >= min_nb_cells,
-            "Trop d'étoiles à placer ({nb_stars}) pour une grille de {nb_columns} colonnes"
-        );
-        for region in parser.regions() {
-            let nb_cells = parser.region_cells(region).len();
-            assert!(nb_cells >= min_nb_cells,
-                "Trop d'étoiles à placer ({nb_stars}) pour la region '{region}' de {nb_cells} cases dans la grille");
+        let min_nb_cells = |nb_stars: usize| (2 * nb_stars).saturating_sub(1);
+        for (line, nb_stars) in line_stars.iter().enumerate() {
+            if nb_columns < min_nb_cells(*nb_stars) {
+                return Err(format!(
+                    "Trop d'étoiles à placer ({nb_stars}) dans la ligne {} d'une grille de {nb_columns} colonnes",
+                    display_line(line)
+                ));
+            }
+        }
+        for (column, nb_stars) in column_stars.iter().enumerate() {
+            if nb_lines < min_nb_cells(*nb_stars) {
+                return Err(format!(
+                    "Trop d'étoiles à placer ({nb_stars}) dans la colonne {} d'une grille de {nb_lines} lignes",
+                    display_column(column)
+                ));
+            }
+        }
+        for (region, nb_stars) in regions.iter().zip(&region_stars) {
+            let nb_cells = parser.region_cells(*region).len();
+            if nb_cells < min_nb_cells(*nb_stars) {
+                return Err(format!(
+                    "Trop d'étoiles à placer ({nb_stars}) pour la region '{region}' de {nb_cells} cases dans la grille"
+                ));
+            }
         }
 
+        // Liste des cases de chaque région
+        let region_cells = regions
+            .iter()
+            .map(|region| {
+                parser
+                    .region_cells(*region)
+                    .iter()
+                    .map(|cell| cell.line_column)
+                    .collect()
+            })
+            .collect();
+
         // Reconstruction de la région de chaque case
         let mut cells_region = Vec::with_capacity(nb_lines);
         for line in 0..nb_lines {
@@ -72,12 +412,123 @@ impl GridHandler {
             cells_region.push(vec_line_regions);
         }
 
-        Self {
+        // Liste des cases (non bloquées) de chaque ligne et de chaque colonne
+        let mut line_cells = vec![Vec::with_capacity(nb_columns); nb_lines];
+        let mut column_cells = vec![Vec::with_capacity(nb_lines); nb_columns];
+        for (line, vec_line_regions) in cells_region.iter().enumerate() {
+            for (column, region) in vec_line_regions.iter().enumerate() {
+                if *region != BLOCKED_CELL_CHAR {
+                    line_cells[line].push(LineColumn::new(line, column));
+                    column_cells[column].push(LineColumn::new(line, column));
+                }
+            }
+        }
+
+        let handler = Self {
             size: LineColumn::new(nb_lines, nb_columns),
-            regions,
-            cells_region,
-            nb_stars,
+            regions: regions.into(),
+            region_cells,
+            cells_region: cells_region.into(),
+            line_cells: line_cells.into(),
+            column_cells: column_cells.into(),
+            adjacent_cells: Arc::new([]),
+            nb_stars: line_nb_stars,
+            line_stars,
+            column_stars,
+            region_stars,
+            given_stars: parser.given_stars(),
+            cages: Vec::new(),
+            toroidal: false,
+            metadata: parser.metadata().clone(),
+        };
+        Ok(handler.with_adjacent_cells())
+    }
+
+    /// Précalcule les cases adjacentes de chaque case de la grille
+    fn with_adjacent_cells(mut self) -> Self {
+        let mut adjacent_cells = Vec::with_capacity(self.nb_lines() * self.nb_columns());
+        for line in 0..self.nb_lines() {
+            for column in 0..self.nb_columns() {
+                let line_column = LineColumn::new(line, column);
+                adjacent_cells.push(if self.toroidal {
+                    self.toroidal_adjacent_cells(line_column)
+                } else {
+                    self.planar_adjacent_cells(line_column)
+                });
+            }
         }
+        self.adjacent_cells = adjacent_cells.into();
+        self
+    }
+
+    /// Ajoute une zone supplémentaire (une 'cage' : diagonale marquée, ensemble quelconque de cases, ...) qui
+    /// doit contenir exactement `nb_stars` étoiles, en plus des lignes, des colonnes et des régions.<br>
+    /// Les cages sont identifiées par leur rang d'ajout dans la grille ([`GridSurfer::Cage`]).
+    /// # Panics
+    /// Panic si une case de la cage est en dehors de la grille ou bloquée, ou qu'il y a trop d'étoiles à
+    /// placer selon le nombre de cases de la cage
+    #[must_use]
+    pub fn with_cage(mut self, cells: &[LineColumn], nb_stars: usize) -> Self {
+        let cage = GridSurfer::Cage(self.cages.len());
+        let mut cage_cells = Vec::with_capacity(cells.len());
+        for line_column in cells {
+            assert!(
+                line_column.line < self.nb_lines()
+                    && line_column.column < self.nb_columns()
+                    && !self.is_blocked(*line_column),
+                "Case {line_column} invalide pour '{cage}'"
+            );
+            if !cage_cells.contains(line_column) {
+                cage_cells.push(*line_column);
+            }
+        }
+        // Cases dans l'ordre de la grille (voir [`GridHandler::zone_cells`])
+        cage_cells.sort_unstable();
+        assert!(
+            nb_stars > 0 && cage_cells.len() >= (2 * nb_stars) - 1,
+            "Trop d'étoiles à placer ({nb_stars}) pour '{cage}' de {} cases dans la grille",
+            cage_cells.len()
+        );
+        self.cages.push((cage_cells, nb_stars));
+        self
+    }
+
+    /// Liste des cages de la grille (voir [`GridHandler::with_cage`])
+    #[must_use]
+    pub fn cages(&self) -> Vec<GridSurfer> {
+        (0..self.cages.len()).map(GridSurfer::Cage).collect()
+    }
+
+    /// Liste des cases d'une cage de la grille
+    /// # Panics
+    /// Panic si la cage n'existe pas dans la grille
+    #[must_use]
+    pub fn cage_cells(&self, index: usize) -> &[LineColumn] {
+        let Some((cells, _)) = self.cages.get(index) else {
+            panic!("Cage {} inconnue dans la grille", index + 1)
+        };
+        cells
+    }
+
+    /// Active (ou désactive) le mode 'torique' de la grille (variante expérimentale) : les lignes et
+    /// les colonnes 'bouclent', la première ligne (resp. colonne) est adjacente à la dernière ligne (resp.
+    /// colonne), y compris en diagonale
+    #[must_use]
+    pub fn with_toroidal(mut self, toroidal: bool) -> Self {
+        self.toroidal = toroidal;
+        self.with_adjacent_cells()
+    }
+
+    /// Retourne `true` si la grille est 'torique' (voir [`GridHandler::with_toroidal`])
+    #[must_use]
+    pub const fn is_toroidal(&self) -> bool {
+        self.toroidal
+    }
+
+    /// Métadonnées de la grille (voir [`PuzzleMetadata`])
+    #[must_use]
+    pub const fn metadata(&self) -> &PuzzleMetadata {
+        &self.metadata
     }
 
     /// Nombre de lignes de la grille
@@ -92,16 +543,124 @@ impl GridHandler {
         self.size.column
     }
 
-    /// Nombre d'étoiles à placer dans la grille
+    /// Nombre d'étoiles à placer par défaut dans chaque ligne de la grille (et dans chaque colonne et région
+    /// d'une grille 'classique').<br>
+    /// Voir [`GridHandler::zone_nb_stars`] pour le nombre d'étoiles à placer dans une zone particulière.
     #[must_use]
     pub const fn nb_stars(&self) -> usize {
         self.nb_stars
     }
 
-    /// Liste des régions de la grille
+    /// Nombre d'étoiles à placer dans une zone de la grille (ligne(s), colonne(s), région ou toute la grille)
+    /// # Panics
+    /// Panic si la zone n'est pas une zone de la grille (cases adjacentes à une case, région ou cage inconnue
+    /// ou ligne/colonne en dehors de la grille)
+    #[must_use]
+    pub fn zone_nb_stars(&self, zone: &GridSurfer) -> usize {
+        match zone {
+            GridSurfer::AllCells => self.line_stars.iter().sum(),
+            GridSurfer::Line(line) => self.line_stars[*line],
+            GridSurfer::Column(column) => self.column_stars[*column],
+            GridSurfer::Lines(range) => self.line_stars[range.clone()].iter().sum(),
+            GridSurfer::Columns(range) => self.column_stars[range.clone()].iter().sum(),
+            GridSurfer::Region(region) => {
+                let Some(index) = self.regions.iter().position(|other| other == region) else {
+                    panic!("Région '{region}' inconnue dans la grille")
+                };
+                self.region_stars[index]
+            }
+            GridSurfer::Cage(index) => {
+                let Some((_, nb_stars)) = self.cages.get(*index) else {
+                    panic!("Cage {} inconnue dans la grille", index + 1)
+                };
+                *nb_stars
+            }
+            GridSurfer::Adjacent(_) => {
+                panic!("Pas de nombre d'étoiles à placer dans '{zone}'")
+            }
+        }
+    }
+
+    /// Retourne `true` si les régions de la grille sont une contrainte (voir [`GridHandler::without_regions`])
+    #[must_use]
+    pub fn has_regions(&self) -> bool {
+        !self.regions.is_empty()
+    }
+
+    /// Liste des régions de la grille (vide pour une grille sans contrainte de région)
     #[must_use]
     pub fn regions(&self) -> Vec<Region> {
-        self.regions.clone()
+        self.regions.to_vec()
+    }
+
+    /// Liste des cases d'une région de la grille
+    /// # Panics
+    /// Panic si la région n'existe pas dans la grille
+    #[must_use]
+    pub fn region_cells(&self, region: Region) -> &[LineColumn] {
+        let Some(index) = self.regions.iter().position(|other| *other == region) else {
+            panic!("Région '{region}' inconnue dans la grille")
+        };
+        &self.region_cells[index]
+    }
+
+    /// Liste des cases (non bloquées) d'une ligne de la grille
+    /// # Panics
+    /// Panic si la ligne n'existe pas dans la grille
+    #[must_use]
+    pub fn line_cells(&self, line: usize) -> &[LineColumn] {
+        &self.line_cells[line]
+    }
+
+    /// Liste des cases (non bloquées) d'une colonne de la grille
+    /// # Panics
+    /// Panic si la colonne n'existe pas dans la grille
+    #[must_use]
+    pub fn column_cells(&self, column: usize) -> &[LineColumn] {
+        &self.column_cells[column]
+    }
+
+    /// Liste précalculée des cases d'une région, d'une ligne, d'une colonne ou d'une cage de la grille (sans
+    /// les cases bloquées), dans l'ordre de [`GridHandler::surfer`].<br>
+    /// Retourne None pour les autres zones ou une zone qui n'existe pas dans la grille
+    #[must_use]
+    pub fn zone_cells(&self, zone: &GridSurfer) -> Option<&[LineColumn]> {
+        match zone {
+            GridSurfer::Region(region) => self
+                .regions
+                .iter()
+                .position(|other| other == region)
+                .map(|index| self.region_cells[index].as_slice()),
+            GridSurfer::Line(line) => self.line_cells.get(*line).map(Vec::as_slice),
+            GridSurfer::Column(column) => self.column_cells.get(*column).map(Vec::as_slice),
+            GridSurfer::Cage(index) => self.cages.get(*index).map(|(cells, _)| cells.as_slice()),
+            GridSurfer::AllCells
+            | GridSurfer::Adjacent(_)
+            | GridSurfer::Lines(_)
+            | GridSurfer::Columns(_) => None,
+        }
+    }
+
+    /// Nombre de cases de chaque région de la grille
+    #[must_use]
+    pub fn region_sizes(&self) -> HashMap<Region, usize> {
+        self.regions
+            .iter()
+            .zip(self.region_cells.iter())
+            .map(|(region, cells)| (*region, cells.len()))
+            .collect()
+    }
+
+    /// Liste des régions de la grille qui ont le moins de cases
+    #[must_use]
+    pub fn smallest_regions(&self) -> Vec<Region> {
+        let min_size = self.region_cells.iter().map(Vec::len).min();
+        self.regions
+            .iter()
+            .zip(self.region_cells.iter())
+            .filter(|(_, cells)| Some(cells.len()) == min_size)
+            .map(|(region, _)| *region)
+            .collect()
     }
 
     /// Région d'une case de la grille
@@ -110,6 +669,19 @@ impl GridHandler {
         self.cells_region[line_column.line][line_column.column]
     }
 
+    /// Retourne `true` si une case est 'bloquée' : elle n'appartient à aucune zone de la grille et ne peut
+    /// pas contenir d'étoile
+    #[must_use]
+    pub fn is_blocked(&self, line_column: LineColumn) -> bool {
+        self.cell_region(line_column) == BLOCKED_CELL_CHAR
+    }
+
+    /// Retourne `true` si la case est une étoile donnée par la grille (voir [`GridParser::given_stars`])
+    #[must_use]
+    pub fn is_given(&self, line_column: LineColumn) -> bool {
+        self.given_stars.contains(&line_column)
+    }
+
     /// Nombre de cases dans une région
     #[must_use]
     pub fn region_cells_count(&self, region: Region) -> usize {
@@ -124,9 +696,15 @@ impl GridHandler {
         nb
     }
 
-    /// Liste des cases adjacentes d'une case de la grille (y compris en diagonale)
+    /// Liste des cases adjacentes d'une case de la grille (y compris en diagonale).<br>
+    /// Dans une grille 'torique', les cases des bords opposés de la grille sont adjacentes.
     #[must_use]
     pub fn adjacent_cells(&self, line_column: LineColumn) -> Vec<LineColumn> {
+        self.adjacent_cells[line_column.line * self.nb_columns() + line_column.column].clone()
+    }
+
+    /// Liste des cases adjacentes d'une case d'une grille 'classique'
+    fn planar_adjacent_cells(&self, line_column: LineColumn) -> Vec<LineColumn> {
         let (line, column) = (line_column.line, line_column.column);
         let mut adjacent_cells = vec![];
         // North
@@ -164,6 +742,26 @@ impl GridHandler {
         adjacent_cells
     }
 
+    /// Liste des cases adjacentes d'une case d'une grille 'torique'.<br>
+    /// Sur une grille de moins de 3 lignes (ou colonnes), une même case peut être adjacente 'des 2 côtés' :
+    /// elle n'est alors listée qu'une fois (et la case elle-même n'est jamais listée).
+    fn toroidal_adjacent_cells(&self, line_column: LineColumn) -> Vec<LineColumn> {
+        let (nb_lines, nb_columns) = (self.nb_lines(), self.nb_columns());
+        let mut adjacent_cells = vec![];
+        for delta_line in [nb_lines - 1, 0, 1] {
+            for delta_column in [nb_columns - 1, 0, 1] {
+                let adjacent = LineColumn::new(
+                    (line_column.line + delta_line) % nb_lines,
+                    (line_column.column + delta_column) % nb_columns,
+                );
+                if adjacent != line_column && !adjacent_cells.contains(&adjacent) {
+                    adjacent_cells.push(adjacent);
+                }
+            }
+        }
+        adjacent_cells
+    }
+
     /// Retourne `true`si une des cases adjacentes de la case `line_column` est une étoile
     #[must_use]
     pub fn is_star_adjacent(&self, grid: &Grid, line_column: LineColumn) -> bool {
@@ -175,17 +773,51 @@ impl GridHandler {
         false
     }
 
+    /// Retourne les cases adjacentes à toutes les cases candidates (non définies) de la zone définie
+    /// par le `GridSurfer`.<br>
+    /// Une étoile doit être placée dans une de ces cases candidates : les cases retournées sont donc
+    /// adjacentes à une étoile quelle que soit la case retenue dans la zone.<br>
+    /// Retourne une liste vide si la zone ne contient aucune case candidate
+    #[must_use]
+    pub fn common_neighbors(&self, grid: &Grid, surfer: &GridSurfer) -> Vec<LineColumn> {
+        let mut candidates = self
+            .surfer(grid, surfer)
+            .into_iter()
+            .filter(|line_column| grid.cell(*line_column).is_unknown());
+        let Some(first_candidate) = candidates.next() else {
+            return Vec::new();
+        };
+        let mut common_neighbors = self.adjacent_cells(first_candidate);
+        for candidate in candidates {
+            let adjacent_cells = self.adjacent_cells(candidate);
+            common_neighbors.retain(|line_column| adjacent_cells.contains(line_column));
+        }
+        common_neighbors
+    }
+
+    /// Retourne les cases de la zone définie par le `GridSurfer` qui peuvent encore contenir une étoile :
+    /// cases non définies, sans étoile adjacente, dans une zone qui n'a pas encore toutes ses étoiles.<br>
+    /// Retourne une liste vide si la zone contient déjà toutes ses étoiles
+    #[must_use]
+    pub fn star_candidates(&self, grid: &Grid, surfer: &GridSurfer) -> Vec<LineColumn> {
+        if !matches!(surfer, GridSurfer::Adjacent(_))
+            && self.surfer_cells_with_value_count(grid, surfer, &CellValue::Star)
+                >= self.zone_nb_stars(surfer)
+        {
+            return Vec::new();
+        }
+        self.surfer(grid, surfer)
+            .into_iter()
+            .filter(|line_column| {
+                grid.cell(*line_column).is_unknown() && !self.is_star_adjacent(grid, *line_column)
+            })
+            .collect()
+    }
+
     /// Retourne true si toutes les cases de la grille sont définies et que la grille est 'viable'
     #[must_use]
     pub fn is_done(&self, grid: &Grid) -> bool {
-        for line in 0..self.nb_lines() {
-            for column in 0..self.nb_columns() {
-                if grid.cell(LineColumn::new(line, column)).value == CellValue::Unknown {
-                    return false;
-                }
-            }
-        }
-        check_bad_rules(self, grid).is_ok()
+        grid.enumerate_unknown().next().is_none() && check_bad_rules(self, grid).is_ok()
     }
 
     /// Affichage du contenu d'une grille.<br>
@@ -216,14 +848,33 @@ impl GridHandler {
             for column in 0..self.nb_columns() {
                 let line_column = LineColumn::new(line, column);
                 let region = self.cell_region(line_column);
+                if self.is_blocked(line_column) {
+                    output.push_str("   ");
+                    continue;
+                }
                 match grid.cell(line_column).value {
                     CellValue::Star => output.push_str(&format!(" {region}*")),
                     CellValue::Unknown => output.push_str(&format!(" {region}?")),
                     CellValue::NoStar => output.push_str(&format!(" {region}-")),
                 }
             }
+            if self.toroidal {
+                // La grille 'boucle' : on rappelle la région de la première case de la ligne
+                output.push_str(&format!(" |{}", self.cell_region(LineColumn::new(line, 0))));
+            }
             output.push('\n');
         }
+        if self.toroidal {
+            // ... et les régions de la première ligne sous la grille
+            output.push_str(if with_coordinates { "   " } else { "" });
+            for column in 0..self.nb_columns() {
+                output.push_str(&format!(
+                    " {}~",
+                    self.cell_region(LineColumn::new(0, column))
+                ));
+            }
+            output.push_str("\n(grille torique : les bords opposés de la grille sont adjacents)\n");
+        }
         output
     }
 }
@@ -234,6 +885,280 @@ mod tests {
 
     use std::collections::HashSet;
 
+    #[test]
+    fn test_zone_nb_stars() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "AABBB", "DDDDD", "DEEED"]).unwrap();
+        let handler = GridHandler::with_zone_stars(&parser, 1, &[(GridSurfer::Region('A'), 2)]);
+
+        assert_eq!(handler.nb_stars(), 1);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Region('A')), 2);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Region('B')), 1);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Line(2)), 1);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Columns(1..=3)), 3);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::AllCells), 5);
+    }
+
+    #[test]
+    fn test_region_cells() {
+        let grid_parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 1);
+
+        assert_eq!(
+            grid_handler.region_cells('A'),
+            &[LineColumn::new(0, 0), LineColumn::new(1, 0)]
+        );
+        assert_eq!(grid_handler.region_cells('B').len(), 11);
+
+        let region_sizes = grid_handler.region_sizes();
+        assert_eq!(region_sizes.len(), 5);
+        assert_eq!(region_sizes[&'A'], 2);
+        assert_eq!(region_sizes[&'C'], 2);
+        assert_eq!(region_sizes[&'D'], 7);
+        assert_eq!(region_sizes[&'E'], 3);
+
+        let mut smallest_regions = grid_handler.smallest_regions();
+        smallest_regions.sort_unstable();
+        assert_eq!(smallest_regions, vec!['A', 'C']);
+
+        // Pas de région dans une grille sans contrainte de région
+        let grid_handler = GridHandler::without_regions(&grid_parser, 1);
+        assert!(grid_handler.region_sizes().is_empty());
+        assert!(grid_handler.smallest_regions().is_empty());
+    }
+
+    #[test]
+    fn test_zone_cells() {
+        let grid_parser =
+            GridParser::try_from(vec!["AB.BB", "ABBBB", "CCB.B", "DDDDD", ".EEED"]).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 1)
+            .with_cage(&[LineColumn::new(3, 3), LineColumn::new(0, 1)], 1);
+        let grid = Grid::from(&grid_handler);
+
+        // Mêmes cases, dans le même ordre, que le parcours de toutes les cases de la grille
+        let all_cells = grid_handler.surfer(&grid, &GridSurfer::AllCells);
+        let mut zones = vec![GridSurfer::Cage(0)];
+        zones.extend(grid_handler.regions().into_iter().map(GridSurfer::Region));
+        zones.extend((0..5).map(GridSurfer::Line));
+        zones.extend((0..5).map(GridSurfer::Column));
+        for zone in zones {
+            let expected: Vec<LineColumn> = all_cells
+                .iter()
+                .copied()
+                .filter(|line_column| match zone {
+                    GridSurfer::Region(region) => grid.cell(*line_column).region == region,
+                    GridSurfer::Line(line) => line_column.line == line,
+                    GridSurfer::Column(column) => line_column.column == column,
+                    _ => grid_handler.cage_cells(0).contains(line_column),
+                })
+                .collect();
+            assert_eq!(grid_handler.zone_cells(&zone), Some(expected.as_slice()));
+            assert_eq!(grid_handler.surfer(&grid, &zone), expected);
+        }
+        assert_eq!(all_cells.len(), 22);
+        assert_eq!(grid_handler.line_cells(0).len(), 4);
+        assert_eq!(grid_handler.column_cells(2).len(), 4);
+
+        // Zones sans liste précalculée
+        assert_eq!(grid_handler.zone_cells(&GridSurfer::Lines(0..=1)), None);
+        assert_eq!(grid_handler.zone_cells(&GridSurfer::Region('Z')), None);
+        assert_eq!(
+            grid_handler
+                .surfer(&grid, &GridSurfer::Columns(1..=2))
+                .len(),
+            9
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Région 'Z' inconnue dans la grille")]
+    fn test_region_cells_unknown() {
+        let grid_parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 1);
+        let _ = grid_handler.region_cells('Z');
+    }
+
+    #[test]
+    fn test_common_neighbors() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+
+        let mut common_neighbors = grid_handler.common_neighbors(&grid, &GridSurfer::Region('A'));
+        common_neighbors.sort_by_key(|line_column| (line_column.line, line_column.column));
+        assert_eq!(
+            common_neighbors,
+            vec![LineColumn::new(0, 1), LineColumn::new(1, 1)]
+        );
+
+        let mut common_neighbors = grid_handler.common_neighbors(&grid, &GridSurfer::Region('C'));
+        common_neighbors.sort_by_key(|line_column| (line_column.line, line_column.column));
+        assert_eq!(
+            common_neighbors,
+            vec![
+                LineColumn::new(1, 0),
+                LineColumn::new(1, 1),
+                LineColumn::new(3, 0),
+                LineColumn::new(3, 1)
+            ]
+        );
+
+        // Une seule case candidate : toutes ses cases adjacentes
+        grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::NoStar;
+        assert_eq!(
+            grid_handler
+                .common_neighbors(&grid, &GridSurfer::Region('A'))
+                .len(),
+            5
+        );
+
+        // Aucune case candidate
+        grid.cell_mut(LineColumn::new(1, 0)).value = CellValue::Star;
+        assert!(grid_handler
+            .common_neighbors(&grid, &GridSurfer::Region('A'))
+            .is_empty());
+
+        // Pas de case commune à toutes les cases candidates d'une ligne
+        assert!(grid_handler
+            .common_neighbors(&grid, &GridSurfer::Line(3))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_star_candidates() {
+        let (grid_handler, grid) = grid![
+            "ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1; star = "C2", no_star = "A4 B4"
+        ];
+
+        // Cases non définies et sans étoile adjacente
+        assert_eq!(
+            grid_handler.star_candidates(&grid, &GridSurfer::Line(3)),
+            vec![
+                LineColumn::new(3, 2),
+                LineColumn::new(3, 3),
+                LineColumn::new(3, 4)
+            ]
+        );
+        assert_eq!(
+            grid_handler.star_candidates(&grid, &GridSurfer::Region('C')),
+            vec![LineColumn::new(2, 0)]
+        );
+
+        // La ligne 2 et la région 'B' ont déjà leur étoile
+        assert!(grid_handler
+            .star_candidates(&grid, &GridSurfer::Line(1))
+            .is_empty());
+        assert!(grid_handler
+            .star_candidates(&grid, &GridSurfer::Region('B'))
+            .is_empty());
+
+        // Les cases adjacentes à l'étoile ne peuvent pas contenir une étoile
+        assert!(grid_handler
+            .star_candidates(&grid, &GridSurfer::Adjacent(LineColumn::new(1, 2)))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_without_regions() {
+        let parser = GridParser::try_from(vec!["AAAA", "AAAA", "AAAA", "AAAA"]).unwrap();
+        let handler = GridHandler::without_regions(&parser, 1);
+
+        assert!(!handler.has_regions());
+        assert!(handler.regions().is_empty());
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Line(0)), 1);
+        assert_eq!(handler.cell_region(LineColumn::new(0, 0)), 'A');
+    }
+
+    #[test]
+    fn test_rectangular() {
+        let parser = GridParser::try_from(vec![
+            "DDDDDEAAHB",
+            "CGDGEEAAHH",
+            "GGGGEEFFHH",
+            "GGGIFFFFHH",
+            "GGGIJJJHHH",
+        ])
+        .unwrap();
+        let handler = GridHandler::with_stars_per_zone(&parser, 2, 1, 1);
+
+        assert_eq!(handler.nb_lines(), 5);
+        assert_eq!(handler.nb_columns(), 10);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Line(0)), 2);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Column(0)), 1);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Region('A')), 1);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::AllCells), 10);
+    }
+
+    #[test]
+    fn test_cages() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let diagonal: Vec<LineColumn> = (0..5).map(|i| LineColumn::new(i, i)).collect();
+        let handler = GridHandler::new(&parser, 1)
+            .with_cage(&diagonal, 1)
+            .with_cage(
+                &[
+                    LineColumn::new(4, 0),
+                    LineColumn::new(4, 1),
+                    LineColumn::new(4, 2),
+                ],
+                2,
+            );
+        let grid = Grid::from(&handler);
+
+        assert_eq!(
+            handler.cages(),
+            vec![GridSurfer::Cage(0), GridSurfer::Cage(1)]
+        );
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Cage(0)), 1);
+        assert_eq!(handler.zone_nb_stars(&GridSurfer::Cage(1)), 2);
+        assert_eq!(handler.surfer(&grid, &GridSurfer::Cage(0)), diagonal);
+        assert_eq!(GridSurfer::Cage(1).to_string(), "Cage 2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Trop d'étoiles à placer (2) pour 'Cage 1'")]
+    fn test_cage_too_small() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let _ = GridHandler::new(&parser, 1)
+            .with_cage(&[LineColumn::new(0, 0), LineColumn::new(0, 1)], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Le nombre total d'étoiles")]
+    fn test_rectangular_mismatch() {
+        let parser = GridParser::try_from(vec!["AAAAAA", "BBBBBB", "CCCCCC"]).unwrap();
+        let _ = GridHandler::new(&parser, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Le nombre total d'étoiles")]
+    fn test_zone_nb_stars_mismatch() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let _ = GridHandler::with_zone_stars(&parser, 1, &[(GridSurfer::Region('A'), 2)]);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        assert!(GridHandler::try_new(&parser, 1).is_ok());
+        assert!(GridHandler::try_new(&parser, 0).is_err());
+        assert!(GridHandler::try_new(&parser, 3)
+            .unwrap_err()
+            .contains("Trop d'étoiles"));
+
+        // Grille de 5 lignes avec seulement 4 régions
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DDDDD"]).unwrap();
+        assert!(GridHandler::try_new(&parser, 1)
+            .unwrap_err()
+            .contains("4 régions"));
+    }
+
     #[test]
     #[allow(clippy::cognitive_complexity)]
     fn test_ok() {
@@ -314,6 +1239,162 @@ mod tests {
         assert_adjacents(&handler, (2, 2), vec![(1, 1), (1, 2), (2, 1)]);
     }
 
+    #[test]
+    fn test_clone_shares_caches() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let handler = GridHandler::new(&parser, 1);
+        let handler_clone = handler.clone();
+        assert!(Arc::ptr_eq(&handler.regions, &handler_clone.regions));
+        assert!(Arc::ptr_eq(
+            &handler.region_cells,
+            &handler_clone.region_cells
+        ));
+        assert!(Arc::ptr_eq(
+            &handler.cells_region,
+            &handler_clone.cells_region
+        ));
+        assert!(Arc::ptr_eq(
+            &handler.adjacent_cells,
+            &handler_clone.adjacent_cells
+        ));
+        assert!(Arc::ptr_eq(&handler.line_cells, &handler_clone.line_cells));
+        assert!(Arc::ptr_eq(
+            &handler.column_cells,
+            &handler_clone.column_cells
+        ));
+        assert_eq!(handler_clone.region_cells('A'), handler.region_cells('A'));
+
+        // Les cases adjacentes sont recalculées pour une grille 'torique'
+        let handler_toroidal = handler_clone.with_toroidal(true);
+        assert!(!Arc::ptr_eq(
+            &handler.adjacent_cells,
+            &handler_toroidal.adjacent_cells
+        ));
+        assert_eq!(handler.adjacent_cells(LineColumn::new(0, 0)).len(), 3);
+        assert_eq!(
+            handler_toroidal.adjacent_cells(LineColumn::new(0, 0)).len(),
+            8
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_toroidal_adjacent() {
+        let parser = GridParser::try_from(vec!["AAAA", "BBBB", "CCCC", "DDDD"]).unwrap();
+        let handler = GridHandler::new(&parser, 1).with_toroidal(true);
+        assert!(handler.is_toroidal());
+
+        // La case du coin (0, 0) a 8 cases adjacentes dont les cases des bords opposés
+        let adjacent_cells: HashSet<LineColumn> = handler.adjacent_cells(LineColumn::new(0, 0)).into_iter().collect();
+        let expected_cells: HashSet<LineColumn> = [(3, 3), (3, 0), (3, 1), (0, 3), (0, 1), (1, 3), (1, 0), (1, 1)]
+            .into_iter().map(|(line, column)| LineColumn::new(line, column)).collect();
+        assert_eq!(adjacent_cells, expected_cells);
+
+        // Sur une grille de 2 lignes, la ligne voisine n'est comptée qu'une fois
+        let parser = GridParser::try_from(vec!["AAAA", "BBBB"]).unwrap();
+        let handler = GridHandler::with_stars_per_zone(&parser, 2, 1, 2).with_toroidal(true);
+        let mut grid = Grid::from(&handler);
+        assert_eq!(handler.adjacent_cells(LineColumn::new(0, 0)).len(), 5);
+
+        // Les étoiles en (0, 0) et (1, 3) se touchent en diagonale par les bords
+        grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::Star;
+        assert!(handler.is_star_adjacent(&grid, LineColumn::new(1, 3)));
+        assert!(!handler.is_star_adjacent(&grid, LineColumn::new(1, 2)));
+    }
+
+    #[test]
+    fn test_toroidal_display() {
+        let parser = GridParser::try_from(vec!["AB", "CD"]).unwrap();
+        let handler = GridHandler::without_regions(&parser, 1).with_toroidal(true);
+        let grid = Grid::from(&handler);
+
+        assert_eq!(
+            handler.display(&grid, false),
+            " A? B? |A\n C? D? |C\n A~ B~\n(grille torique : les bords opposés de la grille sont adjacents)\n"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"])
+                .unwrap();
+        let handler = GridHandler::new(&parser, 1).with_cage(&[LineColumn::new(4, 4)], 1);
+        let grid = Grid::from(&handler);
+
+        let json = serde_json::to_string(&handler).unwrap();
+        let handler_copy: GridHandler = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            handler_copy.display(&grid, true),
+            handler.display(&grid, true)
+        );
+        assert_eq!(handler_copy.cages(), handler.cages());
+        assert!(handler_copy.is_given(LineColumn::new(0, 0)));
+
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(serde_json::from_str::<Grid>(&json).unwrap(), grid);
+
+        let json = serde_json::to_string(&parser).unwrap();
+        let parser_copy: GridParser = serde_json::from_str(&json).unwrap();
+        assert_eq!(parser_copy.to_string(), parser.to_string());
+
+        // Grille sans contrainte de région, nombre d'étoiles par zone et grille torique
+        let handler = GridHandler::without_regions(&parser, 1).with_toroidal(true);
+        let json = serde_json::to_string(&handler).unwrap();
+        let handler_copy: GridHandler = serde_json::from_str(&json).unwrap();
+        assert!(handler_copy.regions().is_empty() && handler_copy.is_toroidal());
+        let handler = GridHandler::with_zone_stars(
+            &GridParser::try_from(vec!["AAAA", "BBBB", "CCCC", "DDDD", "EEEE", "FFFF"]).unwrap(),
+            1,
+            &[(GridSurfer::Column(0), 2), (GridSurfer::Column(3), 2)],
+        );
+        let json = serde_json::to_string(&handler).unwrap();
+        let handler_copy: GridHandler = serde_json::from_str(&json).unwrap();
+        assert_eq!(handler_copy.zone_nb_stars(&GridSurfer::Column(0)), 2);
+        assert_eq!(handler_copy.zone_nb_stars(&GridSurfer::Column(1)), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_invalid() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let json = serde_json::to_string(&GridHandler::new(&parser, 1)).unwrap();
+        assert!(serde_json::from_str::<GridHandler>(&json).is_ok());
+
+        // Les contrôles des constructeurs s'appliquent à la désérialisation
+        for (valid, invalid) in [
+            // Lignes de longueurs différentes
+            ("\"DEEED\"", "\"DEEE\""),
+            // Trop d'étoiles pour une région
+            ("[\"E\",1]", "[\"E\",2]"),
+            // Nombre d'étoiles des lignes incohérent avec la grille
+            ("\"line_stars\":[1,1,1,1,1]", "\"line_stars\":[1,1,1,1]"),
+        ] {
+            assert!(json.contains(valid), "{valid}");
+            let json = json.replace(valid, invalid);
+            assert!(
+                serde_json::from_str::<GridHandler>(&json).is_err(),
+                "{invalid}"
+            );
+        }
+        let json = json.replace(
+            "\"cages\":[]",
+            "\"cages\":[[[{\"line\":9,\"column\":0}],1]]",
+        );
+        assert!(serde_json::from_str::<GridHandler>(&json).is_err());
+
+        let json = serde_json::to_string(&parser).unwrap();
+        assert!(
+            serde_json::from_str::<GridParser>(&json.replace("\"DEEED\"", "\"DEE ED\"")).is_err()
+        );
+        assert!(
+            serde_json::from_str::<GridParser>(&json.replace("\"DEEED\"", "\"DEEEDD\"")).is_err()
+        );
+    }
+
     #[test]
     fn test_is_star_adjacent() {
         let parser = GridParser::try_from(vec!["AAA", "BBB", "CCC"]).unwrap();
@@ -327,4 +1408,16 @@ mod tests {
         grid.cell_mut(adjacent_line_column).value = crate::CellValue::Star;
         assert!(handler.is_star_adjacent(&grid, line_column));
     }
+
+    #[test]
+    fn test_display_snapshot() {
+        let (grid_handler, mut grid) =
+            grid!["ABBB.", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"; stars = 1];
+        grid.cell_mut(LineColumn::new(1, 1)).value = CellValue::NoStar;
+        insta::assert_snapshot!("display", grid_handler.display(&grid, false));
+        insta::assert_snapshot!("display_coordinates", grid_handler.display(&grid, true));
+
+        let grid_handler = grid_handler.with_toroidal(true);
+        insta::assert_snapshot!("display_toroidal", grid_handler.display(&grid, true));
+    }
 }
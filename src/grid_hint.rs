@@ -0,0 +1,169 @@
+//! Indice pour la prochaine étape de la résolution d'une grille.
+//!
+//! [`next_hint`] recherche la prochaine règle de construction applicable sans l'appliquer : un
+//! entraîneur peut montrer à l'élève la zone à examiner et l'idée de la règle ([`Hint::text`]) avant de
+//! dévoiler les cases concernées ([`Hint::cells`]) puis les actions de la règle.
+
+use crate::get_good_rule_with_config;
+use crate::BadRuleError;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::LineColumn;
+use crate::Locale;
+use crate::SolverConfig;
+
+/// Indice pour la prochaine étape de la résolution d'une grille (voir [`next_hint`])
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hint {
+    /// Règle de construction applicable
+    good_rule: GoodRule,
+
+    /// Niveau de la règle (voir [`crate::get_good_rule_with_level`])
+    level: usize,
+
+    /// Cases examinées par la règle
+    zone_cells: Vec<LineColumn>,
+
+    /// Cases modifiées par la règle
+    cells: Vec<LineColumn>,
+
+    /// Explication courte de la règle, sans ses actions
+    text: String,
+}
+
+impl Hint {
+    /// Constructeur : indice de la règle `good_rule` de niveau `level` applicable à la grille `grid`
+    fn new(
+        handler: &GridHandler,
+        grid: &Grid,
+        good_rule: GoodRule,
+        level: usize,
+        locale: Locale,
+    ) -> Self {
+        let zone_cells = match (&good_rule, good_rule.zone()) {
+            (
+                GoodRule::NoStarAdjacentToStar(line_column, _)
+                | GoodRule::Contradiction(line_column, _),
+                _,
+            ) => vec![*line_column],
+            (_, Some(zone)) => handler.surfer(grid, zone),
+            (_, None) => Vec::new(),
+        };
+        let cells = good_rule
+            .actions()
+            .iter()
+            .map(GridAction::line_column)
+            .collect();
+        let text = good_rule.summary(locale);
+        Self {
+            good_rule,
+            level,
+            zone_cells,
+            cells,
+            text,
+        }
+    }
+
+    /// Règle de construction applicable
+    #[must_use]
+    pub const fn good_rule(&self) -> &GoodRule {
+        &self.good_rule
+    }
+
+    /// Niveau de la règle (voir [`crate::get_good_rule_with_level`])
+    #[must_use]
+    pub const fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Cases examinées par la règle : cases de la zone examinée ou étoile dont les cases adjacentes ne
+    /// peuvent pas contenir une étoile
+    #[must_use]
+    pub fn zone_cells(&self) -> &[LineColumn] {
+        &self.zone_cells
+    }
+
+    /// Cases modifiées par la règle
+    #[must_use]
+    pub fn cells(&self) -> &[LineColumn] {
+        &self.cells
+    }
+
+    /// Explication courte de la règle, sans ses actions (voir [`GoodRule::explain_in`] pour une
+    /// explication complète)
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Règle de construction applicable (l'indice est consommé)
+    #[must_use]
+    pub fn into_good_rule(self) -> GoodRule {
+        self.good_rule
+    }
+}
+
+/// Indice pour la prochaine étape de la résolution de la grille `grid`, sans appliquer la règle.<br>
+/// Retourne None si aucune règle n'est applicable (grille résolue ou bloquée)
+/// ### Errors
+/// Retourne un [`BadRuleError`] si la grille n'est pas valide
+pub fn next_hint(handler: &GridHandler, grid: &Grid) -> Result<Option<Hint>, BadRuleError> {
+    next_hint_with_config(handler, grid, &SolverConfig::default())
+}
+
+/// Indice pour la prochaine étape de la résolution de la grille `grid` en recherchant la règle selon la
+/// configuration `config` (voir [`get_good_rule_with_config`])
+/// ### Errors
+/// Retourne un [`BadRuleError`] si la grille n'est pas valide
+pub fn next_hint_with_config(
+    handler: &GridHandler,
+    grid: &Grid,
+    config: &SolverConfig,
+) -> Result<Option<Hint>, BadRuleError> {
+    Ok(get_good_rule_with_config(handler, grid, config)?
+        .map(|(good_rule, level)| Hint::new(handler, grid, good_rule, level, config.locale())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::get_good_rule;
+    use crate::GridSurfer;
+
+    #[test]
+    fn test_next_hint() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let saved_grid = grid.clone();
+        let hint = next_hint(&grid_handler, &grid).unwrap().unwrap();
+        assert_eq!(grid, saved_grid);
+        assert_eq!(
+            hint.good_rule(),
+            &get_good_rule(&grid_handler, &grid).unwrap().unwrap()
+        );
+        let zone = hint.good_rule().zone().unwrap();
+        assert_eq!(hint.zone_cells(), grid_handler.surfer(&grid, zone));
+        assert_eq!(hint.cells().len(), hint.good_rule().actions().len());
+        assert!(hint.good_rule().to_string().starts_with(hint.text()));
+
+        // Indice pour les cases adjacentes à une étoile
+        let c3 = LineColumn::new(2, 2);
+        grid.apply_action(&GridAction::SetStar(c3)).unwrap();
+        let hint = next_hint(&grid_handler, &grid).unwrap().unwrap();
+        assert_eq!(hint.zone_cells(), &[c3]);
+        let mut cells = hint.cells().to_vec();
+        cells.sort();
+        assert_eq!(cells, grid_handler.surfer(&grid, &GridSurfer::Adjacent(c3)));
+
+        // Plus d'indice pour une grille résolue
+        let mut grid = saved_grid;
+        while let Some(hint) = next_hint(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&hint.into_good_rule());
+        }
+        assert!(grid_handler.is_done(&grid));
+    }
+}
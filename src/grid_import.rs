@@ -0,0 +1,302 @@
+//! Import des grilles publiées par les sites de Star Battle.
+//!
+//! Les sites décrivent une grille par l'identifiant de la région de chaque case. Ces identifiants sont
+//! renommés dans l'ordre de leur première case ('A', 'B', ...) pour obtenir la définition de la grille.
+//!
+//! Le site en français (puzzle-star-battle.com) code une grille dans le source de sa page par une 'tâche' :
+//! les numéros de région des cases séparés par des virgules, ligne par ligne, pour une grille carrée
+//! (voir [`GridParser::try_from_task`]).
+//!
+//! Les recueils téléchargeables de KrazyDad et Puzzle Baron décrivent une grille par une ligne de numéros de
+//! région par ligne de la grille, séparés par des espaces ou des virgules (`0 0 1 1 2`) ou accolés quand ils
+//! n'ont qu'un chiffre (`00112`). Dans un recueil, les grilles sont séparées par une ligne vide ou par une
+//! ligne de titre (`Puzzle 12` par exemple) (voir [`parse_region_number_book`]).
+//!
+//! Une grille exportée d'un tableur est un fichier CSV ou TSV : une ligne par ligne de la grille et un
+//! identifiant de région quelconque par cellule (`1,1,2,2,3`), `.` pour une case bloquée (voir
+//! [`GridParser::try_from_csv`]).
+
+use crate::grid_generator::{region_char, REGION_CHARS};
+use crate::grid_parser::{BLOCKED_CELL_CHAR, COMMENT_CHARS};
+use crate::GridParser;
+use crate::PuzzleMetadata;
+
+/// Séparateur des numéros de région d'une tâche de puzzle-star-battle.com
+const TASK_SEPARATOR: char = ',';
+
+/// Délimiteurs possibles d'une tâche copiée depuis le source d'une page
+const TASK_QUOTES: [char; 2] = ['\'', '"'];
+
+/// Séparateurs des numéros de région d'une ligne d'un recueil KrazyDad ou Puzzle Baron (en plus des
+/// espaces)
+const REGION_NUMBER_SEPARATORS: [char; 2] = [',', ';'];
+
+/// Séparateurs possibles des cellules d'une grille CSV ou TSV, par ordre de priorité
+const CSV_SEPARATORS: [char; 3] = ['\t', ';', ','];
+
+/// Délimiteur d'une cellule CSV
+const CSV_QUOTE: char = '"';
+
+impl GridParser {
+    /// Définition d'une grille à partir d'une tâche du site puzzle-star-battle.com (`1,1,2,2,...`).<br>
+    /// La tâche peut être copiée avec ses délimiteurs (`task = '1,1,2,...'`) : seul le texte entre les
+    /// premiers délimiteurs est analysé
+    /// ### Errors
+    /// Retourne une erreur si la tâche ne décrit pas une grille carrée ou si la grille est invalide
+    pub fn try_from_task(task: &str) -> Result<Self, String> {
+        let task = if task.contains(TASK_QUOTES) {
+            task.split(TASK_QUOTES).nth(1).unwrap_or_default()
+        } else {
+            task
+        }
+        .trim();
+        let ids: Vec<&str> = task.split(TASK_SEPARATOR).map(str::trim).collect();
+        if ids.iter().any(|id| id.parse::<usize>().is_err()) {
+            return Err(format!(
+                "Tâche '{task}' invalide : numéros de région attendus"
+            ));
+        }
+        let size = ids.len().isqrt();
+        if size * size != ids.len() {
+            return Err(format!(
+                "Tâche invalide : {} cases ne forment pas une grille carrée",
+                ids.len()
+            ));
+        }
+        let lines: Vec<Vec<&str>> = ids.chunks(size).map(<[&str]>::to_vec).collect();
+        parser_from_region_ids(&lines)
+    }
+
+    /// Définition d'une grille à partir de ses lignes de numéros de région (format des recueils KrazyDad
+    /// et Puzzle Baron, voir [`parse_region_number_book`]).<br>
+    /// Les lignes vides, les commentaires et les lignes de titre sont ignorés
+    /// ### Errors
+    /// Retourne une erreur si le texte ne contient pas exactement une grille ou si la grille est invalide
+    pub fn try_from_region_numbers(text: &str) -> Result<Self, String> {
+        let mut grids = parse_region_number_book(text);
+        match grids.len() {
+            1 => grids.remove(0),
+            0 => Err("Aucune grille de numéros de région".to_string()),
+            nb_grids => Err(format!(
+                "{nb_grids} grilles de numéros de région au lieu d'une seule"
+            )),
+        }
+    }
+}
+
+impl GridParser {
+    /// Définition d'une grille CSV ou TSV (exportée d'un tableur) : une ligne par ligne de la grille et un
+    /// identifiant de région par cellule.<br>
+    /// Le séparateur des cellules est la tabulation, le point-virgule ou la virgule (le premier présent
+    /// dans le texte). Les cellules peuvent être délimitées par des guillemets, `.` identifie une case
+    /// bloquée. Les lignes vides et les commentaires sont ignorés
+    /// ### Errors
+    /// Retourne une erreur si une cellule est vide ou si la grille est invalide
+    pub fn try_from_csv(text: &str) -> Result<Self, String> {
+        let separator = CSV_SEPARATORS
+            .into_iter()
+            .find(|separator| text.contains(*separator))
+            .unwrap_or(CSV_SEPARATORS[0]);
+        let mut lines = Vec::new();
+        for (num_line, text_line) in text.lines().enumerate() {
+            if text_line.trim().is_empty() || text_line.trim().starts_with(COMMENT_CHARS) {
+                continue;
+            }
+            let tokens = csv_tokens(text_line, separator);
+            if tokens.iter().any(|token| token.is_empty()) {
+                return Err(format!(
+                    "Erreur à la ligne #{} '{}': cellule vide",
+                    num_line + 1,
+                    text_line.trim()
+                ));
+            }
+            lines.push(tokens);
+        }
+        parser_from_region_ids(&lines)
+    }
+}
+
+/// Cellules d'une ligne CSV ou TSV séparées par `separator` (sans les espaces et guillemets qui les
+/// entourent)
+fn csv_tokens(text_line: &str, separator: char) -> Vec<&str> {
+    text_line
+        .split(separator)
+        .map(|token| {
+            let token = token.trim();
+            token
+                .strip_prefix(CSV_QUOTE)
+                .and_then(|token| token.strip_suffix(CSV_QUOTE))
+                .unwrap_or(token)
+                .trim()
+        })
+        .collect()
+}
+
+/// Grilles d'un recueil KrazyDad ou Puzzle Baron : une ligne de numéros de région par ligne de la grille.<br>
+/// Les grilles sont séparées par des lignes vides ou des lignes de titre (qui ne contiennent pas que des
+/// numéros). Le titre d'une grille est conservé dans ses métadonnées ([`PuzzleMetadata::title`]) et les
+/// commentaires sont ignorés
+#[must_use]
+pub fn parse_region_number_book(text: &str) -> Vec<Result<GridParser, String>> {
+    let mut grids = Vec::new();
+    let mut title: Option<String> = None;
+    let mut lines: Vec<Vec<&str>> = Vec::new();
+    let mut flush = |title: Option<String>, lines: &mut Vec<Vec<&str>>| {
+        if !lines.is_empty() {
+            let metadata = PuzzleMetadata {
+                title,
+                ..PuzzleMetadata::default()
+            };
+            grids.push(parser_from_region_ids(lines).map(|parser| parser.with_metadata(metadata)));
+            lines.clear();
+        }
+    };
+    for text_line in text.lines().map(str::trim) {
+        if text_line.starts_with(COMMENT_CHARS) {
+            continue;
+        }
+        match region_numbers(text_line) {
+            Some(ids) => lines.push(ids),
+            None => {
+                flush(title.take(), &mut lines);
+                if !text_line.is_empty() {
+                    title = Some(text_line.to_string());
+                }
+            }
+        }
+    }
+    flush(title, &mut lines);
+    grids
+}
+
+/// Numéros de région d'une ligne d'un recueil. None si la ligne est vide ou ne contient pas que des numéros
+fn region_numbers(text_line: &str) -> Option<Vec<&str>> {
+    let tokens: Vec<&str> = text_line
+        .split(|c: char| c.is_whitespace() || REGION_NUMBER_SEPARATORS.contains(&c))
+        .filter(|token| !token.is_empty())
+        .collect();
+    if tokens.is_empty()
+        || !tokens
+            .iter()
+            .all(|token| token.chars().all(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+    // Numéros accolés d'un seul chiffre
+    if tokens.len() == 1 {
+        let digits = tokens[0];
+        return Some((0..digits.len()).map(|i| &digits[i..=i]).collect());
+    }
+    Some(tokens)
+}
+
+/// Définition d'une grille à partir de l'identifiant de la région de chaque case (une liste par ligne).<br>
+/// Les régions sont renommées dans l'ordre de leur première case, l'identifiant `.` reste une case bloquée
+pub(crate) fn parser_from_region_ids(lines: &[Vec<&str>]) -> Result<GridParser, String> {
+    let mut ids: Vec<&str> = Vec::new();
+    let mut text_lines = Vec::with_capacity(lines.len());
+    for line in lines {
+        let mut text_line = String::with_capacity(line.len());
+        for id in line {
+            if id.len() == 1 && id.starts_with(BLOCKED_CELL_CHAR) {
+                text_line.push(BLOCKED_CELL_CHAR);
+                continue;
+            }
+            let index = ids.iter().position(|known| known == id).unwrap_or_else(|| {
+                ids.push(id);
+                ids.len() - 1
+            });
+            if index >= REGION_CHARS.len() {
+                return Err(format!(
+                    "Trop de régions dans la grille (max. {})",
+                    REGION_CHARS.len()
+                ));
+            }
+            text_line.push(region_char(index));
+        }
+        text_lines.push(text_line);
+    }
+    GridParser::try_from(text_lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_task() {
+        let task = "1,1,2,2,2,1,1,2,2,2,3,3,2,2,2,4,4,4,4,4,4,5,5,5,4";
+        let grid_parser = GridParser::try_from_task(task).unwrap();
+        assert_eq!(grid_parser.to_string(), "AABBB\nAABBB\nCCBBB\nDDDDD\nDEEED");
+
+        // Tâche copiée depuis le source de la page
+        let copied = GridParser::try_from_task(&format!("var task = '{task}';")).unwrap();
+        assert_eq!(copied.to_string(), grid_parser.to_string());
+
+        assert!(GridParser::try_from_task("1,1,2").is_err());
+        assert!(GridParser::try_from_task("1,1,a,2").is_err());
+    }
+
+    #[test]
+    fn test_parse_region_number_book() {
+        let book = "\
+# Recueil de test
+Puzzle 1
+0 0 1 1
+0 2 2 1
+3 3 2 2
+3 3 3 3
+
+0011
+0221
+3322
+3333
+Puzzle 3
+1,1,2
+1,2
+";
+        let grids = parse_region_number_book(book);
+        assert_eq!(grids.len(), 3);
+        let first = grids[0].as_ref().unwrap();
+        assert_eq!(
+            first.to_string(),
+            "# title: Puzzle 1\nAABB\nACCB\nDDCC\nDDDD"
+        );
+        let second = grids[1].as_ref().unwrap();
+        assert_eq!(second.metadata().title, None);
+        assert_eq!(second.list_cells(), first.list_cells());
+        assert!(grids[2].is_err());
+
+        assert!(GridParser::try_from_region_numbers("0011\n0221\n3322\n3333").is_ok());
+        // Séparateur final après des numéros accolés
+        assert_eq!(
+            GridParser::try_from_region_numbers("0011,\n0221;\n3322 ,\n3333")
+                .unwrap()
+                .list_cells(),
+            first.list_cells()
+        );
+        assert!(GridParser::try_from_region_numbers(book).is_err());
+        assert!(GridParser::try_from_region_numbers("Puzzle").is_err());
+    }
+
+    #[test]
+    fn test_try_from_csv() {
+        let expected = "AABB\nACCB\nDDCC\nDDDD";
+        let csv = "1,1,2,2\n1,3,3,2\n4,4,3,3\n4,4,4,4\n";
+        assert_eq!(GridParser::try_from_csv(csv).unwrap().to_string(), expected);
+
+        // Export d'un tableur avec tabulations et identifiants quelconques
+        let tsv = "# Export\nnord\tnord\test\test\r\nnord\tcentre\tcentre\test\n\
+                   sud\tsud\tcentre\tcentre\nsud\tsud\tsud\tsud";
+        assert_eq!(GridParser::try_from_csv(tsv).unwrap().to_string(), expected);
+
+        // Cellules délimitées, point-virgule et case bloquée
+        let csv = "\"1\";\"1\";\"2\"\n\".\";\"1\";\"2\"\n3;3;3";
+        assert_eq!(
+            GridParser::try_from_csv(csv).unwrap().to_string(),
+            "AAB\n.AB\nCCC"
+        );
+
+        assert!(GridParser::try_from_csv("1,,2\n1,2,2").is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! Historique des modifications d'une grille.
+//!
+//! Un [`GridJournal`] enveloppe une [`Grid`] et mémorise chaque modification (une action ou toutes les
+//! actions d'une règle) pour pouvoir l'annuler ([`GridJournal::undo`]) puis la refaire
+//! ([`GridJournal::redo`]) sans cloner toute la grille.
+//!
+//! Des points de reprise nommés ([`GridJournal::checkpoint`]) permettent de revenir d'un coup à un état
+//! antérieur de la grille ([`GridJournal::rollback`]), par exemple après l'échec d'une hypothèse.
+
+use crate::CellValue;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridActionError;
+use crate::LineColumn;
+
+/// Modification d'une case : coordonnées, valeur avant et valeur après la modification
+type CellChange = (LineColumn, CellValue, CellValue);
+
+/// Grille avec l'historique de ses modifications
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridJournal {
+    /// Grille dans son état courant
+    grid: Grid,
+
+    /// Modifications appliquées à la grille (chacune regroupe les changements de plusieurs cases)
+    done: Vec<Vec<CellChange>>,
+
+    /// Modifications annulées qui peuvent être refaites
+    undone: Vec<Vec<CellChange>>,
+
+    /// Points de reprise : nom et nombre de modifications appliquées à la grille à ce moment
+    checkpoints: Vec<(String, usize)>,
+}
+
+impl From<Grid> for GridJournal {
+    fn from(grid: Grid) -> Self {
+        Self {
+            grid,
+            ..Self::default()
+        }
+    }
+}
+
+impl GridJournal {
+    /// Grille dans son état courant
+    #[must_use]
+    pub const fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Retourne la grille dans son état courant (l'historique est perdu)
+    #[must_use]
+    pub fn into_grid(self) -> Grid {
+        self.grid
+    }
+
+    /// Applique une action à la grille et la mémorise dans l'historique
+    /// ### Errors
+    /// Retourne un [`GridActionError`] si l'action modifie une étoile donnée par la définition de la grille
+    pub fn apply_action(&mut self, action: &GridAction) -> Result<(), GridActionError> {
+        self.apply_actions(std::slice::from_ref(action))
+    }
+
+    /// Applique toutes les actions d'une règle à la grille et les mémorise comme une seule modification
+    pub fn apply_good_rule(&mut self, rule: &GoodRule) {
+        // Les règles ne portent que sur des cases non définies, jamais sur une étoile donnée
+        let _ = self.apply_actions(rule.actions());
+    }
+
+    /// Applique des actions à la grille et les mémorise comme une seule modification.<br>
+    /// Si une action est refusée, aucune des actions n'est appliquée.
+    fn apply_actions(&mut self, actions: &[GridAction]) -> Result<(), GridActionError> {
+        let mut changes = Vec::with_capacity(actions.len());
+        for action in actions {
+            let line_column = action.line_column();
+            let before = self.grid.cell(line_column).value.clone();
+            if let Err(e) = action.apply_action(&mut self.grid) {
+                Self::revert(&mut self.grid, &changes);
+                return Err(e);
+            }
+            let after = self.grid.cell(line_column).value.clone();
+            changes.push((line_column, before, after));
+        }
+
+        // Une nouvelle modification rend impossible de refaire les modifications annulées
+        self.undone.clear();
+        let nb_done = self.done.len();
+        self.checkpoints
+            .retain(|(_, position)| *position <= nb_done);
+        self.done.push(changes);
+        Ok(())
+    }
+
+    /// Annule la dernière modification de la grille.<br>
+    /// Retourne `false` s'il n'y a aucune modification à annuler.
+    pub fn undo(&mut self) -> bool {
+        let Some(changes) = self.done.pop() else {
+            return false;
+        };
+        Self::revert(&mut self.grid, &changes);
+        self.undone.push(changes);
+        true
+    }
+
+    /// Refait la dernière modification annulée de la grille.<br>
+    /// Retourne `false` s'il n'y a aucune modification à refaire.
+    pub fn redo(&mut self) -> bool {
+        let Some(changes) = self.undone.pop() else {
+            return false;
+        };
+        for (line_column, _, after) in &changes {
+            self.grid.set_cell_value(*line_column, after.clone());
+        }
+        self.done.push(changes);
+        true
+    }
+
+    /// Nombre de modifications qui peuvent être annulées
+    #[must_use]
+    pub fn nb_undo(&self) -> usize {
+        self.done.len()
+    }
+
+    /// Nombre de modifications annulées qui peuvent être refaites
+    #[must_use]
+    pub fn nb_redo(&self) -> usize {
+        self.undone.len()
+    }
+
+    /// Mémorise l'état courant de la grille sous un nom (qui remplace un éventuel point de reprise de même
+    /// nom)
+    pub fn checkpoint(&mut self, name: &str) {
+        self.checkpoints.retain(|(other, _)| other != name);
+        self.checkpoints.push((name.to_string(), self.done.len()));
+    }
+
+    /// Annule toutes les modifications de la grille depuis le point de reprise `name`.<br>
+    /// Les modifications annulées peuvent être refaites par [`GridJournal::redo`].<br>
+    /// Retourne `false` si ce point de reprise n'existe pas (ou plus).
+    pub fn rollback(&mut self, name: &str) -> bool {
+        let Some(position) = self
+            .checkpoints
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, position)| *position)
+        else {
+            return false;
+        };
+        while self.done.len() > position {
+            self.undo();
+        }
+        true
+    }
+
+    /// Remet les cases modifiées dans leur état d'avant les modifications
+    fn revert(grid: &mut Grid, changes: &[CellChange]) {
+        for (line_column, before, _) in changes.iter().rev() {
+            grid.set_cell_value(*line_column, before.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::get_good_rule;
+    use crate::GridHandler;
+    use crate::GridParser;
+
+    // Construction d'un objet GridHandler et d'un GridJournal à partir d'une grille de test
+    fn get_test_journal() -> (GridHandler, GridJournal) {
+        let grid_parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"])
+                .unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 1);
+        let journal = GridJournal::from(Grid::from(&grid_handler));
+        (grid_handler, journal)
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let (grid_handler, mut journal) = get_test_journal();
+        let initial = journal.grid().clone();
+        assert!(!journal.undo());
+
+        let good_rule = get_good_rule(&grid_handler, journal.grid())
+            .unwrap()
+            .unwrap();
+        journal.apply_good_rule(&good_rule);
+        journal
+            .apply_action(&GridAction::SetStar(LineColumn::new(2, 2)))
+            .unwrap();
+        let modified = journal.grid().clone();
+        assert_eq!(journal.nb_undo(), 2);
+
+        // Annulation de l'action puis de toute la règle
+        assert!(journal.undo());
+        assert!(journal.grid().cell(LineColumn::new(2, 2)).is_unknown());
+        assert!(journal.undo());
+        assert_eq!(journal.grid(), &initial);
+        assert!(!journal.undo());
+
+        // ... que l'on refait
+        assert!(journal.redo());
+        assert!(journal.redo());
+        assert!(!journal.redo());
+        assert_eq!(journal.grid(), &modified);
+
+        // Une nouvelle modification après une annulation ne permet plus de refaire
+        assert!(journal.undo());
+        journal
+            .apply_action(&GridAction::SetNoStar(LineColumn::new(2, 2)))
+            .unwrap();
+        assert_eq!(journal.nb_redo(), 0);
+    }
+
+    #[test]
+    fn test_given_star() {
+        let (_, mut journal) = get_test_journal();
+
+        // L'action refusée n'est pas mémorisée
+        assert!(journal
+            .apply_action(&GridAction::SetNoStar(LineColumn::new(0, 0)))
+            .is_err());
+        assert_eq!(journal.nb_undo(), 0);
+        assert!(journal.grid().cell(LineColumn::new(0, 0)).is_star());
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        let (_, mut journal) = get_test_journal();
+
+        journal
+            .apply_action(&GridAction::SetNoStar(LineColumn::new(1, 1)))
+            .unwrap();
+        journal.checkpoint("hypothèse");
+        let before = journal.grid().clone();
+        journal
+            .apply_action(&GridAction::SetStar(LineColumn::new(2, 2)))
+            .unwrap();
+        journal
+            .apply_action(&GridAction::SetNoStar(LineColumn::new(3, 3)))
+            .unwrap();
+
+        assert!(journal.rollback("hypothèse"));
+        assert_eq!(journal.grid(), &before);
+        assert_eq!(journal.nb_redo(), 2);
+        assert!(!journal.rollback("inconnu"));
+
+        // Un point de reprise disparaît quand les modifications qui le précèdent sont remplacées
+        assert!(journal.undo());
+        journal
+            .apply_action(&GridAction::SetStar(LineColumn::new(1, 1)))
+            .unwrap();
+        assert!(!journal.rollback("hypothèse"));
+    }
+}
@@ -0,0 +1,127 @@
+//! Observation des modifications des cases d'une grille.
+//!
+//! Un [`GridObserver`] attaché à une [`crate::Grid`] (voir [`crate::Grid::set_observer`]) est prévenu de
+//! chaque changement de valeur d'une case par une action, par exemple pour animer l'affichage d'une grille
+//! ou tracer les modifications.
+//!
+//! L'observateur est partagé par un [`Arc`] et un [`Mutex`] : une grille observée peut ainsi être
+//! transmise à un autre thread.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::CellValue;
+use crate::LineColumn;
+
+/// Observateur des modifications des cases d'une grille
+pub trait GridObserver {
+    /// La valeur de la case `line_column` passe de `old` à `new`
+    fn on_change(&mut self, line_column: LineColumn, old: CellValue, new: CellValue);
+}
+
+/// Observateur partagé entre la grille et son propriétaire
+pub type SharedGridObserver = Arc<Mutex<dyn GridObserver + Send>>;
+
+/// Emplacement (éventuellement vide) de l'observateur d'une grille.<br>
+/// L'observateur ne fait pas partie du contenu de la grille : il est ignoré pour comparer ou sérialiser
+/// une grille et une copie de la grille n'est pas observée
+#[derive(Default)]
+pub struct ObserverSlot(Option<SharedGridObserver>);
+
+impl ObserverSlot {
+    /// Définit (ou supprime) l'observateur
+    pub fn set(&mut self, observer: Option<SharedGridObserver>) {
+        self.0 = observer;
+    }
+
+    /// Prévient l'observateur d'un changement de valeur d'une case
+    pub fn notify(&self, line_column: LineColumn, old: &CellValue, new: &CellValue) {
+        if let Some(observer) = &self.0 {
+            observer
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .on_change(line_column, old.clone(), new.clone());
+        }
+    }
+}
+
+impl Clone for ObserverSlot {
+    fn clone(&self) -> Self {
+        Self(None)
+    }
+}
+
+impl std::fmt::Debug for ObserverSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ObserverSlot({})", self.0.is_some())
+    }
+}
+
+impl PartialEq for ObserverSlot {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ObserverSlot {}
+
+impl std::hash::Hash for ObserverSlot {
+    fn hash<H: std::hash::Hasher>(&self, _state: &mut H) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridAction;
+
+    /// Observateur qui mémorise toutes les modifications
+    #[derive(Default)]
+    struct Logger {
+        changes: Vec<(LineColumn, CellValue, CellValue)>,
+    }
+
+    impl GridObserver for Logger {
+        fn on_change(&mut self, line_column: LineColumn, old: CellValue, new: CellValue) {
+            self.changes.push((line_column, old, new));
+        }
+    }
+
+    #[test]
+    fn test_observer() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let logger = Arc::new(Mutex::new(Logger::default()));
+        grid.set_observer(Some(logger.clone()));
+
+        let a1 = LineColumn::new(0, 0);
+        grid.apply_action(&GridAction::SetStar(a1)).unwrap();
+        // Pas de changement de valeur : l'observateur n'est pas prévenu
+        grid.apply_action(&GridAction::SetStar(a1)).unwrap();
+        assert_eq!(
+            logger.lock().unwrap().changes,
+            vec![(a1, CellValue::Unknown, CellValue::Star)]
+        );
+
+        // Les modifications annulées par `apply_actions_checked` sont aussi signalées
+        let b1 = LineColumn::new(0, 1);
+        assert!(grid
+            .apply_actions_checked(&grid_handler, &[GridAction::SetStar(b1)])
+            .is_err());
+        assert_eq!(logger.lock().unwrap().changes.len(), 3);
+        assert_eq!(
+            logger.lock().unwrap().changes[2],
+            (b1, CellValue::Star, CellValue::Unknown)
+        );
+
+        // Une copie de la grille n'est pas observée
+        let mut grid_copy = grid.clone();
+        assert_eq!(grid_copy, grid);
+        grid_copy.apply_action(&GridAction::SetNoStar(b1)).unwrap();
+        assert_eq!(logger.lock().unwrap().changes.len(), 3);
+
+        // Observateur détaché
+        grid.set_observer(None);
+        grid.apply_action(&GridAction::SetNoStar(b1)).unwrap();
+        assert_eq!(logger.lock().unwrap().changes.len(), 3);
+    }
+}
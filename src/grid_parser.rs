@@ -4,7 +4,15 @@
 //!
 //! Dans ce fichier, chaque ligne de texte correspond à une ligne de la grille. Les différentes régions sont identifiées par une 'lettre' distincte dans la case correspondante.
 //!
-//! Les lignes 'vides' ou qui commencent par l'un des caractères suivants sont ignorées : '*', '#', '/' (considérées comme d'éventuels commentaires dans le fichier).
+//! Les lignes 'vides' ou qui commencent par l'un des caractères suivants sont ignorées : '#', ';', '@' (considérées comme d'éventuels commentaires dans le fichier).
+//! Les commentaires de la forme `# clé: valeur` définissent les métadonnées de la grille (voir [`PuzzleMetadata`]).
+//!
+//! Une ligne qui commence par '* ' liste les étoiles 'données' par la grille (indices immuables), selon leurs
+//! coordonnées séparées par des espaces (par exemple `* A1 C3` pour des étoiles dans la case de la colonne 'A'
+//! de la ligne 1 et dans la case de la colonne 'C' de la ligne 3).
+//!
+//! Le caractère '.' identifie une case 'bloquée' (trou ou case décorative) : elle n'appartient à aucune région,
+//! ligne ou colonne et ne peut pas contenir d'étoile.
 //!
 //! Chaque ligne 'utile' de ce fichier doit définir le même nombre de cases. Elles doivent donc toutes avoir la même longueur.
 //!
@@ -20,16 +28,24 @@
 //! ```
 
 use std::collections::HashSet;
+use std::fmt::Display;
 
 use crate::CellValue;
 use crate::GridCell;
 use crate::GridParserChecker;
 use crate::LineColumn;
+use crate::PuzzleMetadata;
 use crate::Region;
 
 /// Caractères de commentaire au début d'une ligne du fichier pour une grille à résoudre
 pub const COMMENT_CHARS: [char; 3] = ['#', ';', '@'];
 
+/// Caractère d'une case 'bloquée' qui n'appartient à aucune zone et ne peut pas contenir d'étoile
+pub const BLOCKED_CELL_CHAR: char = '.';
+
+/// Début d'une ligne du fichier qui liste les étoiles données par la grille
+pub const GIVEN_STARS_PREFIX: &str = "* ";
+
 /// Caractères non admissibles comme symboles d'une région
 const ILLEGAL_REGION_CHARS: [char; 4] = [' ', '\t', '\n', '\r'];
 
@@ -43,12 +59,23 @@ struct ParsedGrid(Vec<ParsedLine>);
 
 /// Grid parser
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SerializedGridParser", into = "SerializedGridParser")
+)]
 pub struct GridParser {
     /// Symboles identifiés comme 'région' dans la grille
     regions: HashSet<Region>,
 
     /// Grille parsée
     parsed_grid: ParsedGrid,
+
+    /// Étoiles données par la grille
+    given_stars: Vec<LineColumn>,
+
+    /// Métadonnées de la grille (titre, auteur, source...)
+    metadata: PuzzleMetadata,
 }
 
 impl TryFrom<&Vec<String>> for GridParser {
@@ -59,8 +86,14 @@ impl TryFrom<&Vec<String>> for GridParser {
         // Parsing des lignes de la définition de la grille
         for (num_line, text_line) in value.iter().enumerate() {
             let text_line = text_line.trim();
-            if !text_line.is_empty() && !text_line.starts_with(COMMENT_CHARS) {
-                if let Err(e) = grid_parsed.parse_text_line(text_line) {
+            if text_line.starts_with(COMMENT_CHARS) {
+                grid_parsed.metadata.parse_comment(text_line);
+            } else if !text_line.is_empty() {
+                let result = match text_line.strip_prefix(GIVEN_STARS_PREFIX) {
+                    Some(given_stars) => grid_parsed.parse_given_stars(given_stars),
+                    None => grid_parsed.parse_text_line(text_line),
+                };
+                if let Err(e) = result {
                     return Err(format!(
                         "Erreur à la ligne #{} '{}': {}",
                         num_line + 1,
@@ -76,6 +109,23 @@ impl TryFrom<&Vec<String>> for GridParser {
             return Err("La grille n'a aucune région définie".to_string());
         }
 
+        // Les étoiles données doivent être dans des cases de la grille
+        for line_column in grid_parsed.given_stars.clone() {
+            match grid_parsed.cell(line_column) {
+                Some(cell) if cell.region != BLOCKED_CELL_CHAR => {
+                    let cell =
+                        &mut grid_parsed.parsed_grid.0[line_column.line].0[line_column.column];
+                    cell.value = CellValue::Star;
+                    cell.given = true;
+                }
+                _ => {
+                    return Err(format!(
+                        "L'étoile donnée {line_column} n'est pas une case valide de la grille"
+                    ))
+                }
+            }
+        }
+
         // Contrôle de la grille parsée
         let checker = GridParserChecker::new(grid_parsed.clone());
         checker.check()?;
@@ -92,6 +142,55 @@ impl TryFrom<Vec<String>> for GridParser {
     }
 }
 
+/// Représentation sérialisée d'un [`GridParser`] : une chaîne par ligne de la grille, étoiles données et
+/// métadonnées.<br>
+/// La grille est parsée et contrôlée à la désérialisation comme une définition 'textuelle'
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct SerializedGridParser {
+    /// Régions des cases : une chaîne par ligne, un caractère par case
+    pub(crate) lines: Vec<String>,
+
+    /// Étoiles données par la grille
+    #[serde(default)]
+    pub(crate) given_stars: Vec<LineColumn>,
+
+    /// Métadonnées de la grille
+    #[serde(default)]
+    pub(crate) metadata: PuzzleMetadata,
+}
+
+#[cfg(feature = "serde")]
+impl From<GridParser> for SerializedGridParser {
+    fn from(parser: GridParser) -> Self {
+        Self {
+            lines: parser
+                .parsed_grid
+                .0
+                .iter()
+                .map(|line| line.0.iter().map(|cell| cell.region).collect())
+                .collect(),
+            given_stars: parser.given_stars,
+            metadata: parser.metadata,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedGridParser> for GridParser {
+    type Error = String;
+
+    fn try_from(value: SerializedGridParser) -> Result<Self, Self::Error> {
+        let mut lines = value.lines;
+        if !value.given_stars.is_empty() {
+            let given_stars: Vec<String> =
+                value.given_stars.iter().map(ToString::to_string).collect();
+            lines.push(format!("{GIVEN_STARS_PREFIX}{}", given_stars.join(" ")));
+        }
+        Ok(Self::try_from(lines)?.with_metadata(value.metadata))
+    }
+}
+
 impl TryFrom<&[String]> for GridParser {
     type Error = String;
 
@@ -131,10 +230,13 @@ impl GridParser {
         self.parsed_grid.0[0].0.len()
     }
 
-    /// Liste des régions de la grille parsée
+    /// Liste des régions de la grille parsée (hors cases bloquées)
     #[must_use]
     pub fn regions(&self) -> Vec<Region> {
-        self.regions.iter().copied().collect()
+        // Tri pour que l'ordre des régions ne dépende pas du `HashSet`
+        let mut regions: Vec<Region> = self.regions.iter().copied().collect();
+        regions.sort_unstable();
+        regions
     }
 
     /// Retourne la case de la grille en (line, column) (si existe)
@@ -153,6 +255,25 @@ impl GridParser {
         self.parsed_grid.0[line_column.line].0[line_column.column].region
     }
 
+    /// Liste des étoiles données par la grille
+    #[must_use]
+    pub fn given_stars(&self) -> Vec<LineColumn> {
+        self.given_stars.clone()
+    }
+
+    /// Métadonnées de la grille (voir [`PuzzleMetadata`])
+    #[must_use]
+    pub const fn metadata(&self) -> &PuzzleMetadata {
+        &self.metadata
+    }
+
+    /// Définit les métadonnées de la grille
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: PuzzleMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     /// Liste des cases d'une grille parsée
     #[must_use]
     pub fn list_cells(&self) -> Vec<GridCell> {
@@ -188,11 +309,14 @@ impl GridParser {
                     "Le caractère '{region}' n'est pas valide pour identifier une région"
                 ));
             }
-            self.regions.insert(region);
+            if region != BLOCKED_CELL_CHAR {
+                self.regions.insert(region);
+            }
             let cur_cell = GridCell {
                 line_column: LineColumn::from((line, column)),
                 region,
                 value: CellValue::Unknown,
+                given: false,
             };
             line_parsed.0.push(cur_cell);
         }
@@ -206,6 +330,45 @@ impl GridParser {
         self.parsed_grid.0.push(line_parsed);
         Ok(())
     }
+
+    /// Analyse la liste textuelle des coordonnées des étoiles données par la grille (`A1 C3` par exemple)
+    fn parse_given_stars(&mut self, text_stars: &str) -> Result<(), String> {
+        for text_star in text_stars.split_whitespace() {
+            let Ok(line_column) = text_star.parse::<LineColumn>() else {
+                return Err(format!(
+                    "'{text_star}' n'est pas une coordonnée valide pour une étoile donnée"
+                ));
+            };
+            if !self.given_stars.contains(&line_column) {
+                self.given_stars.push(line_column);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for GridParser {
+    /// Définition 'textuelle' de la grille : les métadonnées puis une ligne de texte par ligne de la grille
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.metadata.is_empty() {
+            writeln!(f, "{}", self.metadata)?;
+        }
+        for (line, line_parsed) in self.parsed_grid.0.iter().enumerate() {
+            if line > 0 {
+                writeln!(f)?;
+            }
+            for cell in &line_parsed.0 {
+                write!(f, "{}", cell.region)?;
+            }
+        }
+        if !self.given_stars.is_empty() {
+            write!(f, "\n{}", GIVEN_STARS_PREFIX.trim_end())?;
+            for line_column in &self.given_stars {
+                write!(f, " {line_column}")?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -271,7 +434,7 @@ mod tests {
     }
 
     // Toutes les grilles suivantes sont invalides
-    const INVALID_GRIDS: [&str; 4] = [
+    const INVALID_GRIDS: [&str; 7] = [
         "
             # Grille invalide: Vide de toute définition
             # Manque des définitions de symboles
@@ -292,8 +455,111 @@ mod tests {
             BBA
             AAB
         ",
+        "
+            # Grille invalide: Coordonnée non admissible pour une étoile donnée
+            AAA
+            BBB
+            CCC
+            * 1A
+        ",
+        "
+            # Grille invalide: Étoile donnée en dehors de la grille
+            AAA
+            BBB
+            CCC
+            * A4
+        ",
+        "
+            # Grille invalide: Étoile donnée dans une case bloquée
+            A.A
+            BBB
+            CCC
+            * B1
+        ",
     ];
 
+    #[test]
+    fn test_display() {
+        let grid = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let text = grid.to_string();
+        assert_eq!(text, "ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED");
+        assert_eq!(
+            GridParser::try_from(text.as_str()).unwrap().list_cells(),
+            grid.list_cells()
+        );
+    }
+
+    #[test]
+    fn test_given_stars() {
+        let grid = GridParser::try_from(vec![
+            "* A1",
+            "ABBBB",
+            "ABBBB",
+            "CCBBB",
+            "DDDDD",
+            "DEEED",
+            "* C3 A1 E4",
+        ])
+        .unwrap();
+        let given_stars = vec![
+            LineColumn::new(0, 0),
+            LineColumn::new(2, 2),
+            LineColumn::new(3, 4),
+        ];
+        assert_eq!(grid.given_stars(), given_stars);
+        assert!(grid.cell(LineColumn::new(2, 2)).unwrap().is_given());
+        assert_eq!(
+            grid.cell(LineColumn::new(2, 2)).unwrap().value,
+            CellValue::Star
+        );
+        assert!(!grid.cell(LineColumn::new(2, 3)).unwrap().is_given());
+
+        // Les étoiles données sont conservées par l'affichage 'textuel' de la grille
+        let text = grid.to_string();
+        assert_eq!(text, "ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED\n* A1 C3 E4");
+        assert_eq!(
+            GridParser::try_from(text.as_str()).unwrap().given_stars(),
+            given_stars
+        );
+
+        // Étoile donnée au-delà de la colonne 'Z'
+        let wide_line = "A".repeat(30);
+        let mut lines = vec![wide_line.as_str(); 30];
+        lines.push("* AB2");
+        let grid = GridParser::try_from(lines).unwrap();
+        assert_eq!(grid.given_stars(), vec![LineColumn::new(1, 27)]);
+        assert!(grid.to_string().ends_with("\n* AB2"));
+    }
+
+    #[test]
+    fn test_metadata() {
+        let grid = GridParser::try_from(vec![
+            "# Exemple de grille",
+            "# title: Grille du jour",
+            "# author: ddurler",
+            "ABBBB",
+            "ABBBB",
+            "CCBBB",
+            "DDDDD",
+            "DEEED",
+        ])
+        .unwrap();
+        assert_eq!(grid.metadata().title.as_deref(), Some("Grille du jour"));
+        assert_eq!(grid.metadata().author.as_deref(), Some("ddurler"));
+        assert!(grid.metadata().source.is_none());
+
+        // Les métadonnées sont conservées par l'affichage 'textuel' de la grille
+        let text = grid.to_string();
+        assert_eq!(
+            text,
+            "# title: Grille du jour\n# author: ddurler\nABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED"
+        );
+        assert_eq!(
+            GridParser::try_from(text.as_str()).unwrap().metadata(),
+            grid.metadata()
+        );
+    }
+
     #[test]
     fn test_try_from_nok() {
         for s in INVALID_GRIDS {
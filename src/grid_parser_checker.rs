@@ -32,12 +32,11 @@ impl GridParserChecker {
     fn region_ok(&self, region: Region) -> bool {
         // Liste des cases de la région
         let all_region_cells = self.parser.region_cells(region);
-        if all_region_cells.is_empty() {
-            return false;
-        }
 
         // Première case de la region
-        let first_cell = all_region_cells[0].clone();
+        let Some(first_cell) = all_region_cells.first().cloned() else {
+            return false;
+        };
 
         // On construit la liste de toutes les cases adjacentes à cette 'first_cell'
         // Pour cela, on a une liste des cases à parcourir qu'on initialise avec first_cell et qu'on
@@ -70,30 +69,30 @@ impl GridParserChecker {
 
     // Liste des case adjacentes à une case
     fn adjacent_cells(&self, cell: &GridCell) -> Vec<GridCell> {
-        let mut cells = vec![];
+        let mut adjacent_line_columns = vec![];
         let (line, column) = (cell.line_column.line, cell.line_column.column);
 
         // North ?
         if line > 0 {
-            cells.push(self.parser.cell(LineColumn::new(line - 1, column)).unwrap());
+            adjacent_line_columns.push(LineColumn::new(line - 1, column));
         }
 
         // South ?
-        if line < self.parser.nb_lines() - 1 {
-            cells.push(self.parser.cell(LineColumn::new(line + 1, column)).unwrap());
-        }
+        adjacent_line_columns.push(LineColumn::new(line + 1, column));
 
         // West ?
         if column > 0 {
-            cells.push(self.parser.cell(LineColumn::new(line, column - 1)).unwrap());
+            adjacent_line_columns.push(LineColumn::new(line, column - 1));
         }
 
         // East ?
-        if column < self.parser.nb_columns() - 1 {
-            cells.push(self.parser.cell(LineColumn::new(line, column + 1)).unwrap());
-        }
+        adjacent_line_columns.push(LineColumn::new(line, column + 1));
 
-        cells
+        // Seules les cases de la grille sont retenues
+        adjacent_line_columns
+            .into_iter()
+            .filter_map(|line_column| self.parser.cell(line_column))
+            .collect()
     }
 
     /// Liste des cases adjacentes à la case (line, column) de la même région
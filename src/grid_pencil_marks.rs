@@ -0,0 +1,334 @@
+//! Annotations des cases candidates d'une grille ('pencil marks').
+//!
+//! Comme un joueur qui annote sa grille au crayon, [`PencilMarks`] mémorise pour chaque case les
+//! contraintes qui empêchent d'y placer une étoile ([`StarBlockers`]) : case déjà définie, étoile adjacente,
+//! ligne, colonne, région ou cage qui a déjà toutes ses étoiles.
+//!
+//! Les annotations sont tenues à jour au fur et à mesure des actions appliquées à la grille (voir
+//! [`crate::GridObserver`]) : les interroger ne nécessite pas de parcourir la grille, contrairement à
+//! [`GridHandler::star_candidates`].
+
+use std::ops::{BitOr, BitOrAssign};
+use std::sync::{Arc, Mutex};
+
+use crate::CellValue;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridObserver;
+use crate::GridSurfer;
+use crate::LineColumn;
+
+/// Contraintes qui empêchent de placer une étoile dans une case
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StarBlockers(u8);
+
+impl StarBlockers {
+    /// La case est déjà définie (étoile ou pas d'étoile)
+    pub const CELL: Self = Self(1);
+
+    /// Une case adjacente contient une étoile
+    pub const ADJACENT: Self = Self(1 << 1);
+
+    /// La ligne de la case a déjà toutes ses étoiles
+    pub const LINE: Self = Self(1 << 2);
+
+    /// La colonne de la case a déjà toutes ses étoiles
+    pub const COLUMN: Self = Self(1 << 3);
+
+    /// La région de la case a déjà toutes ses étoiles
+    pub const REGION: Self = Self(1 << 4);
+
+    /// Une cage de la case a déjà toutes ses étoiles
+    pub const CAGE: Self = Self(1 << 5);
+
+    /// Retourne `true` si aucune contrainte n'empêche de placer une étoile
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Retourne `true` si toutes les contraintes de `other` sont présentes
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for StarBlockers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for StarBlockers {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// Annotations des cases candidates d'une grille, tenues à jour par les actions appliquées à la grille
+#[derive(Debug)]
+pub struct PencilMarks {
+    /// Nombre de colonnes de la grille (pour l'indexation des cases)
+    nb_columns: usize,
+
+    /// Valeur courante des cases (par index)
+    values: Vec<CellValue>,
+
+    /// Cases adjacentes de chaque case (par index)
+    adjacents: Vec<Vec<usize>>,
+
+    /// Nombre d'étoiles adjacentes à chaque case (par index)
+    adjacent_stars: Vec<usize>,
+
+    /// Zones suivies : lignes, colonnes, régions (si elles sont une contrainte) et cages
+    zones: Vec<GridSurfer>,
+
+    /// Cases (par index) de chaque zone
+    zone_cells: Vec<Vec<usize>>,
+
+    /// Nombre d'étoiles attendues dans chaque zone
+    zone_quotas: Vec<usize>,
+
+    /// Nombre d'étoiles courant dans chaque zone
+    zone_stars: Vec<usize>,
+
+    /// Zones (par index) auxquelles appartient chaque case (par index)
+    cell_zones: Vec<Vec<usize>>,
+}
+
+impl PencilMarks {
+    /// Constructeur : annotations de l'état courant de la grille `grid`
+    #[must_use]
+    pub fn new(handler: &GridHandler, grid: &Grid) -> Self {
+        let nb_columns = handler.nb_columns();
+        let index = |line_column: LineColumn| line_column.line * nb_columns + line_column.column;
+        let nb_cells = handler.nb_lines() * nb_columns;
+
+        let mut values = Vec::with_capacity(nb_cells);
+        let mut adjacents = Vec::with_capacity(nb_cells);
+        for line in 0..handler.nb_lines() {
+            for column in 0..nb_columns {
+                let line_column = LineColumn::new(line, column);
+                values.push(grid.cell(line_column).value.clone());
+                adjacents.push(if handler.is_blocked(line_column) {
+                    Vec::new()
+                } else {
+                    handler
+                        .adjacent_cells(line_column)
+                        .into_iter()
+                        .map(index)
+                        .collect()
+                });
+            }
+        }
+        let adjacent_stars = adjacents
+            .iter()
+            .map(|cells: &Vec<usize>| {
+                cells
+                    .iter()
+                    .filter(|cell| values[**cell] == CellValue::Star)
+                    .count()
+            })
+            .collect();
+
+        let mut zones: Vec<GridSurfer> = (0..handler.nb_lines()).map(GridSurfer::Line).collect();
+        zones.extend((0..nb_columns).map(GridSurfer::Column));
+        if handler.has_regions() {
+            zones.extend(handler.regions().into_iter().map(GridSurfer::Region));
+        }
+        zones.extend(handler.cages());
+
+        let mut zone_cells = Vec::with_capacity(zones.len());
+        let mut zone_quotas = Vec::with_capacity(zones.len());
+        let mut zone_stars = Vec::with_capacity(zones.len());
+        let mut cell_zones = vec![Vec::new(); nb_cells];
+        for (num_zone, zone) in zones.iter().enumerate() {
+            let cells: Vec<usize> = handler.surfer(grid, zone).into_iter().map(index).collect();
+            for cell in &cells {
+                cell_zones[*cell].push(num_zone);
+            }
+            zone_quotas.push(handler.zone_nb_stars(zone));
+            zone_stars.push(
+                cells
+                    .iter()
+                    .filter(|cell| values[**cell] == CellValue::Star)
+                    .count(),
+            );
+            zone_cells.push(cells);
+        }
+
+        Self {
+            nb_columns,
+            values,
+            adjacents,
+            adjacent_stars,
+            zones,
+            zone_cells,
+            zone_quotas,
+            zone_stars,
+            cell_zones,
+        }
+    }
+
+    /// Annotations de l'état courant de la grille `grid`, attachées à la grille comme observateur (voir
+    /// [`Grid::set_observer`]) pour être tenues à jour par les actions appliquées à la grille
+    #[must_use]
+    pub fn attach(handler: &GridHandler, grid: &mut Grid) -> Arc<Mutex<Self>> {
+        let pencil_marks = Arc::new(Mutex::new(Self::new(handler, grid)));
+        grid.set_observer(Some(pencil_marks.clone()));
+        pencil_marks
+    }
+
+    /// Index d'une case
+    const fn index(&self, line_column: LineColumn) -> usize {
+        line_column.line * self.nb_columns + line_column.column
+    }
+
+    /// Case d'un index
+    const fn line_column(&self, index: usize) -> LineColumn {
+        LineColumn::new(index / self.nb_columns, index % self.nb_columns)
+    }
+
+    /// Contraintes qui empêchent de placer une étoile dans la case `line_column`
+    #[must_use]
+    pub fn blockers(&self, line_column: LineColumn) -> StarBlockers {
+        let index = self.index(line_column);
+        let mut blockers = StarBlockers::default();
+        if self.values[index] != CellValue::Unknown {
+            blockers |= StarBlockers::CELL;
+        }
+        if self.adjacent_stars[index] > 0 {
+            blockers |= StarBlockers::ADJACENT;
+        }
+        for num_zone in &self.cell_zones[index] {
+            if self.zone_stars[*num_zone] >= self.zone_quotas[*num_zone] {
+                blockers |= match self.zones[*num_zone] {
+                    GridSurfer::Line(_) => StarBlockers::LINE,
+                    GridSurfer::Column(_) => StarBlockers::COLUMN,
+                    GridSurfer::Region(_) => StarBlockers::REGION,
+                    _ => StarBlockers::CAGE,
+                };
+            }
+        }
+        blockers
+    }
+
+    /// Retourne `true` si une étoile peut encore être placée dans la case `line_column`
+    #[must_use]
+    pub fn is_star_possible(&self, line_column: LineColumn) -> bool {
+        self.blockers(line_column).is_empty()
+    }
+
+    /// Cases de la zone `zone` (ligne, colonne, région ou cage) qui peuvent encore contenir une étoile
+    /// (voir [`GridHandler::star_candidates`]).<br>
+    /// Retourne `None` si la zone n'est pas suivie par les annotations
+    #[must_use]
+    pub fn star_candidates(&self, zone: &GridSurfer) -> Option<Vec<LineColumn>> {
+        let num_zone = self.zones.iter().position(|other| other == zone)?;
+        if self.zone_stars[num_zone] >= self.zone_quotas[num_zone] {
+            return Some(Vec::new());
+        }
+        Some(
+            self.zone_cells[num_zone]
+                .iter()
+                .map(|index| self.line_column(*index))
+                .filter(|line_column| {
+                    let index = self.index(*line_column);
+                    self.values[index] == CellValue::Unknown && self.adjacent_stars[index] == 0
+                })
+                .collect(),
+        )
+    }
+}
+
+impl GridObserver for PencilMarks {
+    fn on_change(&mut self, line_column: LineColumn, old: CellValue, new: CellValue) {
+        let index = self.index(line_column);
+        let delta = |stars: &mut usize| {
+            if old == CellValue::Star {
+                *stars -= 1;
+            }
+            if new == CellValue::Star {
+                *stars += 1;
+            }
+        };
+        for adjacent in &self.adjacents[index] {
+            delta(&mut self.adjacent_stars[*adjacent]);
+        }
+        for num_zone in &self.cell_zones[index] {
+            delta(&mut self.zone_stars[*num_zone]);
+        }
+        self.values[index] = new;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::get_good_rule;
+    use crate::GridAction;
+
+    #[test]
+    fn test_pencil_marks() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let pencil_marks = PencilMarks::attach(&grid_handler, &mut grid);
+        let a1 = LineColumn::new(0, 0);
+        let b2 = LineColumn::new(1, 1);
+        let a3 = LineColumn::new(2, 0);
+        assert!(pencil_marks.lock().unwrap().is_star_possible(b2));
+
+        grid.apply_action(&GridAction::SetStar(a1)).unwrap();
+        {
+            let pencil_marks = pencil_marks.lock().unwrap();
+            assert_eq!(
+                pencil_marks.blockers(a1),
+                StarBlockers::CELL
+                    | StarBlockers::LINE
+                    | StarBlockers::COLUMN
+                    | StarBlockers::REGION
+            );
+            assert_eq!(pencil_marks.blockers(b2), StarBlockers::ADJACENT);
+            assert!(pencil_marks.blockers(a3).contains(StarBlockers::COLUMN));
+            assert_eq!(
+                pencil_marks.star_candidates(&GridSurfer::Region('A')),
+                Some(Vec::new())
+            );
+            assert_eq!(pencil_marks.star_candidates(&GridSurfer::AllCells), None);
+        }
+
+        // Retour en arrière
+        grid.apply_action(&GridAction::SetUnknown(a1)).unwrap();
+        assert!(pencil_marks.lock().unwrap().is_star_possible(b2));
+        assert!(pencil_marks.lock().unwrap().is_star_possible(a1));
+    }
+
+    #[test]
+    fn test_pencil_marks_follow_rules() {
+        // Les annotations restent identiques à `GridHandler::star_candidates` pendant la résolution
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let pencil_marks = PencilMarks::attach(&grid_handler, &mut grid);
+        let mut zones: Vec<GridSurfer> = grid_handler
+            .regions()
+            .into_iter()
+            .map(GridSurfer::Region)
+            .collect();
+        zones.extend((0..5).map(GridSurfer::Line));
+        zones.extend((0..5).map(GridSurfer::Column));
+        while let Some(good_rule) = get_good_rule(&grid_handler, &grid).unwrap() {
+            grid.apply_good_rule(&good_rule);
+            for zone in &zones {
+                assert_eq!(
+                    pencil_marks.lock().unwrap().star_candidates(zone),
+                    Some(grid_handler.star_candidates(&grid, zone))
+                );
+            }
+        }
+        assert!(grid_handler.is_done(&grid));
+    }
+}
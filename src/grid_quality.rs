@@ -0,0 +1,202 @@
+//! Évaluation de la 'qualité' d'une grille.
+//!
+//! Une grille intéressante à résoudre :
+//!
+//! * Nécessite des règles de construction variées (voir [`get_good_rule_with_level`](crate::get_good_rule_with_level))
+//! * N'a pas de région 'triviale' dont la forme seule impose la position des étoiles
+//! * A des régions de tailles comparables (pas de régions minuscules à côté de régions immenses)
+//!
+//! Chacun de ces critères est mesuré et un score global entre 0 et 100 permet de comparer les grilles.
+
+use crate::rate_grid;
+use crate::BadRuleError;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridSurfer;
+use crate::LineColumn;
+
+/// Poids de la diversité des règles dans le score de qualité
+const RULE_DIVERSITY_WEIGHT: f64 = 50.0;
+
+/// Nombre de niveaux de règles différents qui donne le score maximal de diversité.<br>
+/// Cette valeur est fixe pour que les scores (et les seuils de qualité minimum) ne changent pas quand une
+/// règle de construction est ajoutée
+const RULE_DIVERSITY_MAX_LEVELS: usize = 16;
+
+/// Poids de l'absence de régions triviales dans le score de qualité
+const NON_TRIVIAL_REGIONS_WEIGHT: f64 = 30.0;
+
+/// Poids de la régularité de la taille des régions dans le score de qualité
+const REGION_SIZE_WEIGHT: f64 = 20.0;
+
+/// Mesures de la qualité d'une grille
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PuzzleQuality {
+    /// Nombre de niveaux de règles de construction différents utilisés pour la résolution
+    pub nb_rule_levels: usize,
+
+    /// Nombre de régions dont la forme seule impose la position des étoiles
+    pub nb_trivial_regions: usize,
+
+    /// Variance du nombre de cases des régions
+    pub region_size_variance: f64,
+
+    /// Score global de qualité (entre 0 et 100)
+    pub score: f64,
+}
+
+/// Évalue la qualité d'une grille (à partir d'une grille vide)
+/// ### Errors
+/// Retourne un [`BadRuleError`] si la grille n'est pas valide
+#[allow(clippy::cast_precision_loss)]
+pub fn puzzle_quality(handler: &GridHandler) -> Result<PuzzleQuality, BadRuleError> {
+    let grid = Grid::from(handler);
+    let rating = rate_grid(handler, &grid)?;
+    let nb_rule_levels = rating
+        .nb_steps_by_level
+        .iter()
+        .filter(|nb_steps| **nb_steps > 0)
+        .count();
+
+    let regions = handler.regions();
+    let mut nb_trivial_regions = 0;
+    let mut region_sizes = Vec::with_capacity(regions.len());
+    for region in regions {
+        let cells = handler.region_cells(region);
+        if count_region_placements(cells, handler.zone_nb_stars(&GridSurfer::Region(region)), 2)
+            == 1
+        {
+            nb_trivial_regions += 1;
+        }
+        region_sizes.push(cells.len() as f64);
+    }
+    // Une grille sans contrainte de région n'a ni région triviale ni différence de taille des régions
+    let nb_regions = region_sizes.len().max(1) as f64;
+    let mean_size = region_sizes.iter().sum::<f64>() / nb_regions;
+    let region_size_variance = region_sizes
+        .iter()
+        .map(|size| (size - mean_size).powi(2))
+        .sum::<f64>()
+        / nb_regions;
+
+    // Coefficient de variation de la taille des régions
+    let size_variation = if mean_size > 0.0 {
+        region_size_variance.sqrt() / mean_size
+    } else {
+        0.0
+    };
+    let score = rule_diversity_score(nb_rule_levels)
+        + NON_TRIVIAL_REGIONS_WEIGHT * (1.0 - nb_trivial_regions as f64 / nb_regions)
+        + REGION_SIZE_WEIGHT / (1.0 + size_variation);
+
+    Ok(PuzzleQuality {
+        nb_rule_levels,
+        nb_trivial_regions,
+        region_size_variance,
+        score,
+    })
+}
+
+/// Part du score de qualité due à la diversité des `nb_rule_levels` niveaux de règles utilisés (entre 0 et
+/// [`RULE_DIVERSITY_WEIGHT`])
+#[allow(clippy::cast_precision_loss)]
+fn rule_diversity_score(nb_rule_levels: usize) -> f64 {
+    RULE_DIVERSITY_WEIGHT * nb_rule_levels.min(RULE_DIVERSITY_MAX_LEVELS) as f64
+        / RULE_DIVERSITY_MAX_LEVELS as f64
+}
+
+/// Compte (jusqu'à `max_count`) les façons de placer `nb_stars` étoiles non adjacentes parmi `cells`
+fn count_region_placements(cells: &[LineColumn], nb_stars: usize, max_count: usize) -> usize {
+    fn count_from(
+        cells: &[LineColumn],
+        from: usize,
+        nb_stars: usize,
+        stars: &mut Vec<LineColumn>,
+        max_count: usize,
+    ) -> usize {
+        if stars.len() == nb_stars {
+            return 1;
+        }
+        let mut count = 0;
+        for index in from..cells.len() {
+            let cell = cells[index];
+            if stars.iter().all(|star| {
+                star.line.abs_diff(cell.line) > 1 || star.column.abs_diff(cell.column) > 1
+            }) {
+                stars.push(cell);
+                count += count_from(cells, index + 1, nb_stars, stars, max_count - count);
+                stars.pop();
+                if count >= max_count {
+                    break;
+                }
+            }
+        }
+        count
+    }
+
+    count_from(
+        cells,
+        0,
+        nb_stars,
+        &mut Vec::with_capacity(nb_stars),
+        max_count,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridParser;
+
+    #[test]
+    fn test_count_region_placements() {
+        let line = |columns: &[usize]| -> Vec<LineColumn> {
+            columns
+                .iter()
+                .map(|column| LineColumn::new(0, *column))
+                .collect()
+        };
+        // 2 étoiles sur 3 cases alignées : une seule possibilité
+        assert_eq!(count_region_placements(&line(&[0, 1, 2]), 2, 10), 1);
+        // 2 étoiles sur 4 cases alignées : 3 possibilités
+        assert_eq!(count_region_placements(&line(&[0, 1, 2, 3]), 2, 10), 3);
+        assert_eq!(count_region_placements(&line(&[0, 1, 2, 3]), 2, 2), 2);
+        // 1 étoile sur 1 case
+        assert_eq!(count_region_placements(&line(&[4]), 1, 10), 1);
+    }
+
+    #[test]
+    fn test_rule_diversity_score() {
+        assert!(rule_diversity_score(0).abs() < f64::EPSILON);
+        assert!(
+            (rule_diversity_score(RULE_DIVERSITY_MAX_LEVELS / 2) - RULE_DIVERSITY_WEIGHT / 2.0)
+                .abs()
+                < 1e-9
+        );
+        // Le score ne dépend pas du nombre de règles de construction disponibles
+        assert!(
+            (rule_diversity_score(crate::NB_GOOD_RULE_LEVELS) - RULE_DIVERSITY_WEIGHT).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_puzzle_quality() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let handler = GridHandler::new(&parser, 1);
+        let quality = puzzle_quality(&handler).unwrap();
+        assert!(quality.nb_rule_levels >= 2);
+        assert_eq!(quality.nb_trivial_regions, 0);
+        assert!(quality.region_size_variance > 0.0);
+        assert!(quality.score > 0.0 && quality.score < 100.0);
+
+        // Une grille avec des régions d'une seule case est moins intéressante
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "BBBBB", "CCCCC", "DDDDD", "DDEDD"]).unwrap();
+        let handler = GridHandler::new(&parser, 1);
+        let bland = puzzle_quality(&handler).unwrap();
+        assert_eq!(bland.nb_trivial_regions, 2);
+        assert!(bland.score < quality.score);
+    }
+}
@@ -0,0 +1,249 @@
+//! Évaluation de la difficulté d'une grille.
+//!
+//! La grille est résolue avec les règles de construction (voir [`get_good_rule_with_level`]) et on
+//! cumule le niveau des règles utilisées à chaque étape : plus la résolution nécessite de règles
+//! complexes, plus la grille est difficile.<br>
+//! Les seuils de difficulté ont été calés sur les grilles d'exemple du répertoire `test_grids`.<br>
+//! L'évaluation indique aussi les techniques nécessaires à la résolution : nombre de règles de chaque
+//! catégorie ([`RuleKind`]), catégorie la plus difficile pour un humain et taille de la plus grande zone
+//! examinée par une règle.
+
+use std::fmt::Display;
+
+use crate::get_good_rule_with_level;
+use crate::BadRuleError;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridHandler;
+use crate::RuleKind;
+use crate::Technique;
+use crate::NB_GOOD_RULE_LEVELS;
+
+/// Score maximum d'une grille 'facile'
+const MAX_EASY_SCORE: usize = 40;
+
+/// Score maximum d'une grille 'moyenne'
+const MAX_MEDIUM_SCORE: usize = 55;
+
+/// Score maximum d'une grille 'difficile'
+const MAX_HARD_SCORE: usize = 70;
+
+/// Niveau de difficulté d'une grille
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Grille facile
+    Easy,
+
+    /// Grille moyenne
+    Medium,
+
+    /// Grille difficile
+    Hard,
+
+    /// Grille experte
+    Expert,
+}
+
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Easy => write!(f, "facile"),
+            Self::Medium => write!(f, "moyen"),
+            Self::Hard => write!(f, "difficile"),
+            Self::Expert => write!(f, "expert"),
+        }
+    }
+}
+
+/// Résultat de l'évaluation de la difficulté d'une grille
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GridRating {
+    /// Nombre de règles de construction appliquées
+    pub nb_steps: usize,
+
+    /// Niveau de la règle la plus complexe utilisée
+    pub max_level: usize,
+
+    /// Score de difficulté (somme des niveaux des règles appliquées)
+    pub score: usize,
+
+    /// Nombre de règles appliquées pour chaque niveau
+    pub nb_steps_by_level: [usize; NB_GOOD_RULE_LEVELS],
+
+    /// Nombre de règles appliquées pour chaque catégorie de règle (dans l'ordre de la première utilisation)
+    pub nb_steps_by_kind: Vec<(RuleKind, usize)>,
+
+    /// Catégorie de règle appliquée la plus difficile pour un humain (voir [`RuleKind::difficulty`])
+    pub hardest_kind: Option<RuleKind>,
+
+    /// Nombre de cases de la plus grande zone examinée par une règle appliquée
+    pub max_zone_size: usize,
+
+    /// Grille entièrement résolue par les règles de construction
+    pub solved: bool,
+}
+
+impl GridRating {
+    /// Ajoute à l'évaluation la règle `good_rule` de niveau `level` appliquée à la grille `grid`
+    pub(crate) fn add_step(
+        &mut self,
+        handler: &GridHandler,
+        grid: &Grid,
+        good_rule: &GoodRule,
+        level: usize,
+    ) {
+        self.nb_steps += 1;
+        self.max_level = self.max_level.max(level);
+        self.score += level;
+        self.nb_steps_by_level[level] += 1;
+
+        let kind = good_rule.kind();
+        match self
+            .nb_steps_by_kind
+            .iter_mut()
+            .find(|(other, _)| *other == kind)
+        {
+            Some((_, nb_steps)) => *nb_steps += 1,
+            None => self.nb_steps_by_kind.push((kind, 1)),
+        }
+        if self
+            .hardest_kind
+            .is_none_or(|hardest| kind.difficulty() > hardest.difficulty())
+        {
+            self.hardest_kind = Some(kind);
+        }
+        if let Some(zone) = good_rule.zone() {
+            self.max_zone_size = self.max_zone_size.max(handler.surfer(grid, zone).len());
+        }
+    }
+
+    /// Niveau de difficulté de la grille (`None` si la grille n'est pas résolue par les règles de
+    /// construction)
+    #[must_use]
+    pub const fn difficulty(&self) -> Option<Difficulty> {
+        if !self.solved {
+            None
+        } else if self.score <= MAX_EASY_SCORE {
+            Some(Difficulty::Easy)
+        } else if self.score <= MAX_MEDIUM_SCORE {
+            Some(Difficulty::Medium)
+        } else if self.score <= MAX_HARD_SCORE {
+            Some(Difficulty::Hard)
+        } else {
+            Some(Difficulty::Expert)
+        }
+    }
+
+    /// Palier de technique le plus élevé des règles appliquées (`None` si aucune règle appliquée)
+    #[must_use]
+    pub fn hardest_technique(&self) -> Option<Technique> {
+        self.nb_steps_by_kind
+            .iter()
+            .map(|(kind, _)| kind.technique())
+            .max()
+    }
+}
+
+impl Display for GridRating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.difficulty() {
+            Some(difficulty) => writeln!(f, "Difficulté : {difficulty} (score {})", self.score)?,
+            None => writeln!(
+                f,
+                "Difficulté : grille non résolue par les règles (score {})",
+                self.score
+            )?,
+        }
+        writeln!(
+            f,
+            "  Règles appliquées      : {} (niveau maximum {})",
+            self.nb_steps, self.max_level
+        )?;
+        let kinds = self
+            .nb_steps_by_kind
+            .iter()
+            .map(|(kind, nb_steps)| format!("{kind}:{nb_steps}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(f, "  Catégories de règles   : {kinds}")?;
+        if let Some(hardest_kind) = self.hardest_kind {
+            writeln!(
+                f,
+                "  Règle plus difficile  : {hardest_kind} ({})",
+                hardest_kind.difficulty()
+            )?;
+        }
+        write!(f, "  Plus grande zone       : {} cases", self.max_zone_size)
+    }
+}
+
+/// Évalue la difficulté de la résolution d'une grille à partir de son état `grid`
+/// ### Errors
+/// Retourne un [`BadRuleError`] si la grille n'est pas valide
+pub fn rate_grid(handler: &GridHandler, grid: &Grid) -> Result<GridRating, BadRuleError> {
+    let mut grid = grid.clone();
+    let mut rating = GridRating::default();
+    while let Some((good_rule, level)) = get_good_rule_with_level(handler, &grid)? {
+        rating.add_step(handler, &grid, &good_rule, level);
+        grid.apply_good_rule_checked(handler, &good_rule)?;
+    }
+    rating.solved = handler.is_done(&grid);
+    Ok(rating)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridParser;
+
+    fn rate_file(file_name: &str, nb_stars: usize) -> GridRating {
+        let contents = std::fs::read_to_string(file_name).unwrap();
+        let parser = GridParser::try_from(contents.as_str()).unwrap();
+        let handler = GridHandler::new(&parser, nb_stars);
+        rate_grid(&handler, &Grid::from(&handler)).unwrap()
+    }
+
+    #[test]
+    fn test_rate_grid() {
+        let rating = rate_file("./test_grids/test01.txt", 1);
+        assert!(rating.solved);
+        assert_eq!(rating.difficulty(), Some(Difficulty::Easy));
+
+        let easy = rate_file("./test_grids/facile02_2.txt", 2);
+        let expert = rate_file("./test_grids/expert01_2.txt", 2);
+        assert_eq!(easy.difficulty(), Some(Difficulty::Easy));
+        assert_eq!(expert.difficulty(), Some(Difficulty::Expert));
+        assert!(easy.max_level < expert.max_level);
+        assert_eq!(
+            expert.nb_steps_by_level.iter().sum::<usize>(),
+            expert.nb_steps
+        );
+        assert_eq!(
+            expert
+                .nb_steps_by_kind
+                .iter()
+                .map(|(_, nb_steps)| nb_steps)
+                .sum::<usize>(),
+            expert.nb_steps
+        );
+        assert_eq!(expert.hardest_kind, Some(RuleKind::InvariantWithZone));
+        assert_eq!(expert.hardest_technique(), Some(Technique::Advanced));
+        assert!(easy.max_zone_size <= expert.max_zone_size);
+        assert!(expert
+            .to_string()
+            .starts_with(&format!("Difficulté : expert (score {})", expert.score)));
+    }
+
+    #[test]
+    fn test_unsolved_rating() {
+        let rating = GridRating {
+            nb_steps: 3,
+            max_level: 2,
+            score: 4,
+            solved: false,
+            ..GridRating::default()
+        };
+        assert_eq!(rating.difficulty(), None);
+    }
+}
@@ -0,0 +1,258 @@
+//! Notation compacte 'SBN' des sites de Star Battle.
+//!
+//! Les sites de Star Battle (puzz.link par exemple) échangent une grille sous la forme
+//! `<colonnes>/<lignes>/<étoiles>/<bords>` où les régions sont codées par les bords qui séparent deux cases
+//! voisines de régions différentes :
+//! - d'abord les bords verticaux, ligne par ligne, entre chaque case et sa voisine de droite ;
+//! - puis les bords horizontaux, ligne par ligne, entre chaque case et sa voisine du dessous.
+//!
+//! Chaque série de bords est regroupée par 5 (le premier bord sur le bit de poids fort, 0 en complément de
+//! la dernière série) et chaque groupe est écrit en base 32 (`0`-`9` puis `a`-`v`).
+//!
+//! Une URL complète (`https://puzz.link/p?starbattle/5/5/1/...`) est acceptée : seule la partie qui suit
+//! `starbattle/` est analysée.
+
+use crate::grid_generator::{region_char, REGION_CHARS};
+use crate::grid_parser::BLOCKED_CELL_CHAR;
+use crate::GridParser;
+use crate::LineColumn;
+use crate::PuzzleMetadata;
+
+/// Préfixe d'une grille dans une URL des sites de Star Battle
+const SBN_URL_PREFIX: &str = "starbattle/";
+
+/// Nombre de bords codés par un caractère de la notation
+const SBN_BITS_PER_CHAR: usize = 5;
+
+/// Base du codage des bords
+const SBN_RADIX: u32 = 32;
+
+/// Nombre maximum de lignes ou de colonnes d'une grille en notation SBN
+const SBN_MAX_SIZE: usize = 100;
+
+impl GridParser {
+    /// Définition d'une grille à partir de sa notation 'SBN' (voir le module `grid_sbn`).<br>
+    /// Le nombre d'étoiles de la notation est conservé dans les métadonnées de la grille
+    /// ([`PuzzleMetadata::nb_stars`])
+    /// ### Errors
+    /// Retourne une erreur si la notation est mal formée, si la grille a plus de 100 lignes ou colonnes ou
+    /// si la grille obtenue est invalide
+    pub fn try_from_sbn(sbn: &str) -> Result<Self, String> {
+        let sbn = sbn.trim();
+        let sbn = sbn
+            .rfind(SBN_URL_PREFIX)
+            .map_or(sbn, |index| &sbn[index + SBN_URL_PREFIX.len()..]);
+        let fields: Vec<&str> = sbn.split('/').collect();
+        if fields.len() < 4 {
+            return Err(format!(
+                "Notation SBN '{sbn}' incomplète : <colonnes>/<lignes>/<étoiles>/<bords> attendu"
+            ));
+        }
+        let parse_number = |field: &str, name: &str| {
+            field
+                .parse::<usize>()
+                .map_err(|_| format!("Nombre de {name} '{field}' invalide dans la notation SBN"))
+        };
+        let nb_columns = parse_number(fields[0], "colonnes")?;
+        let nb_lines = parse_number(fields[1], "lignes")?;
+        let nb_stars = parse_number(fields[2], "étoiles")?;
+        if nb_lines == 0 || nb_columns == 0 {
+            return Err("La notation SBN définit une grille vide".to_string());
+        }
+        if nb_lines > SBN_MAX_SIZE || nb_columns > SBN_MAX_SIZE {
+            return Err(format!(
+                "Grille {nb_columns}x{nb_lines} trop grande dans la notation SBN (max. {SBN_MAX_SIZE})"
+            ));
+        }
+
+        // Bords verticaux puis horizontaux
+        let too_many_borders = || "Trop de bords dans la notation SBN".to_string();
+        let nb_vertical_borders = nb_lines
+            .checked_mul(nb_columns - 1)
+            .ok_or_else(too_many_borders)?;
+        let nb_horizontal_borders = (nb_lines - 1)
+            .checked_mul(nb_columns)
+            .ok_or_else(too_many_borders)?;
+        let nb_chars = nb_vertical_borders.div_ceil(SBN_BITS_PER_CHAR)
+            + nb_horizontal_borders.div_ceil(SBN_BITS_PER_CHAR);
+        if fields[3].chars().count() != nb_chars {
+            return Err(format!(
+                "{nb_chars} caractères attendus pour les bords de la notation SBN au lieu de {}",
+                fields[3].chars().count()
+            ));
+        }
+        let mut chars = fields[3].chars();
+        let vertical_borders = decode_borders(&mut chars, nb_vertical_borders)?;
+        let horizontal_borders = decode_borders(&mut chars, nb_horizontal_borders)?;
+        let has_right_border =
+            |line: usize, column: usize| vertical_borders[line * (nb_columns - 1) + column];
+        let has_bottom_border =
+            |line: usize, column: usize| horizontal_borders[line * nb_columns + column];
+
+        // Régions : cases reliées sans franchir de bord, dans l'ordre de leur première case
+        let mut regions: Vec<Vec<Option<usize>>> = vec![vec![None; nb_columns]; nb_lines];
+        let mut nb_regions = 0;
+        for line in 0..nb_lines {
+            for column in 0..nb_columns {
+                if regions[line][column].is_some() {
+                    continue;
+                }
+                if nb_regions >= REGION_CHARS.len() {
+                    return Err(format!(
+                        "Trop de régions dans la notation SBN (max. {})",
+                        REGION_CHARS.len()
+                    ));
+                }
+                regions[line][column] = Some(nb_regions);
+                let mut to_visit = vec![LineColumn::new(line, column)];
+                while let Some(cell) = to_visit.pop() {
+                    let (l, c) = (cell.line, cell.column);
+                    let mut neighbors = Vec::with_capacity(4);
+                    if c + 1 < nb_columns && !has_right_border(l, c) {
+                        neighbors.push(LineColumn::new(l, c + 1));
+                    }
+                    if c > 0 && !has_right_border(l, c - 1) {
+                        neighbors.push(LineColumn::new(l, c - 1));
+                    }
+                    if l + 1 < nb_lines && !has_bottom_border(l, c) {
+                        neighbors.push(LineColumn::new(l + 1, c));
+                    }
+                    if l > 0 && !has_bottom_border(l - 1, c) {
+                        neighbors.push(LineColumn::new(l - 1, c));
+                    }
+                    for neighbor in neighbors {
+                        let region = &mut regions[neighbor.line][neighbor.column];
+                        if region.is_none() {
+                            *region = Some(nb_regions);
+                            to_visit.push(neighbor);
+                        }
+                    }
+                }
+                nb_regions += 1;
+            }
+        }
+
+        let lines: Vec<String> = regions
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|region| region_char(region.expect("Toutes les cases ont une région")))
+                    .collect()
+            })
+            .collect();
+        let metadata = PuzzleMetadata {
+            nb_stars: Some(nb_stars),
+            ..PuzzleMetadata::default()
+        };
+        Ok(Self::try_from(lines)?.with_metadata(metadata))
+    }
+
+    /// Notation 'SBN' de la grille avec `nb_stars` étoiles (voir le module `grid_sbn`).<br>
+    /// Les étoiles données par la grille ne font pas partie de la notation
+    /// ### Errors
+    /// Retourne une erreur si la grille contient des cases bloquées (non représentables dans la notation)
+    pub fn to_sbn(&self, nb_stars: usize) -> Result<String, String> {
+        let (nb_lines, nb_columns) = (self.nb_lines(), self.nb_columns());
+        if self
+            .list_cells()
+            .iter()
+            .any(|cell| cell.region == BLOCKED_CELL_CHAR)
+        {
+            return Err(
+                "Les cases bloquées ne sont pas représentables en notation SBN".to_string(),
+            );
+        }
+        let region = |line: usize, column: usize| self.cell_region(LineColumn::new(line, column));
+        let vertical_borders: Vec<bool> = (0..nb_lines)
+            .flat_map(|line| {
+                (0..nb_columns - 1)
+                    .map(move |column| region(line, column) != region(line, column + 1))
+            })
+            .collect();
+        let horizontal_borders: Vec<bool> = (0..nb_lines - 1)
+            .flat_map(|line| {
+                (0..nb_columns).map(move |column| region(line, column) != region(line + 1, column))
+            })
+            .collect();
+        Ok(format!(
+            "{nb_columns}/{nb_lines}/{nb_stars}/{}{}",
+            encode_borders(&vertical_borders),
+            encode_borders(&horizontal_borders)
+        ))
+    }
+}
+
+/// Décode `nb_borders` bords à partir des caractères `chars` de la notation
+fn decode_borders(chars: &mut std::str::Chars, nb_borders: usize) -> Result<Vec<bool>, String> {
+    let mut borders = Vec::with_capacity(nb_borders);
+    while borders.len() < nb_borders {
+        let c = chars
+            .next()
+            .ok_or_else(|| "Bords manquants dans la notation SBN".to_string())?;
+        let value = c
+            .to_digit(SBN_RADIX)
+            .ok_or_else(|| format!("Caractère '{c}' invalide dans les bords de la notation SBN"))?;
+        for bit in (0..SBN_BITS_PER_CHAR).rev() {
+            if borders.len() < nb_borders {
+                borders.push(value & (1 << bit) != 0);
+            }
+        }
+    }
+    Ok(borders)
+}
+
+/// Code des bords en caractères de la notation
+fn encode_borders(borders: &[bool]) -> String {
+    borders
+        .chunks(SBN_BITS_PER_CHAR)
+        .map(|chunk| {
+            let value = chunk
+                .iter()
+                .enumerate()
+                .filter(|(_, border)| **border)
+                .fold(0, |value, (index, _)| {
+                    value | (1 << (SBN_BITS_PER_CHAR - 1 - index))
+                });
+            char::from_digit(value, SBN_RADIX).expect("Valeur sur 5 bits")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sbn() {
+        let grid_parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let sbn = grid_parser.to_sbn(1).unwrap();
+        assert!(sbn.starts_with("5/5/1/"));
+
+        // Aller-retour (les régions sont renommées dans l'ordre de leur première case)
+        let parsed =
+            GridParser::try_from_sbn(&format!("https://puzz.link/p?starbattle/{sbn}")).unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "# stars: 1\n".to_string() + &grid_parser.to_string()
+        );
+        assert_eq!(parsed.metadata().nb_stars, Some(1));
+        assert_eq!(parsed.to_sbn(1).unwrap(), sbn);
+
+        // Une seule région sans aucun bord
+        let parsed = GridParser::try_from_sbn("2/3/1/00").unwrap();
+        assert_eq!(parsed.regions(), vec!['A']);
+
+        assert!(GridParser::try_from_sbn("5/5/1").is_err());
+        assert!(GridParser::try_from_sbn("5/5/1/00").is_err());
+        assert!(GridParser::try_from_sbn("5/5/1/zzzzzzzzzz").is_err());
+        assert!(GridParser::try_from_sbn(&format!("{sbn}0")).is_err());
+        // Dimensions démesurées refusées avant toute allocation
+        assert!(GridParser::try_from_sbn("18446744073709551615/3/1/0").is_err());
+        assert!(GridParser::try_from_sbn("4000000000/4000000000/1/0").is_err());
+        assert!(GridParser::try_from(vec!["A.", "AA"])
+            .unwrap()
+            .to_sbn(1)
+            .is_err());
+    }
+}
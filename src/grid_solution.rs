@@ -0,0 +1,180 @@
+//! Solution d'une grille.
+//!
+//! Une solution est définie par la liste des coordonnées des étoiles d'une grille résolue.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::CellValue;
+use crate::Grid;
+use crate::GridHandler;
+use crate::LineColumn;
+
+/// Solution d'une grille : liste des étoiles d'une grille résolue.<br>
+/// Les étoiles sont triées par ligne puis par colonne pour que deux solutions identiques
+/// soient toujours égales
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solution {
+    /// Coordonnées des étoiles de la solution
+    stars: Vec<LineColumn>,
+}
+
+impl Display for Solution {
+    /// Définition 'textuelle' de la solution : coordonnées des étoiles séparées par un espace (`A1 C3` par exemple)
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, line_column) in self.stars.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{line_column}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Solution {
+    type Err = String;
+
+    /// Analyse de la définition 'textuelle' d'une solution (voir [`Display`])
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let stars = s
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<LineColumn>, String>>()?;
+        Ok(Self::new(stars))
+    }
+}
+
+impl Solution {
+    /// Constructeur à partir de la liste des étoiles de la solution
+    #[must_use]
+    pub fn new(mut stars: Vec<LineColumn>) -> Self {
+        stars.sort_by_key(|line_column| (line_column.line, line_column.column));
+        stars.dedup();
+        Self { stars }
+    }
+
+    /// Coordonnées des étoiles de la solution (triées par ligne puis par colonne)
+    #[must_use]
+    pub fn stars(&self) -> &[LineColumn] {
+        &self.stars
+    }
+
+    /// Nombre d'étoiles de la solution
+    #[must_use]
+    pub fn nb_stars(&self) -> usize {
+        self.stars.len()
+    }
+
+    /// Retourne `true` si la solution contient une étoile en `line_column`
+    #[must_use]
+    pub fn is_star(&self, line_column: LineColumn) -> bool {
+        self.stars.contains(&line_column)
+    }
+
+    /// Liste des étoiles qui ne sont présentes que dans une seule des deux solutions
+    #[must_use]
+    pub fn differences(&self, other: &Self) -> Vec<LineColumn> {
+        let mut differences: Vec<LineColumn> = self
+            .stars
+            .iter()
+            .filter(|line_column| !other.is_star(**line_column))
+            .chain(
+                other
+                    .stars
+                    .iter()
+                    .filter(|line_column| !self.is_star(**line_column)),
+            )
+            .copied()
+            .collect();
+        differences.sort_by_key(|line_column| (line_column.line, line_column.column));
+        differences
+    }
+
+    /// Construit la grille résolue correspondant à cette solution à partir d'une grille vide : les étoiles
+    /// de la solution sont placées et toutes les autres cases ne peuvent pas contenir d'étoile
+    #[must_use]
+    pub fn to_grid(&self, handler: &GridHandler) -> Grid {
+        let mut grid = Grid::from(handler);
+        for line in 0..grid.nb_lines() {
+            for column in 0..grid.nb_columns() {
+                let line_column = LineColumn::new(line, column);
+                grid.cell_mut(line_column).value = if self.is_star(line_column) {
+                    CellValue::Star
+                } else {
+                    CellValue::NoStar
+                };
+            }
+        }
+        grid
+    }
+}
+
+impl GridHandler {
+    /// Extrait la solution d'une grille résolue.<br>
+    /// Retourne None si la grille n'est pas résolue (voir [`GridHandler::is_done`])
+    #[must_use]
+    pub fn extract_solution(&self, grid: &Grid) -> Option<Solution> {
+        if !self.is_done(grid) {
+            return None;
+        }
+        Some(Solution::new(
+            grid.iter_with_value(CellValue::Star)
+                .map(|cell| cell.line_column)
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::find_solutions;
+
+    #[test]
+    fn test_extract_solution() {
+        let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+
+        // Une grille non résolue n'a pas de solution
+        assert!(grid_handler.extract_solution(&grid).is_none());
+
+        let solutions = find_solutions(&grid_handler, &grid, 1);
+        let solution = grid_handler.extract_solution(&solutions[0]).unwrap();
+        assert_eq!(solution.nb_stars(), 5);
+        assert_eq!(solution.to_grid(&grid_handler), solutions[0]);
+
+        // Définition textuelle
+        let text = solution.to_string();
+        assert_eq!(text.parse::<Solution>(), Ok(solution.clone()));
+        assert!("A1 B".parse::<Solution>().is_err());
+    }
+
+    #[test]
+    fn test_differences() {
+        let solution = Solution::new(vec![LineColumn::new(2, 2), LineColumn::new(0, 0)]);
+        let same_solution: Solution = "A1 C3".parse().unwrap();
+        assert_eq!(solution, same_solution);
+        assert_eq!(
+            solution.stars(),
+            &[LineColumn::new(0, 0), LineColumn::new(2, 2)]
+        );
+        assert!(solution.differences(&same_solution).is_empty());
+
+        let other_solution: Solution = "A1 D3".parse().unwrap();
+        assert_ne!(solution, other_solution);
+        assert_eq!(
+            solution.differences(&other_solution),
+            vec![LineColumn::new(2, 2), LineColumn::new(2, 3)]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let solution: Solution = "A1 C3 E5".parse().unwrap();
+        let json = serde_json::to_string(&solution).unwrap();
+        assert_eq!(serde_json::from_str::<Solution>(&json).unwrap(), solution);
+    }
+}
@@ -0,0 +1,533 @@
+//! Résolution complète d'une grille avec les règles de construction.
+//!
+//! Ce module enchaîne la lecture d'un fichier de grille et l'application des règles de construction
+//! (voir [`crate::get_good_rule_with_config`]) jusqu'à la résolution de la grille ou l'absence de
+//! nouvelle règle applicable.
+
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::parallel::map_ordered;
+use crate::solve_metrics::count_overflow_zones;
+use crate::try_get_good_rule;
+use crate::BadRuleError;
+use crate::Checkpoint;
+use crate::CheckpointConfig;
+use crate::CheckpointError;
+use crate::GoodRule;
+use crate::GoodRuleError;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::GridRating;
+use crate::Interruption;
+use crate::SolverConfig;
+
+/// Erreur lors de la lecture d'un fichier de grille à résoudre
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum SolveFileError {
+    /// Le fichier ne peut pas être lu
+    #[error("Erreur lecture du fichier {0}: {1}")]
+    Read(String, String),
+
+    /// Le contenu du fichier n'est pas une grille valide
+    #[error("Erreur dans le fichier {0}: {1}")]
+    Parse(String, String),
+
+    /// Le point de reprise de la résolution ne peut pas être écrit (voir [`solve_file_with_checkpoint`])
+    #[error("{0}")]
+    Checkpoint(#[from] CheckpointError),
+}
+
+/// Résultat de la résolution d'une grille
+#[derive(Debug)]
+pub struct SolveOutcome {
+    /// Handler de la grille résolue
+    pub handler: GridHandler,
+
+    /// Règles de construction appliquées successivement avec leur niveau
+    pub steps: Vec<(GoodRule, usize)>,
+
+    /// Grille au début de la résolution, avant l'application des règles (grille initiale ou grille d'un
+    /// point de reprise, voir [`crate::Checkpoint`])
+    pub start: Grid,
+
+    /// Grille après l'application de toutes les règles
+    pub grid: Grid,
+
+    /// Incohérence détectée dans la grille qui a interrompu la résolution
+    pub error: Option<BadRuleError>,
+
+    /// Cause de l'interruption de la résolution par les limites de la configuration (voir
+    /// [`SolverConfig::with_limits`])
+    pub interruption: Option<Interruption>,
+
+    /// Nombre de zones abandonnées car elles ont trop de grilles possibles (voir
+    /// [`SolverConfig::with_max_possible_grids`]) par la dernière recherche, qui n'a trouvé aucune règle :
+    /// une grille bloquée avec des zones abandonnées peut avancer avec un budget plus grand
+    pub nb_abandoned_zones: usize,
+}
+
+impl SolveOutcome {
+    /// Retourne `true` si la grille est entièrement résolue
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.error.is_none() && self.handler.is_done(&self.grid)
+    }
+
+    /// Grille au début de la résolution, avant l'application des règles
+    #[must_use]
+    pub fn initial_grid(&self) -> Grid {
+        self.start.clone()
+    }
+
+    /// Évaluation de la difficulté de la grille selon les règles appliquées (voir [`crate::rate_grid`])
+    #[must_use]
+    pub fn rating(&self) -> GridRating {
+        let mut rating = GridRating::default();
+        for (good_rule, level) in &self.steps {
+            // Les cases d'une zone ne dépendent pas du contenu de la grille
+            rating.add_step(&self.handler, &self.grid, good_rule, *level);
+        }
+        rating.solved = self.is_solved();
+        rating
+    }
+
+    /// Règle de construction appliquée de plus haut niveau avec son niveau (None si aucune règle appliquée)
+    #[must_use]
+    pub fn hardest_step(&self) -> Option<&(GoodRule, usize)> {
+        self.steps.iter().max_by_key(|(_, level)| *level)
+    }
+
+    /// Statistiques de la résolution de la grille du fichier `file_name` en `duration` sous la forme
+    /// d'une ligne CSV (sans fin de ligne) dont les colonnes sont celles de [`STATS_CSV_HEADER`]
+    #[must_use]
+    pub fn stats_csv_record(&self, file_name: &str, duration: Duration) -> String {
+        // Champ CSV entre guillemets si nécessaire
+        fn csv_field(field: &str) -> String {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        }
+
+        let hardest_rule = self
+            .hardest_step()
+            .map(|(good_rule, _)| good_rule.kind().name())
+            .unwrap_or_default();
+        format!(
+            "{},{}x{},{},{},{},{},{},{}",
+            csv_field(file_name),
+            self.handler.nb_lines(),
+            self.handler.nb_columns(),
+            self.handler.nb_stars(),
+            self.is_solved(),
+            self.steps.len(),
+            duration.as_millis(),
+            csv_field(hardest_rule),
+            csv_field(self.handler.metadata().title.as_deref().unwrap_or_default())
+        )
+    }
+}
+
+/// Entête CSV des statistiques de résolution (voir [`SolveOutcome::stats_csv_record`]) : fichier,
+/// taille, nombre d'étoiles, grille résolue, nombre de règles appliquées, durée de la résolution en
+/// millisecondes, catégorie de la règle appliquée de plus haut niveau et titre de la grille (voir
+/// [`crate::PuzzleMetadata`])
+pub const STATS_CSV_HEADER: &str = "file,size,stars,solved,steps,duration_ms,hardest_rule,title";
+
+/// Ajoute la ligne `record` (voir [`SolveOutcome::stats_csv_record`]) au fichier CSV de statistiques
+/// `path`, créé avec l'entête [`STATS_CSV_HEADER`] s'il n'existe pas
+/// ### Errors
+/// Retourne une erreur si le fichier ne peut pas être créé ou écrit
+pub fn append_stats_csv(path: impl AsRef<Path>, record: &str) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let is_new = !path.exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let contents = if is_new {
+        format!("{STATS_CSV_HEADER}\n{record}\n")
+    } else {
+        format!("{record}\n")
+    };
+    file.write_all(contents.as_bytes())
+}
+
+/// Résolution d'une grille avec `nb_stars` étoiles par ligne, colonne et région selon la
+/// configuration `config`
+/// ### Errors
+/// Retourne une erreur si la grille est incohérente avec ce nombre d'étoiles (voir [`GridHandler::try_new`])
+pub fn solve_grid(
+    parser: &GridParser,
+    nb_stars: usize,
+    config: &SolverConfig,
+) -> Result<SolveOutcome, String> {
+    let handler = GridHandler::try_new(parser, nb_stars)?;
+    let grid = Grid::from(&handler);
+    Ok(solve_from(handler, grid, config, |_, _| true))
+}
+
+/// Résolution des grilles `parsers` avec `nb_stars` étoiles par ligne, colonne et région selon la
+/// configuration `config`.<br>
+/// Avec la feature `parallel`, les grilles sont résolues en parallèle : les résultats sont dans l'ordre
+/// des grilles et identiques à ceux de [`solve_grid`] pour chacune d'elles (une erreur pour une grille
+/// incohérente avec ce nombre d'étoiles)
+#[must_use]
+pub fn solve_grids(
+    parsers: &[GridParser],
+    nb_stars: usize,
+    config: &SolverConfig,
+) -> Vec<Result<SolveOutcome, String>> {
+    map_ordered(parsers, |parser| solve_grid(parser, nb_stars, config))
+}
+
+/// Résolution de la grille à partir de son état `grid` selon la configuration `config`.<br>
+/// `on_step` est appelé après chaque règle appliquée avec la grille obtenue et les règles déjà
+/// appliquées : la résolution est interrompue si `on_step` retourne `false` ou si les limites de la
+/// configuration sont atteintes
+pub(crate) fn solve_from(
+    handler: GridHandler,
+    grid: Grid,
+    config: &SolverConfig,
+    mut on_step: impl FnMut(&Grid, &[(GoodRule, usize)]) -> bool,
+) -> SolveOutcome {
+    let start = grid.clone();
+    let mut grid = grid;
+    let mut steps = Vec::new();
+    let mut interruption = None;
+    let mut nb_abandoned_zones = 0;
+    let error = loop {
+        let (result, nb_overflow_zones) =
+            count_overflow_zones(|| try_get_good_rule(&handler, &grid, config));
+        match result {
+            Ok(Some((good_rule, level))) => {
+                grid.apply_good_rule(&good_rule);
+                steps.push((good_rule, level));
+                if !on_step(&grid, &steps) {
+                    break None;
+                }
+            }
+            Ok(None) => {
+                nb_abandoned_zones = nb_overflow_zones;
+                break None;
+            }
+            Err(GoodRuleError::BadRule(bad_rule)) => break Some(bad_rule),
+            Err(GoodRuleError::Interrupted(cause)) => {
+                interruption = Some(cause);
+                break None;
+            }
+        }
+    };
+    SolveOutcome {
+        handler,
+        steps,
+        start,
+        grid,
+        error,
+        interruption,
+        nb_abandoned_zones,
+    }
+}
+
+/// Suivi des étapes de [`solve_from`] qui appelle `on_rule` pour chaque règle trouvée avec la grille à
+/// laquelle elle s'applique (avant son application)
+pub(crate) fn each_rule<'a>(
+    handler: &GridHandler,
+    start: &Grid,
+    mut on_rule: impl FnMut(&GridHandler, &Grid, &GoodRule) + 'a,
+) -> impl FnMut(&Grid, &[(GoodRule, usize)]) + 'a {
+    let handler = handler.clone();
+    let mut previous = start.clone();
+    move |grid, steps| {
+        if let Some((good_rule, _)) = steps.last() {
+            on_rule(&handler, &previous, good_rule);
+        }
+        previous = grid.clone();
+    }
+}
+
+/// Lecture du fichier `path` d'une grille (voir [`GridParser`], ou [`GridParser::try_from_csv`] pour un
+/// fichier d'extension `.csv` ou `.tsv`) et résolution de cette grille avec
+/// `nb_stars` étoiles par ligne, colonne et région selon la configuration `config`
+/// ### Errors
+/// Retourne un [`SolveFileError`] si le fichier ne peut pas être lu ou ne contient pas une grille valide
+pub fn solve_file(
+    path: impl AsRef<Path>,
+    nb_stars: usize,
+    config: &SolverConfig,
+) -> Result<SolveOutcome, SolveFileError> {
+    solve_file_with_steps(path, nb_stars, config, |_, _, _| {})
+}
+
+/// Lecture et résolution du fichier `path` d'une grille (voir [`solve_file`]).<br>
+/// `on_rule` est appelé dès qu'une règle est trouvée, avec la grille à laquelle elle s'applique (avant
+/// son application) : une application peut ainsi afficher la résolution d'une grille difficile au fur et
+/// à mesure
+/// ### Errors
+/// Retourne un [`SolveFileError`] si le fichier ne peut pas être lu ou ne contient pas une grille valide
+pub fn solve_file_with_steps(
+    path: impl AsRef<Path>,
+    nb_stars: usize,
+    config: &SolverConfig,
+    on_rule: impl FnMut(&GridHandler, &Grid, &GoodRule),
+) -> Result<SolveOutcome, SolveFileError> {
+    let (file_name, parser) = read_grid_file(path.as_ref())?;
+    let handler =
+        GridHandler::try_new(&parser, nb_stars).map_err(|e| SolveFileError::Parse(file_name, e))?;
+    let grid = Grid::from(&handler);
+    let mut on_step = each_rule(&handler, &grid, on_rule);
+    Ok(solve_from(handler, grid, config, |grid, steps| {
+        on_step(grid, steps);
+        true
+    }))
+}
+
+/// Lecture et résolution du fichier `path` d'une grille (voir [`solve_file_with_steps`]) avec des points
+/// de reprise selon `checkpoint_config` (voir [`Checkpoint`])
+/// ### Errors
+/// Retourne un [`SolveFileError`] si le fichier ne peut pas être lu, ne contient pas une grille valide
+/// ou si un point de reprise ne peut pas être écrit
+pub fn solve_file_with_checkpoint(
+    path: impl AsRef<Path>,
+    nb_stars: usize,
+    config: &SolverConfig,
+    checkpoint_config: &CheckpointConfig,
+    on_rule: impl FnMut(&GridHandler, &Grid, &GoodRule),
+) -> Result<SolveOutcome, SolveFileError> {
+    let (file_name, parser) = read_grid_file(path.as_ref())?;
+    let checkpoint = Checkpoint::try_new(&parser, nb_stars).map_err(|e| match e {
+        CheckpointError::Parse(e) => SolveFileError::Parse(file_name, e),
+        e => SolveFileError::Checkpoint(e),
+    })?;
+    Ok(checkpoint.solve_with_steps(config, checkpoint_config, on_rule)?)
+}
+
+/// Lecture du fichier `path` d'une grille (voir [`GridParser`], ou [`GridParser::try_from_csv`] pour un
+/// fichier d'extension `.csv` ou `.tsv`) : nom du fichier et grille lue
+/// ### Errors
+/// Retourne un [`SolveFileError`] si le fichier ne peut pas être lu ou ne contient pas une grille valide
+pub(crate) fn read_grid_file(path: &Path) -> Result<(String, GridParser), SolveFileError> {
+    let file_name = path.display().to_string();
+    let file_contents = std::fs::read_to_string(path)
+        .map_err(|e| SolveFileError::Read(file_name.clone(), e.to_string()))?;
+    let is_csv = path.extension().is_some_and(|extension| {
+        extension.eq_ignore_ascii_case("csv") || extension.eq_ignore_ascii_case("tsv")
+    });
+    let parser = if is_csv {
+        GridParser::try_from_csv(&file_contents)
+    } else {
+        GridParser::try_from(file_contents.as_str())
+    };
+    match parser {
+        Ok(parser) => Ok((file_name, parser)),
+        Err(e) => Err(SolveFileError::Parse(file_name, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_file_invalid_grid() {
+        // Grille de 5 lignes avec seulement 4 régions : erreur au lieu d'une panique
+        let path = std::env::temp_dir().join("star_battle_test_solve_file_invalid_grid.txt");
+        std::fs::write(&path, "ABBBB\nABBBB\nCCBBB\nDDDDD\nDDDDD\n").unwrap();
+        let result = solve_file(&path, 1, &SolverConfig::default());
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(SolveFileError::Parse(_, _))));
+    }
+
+    #[test]
+    fn test_solve_file() {
+        let outcome = solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap();
+        assert!(outcome.is_solved());
+        assert!(!outcome.steps.is_empty());
+
+        // Les règles rejouées depuis la grille initiale donnent la grille résolue
+        let mut grid = outcome.initial_grid();
+        for (good_rule, _) in &outcome.steps {
+            grid.apply_good_rule(good_rule);
+        }
+        assert_eq!(grid, outcome.grid);
+    }
+
+    #[test]
+    fn test_solve_interrupted() {
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+
+        use crate::SolverLimits;
+
+        // Résolution annulée avant la première règle
+        let limits = SolverLimits::new().with_cancel_flag(Arc::new(AtomicBool::new(true)));
+        let config = SolverConfig::new().with_limits(limits);
+        let outcome = solve_file("./test_grids/test01.txt", 1, &config).unwrap();
+        assert_eq!(outcome.interruption, Some(Interruption::Cancelled));
+        assert!(outcome.steps.is_empty());
+        assert!(!outcome.is_solved());
+
+        // Résolution interrompue pendant la recherche des grilles possibles : les règles déjà appliquées
+        // restent valides
+        let limits = SolverLimits::new().with_max_combinations(100);
+        let config = SolverConfig::new().with_limits(limits);
+        let outcome = solve_file("./test_grids/expert01_2.txt", 2, &config).unwrap();
+        assert_eq!(
+            outcome.interruption,
+            Some(Interruption::TooManyCombinations)
+        );
+        assert!(outcome.error.is_none());
+        let mut grid = outcome.initial_grid();
+        for (good_rule, _) in &outcome.steps {
+            grid.apply_good_rule_checked(&outcome.handler, good_rule)
+                .unwrap();
+        }
+
+        // Sans limite, la grille est résolue
+        let outcome = solve_file("./test_grids/expert01_2.txt", 2, &SolverConfig::new()).unwrap();
+        assert_eq!(outcome.interruption, None);
+        assert!(outcome.is_solved());
+    }
+
+    #[test]
+    fn test_solve_grids() {
+        let parsers: Vec<GridParser> = ["facile01_2", "moyen01_2", "difficile01_2", "expert01_2"]
+            .iter()
+            .map(|name| {
+                let text = std::fs::read_to_string(format!("./test_grids/{name}.txt")).unwrap();
+                GridParser::try_from(text.as_str()).unwrap()
+            })
+            .collect();
+        let config = SolverConfig::default();
+
+        // Mêmes règles appliquées que la résolution séquentielle de chaque grille
+        let outcomes = solve_grids(&parsers, 2, &config);
+        assert_eq!(outcomes.len(), parsers.len());
+        for (parser, outcome) in parsers.iter().zip(&outcomes) {
+            let outcome = outcome.as_ref().unwrap();
+            let expected = solve_grid(parser, 2, &config).unwrap();
+            assert_eq!(outcome.steps, expected.steps);
+            assert_eq!(outcome.grid, expected.grid);
+        }
+    }
+
+    #[test]
+    fn test_rating() {
+        let outcome = solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap();
+        let grid = outcome.initial_grid();
+        assert_eq!(
+            outcome.rating(),
+            crate::rate_grid(&outcome.handler, &grid).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stats_csv_record() {
+        let outcome = solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap();
+        let (_, level) = outcome.hardest_step().unwrap();
+        assert!(outcome.steps.iter().all(|(_, other)| other <= level));
+
+        let record = outcome.stats_csv_record("test01.txt", Duration::from_millis(12));
+        assert_eq!(
+            record.split(',').count(),
+            STATS_CSV_HEADER.split(',').count()
+        );
+        assert!(record.starts_with(&format!(
+            "test01.txt,5x5,1,true,{},12,",
+            outcome.steps.len()
+        )));
+
+        // Nom de fichier avec une virgule
+        let record = outcome.stats_csv_record("a,\"b\".txt", Duration::ZERO);
+        assert!(record.starts_with("\"a,\"\"b\"\".txt\",5x5,"));
+    }
+
+    #[test]
+    fn test_append_stats_csv() {
+        let path = std::env::temp_dir().join("star_battle_test_stats.csv");
+        let _ = std::fs::remove_file(&path);
+        append_stats_csv(&path, "a.txt,5x5,1,true,3,1,Exclusions").unwrap();
+        append_stats_csv(&path, "b.txt,5x5,1,false,0,1,").unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            contents,
+            format!(
+                "{STATS_CSV_HEADER}\na.txt,5x5,1,true,3,1,Exclusions\nb.txt,5x5,1,false,0,1,\n"
+            )
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_solve_file_with_checkpoint() {
+        let path = std::env::temp_dir().join("star_battle_test_solve_file_with_checkpoint.txt");
+        let checkpoint_config = CheckpointConfig::new(&path).with_interval(Duration::ZERO);
+        let config = SolverConfig::default();
+        let mut nb_rules = 0;
+        let outcome = solve_file_with_checkpoint(
+            "./test_grids/test01.txt",
+            1,
+            &config,
+            &checkpoint_config,
+            |_, _, _| nb_rules += 1,
+        )
+        .unwrap();
+        assert!(outcome.is_solved());
+        assert_eq!(nb_rules, outcome.steps.len());
+        assert_eq!(Checkpoint::load(&path).unwrap().trace.len(), nb_rules);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            solve_file_with_checkpoint(
+                "./test_grids/test01.txt",
+                3,
+                &config,
+                &checkpoint_config,
+                |_, _, _| {}
+            ),
+            Err(SolveFileError::Parse(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_solve_file_errors() {
+        assert!(matches!(
+            solve_file("./test_grids/inconnu.txt", 1, &SolverConfig::default()),
+            Err(SolveFileError::Read(_, _))
+        ));
+        assert!(matches!(
+            solve_file("./Cargo.toml", 1, &SolverConfig::default()),
+            Err(SolveFileError::Parse(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_solve_grid_unsolved() {
+        // Seules les règles les plus simples : la grille n'est pas résolue
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let outcome = solve_grid(&parser, 1, &SolverConfig::new().with_max_level(1)).unwrap();
+        assert!(!outcome.is_solved());
+        assert!(outcome.error.is_none());
+        assert!(outcome.steps.is_empty());
+
+        // Grille incohérente avec le nombre d'étoiles : erreur plutôt que panique
+        assert!(solve_grid(&parser, 3, &SolverConfig::default()).is_err());
+        assert!(solve_grids(&[parser], 0, &SolverConfig::default())[0].is_err());
+        assert_eq!(outcome.nb_abandoned_zones, 0);
+
+        // Budget de grilles possibles trop petit : la grille est bloquée sur des zones abandonnées
+        let config = SolverConfig::new().with_max_level(4);
+        let small_budget = config.clone().with_max_possible_grids(1);
+        let outcome = solve_file("./test_grids/facile01_2.txt", 2, &small_budget).unwrap();
+        assert!(!outcome.is_solved());
+        assert!(outcome.nb_abandoned_zones > 0);
+        let outcome = solve_file("./test_grids/facile01_2.txt", 2, &config).unwrap();
+        assert!(outcome.is_solved());
+        assert_eq!(outcome.nb_abandoned_zones, 0);
+    }
+}
@@ -6,15 +6,18 @@ use std::fmt::Display;
 use std::ops::RangeInclusive;
 
 use crate::line_column::{display_column, display_line};
+use crate::CellSet;
 use crate::CellValue;
 use crate::Grid;
 use crate::GridCell;
 use crate::GridHandler;
 use crate::LineColumn;
+use crate::Locale;
 use crate::Region;
 
 /// Navigation dans la grille
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GridSurfer {
     /// Navigation sur toutes les case de la grille
     AllCells,
@@ -36,53 +39,153 @@ pub enum GridSurfer {
 
     /// Navigation sur plusieurs colonnes
     Columns(RangeInclusive<usize>),
+
+    /// Navigation sur toutes les cases d'une zone supplémentaire de la grille (diagonale, cage, ...) selon
+    /// son rang (voir [`GridHandler::with_cage`])
+    Cage(usize),
 }
 
 impl Display for GridSurfer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text(Locale::French))
+    }
+}
+
+impl GridSurfer {
+    /// Texte de la zone dans la langue `locale`
+    #[must_use]
+    pub fn text(&self, locale: Locale) -> String {
+        // Texte d'une plage de lignes ou colonnes
+        fn display_range(name: &str, names: &str, start: String, end: String) -> String {
+            if start == end {
+                format!("{name} {start}")
+            } else {
+                format!("{names} {start}-{end}")
+            }
+        }
+
+        let english = locale == Locale::English;
         match self {
-            Self::AllCells => write!(f, "Toute la grille"),
-            Self::Region(region) => write!(f, "Region '{region}'"),
-            Self::Adjacent(line_column) => write!(f, "Cases adjacentes à '{line_column}'"),
-            Self::Line(line) => write!(f, "Ligne {}", display_line(*line)),
-            Self::Column(column) => write!(f, "Colonne {}", display_column(*column)),
+            Self::AllCells if english => "Whole grid".to_string(),
+            Self::AllCells => "Toute la grille".to_string(),
+            Self::Region(region) => format!("Region '{region}'"),
+            Self::Adjacent(line_column) if english => format!("Cells adjacent to '{line_column}'"),
+            Self::Adjacent(line_column) => format!("Cases adjacentes à '{line_column}'"),
+            Self::Line(line) if english => format!("Line {}", display_line(*line)),
+            Self::Line(line) => format!("Ligne {}", display_line(*line)),
+            Self::Column(column) if english => format!("Column {}", display_column(*column)),
+            Self::Column(column) => format!("Colonne {}", display_column(*column)),
             Self::Lines(range) => {
-                if *range.start() == *range.end() {
-                    write!(f, "Ligne {}", display_line(*range.start()))
+                let (name, names) = if english {
+                    ("Line", "Lines")
                 } else {
-                    write!(
-                        f,
-                        "Lignes {}-{}",
-                        display_line(*range.start()),
-                        display_line(*range.end())
-                    )
-                }
+                    ("Ligne", "Lignes")
+                };
+                display_range(
+                    name,
+                    names,
+                    display_line(*range.start()),
+                    display_line(*range.end()),
+                )
             }
             Self::Columns(range) => {
-                if *range.start() == *range.end() {
-                    write!(f, "Colonne {}", display_column(*range.start()))
+                let (name, names) = if english {
+                    ("Column", "Columns")
                 } else {
-                    write!(
-                        f,
-                        "Colonnes {}-{}",
-                        display_column(*range.start()),
-                        display_column(*range.end())
-                    )
-                }
+                    ("Colonne", "Colonnes")
+                };
+                display_range(
+                    name,
+                    names,
+                    display_column(*range.start()),
+                    display_column(*range.end()),
+                )
             }
+            Self::Cage(index) => format!("Cage {}", index + 1),
+        }
+    }
+
+    /// Représentation canonique de la zone : une plage d'une seule ligne (ou colonne) est remplacée par
+    /// cette ligne (ou colonne)
+    #[must_use]
+    pub fn normalized(&self) -> Self {
+        match self {
+            Self::Lines(range) if range.start() == range.end() => Self::Line(*range.start()),
+            Self::Columns(range) if range.start() == range.end() => Self::Column(*range.start()),
+            _ => self.clone(),
+        }
+    }
+
+    /// Clé de tri de la zone (`RangeInclusive` n'est pas ordonné)
+    const fn sort_key(&self) -> (usize, usize, usize) {
+        match self {
+            Self::AllCells => (0, 0, 0),
+            Self::Region(region) => (1, *region as usize, 0),
+            Self::Adjacent(line_column) => (2, line_column.line, line_column.column),
+            Self::Line(line) => (3, *line, 0),
+            Self::Column(column) => (4, *column, 0),
+            Self::Lines(range) => (5, *range.start(), *range.end()),
+            Self::Columns(range) => (6, *range.start(), *range.end()),
+            Self::Cage(index) => (7, *index, 0),
         }
     }
 }
 
+impl PartialOrd for GridSurfer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GridSurfer {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
 impl GridHandler {
     /// Retourne la liste des cases d'une grille qui satisfont à un certain critère.<br>
-    /// Le critère est défini par l'énumération `GridSurfer`
+    /// Le critère est défini par l'énumération `GridSurfer`.<br>
+    /// Les cases bloquées (voir [`GridHandler::is_blocked`]) ne font partie d'aucune zone.<br>
+    /// Les cases des régions, lignes, colonnes et cages sont précalculées (voir [`GridHandler::zone_cells`])
     #[must_use]
     pub fn surfer(&self, grid: &Grid, surfer: &GridSurfer) -> Vec<LineColumn> {
+        if let Some(cells) = self.zone_cells(surfer) {
+            return cells.to_vec();
+        }
+        match surfer {
+            // Concaténation des cases précalculées des lignes
+            GridSurfer::AllCells => {
+                return (0..self.nb_lines())
+                    .flat_map(|line| self.line_cells(line))
+                    .copied()
+                    .collect();
+            }
+            GridSurfer::Lines(line_range) => {
+                return line_range
+                    .clone()
+                    .filter(|line| *line < self.nb_lines())
+                    .flat_map(|line| self.line_cells(line))
+                    .copied()
+                    .collect();
+            }
+            GridSurfer::Columns(column_range) => {
+                return (0..self.nb_lines())
+                    .flat_map(|line| self.line_cells(line))
+                    .filter(|line_column| column_range.contains(&line_column.column))
+                    .copied()
+                    .collect();
+            }
+            _ => (),
+        }
+
         let mut cells = Vec::new();
         for line in 0..self.nb_lines() {
             for column in 0..self.nb_columns() {
                 let line_column = LineColumn::new(line, column);
+                if self.is_blocked(line_column) {
+                    continue;
+                }
                 let cell: &GridCell = grid.cell(line_column);
                 let cell_is_matching = match surfer {
                     // Toutes les case de la grille
@@ -104,6 +207,8 @@ impl GridHandler {
                     GridSurfer::Lines(line_range) => line_range.contains(&line),
                     // Toutes les cases de plusieurs colonnes
                     GridSurfer::Columns(column_range) => column_range.contains(&column),
+                    // Toutes les cases d'une cage
+                    GridSurfer::Cage(index) => self.cage_cells(*index).contains(&line_column),
                 };
                 if cell_is_matching {
                     cells.push(line_column);
@@ -114,10 +219,23 @@ impl GridHandler {
         cells
     }
 
+    /// Retourne l'ensemble des cases d'une grille qui satisfont au critère `surfer` (mêmes cases que
+    /// [`GridHandler::surfer`]) sous la forme d'un masque de bits : le test d'appartenance d'une case à
+    /// cet ensemble ne nécessite pas de parcourir les cases
+    #[must_use]
+    pub fn surfer_set(&self, grid: &Grid, surfer: &GridSurfer) -> CellSet {
+        let mut cells = CellSet::new(self.nb_lines(), self.nb_columns());
+        for line_column in self.surfer(grid, surfer) {
+            cells.insert(line_column);
+        }
+        cells
+    }
+
     /// Retourne le nombre de cases sans la zone définie par le `GridSurfer`
     #[must_use]
     pub fn surfer_cells_count(&self, grid: &Grid, surfer: &GridSurfer) -> usize {
-        self.surfer(grid, surfer).len()
+        self.zone_cells(surfer)
+            .map_or_else(|| self.surfer(grid, surfer).len(), <[LineColumn]>::len)
     }
 
     /// Retourne le nombre de cases contenant une valeur particulière dans la zone définie par le `GridSurfer`
@@ -128,10 +246,12 @@ impl GridHandler {
         surfer: &GridSurfer,
         value: &CellValue,
     ) -> usize {
-        self.surfer(grid, surfer)
-            .iter()
-            .filter(|line_column| grid.cell(**line_column).value == *value)
-            .count()
+        let is_matching = |line_column: &&LineColumn| grid.cell(**line_column).value == *value;
+        match self.zone_cells(surfer) {
+            // Pas de liste de cases à construire pour une zone précalculée
+            Some(cells) => cells.iter().filter(is_matching).count(),
+            None => self.surfer(grid, surfer).iter().filter(is_matching).count(),
+        }
     }
 }
 
@@ -160,6 +280,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blocked_cells() {
+        let parser =
+            GridParser::try_from(vec!["AB.BB", "ABBBB", "CCB.B", "DDDDD", ".EEED"]).unwrap();
+        let grid_handler = GridHandler::new(&parser, 1);
+        let grid = Grid::from(&grid_handler);
+        assert_eq!(
+            grid_handler.surfer_cells_count(&grid, &GridSurfer::AllCells),
+            22
+        );
+        assert_eq!(
+            grid_handler.surfer(&grid, &GridSurfer::Column(0)),
+            vec![
+                LineColumn::new(0, 0),
+                LineColumn::new(1, 0),
+                LineColumn::new(2, 0),
+                LineColumn::new(3, 0)
+            ]
+        );
+        assert!(grid_handler.is_blocked(LineColumn::new(0, 2)));
+        assert!(!grid_handler.regions().contains(&'.'));
+        assert_eq!(grid.cell(LineColumn::new(0, 2)).value, CellValue::NoStar);
+    }
+
+    #[test]
+    fn test_surfer_set() {
+        let (grid_handler, grid) = get_test_grid();
+        for grid_surfer in [
+            GridSurfer::Region('B'),
+            GridSurfer::Columns(1..=2),
+            GridSurfer::Adjacent(LineColumn::new(2, 2)),
+        ] {
+            let cells = grid_handler.surfer(&grid, &grid_surfer);
+            let cell_set = grid_handler.surfer_set(&grid, &grid_surfer);
+            assert_eq!(cell_set.len(), cells.len());
+            assert_eq!(cell_set.iter().collect::<Vec<_>>(), cells);
+            for line_column in grid_handler.surfer(&grid, &GridSurfer::AllCells) {
+                assert_eq!(
+                    cell_set.contains(&line_column),
+                    cells.contains(&line_column)
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_region() {
         let (grid_handler, grid) = get_test_grid();
@@ -0,0 +1,168 @@
+//! Affichage d'une grille au format SVG.
+//!
+//! Les régions sont délimitées par des traits épais, les étoiles sont dessinées par des '★' (en couleur
+//! pour les étoiles données par la grille) et les cases qui ne peuvent pas contenir d'étoile par un point.
+
+use std::fmt::Write;
+
+use crate::CellValue;
+use crate::Grid;
+use crate::GridHandler;
+use crate::LineColumn;
+
+/// Taille (en pixels) d'une case de la grille
+const SVG_CELL_SIZE: usize = 40;
+
+/// Marge (en pixels) autour de la grille
+const SVG_MARGIN: usize = 4;
+
+impl GridHandler {
+    /// Affichage du contenu d'une grille au format SVG
+    #[must_use]
+    pub fn display_svg(&self, grid: &Grid) -> String {
+        let width = self.nb_columns() * SVG_CELL_SIZE + 2 * SVG_MARGIN;
+        let height = self.nb_lines() * SVG_CELL_SIZE + 2 * SVG_MARGIN;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\n"
+        );
+        if let Some(title) = &self.metadata().title {
+            let _ = writeln!(svg, "<title>{}</title>", escape_xml(title));
+        }
+        svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+        // Contenu des cases
+        for line in 0..self.nb_lines() {
+            for column in 0..self.nb_columns() {
+                let line_column = LineColumn::new(line, column);
+                let x = SVG_MARGIN + column * SVG_CELL_SIZE;
+                let y = SVG_MARGIN + line * SVG_CELL_SIZE;
+                let (cx, cy) = (x + SVG_CELL_SIZE / 2, y + SVG_CELL_SIZE / 2);
+                if self.is_blocked(line_column) {
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{x}\" y=\"{y}\" width=\"{SVG_CELL_SIZE}\" height=\"{SVG_CELL_SIZE}\" fill=\"gray\"/>"
+                    );
+                    continue;
+                }
+                match grid.cell(line_column).value {
+                    CellValue::Star => {
+                        let color = if grid.cell(line_column).is_given() {
+                            "darkblue"
+                        } else {
+                            "black"
+                        };
+                        let _ = writeln!(
+                            svg,
+                            "<text x=\"{cx}\" y=\"{cy}\" font-size=\"{}\" text-anchor=\"middle\" \
+                             dominant-baseline=\"central\" fill=\"{color}\">★</text>",
+                            SVG_CELL_SIZE * 3 / 4
+                        );
+                    }
+                    CellValue::NoStar => {
+                        let _ = writeln!(
+                            svg,
+                            "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{}\" fill=\"gray\"/>",
+                            SVG_CELL_SIZE / 12
+                        );
+                    }
+                    CellValue::Unknown => {}
+                }
+            }
+        }
+
+        // Traits entre les cases : épais entre 2 régions différentes, fins sinon
+        for line in 0..=self.nb_lines() {
+            for column in 0..=self.nb_columns() {
+                let x = SVG_MARGIN + column * SVG_CELL_SIZE;
+                let y = SVG_MARGIN + line * SVG_CELL_SIZE;
+                if column < self.nb_columns() {
+                    // Trait horizontal au-dessus de la case (line, column)
+                    let is_border = line == 0
+                        || line == self.nb_lines()
+                        || self.cell_region(LineColumn::new(line - 1, column))
+                            != self.cell_region(LineColumn::new(line, column));
+                    push_svg_line(&mut svg, (x, y), (x + SVG_CELL_SIZE, y), is_border);
+                }
+                if line < self.nb_lines() {
+                    // Trait vertical à gauche de la case (line, column)
+                    let is_border = column == 0
+                        || column == self.nb_columns()
+                        || self.cell_region(LineColumn::new(line, column - 1))
+                            != self.cell_region(LineColumn::new(line, column));
+                    push_svg_line(&mut svg, (x, y), (x, y + SVG_CELL_SIZE), is_border);
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Ajoute un trait entre 2 points, épais pour une bordure de région
+fn push_svg_line(
+    svg: &mut String,
+    (x1, y1): (usize, usize),
+    (x2, y2): (usize, usize),
+    is_border: bool,
+) {
+    let (stroke, width) = if is_border {
+        ("black", 3)
+    } else {
+        ("lightgray", 1)
+    };
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"{stroke}\" stroke-width=\"{width}\" \
+         stroke-linecap=\"square\"/>"
+    );
+}
+
+/// Texte avec les caractères spéciaux XML remplacés par leurs entités
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_svg() {
+        let (grid_handler, mut grid) =
+            grid!["ABBB.", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"; stars = 1];
+        grid.cell_mut(LineColumn::new(1, 1)).value = CellValue::NoStar;
+
+        let svg = grid_handler.display_svg(&grid);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        // 1 étoile donnée, 1 case sans étoile et 1 case bloquée
+        assert_eq!(svg.matches('★').count(), 1);
+        assert!(svg.contains("fill=\"darkblue\""));
+        assert_eq!(svg.matches("<circle").count(), 1);
+        assert_eq!(svg.matches("fill=\"gray\"/>").count(), 2);
+        // 2 x 5 x 6 traits entre les cases
+        assert_eq!(svg.matches("<line").count(), 60);
+    }
+
+    #[test]
+    fn test_display_svg_title() {
+        let (grid_handler, grid) =
+            grid!["# title: Grille <1>", "ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let svg = grid_handler.display_svg(&grid);
+        assert!(svg.contains("<title>Grille &lt;1&gt;</title>"));
+    }
+
+    #[test]
+    fn test_display_svg_snapshot() {
+        let (grid_handler, mut grid) =
+            grid!["ABBB.", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"; stars = 1];
+        grid.cell_mut(LineColumn::new(1, 1)).value = CellValue::NoStar;
+        grid.cell_mut(LineColumn::new(2, 2)).value = CellValue::Star;
+        insta::assert_snapshot!(grid_handler.display_svg(&grid));
+    }
+}
@@ -0,0 +1,284 @@
+//! Mode 'tutoriel' de la résolution d'une grille.
+//!
+//! Une [`TeachingSession`] applique les règles de construction une par une et consulte l'élève après
+//! chacune d'elles (voir [`TeachingEvent`]) : il peut continuer, arrêter ou jouer lui-même des actions
+//! avant la règle suivante ('je veux essayer d'abord'). Les actions de l'élève sont refusées si elles
+//! rendent la grille invalide.
+//!
+//! L'élève est consulté par une fonction (voir [`TeachingSession::run`]) ou par des canaux entre threads
+//! (voir [`TeachingSession::run_with_channels`]), par exemple pour une interface graphique. L'élève
+//! consulté par des canaux ne reçoit que les règles appliquées : il tient à jour sa propre copie de la
+//! grille avec les actions de ces règles.
+
+use std::sync::mpsc::{Receiver, Sender};
+
+use crate::get_good_rule_with_config;
+use crate::solve_metrics::count_overflow_zones;
+use crate::BadRuleError;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::SolveOutcome;
+use crate::SolverConfig;
+
+/// Événement communiqué à l'élève pendant une [`TeachingSession`]
+#[derive(Clone, Debug)]
+pub enum TeachingEvent {
+    /// La règle de construction `good_rule` de niveau `level` vient d'être appliquée
+    Rule {
+        /// Règle de construction appliquée
+        good_rule: GoodRule,
+
+        /// Niveau de la règle appliquée
+        level: usize,
+    },
+
+    /// Les actions de l'élève sont refusées car elles rendent la grille invalide : la grille est inchangée
+    Rejected(BadRuleError),
+}
+
+/// Réponse de l'élève à un [`TeachingEvent`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TeachingResponse {
+    /// Appliquer la règle de construction suivante
+    Continue,
+
+    /// Jouer ces actions avant la règle de construction suivante
+    Play(Vec<GridAction>),
+
+    /// Arrêter la résolution
+    Stop,
+}
+
+/// Résolution d'une grille règle par règle avec la confirmation de l'élève
+#[derive(Debug)]
+pub struct TeachingSession {
+    /// Handler de la grille
+    handler: GridHandler,
+
+    /// Configuration des règles de construction
+    config: SolverConfig,
+
+    /// Grille au début de la session
+    start: Grid,
+
+    /// Grille en cours de résolution
+    grid: Grid,
+
+    /// Règles de construction appliquées avec leur niveau
+    steps: Vec<(GoodRule, usize)>,
+
+    /// Actions jouées par l'élève
+    user_actions: Vec<GridAction>,
+
+    /// Nombre de zones abandonnées par la dernière recherche sans règle trouvée (voir
+    /// [`SolveOutcome::nb_abandoned_zones`])
+    nb_abandoned_zones: usize,
+}
+
+impl TeachingSession {
+    /// Constructeur : session de résolution de la grille `grid` selon la configuration `config`
+    #[must_use]
+    pub fn new(handler: GridHandler, grid: Grid, config: SolverConfig) -> Self {
+        Self {
+            handler,
+            config,
+            start: grid.clone(),
+            grid,
+            steps: Vec::new(),
+            user_actions: Vec::new(),
+            nb_abandoned_zones: 0,
+        }
+    }
+
+    /// Grille en cours de résolution
+    #[must_use]
+    pub const fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Actions jouées par l'élève et acceptées depuis le début de la session
+    #[must_use]
+    pub fn user_actions(&self) -> &[GridAction] {
+        &self.user_actions
+    }
+
+    /// Joue les actions de l'élève sur la grille en cours de résolution.<br>
+    /// Les actions sont toutes appliquées ou toutes refusées (voir [`Grid::apply_actions_checked`])
+    /// ### Errors
+    /// Retourne un [`BadRuleError`] si une action est impossible ou rend la grille invalide
+    pub fn play(&mut self, actions: &[GridAction]) -> Result<(), BadRuleError> {
+        self.grid.apply_actions_checked(&self.handler, actions)?;
+        self.user_actions.extend_from_slice(actions);
+        Ok(())
+    }
+
+    /// Applique la règle de construction suivante et retourne l'événement correspondant.<br>
+    /// Retourne `Ok(None)` si aucune règle n'est applicable (grille résolue ou bloquée)
+    /// ### Errors
+    /// Retourne un [`BadRuleError`] si la grille est incohérente
+    pub fn step(&mut self) -> Result<Option<TeachingEvent>, BadRuleError> {
+        let (result, nb_overflow_zones) = count_overflow_zones(|| {
+            get_good_rule_with_config(&self.handler, &self.grid, &self.config)
+        });
+        let Some((good_rule, level)) = result? else {
+            self.nb_abandoned_zones = nb_overflow_zones;
+            return Ok(None);
+        };
+        self.grid.apply_good_rule(&good_rule);
+        self.steps.push((good_rule.clone(), level));
+        Ok(Some(TeachingEvent::Rule { good_rule, level }))
+    }
+
+    /// Résolution de la grille en consultant l'élève par la fonction `on_event` après chaque règle
+    /// appliquée et après chaque refus de ses actions (avec la grille en cours de résolution).<br>
+    /// Les règles du résultat sont celles appliquées par la session : les actions de l'élève n'y figurent
+    /// pas (voir [`TeachingSession::user_actions`])
+    pub fn run(
+        mut self,
+        mut on_event: impl FnMut(&TeachingEvent, &Grid) -> TeachingResponse,
+    ) -> SolveOutcome {
+        let error = 'session: loop {
+            let mut event = match self.step() {
+                Ok(Some(event)) => event,
+                Ok(None) => break None,
+                Err(bad_rule) => break Some(bad_rule),
+            };
+            loop {
+                match on_event(&event, &self.grid) {
+                    TeachingResponse::Continue => break,
+                    TeachingResponse::Stop => break 'session None,
+                    TeachingResponse::Play(actions) => match self.play(&actions) {
+                        Ok(()) => break,
+                        Err(bad_rule) => event = TeachingEvent::Rejected(bad_rule),
+                    },
+                }
+            }
+        };
+        SolveOutcome {
+            handler: self.handler,
+            steps: self.steps,
+            start: self.start,
+            grid: self.grid,
+            error,
+            interruption: None,
+            nb_abandoned_zones: self.nb_abandoned_zones,
+        }
+    }
+
+    /// Résolution de la grille en envoyant chaque événement à l'élève par le canal `events` et en
+    /// attendant sa réponse sur le canal `responses`.<br>
+    /// La résolution est arrêtée si l'un des canaux est fermé
+    pub fn run_with_channels(
+        self,
+        events: &Sender<TeachingEvent>,
+        responses: &Receiver<TeachingResponse>,
+    ) -> SolveOutcome {
+        self.run(|event, _| {
+            if events.send(event.clone()).is_err() {
+                return TeachingResponse::Stop;
+            }
+            responses.recv().unwrap_or(TeachingResponse::Stop)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridParser;
+    use crate::LineColumn;
+
+    #[test]
+    fn test_teaching_session() {
+        let (handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+
+        // L'élève confirme chaque règle
+        let session = TeachingSession::new(handler, grid, SolverConfig::default());
+        let mut nb_rules = 0;
+        let outcome = session.run(|event, _| {
+            assert!(matches!(event, TeachingEvent::Rule { .. }));
+            nb_rules += 1;
+            TeachingResponse::Continue
+        });
+        assert!(outcome.is_solved());
+        assert_eq!(outcome.steps.len(), nb_rules);
+
+        // L'élève arrête après la première règle
+        let (handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let session = TeachingSession::new(handler, grid, SolverConfig::default());
+        let outcome = session.run(|_, _| TeachingResponse::Stop);
+        assert_eq!(outcome.steps.len(), 1);
+        assert!(!outcome.is_solved());
+
+        // L'élève joue une action invalide (refusée) puis l'étoile de la région 'A'
+        let solution = crate::solve_grid(
+            &GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap(),
+            1,
+            &SolverConfig::default(),
+        )
+        .unwrap()
+        .grid;
+        let (handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let star_a = handler
+            .region_cells('A')
+            .iter()
+            .copied()
+            .find(|line_column| solution.cell(*line_column).is_star())
+            .unwrap();
+        let mut session = TeachingSession::new(handler, grid, SolverConfig::default());
+        let mut responses = vec![
+            TeachingResponse::Play(vec![
+                GridAction::SetStar(LineColumn::new(0, 0)),
+                GridAction::SetStar(LineColumn::new(1, 0)),
+            ]),
+            TeachingResponse::Play(vec![GridAction::SetStar(star_a)]),
+        ]
+        .into_iter();
+        assert!(session
+            .play(&[
+                GridAction::SetStar(LineColumn::new(4, 1)),
+                GridAction::SetStar(LineColumn::new(4, 2))
+            ])
+            .is_err());
+        assert!(session.user_actions().is_empty());
+        let mut nb_rejected = 0;
+        let outcome = session.run(|event, _| {
+            if matches!(event, TeachingEvent::Rejected(_)) {
+                nb_rejected += 1;
+            }
+            responses.next().unwrap_or(TeachingResponse::Continue)
+        });
+        assert_eq!(nb_rejected, 1);
+        assert!(outcome.is_solved());
+        assert!(outcome.grid.cell(star_a).is_star());
+    }
+
+    #[test]
+    fn test_teaching_session_channels() {
+        let (handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        let (event_sender, event_receiver) = std::sync::mpsc::channel();
+        let (response_sender, response_receiver) = std::sync::mpsc::channel();
+
+        let student = std::thread::spawn(move || {
+            let mut nb_events = 0;
+            while event_receiver.recv().is_ok() {
+                nb_events += 1;
+                let response = if nb_events < 2 {
+                    TeachingResponse::Continue
+                } else {
+                    TeachingResponse::Stop
+                };
+                response_sender.send(response).unwrap();
+            }
+            nb_events
+        });
+        let session = TeachingSession::new(handler, grid, SolverConfig::default());
+        let outcome = session.run_with_channels(&event_sender, &response_receiver);
+        drop(event_sender);
+        assert_eq!(outcome.steps.len(), 2);
+        assert_eq!(student.join().unwrap(), 2);
+    }
+}
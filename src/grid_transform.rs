@@ -0,0 +1,293 @@
+//! Transformations géométriques d'une grille (rotations et symétries).
+//!
+//! Les 8 transformations qui conservent les règles du jeu (adjacence, lignes, colonnes et régions) sont
+//! l'identité, les 3 rotations d'un quart de tour et les 4 symétries axiales.
+//!
+//! Deux grilles sont 'isomorphes' si l'une s'obtient à partir de l'autre par une de ces transformations et
+//! en renommant ses régions : elles ont alors la même forme canonique (voir [`GridParser::canonical`]).
+
+use std::collections::HashMap;
+
+use crate::grid_parser::{BLOCKED_CELL_CHAR, GIVEN_STARS_PREFIX};
+use crate::GridParser;
+use crate::LineColumn;
+use crate::Region;
+
+/// Transformation géométrique d'une grille
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GridTransform {
+    /// Aucune transformation
+    #[default]
+    Identity,
+
+    /// Rotation d'un quart de tour dans le sens des aiguilles d'une montre
+    Rotate90,
+
+    /// Rotation d'un demi-tour
+    Rotate180,
+
+    /// Rotation d'un quart de tour dans le sens inverse des aiguilles d'une montre
+    Rotate270,
+
+    /// Symétrie gauche/droite (axe vertical)
+    MirrorHorizontal,
+
+    /// Symétrie haut/bas (axe horizontal)
+    MirrorVertical,
+
+    /// Symétrie selon la diagonale principale (A1 vers le bas à droite)
+    Transpose,
+
+    /// Symétrie selon l'autre diagonale
+    AntiTranspose,
+}
+
+impl GridTransform {
+    /// Liste de toutes les transformations
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::MirrorHorizontal,
+        Self::MirrorVertical,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    /// Transformation inverse
+    #[must_use]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            other => other,
+        }
+    }
+
+    /// Retourne `true` si la transformation échange les lignes et les colonnes
+    #[must_use]
+    pub const fn swaps_lines_columns(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Transpose | Self::AntiTranspose
+        )
+    }
+
+    /// Dimensions (`nb_lines`, `nb_columns`) d'une grille de `nb_lines` x `nb_columns` cases après
+    /// transformation
+    #[must_use]
+    pub const fn transformed_size(self, nb_lines: usize, nb_columns: usize) -> (usize, usize) {
+        if self.swaps_lines_columns() {
+            (nb_columns, nb_lines)
+        } else {
+            (nb_lines, nb_columns)
+        }
+    }
+
+    /// Image d'une case d'une grille de `nb_lines` x `nb_columns` cases après transformation
+    #[must_use]
+    pub const fn apply(
+        self,
+        line_column: LineColumn,
+        nb_lines: usize,
+        nb_columns: usize,
+    ) -> LineColumn {
+        let (line, column) = (line_column.line, line_column.column);
+        match self {
+            Self::Identity => LineColumn::new(line, column),
+            Self::Rotate90 => LineColumn::new(column, nb_lines - 1 - line),
+            Self::Rotate180 => LineColumn::new(nb_lines - 1 - line, nb_columns - 1 - column),
+            Self::Rotate270 => LineColumn::new(nb_columns - 1 - column, line),
+            Self::MirrorHorizontal => LineColumn::new(line, nb_columns - 1 - column),
+            Self::MirrorVertical => LineColumn::new(nb_lines - 1 - line, column),
+            Self::Transpose => LineColumn::new(column, line),
+            Self::AntiTranspose => LineColumn::new(nb_columns - 1 - column, nb_lines - 1 - line),
+        }
+    }
+}
+
+impl GridParser {
+    /// Définition de la grille après une transformation géométrique
+    /// # Panics
+    /// Panic si la grille transformée n'est pas valide (ne devrait pas arriver puisque les transformations
+    /// conservent la connexité des régions)
+    #[must_use]
+    pub fn transform(&self, transform: GridTransform) -> Self {
+        let (nb_lines, nb_columns) = (self.nb_lines(), self.nb_columns());
+        let (new_nb_lines, new_nb_columns) = transform.transformed_size(nb_lines, nb_columns);
+        let inverse = transform.inverse();
+        let mut lines = Vec::with_capacity(new_nb_lines);
+        for line in 0..new_nb_lines {
+            let mut text_line = String::with_capacity(new_nb_columns);
+            for column in 0..new_nb_columns {
+                let source =
+                    inverse.apply(LineColumn::new(line, column), new_nb_lines, new_nb_columns);
+                text_line.push(self.cell_region(source));
+            }
+            lines.push(text_line);
+        }
+        // Les étoiles données suivent la transformation
+        let given_stars = self.given_stars();
+        if !given_stars.is_empty() {
+            let mut text_line = GIVEN_STARS_PREFIX.trim_end().to_string();
+            for line_column in given_stars {
+                let target = transform.apply(line_column, nb_lines, nb_columns);
+                text_line.push_str(&format!(" {target}"));
+            }
+            lines.push(text_line);
+        }
+        Self::try_from(lines)
+            .expect("Une transformation conserve la validité de la grille")
+            .with_metadata(self.metadata().clone())
+    }
+
+    /// Forme canonique de la grille : parmi les 8 transformations de la grille dont les régions sont
+    /// renommées dans l'ordre de leur première case ('A', 'B', ...), celle dont la définition textuelle
+    /// est la plus petite.<br>
+    /// Deux grilles isomorphes (même grille à une transformation et un renommage des régions près) ont la
+    /// même forme canonique
+    /// # Panics
+    /// Panic si la grille renommée n'est pas valide (ne devrait pas arriver puisque le renommage conserve
+    /// les régions)
+    #[must_use]
+    pub fn canonical(&self) -> Self {
+        let canonical_text = GridTransform::ALL
+            .iter()
+            .map(|transform| self.transform(*transform).renamed_text())
+            .min()
+            .expect("Au moins une transformation");
+        Self::try_from(canonical_text.as_str())
+            .expect("Un renommage des régions conserve la validité de la grille")
+    }
+
+    /// Retourne `true` si la grille est isomorphe à la grille `other` (voir [`GridParser::canonical`])
+    #[must_use]
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        self.nb_lines() * self.nb_columns() == other.nb_lines() * other.nb_columns()
+            && self.canonical().to_string() == other.canonical().to_string()
+    }
+
+    /// Définition textuelle de la grille dont les régions sont renommées dans l'ordre de leur première
+    /// case et dont les étoiles données sont triées
+    fn renamed_text(&self) -> String {
+        let mut names: HashMap<Region, Region> = HashMap::new();
+        let mut lines = Vec::with_capacity(self.nb_lines() + 1);
+        for line in 0..self.nb_lines() {
+            let mut text_line = String::with_capacity(self.nb_columns());
+            for column in 0..self.nb_columns() {
+                let region = self.cell_region(LineColumn::new(line, column));
+                if region == BLOCKED_CELL_CHAR {
+                    text_line.push(region);
+                } else {
+                    let nb_names = names.len();
+                    text_line.push(*names.entry(region).or_insert_with(|| region_name(nb_names)));
+                }
+            }
+            lines.push(text_line);
+        }
+        let mut given_stars = self.given_stars();
+        if !given_stars.is_empty() {
+            given_stars.sort_unstable_by_key(|line_column| (line_column.line, line_column.column));
+            let mut text_line = GIVEN_STARS_PREFIX.trim_end().to_string();
+            for line_column in given_stars {
+                text_line.push_str(&format!(" {line_column}"));
+            }
+            lines.push(text_line);
+        }
+        lines.join("\n")
+    }
+}
+
+/// Nom de la région de rang `index` dans une forme canonique : 'A' à 'Z', 'a' à 'z', '0' à '9' puis des
+/// lettres accentuées à partir de 'À'
+fn region_name(index: usize) -> Region {
+    let code = match u32::try_from(index).unwrap_or(u32::MAX) {
+        index @ 0..=25 => u32::from('A') + index,
+        index @ 26..=51 => u32::from('a') + index - 26,
+        index @ 52..=61 => u32::from('0') + index - 52,
+        index => u32::from('À').saturating_add(index - 62),
+    };
+    char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_inverse() {
+        let (nb_lines, nb_columns) = (3, 5);
+        for transform in GridTransform::ALL {
+            let (new_nb_lines, new_nb_columns) = transform.transformed_size(nb_lines, nb_columns);
+            for line in 0..nb_lines {
+                for column in 0..nb_columns {
+                    let line_column = LineColumn::new(line, column);
+                    let image = transform.apply(line_column, nb_lines, nb_columns);
+                    assert!(image.line < new_nb_lines && image.column < new_nb_columns);
+                    assert_eq!(
+                        transform
+                            .inverse()
+                            .apply(image, new_nb_lines, new_nb_columns),
+                        line_column
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_parser() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+
+        let rotated = parser.transform(GridTransform::Rotate90);
+        assert_eq!(rotated.cell_region(LineColumn::new(0, 0)), 'D');
+        assert_eq!(rotated.cell_region(LineColumn::new(0, 4)), 'A');
+
+        let mirrored = parser.transform(GridTransform::MirrorHorizontal);
+        assert_eq!(mirrored.cell_region(LineColumn::new(0, 4)), 'A');
+        assert_eq!(mirrored.cell_region(LineColumn::new(4, 0)), 'D');
+
+        // 4 rotations d'un quart de tour redonnent la grille initiale
+        let mut turned = parser.clone();
+        for _ in 0..4 {
+            turned = turned.transform(GridTransform::Rotate90);
+        }
+        assert_eq!(turned.list_cells(), parser.list_cells());
+
+        // Les étoiles données suivent la transformation
+        let parser = GridParser::try_from(vec!["AAB", "CCB", "CCB", "* A1"]).unwrap();
+        let rotated = parser.transform(GridTransform::Rotate90);
+        assert_eq!(rotated.given_stars(), vec![LineColumn::new(0, 2)]);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let canonical = parser.canonical();
+        assert_eq!(canonical.to_string(), canonical.canonical().to_string());
+
+        // Toutes les transformations et un renommage des régions donnent la même forme canonique
+        for transform in GridTransform::ALL {
+            let transformed = parser.transform(transform);
+            assert_eq!(transformed.canonical().to_string(), canonical.to_string());
+            assert!(transformed.is_isomorphic(&parser));
+        }
+        let renamed =
+            GridParser::try_from(vec!["xyyyy", "xyyyy", "zzyyy", "wwwww", "wvvvw"]).unwrap();
+        assert!(renamed.is_isomorphic(&parser));
+
+        let other =
+            GridParser::try_from(vec!["AABBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        assert!(!other.is_isomorphic(&parser));
+
+        // Les étoiles données font partie de la forme canonique
+        let given = GridParser::try_from(vec!["AAB", "CCB", "CCB", "* A1"]).unwrap();
+        let rotated = given.transform(GridTransform::Rotate90);
+        assert!(rotated.is_isomorphic(&given));
+        let other_given = GridParser::try_from(vec!["AAB", "CCB", "CCB", "* C3"]).unwrap();
+        assert!(!other_given.is_isomorphic(&given));
+    }
+}
@@ -0,0 +1,289 @@
+//! Rapport HTML de la résolution d'un ensemble de grilles.
+//!
+//! Le rapport est une page HTML autonome avec une 'carte' par grille : la grille au format SVG, sa
+//! difficulté (évaluée et annoncée), la durée de sa résolution et la trace complète des règles appliquées.
+//! Il permet de passer en revue une collection de grilles fraîchement générée ou importée.
+//!
+//! [`DirectoryReport::from_dir`] résout toutes les grilles d'un répertoire pour en écrire le rapport.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::grid_solve::{read_grid_file, solve_from};
+use crate::grid_svg::escape_xml;
+use crate::list_grid_files;
+use crate::parallel::map_ordered;
+use crate::Grid;
+use crate::GridHandler;
+use crate::SolveFileError;
+use crate::SolveOutcome;
+use crate::SolverConfig;
+
+/// Style CSS du rapport
+const REPORT_STYLE: &str = "body { font-family: sans-serif; margin: 1em; }
+.cards { display: flex; flex-wrap: wrap; gap: 1em; }
+.card { border: 1px solid #ccc; border-radius: 6px; padding: 0.5em 1em; width: 22em; }
+.card svg { width: 100%; height: auto; }
+.solved { color: darkgreen; }
+.unsolved { color: darkred; }";
+
+/// Erreur lors du rapport HTML des grilles d'un répertoire
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ReportError {
+    /// Le répertoire ne peut pas être lu
+    #[error("Erreur lecture du répertoire {0}: {1}")]
+    ReadDir(String, String),
+
+    /// Le fichier du rapport ne peut pas être écrit
+    #[error("Erreur écriture du fichier {0}: {1}")]
+    Write(String, String),
+}
+
+/// Résolution d'une grille pour le rapport HTML
+#[derive(Debug)]
+pub struct ReportEntry {
+    /// Nom du fichier de la grille
+    pub file_name: String,
+
+    /// Résultat de la résolution de la grille
+    pub outcome: SolveOutcome,
+
+    /// Durée de la résolution
+    pub duration: Duration,
+}
+
+impl ReportEntry {
+    /// Lecture du fichier `path` d'une grille et résolution de cette grille selon la configuration
+    /// `config`.<br>
+    /// Le nombre d'étoiles est celui annoncé par les métadonnées de la grille (voir
+    /// [`crate::PuzzleMetadata`]) ou `nb_stars` par défaut
+    /// ### Errors
+    /// Retourne un [`SolveFileError`] si le fichier ne peut pas être lu ou ne contient pas une grille valide
+    pub fn from_file(
+        path: impl AsRef<Path>,
+        nb_stars: usize,
+        config: &SolverConfig,
+    ) -> Result<Self, SolveFileError> {
+        let (file_name, parser) = read_grid_file(path.as_ref())?;
+        let nb_stars = parser.metadata().nb_stars.unwrap_or(nb_stars);
+        let handler = GridHandler::try_new(&parser, nb_stars)
+            .map_err(|e| SolveFileError::Parse(file_name.clone(), e))?;
+
+        let start = Instant::now();
+        let outcome = solve_from(handler.clone(), Grid::from(&handler), config, |_, _| true);
+        Ok(Self {
+            file_name,
+            outcome,
+            duration: start.elapsed(),
+        })
+    }
+
+    /// Lecture et résolution des fichiers de grilles `paths` (voir [`ReportEntry::from_file`]).<br>
+    /// Avec la feature `parallel`, les grilles sont résolues en parallèle : les résultats restent dans
+    /// l'ordre des fichiers
+    pub fn from_files<P: AsRef<Path> + Sync>(
+        paths: &[P],
+        nb_stars: usize,
+        config: &SolverConfig,
+    ) -> Vec<Result<Self, SolveFileError>> {
+        map_ordered(paths, |path| Self::from_file(path, nb_stars, config))
+    }
+
+    /// Carte HTML de la grille (`index` est le rang de la grille dans le rapport)
+    fn html_card(&self, index: usize) -> String {
+        let handler = &self.outcome.handler;
+        let metadata = handler.metadata();
+        let title = metadata.title.as_deref().unwrap_or(&self.file_name);
+        let difficulty = self.outcome.rating().difficulty().map_or_else(
+            || "non résolue".to_string(),
+            |difficulty| difficulty.to_string(),
+        );
+        let (class, status) = if self.outcome.is_solved() {
+            ("solved", "Grille résolue")
+        } else {
+            ("unsolved", "Grille non résolue")
+        };
+
+        let mut card = String::new();
+        let _ = writeln!(card, "<div class=\"card\" id=\"grid-{index}\">");
+        let _ = writeln!(card, "<h2>{}</h2>", escape_xml(title));
+        let _ = writeln!(card, "<p>{}</p>", escape_xml(&self.file_name));
+        card.push_str(&handler.display_svg(&self.outcome.initial_grid()));
+        let _ = writeln!(card, "<ul>");
+        let _ = writeln!(
+            card,
+            "<li>{} étoile(s), {}x{}</li>",
+            handler.nb_stars(),
+            handler.nb_lines(),
+            handler.nb_columns()
+        );
+        let _ = writeln!(card, "<li>Difficulté : {difficulty}</li>");
+        if let Some(declared) = &metadata.difficulty {
+            let _ = writeln!(
+                card,
+                "<li>Difficulté annoncée : {}</li>",
+                escape_xml(declared)
+            );
+        }
+        let _ = writeln!(
+            card,
+            "<li>Résolution : {} ms</li>",
+            self.duration.as_millis()
+        );
+        let _ = writeln!(card, "<li class=\"{class}\">{status}</li>");
+        let _ = writeln!(card, "</ul>");
+        let _ = writeln!(
+            card,
+            "<details id=\"trace-{index}\"><summary>Trace complète ({} règles)</summary>\n<ol>",
+            self.outcome.steps.len()
+        );
+        for (good_rule, level) in &self.outcome.steps {
+            let _ = writeln!(
+                card,
+                "<li>[niveau {level}] {}</li>",
+                escape_xml(&good_rule.to_string())
+            );
+        }
+        if let Some(bad_rule) = &self.outcome.error {
+            let _ = writeln!(card, "<li>{} !!!</li>", escape_xml(&bad_rule.to_string()));
+        }
+        let _ = writeln!(card, "</ol></details>\n</div>");
+        card
+    }
+}
+
+/// Page HTML du rapport de titre `title` avec une carte pour chaque grille de `entries`
+#[must_use]
+pub fn html_report(title: &str, entries: &[ReportEntry]) -> String {
+    let title = escape_xml(title);
+    let nb_solved = entries
+        .iter()
+        .filter(|entry| entry.outcome.is_solved())
+        .count();
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"fr\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>\n{REPORT_STYLE}\n</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<p>{nb_solved} grille(s) résolue(s) sur {}</p>\n<div class=\"cards\">\n",
+        entries.len()
+    );
+    for (index, entry) in entries.iter().enumerate() {
+        html.push_str(&entry.html_card(index + 1));
+    }
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+/// Rapport HTML des grilles d'un répertoire (voir [`DirectoryReport::from_dir`])
+#[derive(Debug)]
+pub struct DirectoryReport {
+    /// Titre du rapport
+    pub title: String,
+
+    /// Résolution des grilles du répertoire, dans l'ordre des fichiers
+    pub entries: Vec<ReportEntry>,
+
+    /// Fichiers ignorés car ils ne peuvent pas être lus ou ne contiennent pas une grille valide
+    pub invalid: Vec<(PathBuf, SolveFileError)>,
+}
+
+impl DirectoryReport {
+    /// Lecture et résolution des grilles des fichiers `.txt` du répertoire `dir` (voir
+    /// [`ReportEntry::from_files`])
+    /// ### Errors
+    /// Retourne un [`ReportError`] si le répertoire ne peut pas être lu
+    pub fn from_dir(
+        dir: impl AsRef<Path>,
+        nb_stars: usize,
+        config: &SolverConfig,
+    ) -> Result<Self, ReportError> {
+        let dir = dir.as_ref();
+        let files = list_grid_files(dir)
+            .map_err(|e| ReportError::ReadDir(dir.display().to_string(), e.to_string()))?;
+        let mut report = Self {
+            title: format!("Grilles du répertoire {}", dir.display()),
+            entries: Vec::new(),
+            invalid: Vec::new(),
+        };
+        for (file, entry) in files
+            .iter()
+            .zip(ReportEntry::from_files(&files, nb_stars, config))
+        {
+            match entry {
+                Ok(entry) => report.entries.push(entry),
+                Err(e) => report.invalid.push((file.clone(), e)),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Page HTML du rapport (voir [`html_report`])
+    #[must_use]
+    pub fn html(&self) -> String {
+        html_report(&self.title, &self.entries)
+    }
+
+    /// Écriture de la page HTML du rapport dans le fichier `path`
+    /// ### Errors
+    /// Retourne un [`ReportError`] si le fichier ne peut pas être écrit
+    pub fn write_html(&self, path: impl AsRef<Path>) -> Result<(), ReportError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.html())
+            .map_err(|e| ReportError::Write(path.display().to_string(), e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_report() {
+        let config = SolverConfig::default();
+        let entries = vec![
+            ReportEntry::from_file("./test_grids/test01.txt", 1, &config).unwrap(),
+            ReportEntry::from_file("./test_grids/facile01_2.txt", 2, &config).unwrap(),
+        ];
+        let html = html_report("Grilles <test>", &entries);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Grilles &lt;test&gt;</h1>"));
+        assert!(html.contains("2 grille(s) résolue(s) sur 2"));
+        assert_eq!(html.matches("<div class=\"card\"").count(), 2);
+        assert_eq!(html.matches("<svg ").count(), 2);
+        assert!(html.contains("id=\"trace-2\""));
+        assert_eq!(
+            html.matches("<li>[niveau ").count(),
+            entries
+                .iter()
+                .map(|entry| entry.outcome.steps.len())
+                .sum::<usize>()
+        );
+
+        assert!(matches!(
+            ReportEntry::from_file("./test_grids/inconnu.txt", 1, &config),
+            Err(SolveFileError::Read(..))
+        ));
+    }
+
+    #[test]
+    fn test_directory_report() {
+        let dir = std::env::temp_dir().join("star_battle_test_directory_report");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::copy("./test_grids/test01.txt", dir.join("a.txt")).unwrap();
+        std::fs::write(dir.join("b.txt"), "ABBBB\nABBBB\n").unwrap();
+
+        let report = DirectoryReport::from_dir(&dir, 1, &SolverConfig::default()).unwrap();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(report.invalid.len(), 1);
+        assert_eq!(report.invalid[0].0, dir.join("b.txt"));
+        let out = dir.join("report.html");
+        report.write_html(&out).unwrap();
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), report.html());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(
+            DirectoryReport::from_dir(&dir, 1, &SolverConfig::default()),
+            Err(ReportError::ReadDir(..))
+        ));
+    }
+}
@@ -0,0 +1,27 @@
+//! Règle de construction échangée au format JSON (features `wasm` et `server`).
+
+use crate::GoodRule;
+
+/// Règle de construction avec son niveau et sa description textuelle
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct JsonRule {
+    /// Règle de construction (voir [`GoodRule`])
+    pub rule: GoodRule,
+
+    /// Niveau de la règle (voir [`crate::get_good_rule_with_level`])
+    pub level: usize,
+
+    /// Description textuelle de la règle
+    pub text: String,
+}
+
+impl JsonRule {
+    /// Constructeur selon une règle et son niveau
+    pub fn new(rule: GoodRule, level: usize) -> Self {
+        Self {
+            text: rule.to_string(),
+            rule,
+            level,
+        }
+    }
+}
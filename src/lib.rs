@@ -37,8 +37,12 @@ Le constructeur est une forme de [`TryFrom`] pour l'un des types suivants :
 Chaque ligne du texte (ou chaque élément du vecteur) correspond à une ligne de la grille à résoudre.<br>
 Les différentes régions de la grille sont identifiées par des caractères distincts dans les cases correspondantes.<br>
 Les espaces ou séparateurs équivalents (e.g. TAB) sont ignorés.<br>
-Les lignes 'vides' ou qui débutent par l'un des caractères suivants sont ignorées : '*', '#' ou '/'
+Les lignes 'vides' ou qui débutent par l'un des caractères suivants sont ignorées : '#', ';' ou '@'
 (considérés comme d'éventuels commentaires).<br>
+Une ligne qui débute par '* ' liste les étoiles 'données' par la grille selon leurs coordonnées (`* A1 C3`
+par exemple).<br>
+Le caractère '.' identifie une case 'bloquée' (trou ou case décorative) qui n'appartient à aucune région,
+ligne ou colonne et ne peut pas contenir d'étoile.<br>
 
 ```rust
 use star_battle::GridParser;
@@ -60,6 +64,31 @@ Le [`GridParser`] est utilisé pour définir la grille initiale. La cohérence d
 * Nombre cohérent de colonnes dans chaque ligne
 * Régions connexes dans la grille
 
+## [`PuzzleMetadata`]
+
+Les commentaires `# title: ...`, `# author: ...`, `# source: ...`, `# difficulty: ...` et `# stars: ...` du
+texte d'une grille définissent ses métadonnées [`PuzzleMetadata`] : [`GridParser::metadata`] et
+[`GridHandler::metadata`] les conservent pour les rapports et les exports (SVG, statistiques CSV, fichiers
+des grilles générées) afin de garder la provenance des grilles.
+
+```rust
+use star_battle::{GridHandler, GridParser};
+
+let grid_parser = GridParser::try_from(vec![
+    "# title: Exemple",
+    "# stars: 1",
+    "ABBBB",
+    "ABBBB",
+    "CCBBB",
+    "DDDDD",
+    "DEEED",
+])
+.unwrap();
+let handler = GridHandler::new(&grid_parser, 1);
+assert_eq!(handler.metadata().title.as_deref(), Some("Exemple"));
+assert_eq!(handler.metadata().nb_stars, Some(1));
+```
+
 ## [`LineColumn`]
 
 [`LineColumn`] repère une case dans la grille par ses coordonnées (`line`, `column`) base 0.
@@ -76,6 +105,21 @@ let line_column = LineColumn::new(0, 1);
 assert_eq!(line_column.line(), 0);
 assert_eq!(line_column.column(), 1);
 assert_eq!(format!("{}", line_column), "B1");
+assert_eq!("B1".parse::<LineColumn>(), Ok(line_column));
+```
+
+Les fonctions [`display_line`] et [`display_column`] affichent un numéro de ligne ou de colonne de la même
+façon et les fonctions [`parse_line`] et [`parse_column`] les relisent. Après la colonne 'Z', les colonnes
+sont numérotées comme celles d'un tableur ('AA', 'AB', ...).
+
+```rust
+use star_battle::{display_column, display_line, parse_column, parse_line};
+
+assert_eq!(display_line(0), "1");
+assert_eq!(display_column(1), "B");
+assert_eq!(parse_line("1"), Some(0));
+assert_eq!(parse_column("B"), Some(1));
+assert_eq!(display_column(26), "AA");
 ```
 
 ## [`CellValue`]
@@ -114,7 +158,8 @@ assert_eq!(grid_parser.cell(LineColumn::new(0, 0)).unwrap().value, CellValue::Un
 
 * `nb_lines`: nombre de lignes de la grille
 * `nb_columns`: nombre de colonnes de la grille
-* `nb_stars`: nombre d'étoiles à placer dans chaque ligne, colonne et région de la grille
+* `nb_stars`: nombre d'étoiles à placer (par défaut) dans chaque ligne, colonne et région de la grille
+* `zone_nb_stars`: nombre d'étoiles à placer dans une zone (ligne(s), colonne(s), région) de la grille
 * `regions`: liste des régions de la grille (par ordre de taille croissante)
 * `cell_region`: région d'une case de la grille
 
@@ -137,8 +182,65 @@ assert_eq!(grid.regions().len(), 5);
 assert_eq!(grid.cell_region(LineColumn::new(0, 0)), 'A');
 ```
 
+[`GridHandler::new`] panique si le nombre d'étoiles est incompatible avec la grille (trop d'étoiles pour une
+zone, nombre de régions différent du nombre de lignes, ...). Pour une grille qui provient de l'extérieur
+(fichier, requête HTTP, ...), [`GridHandler::try_new`] retourne une erreur à la place.
+
+```rust
+use star_battle::{GridParser, GridHandler};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DDDDD"]).unwrap();
+assert!(GridHandler::try_new(&grid_parser, 1).is_err());
+```
+
+Certaines variantes du jeu demandent un nombre d'étoiles différent selon les lignes, colonnes ou régions.
+Le [`GridHandler`] est alors construit avec [`GridHandler::with_zone_stars`] :
+
+```rust
+use star_battle::{GridParser, GridHandler, GridSurfer};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "AABBB", "DDDDD", "DEEED"]).unwrap();
+let grid = GridHandler::with_zone_stars(&grid_parser, 1, &[(GridSurfer::Region('A'), 2)]);
+
+assert_eq!(grid.zone_nb_stars(&GridSurfer::Region('A')), 2);
+assert_eq!(grid.zone_nb_stars(&GridSurfer::Line(0)), 1);
+```
+
+Une grille rectangulaire peut demander un nombre d'étoiles différent par ligne et par colonne (par exemple
+2 étoiles par ligne et 1 étoile par colonne pour une grille de 5 lignes et 10 colonnes) : le [`GridHandler`]
+est alors construit avec [`GridHandler::with_stars_per_zone`].
+
+Pour les variantes sans région (seules les lignes, les colonnes et l'adjacence des étoiles comptent), le
+[`GridHandler`] est construit avec [`GridHandler::without_regions`] : les règles liées aux régions sont alors
+ignorées.
+
+Les grilles du jeu Queens (une 'reine' par ligne, colonne et région de couleur, sans que 2 reines se
+touchent) sont des grilles 1★ : le [`GridHandler`] est construit avec [`GridHandler::queens`].
+
+Des zones supplémentaires (diagonales marquées, 'cages', ...) qui doivent aussi contenir un nombre donné
+d'étoiles sont ajoutées par [`GridHandler::with_cage`] : elles sont vérifiées et examinées par les règles au
+même titre que les lignes, les colonnes et les régions.
+
+Pour les variantes expérimentales sur une grille 'torique' (les lignes et les colonnes 'bouclent' : les cases
+des bords opposés de la grille sont adjacentes), le mode est activé par [`GridHandler::with_toroidal`].
+
 La fonction [`GridHandler::is_done`] retourne `true` si toutes les cases de la grille ont une valeur définie.
 
+La géométrie des régions est décrite par les cases voisines orthogonalement :
+[`GridHandler::region_perimeter_cells`] (cases en bordure de la région), [`GridHandler::region_interior_cells`],
+[`GridHandler::region_perimeter`] (nombre de côtés de cases en bordure) et [`GridHandler::region_frontier`]
+(couples de cases voisines de part et d'autre de la frontière entre 2 régions).
+
+```rust
+use star_battle::{GridParser, GridHandler, LineColumn};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid = GridHandler::new(&grid_parser, 1);
+
+assert_eq!(grid.region_perimeter('A'), 6);
+assert_eq!(grid.region_frontier('A', 'C'), vec![(LineColumn::new(1, 0), LineColumn::new(2, 0))]);
+```
+
 ## [`Grid`]
 
 [`Grid`] est la structure avec le contenu des cases de la grille.
@@ -177,6 +279,23 @@ assert_eq!(grid.cell(line_column).value, CellValue::Unknown);
 assert_eq!(grid_cloned.cell(line_column).value, CellValue::Star);
 ```
 
+Le contenu d'une grille peut être codé sur une seule ligne par [`Grid::encode`] (`*` pour une étoile, `-` pour
+une case sans étoile et `?` pour une case non définie) et relu par [`Grid::decode`], ce qui est pratique pour
+les traces ou les tests.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, LineColumn, CellValue};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let mut grid = Grid::from(&grid_handler);
+grid.cell_mut(LineColumn::new(0, 0)).value = CellValue::Star;
+
+let encoded = grid.encode();
+assert!(encoded.starts_with("*??"));
+assert_eq!(Grid::decode(&grid_handler, &encoded).unwrap(), grid);
+```
+
 ## [`GridSurfer`]
 
 [`GridSurfer`] est une  énumération qui permet de naviguer sur les case de la grille qui répondre à certains
@@ -206,6 +325,24 @@ let grid_surfer = grid_handler.surfer(&grid, &GridSurfer::Region('A'));
 assert_eq!(grid_surfer, vec![LineColumn::new(0, 0), LineColumn::new(1, 0)]);
 ```
 
+[`GridHandler::surfer_set`] retourne les mêmes cases sous la forme d'un [`CellSet`] (un bit par case de la
+grille) : tester l'appartenance d'une case à la zone ne nécessite alors pas de parcourir ses cases.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, LineColumn, GridSurfer};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let grid = Grid::from(&grid_handler);
+
+let region_cells = grid_handler.surfer_set(&grid, &GridSurfer::Region('A'));
+assert!(region_cells.contains(&LineColumn::new(1, 0)));
+assert!(!region_cells.contains(&LineColumn::new(1, 1)));
+```
+
+Les cases des régions, lignes, colonnes et cages sont calculées une fois pour toutes à la construction du
+[`GridHandler`] : [`GridHandler::zone_cells`] les retourne sans parcourir la grille ni allouer de liste.
+
 ## [`BadRuleError`]
 
 [`BadRuleError`] identifie une situation qui invalide le contenu d'une grille.
@@ -250,6 +387,24 @@ Ces actions sont liées au contenu possible d'une case de la grille défini par
 Symétriquement, le module [`Grid`] implémente la méthode [`Grid::apply_action`] qui permet d'appliquer une
 de ces actions à une case de la grille.
 
+Une étoile donnée par la définition de la grille (voir [`GridParser`]) ne peut pas être modifiée : l'action
+est alors refusée avec une erreur [`GridActionError`].
+
+La méthode [`Grid::apply_actions_checked`] applique une liste d'actions de manière atomique : si une
+action est refusée ou si la grille obtenue n'est pas valide (voir [`check_bad_rules`]), aucune action
+n'est appliquée et une erreur [`BadRuleError`] est retournée.
+
+De même, la méthode [`Grid::apply_good_rule_checked`] applique une règle de construction qui n'a pas été
+trouvée sur la grille elle-même (règle relue depuis une trace par exemple) : elle vérifie en plus que
+toutes les actions de la règle portent sur des cases non définies.
+
+Pour un éditeur interactif, l'action [`GridAction::Toggle`] passe une case à sa valeur suivante (Inconnu →
+Etoile → Pas d'étoile → Inconnu) et [`Grid::toggle_cells_checked`] passe d'un coup plusieurs cases à la
+valeur suivant celle de la première case (voir [`GridAction::toggle_all`]).
+
+Un [`GridObserver`] attaché à une grille par [`Grid::set_observer`] est prévenu de chaque changement
+de valeur d'une case par ces actions (pour animer l'affichage ou tracer les modifications).
+
 ```rust
 use star_battle::{GridParser, GridHandler, Grid, CellValue, GridAction, LineColumn};
 
@@ -257,11 +412,54 @@ let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD",
 let grid_handler = GridHandler::new(&grid_parser, 1);
 let mut grid = Grid::from(&grid_handler);
 
-grid.apply_action(&GridAction::SetStar(LineColumn::new(1, 1)));
+grid.apply_action(&GridAction::SetStar(LineColumn::new(1, 1))).unwrap();
 assert_eq!(grid.cell(LineColumn::new(1, 1)).value, CellValue::Star);
 
-GridAction::SetNoStar(LineColumn::new(1, 1)).apply_action(&mut grid);
+GridAction::SetNoStar(LineColumn::new(1, 1)).apply_action(&mut grid).unwrap();
 assert_eq!(grid.cell(LineColumn::new(1, 1)).value, CellValue::NoStar);
+
+grid.apply_action(&GridAction::Toggle(LineColumn::new(1, 1))).unwrap();
+assert_eq!(grid.cell(LineColumn::new(1, 1)).value, CellValue::Unknown);
+```
+
+# [`PencilMarks`]
+
+[`PencilMarks`] annote chaque case comme un joueur le fait au crayon : les contraintes qui empêchent d'y
+placer une étoile ([`StarBlockers`] : case définie, étoile adjacente, ligne, colonne, région ou cage
+complète). Attachées à une grille comme [`GridObserver`], les annotations sont tenues à jour à chaque
+action et peuvent être interrogées sans parcourir la grille.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, GridAction, LineColumn, PencilMarks, StarBlockers};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let mut grid = Grid::from(&grid_handler);
+let pencil_marks = PencilMarks::attach(&grid_handler, &mut grid);
+
+grid.apply_action(&GridAction::SetStar(LineColumn::new(0, 0))).unwrap();
+assert_eq!(pencil_marks.lock().unwrap().blockers(LineColumn::new(1, 1)), StarBlockers::ADJACENT);
+```
+
+# [`GridJournal`]
+
+[`GridJournal`] enveloppe une [`Grid`] en mémorisant ses modifications pour les annuler
+([`GridJournal::undo`]), les refaire ([`GridJournal::redo`]) ou revenir à un point de reprise nommé
+([`GridJournal::rollback`]) sans cloner toute la grille.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, GridAction, GridJournal, LineColumn};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let mut journal = GridJournal::from(Grid::from(&grid_handler));
+
+journal.checkpoint("hypothèse");
+journal.apply_action(&GridAction::SetStar(LineColumn::new(1, 1))).unwrap();
+assert!(journal.grid().cell(LineColumn::new(1, 1)).is_star());
+
+assert!(journal.rollback("hypothèse"));
+assert!(journal.grid().cell(LineColumn::new(1, 1)).is_unknown());
 ```
 
 # [`GoodRule`]
@@ -269,11 +467,17 @@ assert_eq!(grid.cell(LineColumn::new(1, 1)).value, CellValue::NoStar);
 [`GoodRule`] identifie les règles qui permettent d'avancer dans la construction/résolution d"une grille :
 
 * `NoStarAdjacentToStar(LineColumn, Vec<GridAction>)`:  Indique les cases adjacentes à une étoile qui ne peuvent
-   pas contenir une étoile et indique les actions à effectuer pour les définir
+  pas contenir une étoile et indique les actions à effectuer pour les définir
 * `ZoneNoStarCompleted`: Indique les cases restantes dans une zone ne peuvent pas être des étoiles
 * `ZoneStarCompleted`: Indique les cases restantes dans une zone sont forcement des étoiles
 * `InvariantWithZone(GridSurfer, Vec<GridAction>)`: Indique que quelle que soit la façon de placer les étoiles
-   dans une zone, des cases n'ont toujours qu'une seule et même possibilité
+  dans une zone, des cases n'ont toujours qu'une seule et même possibilité
+* `Contradiction(LineColumn, Vec<GridAction>)`: Indique qu'une étoile dans une case et ses conséquences
+  immédiates rendent la grille invalide (raisonnement par l'absurde, examiné après toutes les autres règles)
+* `BlockCounting(GridSurfer, Vec<GridAction>)`: Indique que chaque bloc de 2x2 cases qui peut encore recevoir
+  une étoile dans 2 lignes ou colonnes consécutives en contient forcément une
+* `LineSegments(GridSurfer, Vec<GridAction>)`: Indique que chaque segment de cases possibles d'une ligne ou
+  colonne contient le maximum d'étoiles qu'il peut recevoir
 
 La fonction [`get_good_rule`] recherche une règle [`GoodRule`] applicable à une grille.<br>
 Cette fonction retourne une erreur [`BadRuleError`] si la grille n'est pas valide.<br>
@@ -305,7 +509,9 @@ Les règles examinées sont :
 * On examine toutes les combinaisons de 1, 2, 3 ou 4 régions qui occupent respectivement uniquement 1, 2, 3 ou 4 lignes
   ou colonnes : S'il restent des cases n'appartenant pas à ces combinaisons dans ces lignes ou colonnes, elles ne
   peuvent pas contenir une étoile.<br>
-  (cette règle est l'inverse de la précédente)
+  (cette règle est l'inverse de la précédente)<br>
+  Les grandes grilles nécessitent parfois 5 ou 6 lignes ou colonnes : [`SolverConfig::with_max_band_size`]
+  étend ces 2 règles au-delà de 4 (voir aussi [`rule_region_n_exclusions`] et [`rule_region_n_combinations`])
 
 * Toutes les combinaisons possibles pour positionner une étoile dans une ligne ou colonne ont des
   cases toujours avec une étoile ou jamais une étoile dans toutes les grilles possibles pour ces combinaisons
@@ -314,6 +520,16 @@ Les règles examinées sont :
   (2, 3 ou 4) nt des cases toujours avec une étoile ou jamais une étoile dans toutes les grilles possibles
   pour ces combinaisons
 
+* Deux lignes ou colonnes consécutives sont découpées en blocs de 2x2 cases qui contiennent chacun au plus une
+  étoile : s'il y a autant de blocs qui peuvent encore recevoir une étoile que d'étoiles dans ces 2 lignes ou
+  colonnes, chaque bloc en contient une (règle examinée après les précédentes, voir
+  [`SolverConfig::with_levels`] pour l'examiner plus tôt)
+
+* Une ligne ou colonne est découpée en segments de cases consécutives qui peuvent contenir une étoile : un
+  segment de n cases contient au plus (n + 1) / 2 étoiles. Si la somme de ces maximums est égale au nombre
+  d'étoiles de la ligne ou colonne, chaque segment contient son maximum d'étoiles (règle peu coûteuse, examinée
+  après les précédentes pour ne pas modifier leur niveau)
+
 ```rust
 use star_battle::{GridParser, GridHandler, Grid, get_good_rule};
 
@@ -329,36 +545,778 @@ let good_rule = some_good_rule.unwrap();
 grid.apply_good_rule(&good_rule);
 ```
 
+La méthode [`GoodRule::kind`] retourne la catégorie [`RuleKind`] d'une règle avec son nom, sa difficulté
+et sa description pour regrouper ou libeller les règles. La méthode [`GoodRule::technique`] retourne son
+palier de technique [`Technique`] (élémentaire, intermédiaire, avancée ou force brute) : les paliers sont
+ordonnés pour filtrer les règles (par exemple n'indiquer que des règles élémentaires).
+
+La méthode [`GoodRule::explain`] explique une règle de façon concise (son affichage) ou pédagogique
+en détaillant son raisonnement selon le niveau de détail [`Verbosity`].
+
+La méthode [`GoodRule::explain_in`] explique une règle dans le contexte d'une grille : chaque case est
+annotée de sa région (`B4 (région C)`) et le nombre d'étoiles qui restent à placer dans la zone
+examinée est précisé.
+
+Les règles sont comparables et ordonnées. La méthode [`GoodRule::normalize`] trie et dédoublonne les actions
+(et les régions) d'une règle et remplace sa zone par sa forme canonique (`Lignes 3-3` devient `Ligne 3`) :
+deux règles normalisées identiques sont égales, ce qui permet de comparer ou dédoublonner des traces.
+
+La fonction [`get_good_rule_with_config`] permet de configurer la recherche avec un [`SolverConfig`] :
+niveau maximum des règles examinées, budget de grilles possibles examinées par zone et niveau de détail
+des explications des règles. Une zone dont la recherche en 'force brute' construit plus de
+[`MAX_EXPLORED_COMBINATIONS`] combinaisons est abandonnée comme si elle avait trop de grilles possibles.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, SolverConfig, get_good_rule_with_config};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let grid = Grid::from(&grid_handler);
+
+let config = SolverConfig::new().with_max_level(5).with_max_possible_grids(100);
+let (_, level) = get_good_rule_with_config(&grid_handler, &grid, &config).unwrap().unwrap();
+assert!(level <= 5);
+```
+
+Une application (interface graphique, serveur) peut limiter la recherche sur les grilles difficiles avec des
+[`SolverLimits`] : durée maximum, nombre maximum de combinaisons d'étoiles examinées et indicateur
+d'annulation positionné par un autre thread. La fonction [`try_get_good_rule`] signale alors une recherche
+interrompue ([`Interruption`]) et la résolution d'une grille (voir [`solve_grid`]) s'arrête avec la cause de
+l'interruption dans [`SolveOutcome::interruption`].
+
+```rust
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use star_battle::{GridParser, GridHandler, Grid, GoodRuleError, Interruption, SolverConfig, SolverLimits};
+use star_battle::try_get_good_rule;
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let grid = Grid::from(&grid_handler);
+
+let cancel_flag = Arc::new(AtomicBool::new(true));
+let config = SolverConfig::new().with_limits(SolverLimits::new().with_cancel_flag(cancel_flag));
+assert_eq!(
+    try_get_good_rule(&grid_handler, &grid, &config),
+    Err(GoodRuleError::Interrupted(Interruption::Cancelled))
+);
+```
+
+Une recherche déterministe ([`SolverConfig::with_deterministic`]) ignore la durée maximum et examine les
+zones et les cases séquentiellement : les règles trouvées ne dépendent que de la grille et de la
+configuration (pour des tests reproductibles par exemple). [`SolverConfig::with_parallel`] permet aussi
+de désactiver l'examen en parallèle de la feature `parallel` sans imposer une recherche déterministe.
+
+La fonction [`get_good_rule_with_strategy`] examine les niveaux de règles dans l'ordre d'une stratégie
+([`RuleStrategy`]) : ordre des niveaux ([`FixedOrder`], par défaut), les moins coûteuses d'abord
+([`CheapestFirst`]), les règles sur les régions d'abord ([`RegionFirst`]) ou le niveau de la dernière règle
+trouvée d'abord ([`LastSuccessfulFirst`]). Une application peut aussi définir sa propre stratégie. Le niveau
+retourné reste celui de la règle trouvée.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, LastSuccessfulFirst, SolverConfig};
+use star_battle::get_good_rule_with_strategy;
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let mut grid = Grid::from(&grid_handler);
+
+let config = SolverConfig::default();
+let mut strategy = LastSuccessfulFirst::new();
+while let Some((good_rule, _)) =
+    get_good_rule_with_strategy(&grid_handler, &grid, &config, Some(&mut strategy)).unwrap()
+{
+    grid.apply_good_rule(&good_rule);
+}
+assert!(grid_handler.is_done(&grid));
+```
+
+Une application peut ajouter ses propres règles de construction aux règles intégrées : une règle ([`Rule`])
+cherche une [`GoodRule`] applicable à la grille et précise son nom et son niveau de coût. Ajoutée à la
+configuration par [`SolverConfig::with_rule`], elle est examinée juste après les règles intégrées de ce
+niveau et trouvée avec ce niveau. Une règle trouvée qui ne modifie aucune case non définie ou qui rend la
+grille invalide est ignorée.
+
+```rust
+use star_battle::{GoodRule, Grid, GridHandler, GridParser, Rule, SolverConfig};
+use star_battle::get_good_rule_with_config;
+
+/// Règle qui ne trouve jamais rien
+struct NoRule;
+
+impl Rule for NoRule {
+    fn apply(&self, _handler: &GridHandler, _grid: &Grid) -> Option<GoodRule> {
+        None
+    }
+
+    fn name(&self) -> &str {
+        "Aucune règle"
+    }
+
+    fn cost(&self) -> usize {
+        0
+    }
+}
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let grid = Grid::from(&grid_handler);
+
+let config = SolverConfig::default().with_rule(NoRule);
+assert!(get_good_rule_with_config(&grid_handler, &grid, &config).unwrap().is_some());
+```
+
+Les règles, actions, zones et erreurs sont affichées en français. Leurs méthodes `text` (par exemple
+[`GoodRule::text`] et [`BadRuleError::text`]) produisent le même texte dans une autre langue ([`Locale`]) :
+la langue d'une configuration ([`SolverConfig::with_locale`]) est celle des indices ([`Hint`]) et de la
+commande `star-battle <grille> --lang en`.
+
+```rust
+use star_battle::{GoodRule, GridAction, GridSurfer, LineColumn, Locale};
+
+let good_rule = GoodRule::ZoneNoStarCompleted(
+    GridSurfer::Line(0),
+    vec![GridAction::SetNoStar(LineColumn::new(0, 2))],
+);
+assert_eq!(
+    good_rule.text(Locale::English),
+    "The remaining cells of Line 1 cannot contain a star : C1->No star"
+);
+assert_eq!(good_rule.text(Locale::French), good_rule.to_string());
+```
+
+# [`next_hint`]
+
+La fonction [`next_hint`] retourne un [`Hint`] pour la prochaine étape de la résolution d'une grille, sans
+appliquer la règle : la règle de construction, les cases examinées, les cases modifiées et une explication
+courte de la règle (sans ses actions). Un entraîneur peut ainsi guider l'élève pas à pas.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, next_hint};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let grid = Grid::from(&grid_handler);
+
+let hint = next_hint(&grid_handler, &grid).unwrap().unwrap();
+assert!(!hint.zone_cells().is_empty());
+assert_eq!(hint.cells().len(), hint.good_rule().actions().len());
+println!("{}", hint.text());
+```
+
+# [`solve_file`]
+
+La fonction [`solve_file`] lit le fichier d'une grille et la résout en appliquant les règles de construction
+selon un [`SolverConfig`] (la fonction [`solve_grid`] fait de même pour un [`GridParser`]).<br>
+Le [`SolveOutcome`] retourné contient les règles appliquées, la grille obtenue et une éventuelle
+incohérence qui a interrompu la résolution.
+
+```rust
+use star_battle::{solve_file, SolverConfig};
+
+let outcome = solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap();
+assert!(outcome.is_solved());
+```
+
+[`solve_file_with_steps`] appelle une fonction dès qu'une règle est trouvée, avec la grille à laquelle elle
+s'applique : la commande `star-battle` affiche ainsi la résolution d'une grille difficile au fur et à
+mesure.
+
+```rust
+use star_battle::{solve_file_with_steps, SolverConfig};
+
+let mut nb_rules = 0;
+let outcome = solve_file_with_steps("./test_grids/test01.txt", 1, &SolverConfig::default(), |_, _, _| {
+    nb_rules += 1;
+})
+.unwrap();
+assert_eq!(nb_rules, outcome.steps.len());
+```
+
+[`SolveMetrics::measure`] relève l'effort d'une résolution : règles trouvées par niveau, zones examinées
+par la recherche des grilles possibles (selon la stratégie utilisée), grilles possibles explorées, pic
+mémoire de ces grilles et durée. Ses statistiques par niveau de règle ([`SolveStats`]) donnent pour chaque
+niveau le nombre de recherches, de règles trouvées, la durée cumulée et les grilles possibles explorées
+([`RuleStats`]). La commande `star-battle <grille> --metrics` affiche ce résumé à la fin de la résolution.
+
+```rust
+use star_battle::{solve_file, SolveMetrics, SolverConfig};
+
+let (outcome, metrics) = SolveMetrics::measure(|| {
+    solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap()
+});
+assert_eq!(metrics.nb_rules(), outcome.steps.len());
+let slowest_level = metrics.stats.slowest_level().unwrap();
+assert!(metrics.stats.levels[slowest_level].invocations > 0);
+```
+
+La fonction [`solve_with_report`] résout une grille en conservant un [`SolveReport`] : pour chaque règle
+appliquée ([`SolveReportStep`]), ses actions, la grille avant et après la règle et la durée de l'étape.
+
+```rust
+use star_battle::{GridParser, SolverConfig, solve_with_report};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let report = solve_with_report(&grid_parser, 1, &SolverConfig::default());
+assert!(report.outcome.is_solved());
+for step in &report.steps {
+    assert_ne!(step.before, step.after);
+}
+```
+
+Pour piloter soi-même la résolution, [`GridHandler::steps`] retourne un itérateur [`SolverSteps`] : chaque
+itération applique à la grille la règle de construction suivante et la retourne. L'itération s'arrête
+quand aucune règle n'est applicable ou après une incohérence de la grille.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let mut grid = Grid::from(&grid_handler);
+for step in grid_handler.steps(&mut grid) {
+    println!("{}", step.unwrap());
+}
+assert!(grid_handler.is_done(&grid));
+```
+
+Pour une longue résolution, [`Checkpoint::solve`] écrit régulièrement l'état de la grille et la trace
+des règles appliquées dans un fichier de reprise (voir [`CheckpointConfig`]) : après une interruption,
+[`Checkpoint::load`] relit ce fichier et la résolution reprend là où elle s'était arrêtée.
+[`solve_file_with_checkpoint`] résout ainsi la grille d'un fichier.
+
+La fonction [`html_report`] produit une page HTML autonome avec une carte par grille résolue
+([`ReportEntry`]) : la grille au format SVG, sa difficulté, la durée de sa résolution et la trace complète
+des règles appliquées. [`DirectoryReport::from_dir`] résout toutes les grilles d'un répertoire (voir
+[`list_grid_files`]) pour produire ce rapport, comme la commande `star-battle report <répertoire>`.
+
+Pour un tutoriel, une [`TeachingSession`] applique les règles une par une et attend la confirmation de
+l'élève après chacune d'elles ([`TeachingEvent`]) : l'élève peut continuer, arrêter ou jouer lui-même des
+actions avant la règle suivante ([`TeachingResponse`]).
+
+```rust
+use star_battle::{Grid, GridHandler, GridParser, SolverConfig, TeachingResponse, TeachingSession};
+
+let parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let handler = GridHandler::new(&parser, 1);
+let grid = Grid::from(&handler);
+let session = TeachingSession::new(handler, grid, SolverConfig::default());
+let outcome = session.run(|_event, _grid| TeachingResponse::Continue);
+assert!(outcome.is_solved());
+```
+
+La méthode [`SolveOutcome::stats_csv_record`] résume une résolution sous la forme d'une ligne CSV
+(colonnes de [`STATS_CSV_HEADER`]) pour analyser un ensemble de grilles avec un tableur : la fonction
+[`append_stats_csv`] ajoute cette ligne à un fichier CSV.
+
+# [`rate_grid`]
+
+La fonction [`rate_grid`] évalue la difficulté d'une grille en la résolvant avec les règles de
+construction : chaque règle a un niveau (voir [`get_good_rule_with_level`]) et le score de la grille
+est la somme des niveaux des règles appliquées. Ce score donne une [`Difficulty`].<br>
+Le [`GridRating`] obtenu détaille aussi les catégories de règles ([`RuleKind`]) nécessaires à la
+résolution, la plus difficile d'entre elles et la taille de la plus grande zone examinée.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, rate_grid, Difficulty};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let rating = rate_grid(&grid_handler, &Grid::from(&grid_handler)).unwrap();
+assert_eq!(rating.difficulty(), Some(Difficulty::Easy));
+```
+
+# [`classify_guessing`]
+
+La fonction [`classify_guessing`] indique si une grille est résolue par les règles de construction
+seules. Sinon, elle indique où se produit la première hypothèse inévitable ([`Bifurcation`] : grille
+bloquée, zone et case de l'hypothèse) et la profondeur de la recherche nécessaire (nombre maximum
+d'hypothèses imbriquées) : un signal important de la qualité d'une grille générée.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, SolverConfig, classify_guessing};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let report = classify_guessing(&grid_handler, &Grid::from(&grid_handler), &SolverConfig::default())
+    .unwrap();
+assert!(report.is_logical());
+```
+
+# [`puzzle_quality`]
+
+La fonction [`puzzle_quality`] évalue la 'qualité' d'une grille : variété des règles nécessaires à sa
+résolution, absence de régions 'triviales' et régularité de la taille des régions.<br>
+Le score global [`PuzzleQuality::score`] (entre 0 et 100) permet d'écarter les grilles peu intéressantes.
+
+```rust
+use star_battle::{GridParser, GridHandler, puzzle_quality};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let quality = puzzle_quality(&grid_handler).unwrap();
+assert_eq!(quality.nb_trivial_regions, 0);
+```
+
+# [`count_solutions`]
+
+La fonction [`count_solutions`] compte les solutions d'une grille par une recherche exhaustive
+('force brute').<br>
+La recherche s'arrête dès que le nombre maximum de solutions demandé est atteint.<br>
+La fonction [`find_solutions`] retourne les grilles solutions trouvées.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, count_solutions};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let grid = Grid::from(&grid_handler);
+
+assert_eq!(count_solutions(&grid_handler, &grid, 2), 1);
+```
+
+La fonction [`cross_check_rules`] confronte les règles de construction à cette recherche exhaustive :
+chaque action des règles appliquées pour résoudre une grille doit être vérifiée par toutes ses
+solutions. Ce contrôle permet de valider une nouvelle règle sur des grilles générées aléatoirement.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, cross_check_rules};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+assert!(cross_check_rules(&grid_handler, &Grid::from(&grid_handler), 10).is_ok());
+```
+
+# [`Solution`]
+
+Une [`Solution`] est la liste des coordonnées des étoiles d'une grille résolue, extraite par
+[`GridHandler::extract_solution`].<br>
+Deux solutions peuvent être comparées ([`Solution::differences`]), une solution peut être appliquée sur
+une grille vide ([`Solution::to_grid`]) et sa définition textuelle (`A1 C3 ...`) est obtenue par `Display`
+et relue par `FromStr`.
+
+```rust
+use star_battle::{GridParser, GridHandler, Grid, Solution, find_solutions};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let grid_handler = GridHandler::new(&grid_parser, 1);
+let grid = Grid::from(&grid_handler);
+
+let solved_grid = &find_solutions(&grid_handler, &grid, 1)[0];
+let solution = grid_handler.extract_solution(solved_grid).unwrap();
+assert_eq!(solution.to_string().parse::<Solution>().unwrap(), solution);
+assert_eq!(&solution.to_grid(&grid_handler), solved_grid);
+```
+
+# [`GridGenerator`]
+
+[`GridGenerator`] génère des grilles carrées à solution unique :
+
+* Les étoiles d'une solution sont placées aléatoirement
+* La grille est découpée aléatoirement en régions autour de ces étoiles
+* Les grilles qui ont plusieurs solutions sont réparées (déplacement de cases vers une région voisine)
+  ou rejetées
+
+Le nombre de solutions de chaque grille générée est celui trouvé par la recherche exhaustive qui a validé
+son découpage final en régions (voir [`GeneratedPuzzle::is_unique`]).
+
+Le nombre de tentatives est limité (voir [`GridGenerator::with_max_attempts`]) et des statistiques
+[`GeneratorStats`] sont disponibles pour chaque grille générée [`GeneratedPuzzle`].
+
+```rust
+use star_battle::{GridGenerator, GridHandler, Grid, count_solutions};
+
+let mut rng = rand::thread_rng();
+let mut generator = GridGenerator::new(6, 1);
+let puzzle = generator.generate(&mut rng).unwrap();
+
+let grid_handler = GridHandler::new(&puzzle.parser, puzzle.nb_stars);
+assert_eq!(count_solutions(&grid_handler, &Grid::from(&grid_handler), 2), 1);
+```
+
+Le générateur accepte n'importe quel générateur aléatoire `rand::Rng` ou une simple graine (voir
+[`GridGenerator::generate_with_seed`]) : la même graine produit toujours la même grille, ce qui permet de
+partager l'identifiant d'une grille ou d'écrire des tests déterministes.
+
+Une [`GeneratorConfig`] regroupe tous les paramètres d'une génération (dont la graine du générateur
+aléatoire) : la même configuration produit toujours la même grille.
+
+```rust
+use star_battle::GeneratorConfig;
+
+let config = GeneratorConfig::new(6, 1, 1234);
+let puzzle = config.generate().unwrap();
+assert_eq!(puzzle.parser.list_cells(), config.generate().unwrap().parser.list_cells());
+```
+
+[`GeneratorConfig::generate_files`] écrit une série de grilles dans des fichiers numérotés, comme la
+commande `star-battle generate`.
+
+La forme des régions peut être contrainte (voir [`GridGenerator::with_region_size_range`] et
+[`GridGenerator::with_max_snakiness`]) pour obtenir des régions 'compactes' comme celles des grilles publiées.
+
+Une difficulté minimum peut être demandée (voir [`GridGenerator::with_target_difficulty`]) : les
+grilles générées sont alors modifiées pour être plus difficiles tout en restant à solution unique.
+Un palier de technique peut aussi être imposé (voir [`GridGenerator::with_target_technique`]) : la
+résolution des grilles générées nécessite alors une règle de ce palier [`Technique`] sans nécessiter de
+règle d'un palier supérieur (par exemple des invariants sans raisonnement par l'absurde).
+
+Le style [`RegionStyle::Queens`] (voir [`GridGenerator::with_region_style`]) produit des grilles
+1★ dont les régions en 'bandes' rappellent celles du jeu Queens.
+
+Le générateur peut aussi produire des grilles dont les régions sont symétriques (voir
+[`GridGenerator::with_symmetry`]) pour obtenir des grilles d'apparence 'faites main'.
+
+Une grille peut aussi être construite autour d'un placement d'étoiles choisi (voir
+[`GridGenerator::generate_from_solution`] et [`puzzle_from_solution`]) pour transformer un motif
+d'étoiles en grille à solution unique.
+
+```rust
+use star_battle::{puzzle_from_solution, LineColumn};
+
+let stars: Vec<LineColumn> = [(0, 0), (1, 2), (2, 4), (3, 1), (4, 3)]
+    .into_iter()
+    .map(|(line, column)| LineColumn::new(line, column))
+    .collect();
+let grid_parser = puzzle_from_solution(&stars, 1).unwrap();
+assert_eq!(grid_parser.regions().len(), 5);
+```
+
+La fonction [`generate_regions`] découpe seulement une grille rectangulaire en un nombre donné de
+régions connexes de tailles voisines, sans placement d'étoiles ni vérification de l'unicité de la
+solution : un point de départ pour des expérimentations ou d'autres générateurs.
+
+```rust
+use star_battle::{generate_regions, GridParser};
+
+let regions = generate_regions(6, 8, 8, &mut rand::thread_rng()).unwrap();
+let lines: Vec<String> = regions.iter().map(|line| line.iter().collect()).collect();
+let grid_parser = GridParser::try_from(lines).unwrap();
+assert_eq!(grid_parser.regions().len(), 8);
+```
+
+# [`StarConfigurations`]
+
+[`StarConfigurations`] énumère (ou tire au hasard de façon uniforme) les placements d'étoiles valides
+d'une grille vide, sans tenir compte des régions.
+
+```rust
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use star_battle::StarConfigurations;
+
+let mut configurations = StarConfigurations::new(6, 1);
+assert_eq!(configurations.count(), 90);
+assert_eq!(configurations.iter().count(), 90);
+
+let mut rng = StdRng::seed_from_u64(0);
+let stars = configurations.sample(&mut rng).unwrap();
+assert_eq!(stars.len(), 6);
+```
+
+# [`check_puzzle`]
+
+La fonction [`check_puzzle`] vérifie qu'un découpage en régions et un placement complet des étoiles
+(par exemple dessinés dans un éditeur) forment une grille valide à solution unique.<br>
+Tous les problèmes détectés sont retournés sous forme de [`PuzzleIssue`].
+
+```rust
+use star_battle::{check_puzzle, GridParser, LineColumn, PuzzleIssue};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let stars: Vec<LineColumn> = [(0, 0), (1, 3), (2, 1), (3, 4), (4, 2)]
+    .iter()
+    .map(|(line, column)| LineColumn::new(*line, *column))
+    .collect();
+assert!(check_puzzle(&grid_parser, 1, &stars).is_ok());
+
+let issues = check_puzzle(&grid_parser, 1, &stars[1..]).unwrap_err();
+assert!(matches!(issues[0], PuzzleIssue::WrongNbStarsInZone(_, 0, 1)));
+```
+
+# [`GridTransform`]
+
+[`GridTransform`] liste les rotations et symétries d'une grille qui conservent les règles du jeu.<br>
+[`GridParser::transform`] retourne la définition d'une grille après une de ces transformations.
+
+```rust
+use star_battle::{GridParser, GridTransform, LineColumn};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let rotated = grid_parser.transform(GridTransform::Rotate90);
+assert_eq!(rotated.cell_region(LineColumn::new(0, 4)), 'A');
+```
+
+[`GridParser::canonical`] retourne la forme canonique d'une grille : deux grilles identiques à une
+transformation et un renommage des régions près ont la même forme canonique
+(voir [`GridParser::is_isomorphic`]).<br>
+La fonction [`find_duplicates`] regroupe ainsi les grilles en double d'un répertoire et
+[`DedupeReport::remove_redundant_files`] supprime les doublons.
+
+```rust
+use star_battle::{GridParser, GridTransform};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let other = GridParser::try_from(vec!["xyyyy", "xyyyy", "zzyyy", "wwwww", "wvvvw"]).unwrap();
+assert!(other.transform(GridTransform::Transpose).is_isomorphic(&grid_parser));
+```
+
+# Notation 'SBN'
+
+[`GridParser::try_from_sbn`] construit une grille à partir de la notation compacte des sites de Star Battle
+(`<colonnes>/<lignes>/<étoiles>/<bords>`, où les régions sont codées par leurs bords, ou l'URL complète
+d'une grille puzz.link) et [`GridParser::to_sbn`] produit cette notation pour un nombre d'étoiles.
+
+```rust
+use star_battle::GridParser;
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let sbn = grid_parser.to_sbn(1).unwrap();
+let parsed = GridParser::try_from_sbn(&sbn).unwrap();
+assert_eq!(parsed.metadata().nb_stars, Some(1));
+assert_eq!(parsed.regions(), grid_parser.regions());
+```
+
+# Import des grilles des sites
+
+[`GridParser::try_from_task`] construit une grille à partir de la 'tâche' du site puzzle-star-battle.com
+(numéros de région des cases séparés par des virgules dans le source de la page) : une grille du site est
+ainsi résolue par un simple copier-coller.
+
+Les recueils téléchargeables de KrazyDad et Puzzle Baron décrivent leurs grilles par des lignes de numéros
+de région : [`GridParser::try_from_region_numbers`] importe une grille et [`parse_region_number_book`] toutes
+les grilles d'un recueil (séparées par des lignes vides ou des lignes de titre).
+
+[`GridParser::try_from_csv`] importe une grille exportée d'un tableur (fichier CSV ou TSV avec un
+identifiant de région par cellule). [`solve_file`] l'utilise pour les fichiers d'extension `.csv` ou `.tsv`.
+
+```rust
+use star_battle::GridParser;
+
+let grid_parser = GridParser::try_from_task("1,1,2,2,1,3,3,2,4,4,3,3,4,4,4,4").unwrap();
+assert_eq!(grid_parser.to_string(), "AABB\nACCB\nDDCC\nDDDD");
+```
+
+# Feature `serde`
+
+Avec la feature `serde`, les types publics ([`GridParser`], [`GridHandler`], [`Grid`], [`GridAction`],
+[`GoodRule`], [`GridSurfer`], [`BadRuleError`], [`GoodRuleError`], [`Hint`], ...) implémentent `Serialize`
+et `Deserialize` pour sauvegarder les traces de résolution et les indices ou les échanger avec une
+interface graphique.
+
+`PuzzleJson` est la représentation JSON documentée d'une grille (dimensions, nombre d'étoiles, carte des
+régions, étoiles données, contenu optionnel des cases et métadonnées) : elle est construite à partir d'un
+[`GridParser`] ou d'un [`GridHandler`] et produit la grille correspondante. Avec la feature `json`, ses
+méthodes `to_json` et `from_json` l'échangent directement sous forme de texte.
+
+```rust
+# #[cfg(feature = "json")]
+# {
+use star_battle::{GridParser, PuzzleJson};
+
+let grid_parser = GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+let text = PuzzleJson::from_parser(&grid_parser, 1).to_json().unwrap();
+let grid_handler = PuzzleJson::from_json(&text).unwrap().handler().unwrap();
+assert_eq!(grid_handler.nb_stars(), 1);
+# }
+```
+
+# Feature `json`
+
+La méthode [`SolveOutcome::log_steps`] retourne le journal structuré d'une résolution : pour chaque règle
+appliquée ([`SolveLogStep`]), sa catégorie, son niveau, sa zone, ses actions et la grille obtenue. Avec la
+feature `json`, la fonction `solve_log_json` produit ce journal au format JSON (un objet par ligne) et la
+commande `star-battle <grille> --log json` l'affiche à la place du texte de la résolution.
+
+# Feature `wasm`
+
+Avec la feature `wasm`, `WasmSolver` exporte vers `JavaScript` (via `wasm-bindgen`) une résolution pas à pas :
+construction à partir du texte d'une grille, prochaine règle de construction au format JSON, application
+de cette règle et affichage de la grille au format SVG (voir [`GridHandler::display_svg`]). La librairie
+WebAssembly est compilée en `cdylib` uniquement pour cette cible :
+`cargo rustc --lib --release --features wasm --target wasm32-unknown-unknown --crate-type cdylib`.
+
+# Feature `server`
+
+Avec la feature `server`, `serve` lance un service HTTP (basé sur `axum`) avec une petite API REST au format
+JSON : `POST /solve` résout une grille et `POST /hint` retourne la prochaine règle de construction applicable
+à une grille dans un état donné (voir [`Grid::encode`]). Ce service est lancé par la commande
+`star-battle serve --port 8080` sur l'interface locale (`serve_on` choisit une autre interface). Une requête
+invalide est refusée (statut 400) et chaque recherche est limitée à `REQUEST_MAX_DURATION`.
+
+# Feature `config`
+
+Avec la feature `config`, une configuration [`SolverConfig`] peut être lue dans un fichier TOML avec
+`SolverConfig::from_path` : règles examinées et ordre de leur examen (`levels`), niveau maximum des règles
+(`max_level`), budget de grilles possibles par zone (`max_possible_grids`), niveau de détail des
+explications (`verbosity`), langue des règles (`locale`) et stratégie imposée pour la recherche des grilles possibles
+(`collector_strategy`, voir [`CollectorStrategy`]). Les paramètres absents du fichier gardent leur valeur par défaut. La commande
+`star-battle <grille> --config <fichier>` utilise cette configuration pour résoudre la grille.
+La commande lit aussi ses options par défaut (nombre d'étoiles, explications détaillées et fichier de
+configuration des règles) dans `~/.config/star_battle/config.toml`.
+
+# Feature `fixtures`
+
+Avec la feature `fixtures`, les grilles d'exemple du répertoire `test_grids/` sont embarquées dans la
+librairie : `corpus::iter()` retourne le nom, le nombre d'étoiles et le [`GridParser`] de chacune de ces
+grilles, sans dépendre de chemins de fichiers.
+
+# Feature `tracing`
+
+Avec la feature `tracing`, la recherche des règles de construction est instrumentée avec des spans et des
+événements `tracing` : [`get_good_rule`] et ses variantes (un span par niveau de règle examiné), chaque
+règle de construction, l'examen de chaque zone (nombre de grilles possibles collectées, abandon de la zone)
+et les collectes de grilles possibles. Un `subscriber` standard (par exemple `tracing-subscriber`)
+permet alors de repérer la règle ou la zone qui consomme le plus de temps.
+
+# Feature `parallel`
+
+Avec la feature `parallel`, les traitements indépendants sont répartis entre plusieurs threads (via
+`rayon`) : [`solve_grids`] et [`ReportEntry::from_files`] résolvent plusieurs grilles en parallèle, par
+exemple pour la commande `star-battle report`. Les résultats sont toujours fusionnés dans l'ordre des
+grilles : les règles appliquées sont exactement les mêmes qu'en mode séquentiel.
+
+La recherche d'une règle répartit aussi entre les threads l'examen des zones par les règles qui
+recherchent les grilles possibles d'une zone (les plus lentes sur les grandes grilles 2★) et l'examen des
+cases par le raisonnement par l'absurde. La règle retenue est toujours la même qu'en mode séquentiel : la
+zone qui a le moins de grilles possibles ou la première case dans l'ordre de la grille. Les niveaux de
+règles restent examinés l'un après l'autre : une règle simple trouvée au niveau 0 n'attend pas la fin de
+l'examen des règles les plus coûteuses. Une configuration peut imposer un examen séquentiel (voir
+[`SolverConfig::with_parallel`]).
+
 */
 
 /// Une région est identifiée par un caractère.
 pub type Region = char;
 
 // Modules
+#[cfg(test)]
+#[macro_use]
+mod test_utils; // Macro `grid!` : doit précéder les modules qui l'utilisent
+mod cell_set;
 mod cell_value;
+#[cfg(feature = "fixtures")]
+pub mod corpus;
 mod grid;
 mod grid_action;
 mod grid_bad_ruler;
+mod grid_brute_force;
 mod grid_cell;
+mod grid_cells;
+mod grid_checkpoint;
+mod grid_cross_check;
+mod grid_dedupe;
+mod grid_generator;
 mod grid_good_ruler;
+mod grid_guessing;
 mod grid_handler;
+mod grid_hint;
+mod grid_import;
+mod grid_journal;
+mod grid_observer;
 mod grid_parser;
 mod grid_parser_checker;
+mod grid_pencil_marks;
+mod grid_quality;
+mod grid_rating;
+mod grid_sbn;
+mod grid_solution;
+mod grid_solve;
 mod grid_surfer;
+mod grid_svg;
+mod grid_teaching;
+mod grid_transform;
+mod html_report;
+#[cfg(any(feature = "wasm", feature = "server"))]
+mod json_rule;
 mod line_column;
+mod locale;
+mod parallel;
+#[cfg(test)]
+mod proptests;
+mod puzzle_checker;
+#[cfg(feature = "serde")]
+mod puzzle_json;
+mod puzzle_metadata;
+mod region_geometry;
+#[cfg(feature = "server")]
+mod server;
+mod solve_log;
+mod solve_metrics;
+mod solve_report;
+mod solver_steps;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 // Internal
 use grid_parser_checker::GridParserChecker;
-use line_column::{display_column, display_line};
 
 // Exported
+pub use cell_set::CellSet;
 pub use cell_value::CellValue;
 pub use grid::Grid;
-pub use grid_action::GridAction;
+pub use grid_action::{GridAction, GridActionError};
 pub use grid_bad_ruler::{check_bad_rules, BadRuleError};
+pub use grid_brute_force::{count_solutions, find_solutions};
 pub use grid_cell::GridCell;
-pub use grid_good_ruler::{get_good_rule, GoodRule};
+pub use grid_checkpoint::{
+    Checkpoint, CheckpointConfig, CheckpointError, DEFAULT_CHECKPOINT_INTERVAL,
+};
+pub use grid_cross_check::{cross_check_rules, CrossCheckError};
+pub use grid_dedupe::{
+    find_duplicates, list_grid_files, DedupeError, DedupeReport, GRID_FILE_EXTENSION,
+};
+pub use grid_generator::{
+    generate_regions, puzzle_from_solution, GeneratedPuzzle, GeneratorConfig, GeneratorError,
+    GeneratorStats, GridGenerator, RegionStyle, StarConfigurations, StarConfigurationsIter,
+};
+#[cfg(feature = "config")]
+pub use grid_good_ruler::SolverConfigError;
+pub use grid_good_ruler::{
+    get_good_rule, get_good_rule_with_config, get_good_rule_with_level,
+    get_good_rule_with_strategy, rule_region_n_combinations, rule_region_n_exclusions,
+    try_get_good_rule, CheapestFirst, CollectorStrategy, FixedOrder, GoodRule, GoodRuleError,
+    Interruption, LastSuccessfulFirst, RegionFirst, Rule, RuleKind, RuleStrategy, SolverConfig,
+    SolverLimits, Technique, Verbosity, BRUTE_FORCE_MAX_COMBINATIONS, MAX_EXPLORED_COMBINATIONS,
+    NB_GOOD_RULE_LEVELS,
+};
+pub use grid_guessing::{classify_guessing, Bifurcation, GuessingReport};
 pub use grid_handler::GridHandler;
+pub use grid_hint::{next_hint, next_hint_with_config, Hint};
+pub use grid_import::parse_region_number_book;
+pub use grid_journal::GridJournal;
+pub use grid_observer::{GridObserver, SharedGridObserver};
 pub use grid_parser::GridParser;
+pub use grid_pencil_marks::{PencilMarks, StarBlockers};
+pub use grid_quality::{puzzle_quality, PuzzleQuality};
+pub use grid_rating::{rate_grid, Difficulty, GridRating};
+pub use grid_solution::Solution;
+pub use grid_solve::{
+    append_stats_csv, solve_file, solve_file_with_checkpoint, solve_file_with_steps, solve_grid,
+    solve_grids, SolveFileError, SolveOutcome, STATS_CSV_HEADER,
+};
 pub use grid_surfer::GridSurfer;
-pub use line_column::LineColumn;
+pub use grid_teaching::{TeachingEvent, TeachingResponse, TeachingSession};
+pub use grid_transform::GridTransform;
+pub use html_report::{html_report, DirectoryReport, ReportEntry, ReportError};
+#[cfg(any(feature = "wasm", feature = "server"))]
+pub use json_rule::JsonRule;
+pub use line_column::{display_column, display_line, parse_column, parse_line, LineColumn};
+pub use locale::Locale;
+pub use puzzle_checker::{check_puzzle, PuzzleIssue};
+#[cfg(feature = "serde")]
+pub use puzzle_json::PuzzleJson;
+pub use puzzle_metadata::PuzzleMetadata;
+#[cfg(feature = "server")]
+pub use server::{
+    hint_request, router, serve, serve_on, solve_request, HintRequest, HintResponse, SolveRequest,
+    SolveResponse, DEFAULT_HOST, REQUEST_MAX_DURATION,
+};
+#[cfg(feature = "json")]
+pub use solve_log::solve_log_json;
+pub use solve_log::SolveLogStep;
+pub use solve_metrics::{RuleStats, SolveMetrics, SolveStats};
+pub use solve_report::{solve_with_report, SolveReport, SolveReportStep};
+pub use solver_steps::SolverSteps;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmSolver;
@@ -1,9 +1,11 @@
 //! Help for grid line and column coordinates.
 
 use std::fmt::Display;
+use std::str::FromStr;
 
 /// Coordonnées d'une case de la grille (`line`, `column`) base 0
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineColumn {
     /// Numéro de la ligne (base 0). Ligne 0 correspond à la première ligne u haut.
     pub line: usize,
@@ -19,15 +21,53 @@ impl From<(usize, usize)> for LineColumn {
 }
 
 /// Affichage du numéro de ligne : 0, 1, ... devient '1', '2', ...
+#[must_use]
 pub fn display_line(line: usize) -> String {
     format!("{}", line + 1)
 }
 
-/// Affichage du numéro de colonne 0, 1, ... devient 'A', 'B', ...
+/// Nombre de lettres utilisées pour afficher un numéro de colonne
+const NB_COLUMN_LETTERS: usize = 26;
+
+/// Affichage du numéro de colonne 0, 1, ... devient 'A', 'B', ...<br>
+/// Après 'Z', les colonnes sont numérotées comme celles d'un tableur : 'AA', 'AB', ...
+#[must_use]
 pub fn display_column(column: usize) -> String {
-    std::char::from_u32(u32::from(b'A') + u32::try_from(column).unwrap())
-        .unwrap()
-        .to_string()
+    let mut letters = Vec::new();
+    let mut rank = column + 1;
+    while rank > 0 {
+        rank -= 1;
+        letters.push(char::from(
+            b'A' + u8::try_from(rank % NB_COLUMN_LETTERS).unwrap(),
+        ));
+        rank /= NB_COLUMN_LETTERS;
+    }
+    letters.iter().rev().collect()
+}
+
+/// Analyse d'un numéro de ligne affiché par [`display_line`] : '1', '2', ... devient 0, 1, ...<br>
+/// Retourne None si le texte n'est pas un numéro de ligne
+#[must_use]
+pub fn parse_line(text: &str) -> Option<usize> {
+    if !text.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    text.parse::<usize>().ok()?.checked_sub(1)
+}
+
+/// Analyse d'un numéro de colonne affiché par [`display_column`] : 'A', 'B', ... devient 0, 1, ... et 'AA'
+/// devient 26<br>
+/// Retourne None si le texte n'est pas une suite de lettres majuscules
+#[must_use]
+pub fn parse_column(text: &str) -> Option<usize> {
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let rank = text.bytes().try_fold(0_usize, |rank, letter| {
+        rank.checked_mul(NB_COLUMN_LETTERS)?
+            .checked_add(usize::from(letter - b'A') + 1)
+    })?;
+    Some(rank - 1)
 }
 
 impl Display for LineColumn {
@@ -43,6 +83,21 @@ impl Display for LineColumn {
     }
 }
 
+impl FromStr for LineColumn {
+    type Err = String;
+
+    /// Analyse des coordonnées textuelles d'une case (`A1`, `C3`, ...)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // La colonne est formée des lettres du début du texte, la ligne est le reste du texte
+        let split = s.find(|c: char| !c.is_ascii_uppercase()).unwrap_or(s.len());
+        let (text_column, text_line) = s.split_at(split);
+        match (parse_line(text_line), parse_column(text_column)) {
+            (Some(line), Some(column)) => Ok(Self::new(line, column)),
+            _ => Err(format!("'{s}' n'est pas une coordonnée valide")),
+        }
+    }
+}
+
 impl LineColumn {
     /// Constructeur
     #[must_use]
@@ -85,4 +140,41 @@ mod tests {
     fn test_eq() {
         assert_eq!(LineColumn::new(1, 2), LineColumn::from((1, 2)));
     }
+
+    #[test]
+    fn test_display_parse() {
+        for line in [0, 1, 9, 16] {
+            assert_eq!(parse_line(&display_line(line)), Some(line));
+        }
+        for column in [0, 1, 16, 25, 26, 51, 52, 701, 702] {
+            assert_eq!(parse_column(&display_column(column)), Some(column));
+        }
+        assert_eq!(display_column(25), "Z");
+        assert_eq!(display_column(26), "AA");
+        assert_eq!(display_column(27), "AB");
+        assert_eq!(display_column(702), "AAA");
+        assert_eq!(parse_line("0"), None);
+        assert_eq!(parse_line("+1"), None);
+        assert_eq!(parse_line(""), None);
+        assert_eq!(parse_column("a"), None);
+        assert_eq!(parse_column("AB"), Some(27));
+        assert_eq!(parse_column("A1"), None);
+        assert_eq!(parse_column(""), None);
+        assert_eq!(parse_column(&"Z".repeat(100)), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("A1".parse(), Ok(LineColumn::new(0, 0)));
+        assert_eq!("C12".parse(), Ok(LineColumn::new(11, 2)));
+        assert_eq!("AB3".parse(), Ok(LineColumn::new(2, 27)));
+        assert_eq!(
+            LineColumn::new(3, 4).to_string().parse(),
+            Ok(LineColumn::new(3, 4))
+        );
+        assert!("A0".parse::<LineColumn>().is_err());
+        assert!("a1".parse::<LineColumn>().is_err());
+        assert!("A".parse::<LineColumn>().is_err());
+        assert!("1A".parse::<LineColumn>().is_err());
+    }
 }
@@ -0,0 +1,52 @@
+//! Langue des textes produits par la librairie.
+//!
+//! Les règles, actions, zones et erreurs sont affichées en français (`Display`). Leurs méthodes `text`
+//! (par exemple [`crate::GoodRule::text`]) produisent le même texte dans une autre langue ([`Locale`]).
+
+/// Langue des textes des règles, actions, zones et erreurs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Locale {
+    /// Français (langue de l'affichage)
+    #[default]
+    French,
+
+    /// Anglais
+    English,
+}
+
+impl Locale {
+    /// Langue de code `code` ('fr' ou 'en', sans tenir compte des majuscules). None si code inconnu
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_lowercase().as_str() {
+            "fr" => Some(Self::French),
+            "en" => Some(Self::English),
+            _ => None,
+        }
+    }
+
+    /// Code de la langue ('fr' ou 'en')
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::French => "fr",
+            Self::English => "en",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_code() {
+        for locale in [Locale::French, Locale::English] {
+            assert_eq!(Locale::from_code(locale.code()), Some(locale));
+        }
+        assert_eq!(Locale::from_code("EN"), Some(Locale::English));
+        assert_eq!(Locale::from_code("de"), None);
+        assert_eq!(Locale::default(), Locale::French);
+    }
+}
@@ -1,21 +1,96 @@
 //! Star Battle Solver
 
 use std::env;
-use std::fs::File;
-use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use star_battle::get_good_rule;
+use star_battle::append_stats_csv;
+use star_battle::find_duplicates;
+#[cfg(test)]
+use star_battle::solve_file;
+use star_battle::solve_file_with_checkpoint;
+use star_battle::solve_file_with_steps;
+use star_battle::Checkpoint;
+use star_battle::CheckpointConfig;
+use star_battle::DirectoryReport;
+use star_battle::GeneratorConfig;
+use star_battle::GoodRule;
 use star_battle::Grid;
 use star_battle::GridHandler;
-use star_battle::GridParser;
+use star_battle::Locale;
+use star_battle::SolveFileError;
+use star_battle::SolveMetrics;
+use star_battle::SolveOutcome;
+use star_battle::SolverConfig;
+use star_battle::Verbosity;
+use star_battle::DEFAULT_CHECKPOINT_INTERVAL;
 
 /// Message d'aide pour l'utilisateur
 const HELP_MESSAGE: &str = "
-STAR BATTLE Usage: ./star-battle <grille> {<nb étoiles>}
+STAR BATTLE Usage: ./star-battle <grille> {<nb étoiles>} {--explain} {--config <fichier>}
+                                      {--stats-csv <fichier.csv>} {--checkpoint <fichier>}
+                                      {--checkpoint-interval <secondes>} {--metrics} {--rating}
+                                      {--log <text|json>} {--lang <fr|en>}
+                   ./star-battle --resume <fichier> {--explain} {--config <fichier>}
+                                 {--stats-csv <fichier.csv>} {--checkpoint <fichier>}
+                                 {--checkpoint-interval <secondes>} {--metrics} {--rating}
+                                 {--log <text|json>} {--lang <fr|en>}
+                   ./star-battle generate {--size <taille>} {--stars <nb étoiles>} {--count <nombre>}
+                                          {--seed <graine>} {--out <répertoire>}
+                   ./star-battle serve {--port <port>}
+                   ./star-battle dedupe <répertoire> {--remove}
+                   ./star-battle report <répertoire> {--stars <nb étoiles>} {--out <fichier.html>}
 
 <grille> est le nom d'un fichier contenant une grille à résoudre.
 <nb_étoiles> est le nombre d'étoiles à placer dans chaque ligne, colonne et région de la grille.
 Par défaut, ce nombre d'étoile est 1.
+L'option '--explain' détaille le raisonnement de chaque règle appliquée pour résoudre la grille.
+L'option '--config' lit la configuration des règles de résolution dans le fichier TOML <fichier>
+(disponible uniquement si star-battle est compilé avec la feature 'config').
+L'option '--stats-csv' ajoute une ligne de statistiques de la résolution (fichier, taille, nombre
+d'étoiles, grille résolue, nombre de règles, durée et règle la plus difficile) au fichier <fichier.csv>.
+L'option '--metrics' affiche à la fin de la résolution un résumé de son effort : règles trouvées par
+niveau, zones examinées par la recherche des grilles possibles, grilles possibles explorées, pic mémoire
+de ces grilles et durée, puis pour chaque niveau de règle recherché le nombre de recherches, de règles
+trouvées, leur durée cumulée et les grilles possibles explorées.
+L'option '--log json' affiche la résolution au format JSON, un objet par règle appliquée (catégorie,
+niveau, zone, actions et grille obtenue), à la place du texte de la résolution (disponible uniquement si
+star-battle est compilé avec la feature 'json').
+L'option '--lang en' affiche les règles appliquées et ce message d'aide en anglais (français par défaut).
+L'option '--rating' affiche à la fin de la résolution l'évaluation de la difficulté de la grille : niveau de
+difficulté et score, catégories de règles utilisées, règle la plus difficile et plus grande zone examinée.
+L'option '--checkpoint' écrit régulièrement l'état de la grille et la trace des règles appliquées dans le
+fichier de reprise <fichier> (toutes les 60 secondes par défaut ou toutes les <secondes> de l'option
+'--checkpoint-interval'). L'option '--resume' reprend la résolution interrompue depuis le fichier de
+reprise <fichier>, qui continue d'être mis à jour (sauf si un autre fichier est indiqué par '--checkpoint'),
+avec les mêmes options d'affichage que la résolution d'une grille.
+
+Avec la feature 'config', les options par défaut sont lues dans le fichier ~/.config/star_battle/config.toml
+(ou $XDG_CONFIG_HOME/star_battle/config.toml) et remplacées par celles de la ligne de commande :
+    nb_stars = 2                     # nombre d'étoiles par défaut
+    explain = true                   # comme l'option '--explain'
+    config = \"/chemin/regles.toml\"   # comme l'option '--config'
+    lang = \"en\"                      # comme l'option '--lang'
+    log = \"json\"                     # comme l'option '--log'
+    timeout = 60                     # durée maximum de la résolution en secondes
+
+La commande 'generate' génère <nombre> grilles (1 par défaut) de <taille> x <taille> cases (8 par défaut)
+dans des fichiers numérotés du répertoire <répertoire> (répertoire courant par défaut).
+Chaque grille est générée à partir de <graine> + son numéro (la graine par défaut dépend de l'heure) :
+la même graine produit toujours les mêmes grilles.
+
+La commande 'serve' lance un service HTTP local (127.0.0.1) de résolution sur le port <port> (8080 par défaut) :
+'POST /solve' résout une grille et 'POST /hint' indique la prochaine règle applicable à une grille
+(disponible uniquement si star-battle est compilé avec la feature 'server').
+
+La commande 'dedupe' recherche les grilles en double (à une rotation, une symétrie ou un renommage des
+régions près) parmi les fichiers '.txt' du répertoire <répertoire>. L'option '--remove' supprime les
+doublons en conservant le premier fichier (par ordre alphabétique) de chaque groupe.
+
+La commande 'report' résout les grilles des fichiers '.txt' du répertoire <répertoire> et produit une page
+HTML <fichier.html> (report.html par défaut) avec une carte par grille : la grille, sa difficulté, la durée
+de sa résolution et la trace complète des règles appliquées. Le nombre d'étoiles d'une grille est celui de
+sa métadonnée '# stars: ...' ou <nb étoiles> (1 par défaut).
 
 Le fichier <grille> définit chaque région de la grille par un caractère.
 Par exemple :
@@ -26,94 +101,760 @@ ABBBB
 CCBBB
 DDDDD
 DEEED
+
+Une ligne '* A1 C3' indique des étoiles données par la grille (ici en colonne 'A' de la ligne 1 et en
+colonne 'C' de la ligne 3).
+";
+
+/// Message d'aide pour l'utilisateur en anglais (option '--lang en')
+const HELP_MESSAGE_EN: &str = "
+STAR BATTLE Usage: ./star-battle <grid> {<nb stars>} {--explain} {--config <file>}
+                                      {--stats-csv <file.csv>} {--checkpoint <file>}
+                                      {--checkpoint-interval <seconds>} {--metrics} {--rating}
+                                      {--log <text|json>} {--lang <fr|en>}
+                   ./star-battle --resume <file> {--explain} {--config <file>}
+                                 {--stats-csv <file.csv>} {--checkpoint <file>}
+                                 {--checkpoint-interval <seconds>} {--metrics} {--rating}
+                                 {--log <text|json>} {--lang <fr|en>}
+                   ./star-battle generate {--size <size>} {--stars <nb stars>} {--count <number>}
+                                          {--seed <seed>} {--out <directory>}
+                   ./star-battle serve {--port <port>}
+                   ./star-battle dedupe <directory> {--remove}
+                   ./star-battle report <directory> {--stars <nb stars>} {--out <file.html>}
+
+<grid> is the name of a file containing a grid to solve.
+<nb stars> is the number of stars to place in each line, column and region of the grid.
+By default, this number of stars is 1.
+The '--explain' option details the reasoning of each rule applied to solve the grid.
+The '--config' option reads the configuration of the solving rules from the TOML file <file>
+(only available if star-battle is built with the 'config' feature).
+The '--stats-csv' option appends a line of statistics about the solve (file, size, number of stars,
+solved grid, number of rules, duration and hardest rule) to the file <file.csv>.
+The '--metrics' option displays a summary of the solving effort at the end of the solve: rules found per
+level, zones examined by the search of possible grids, possible grids explored, peak memory of these grids
+and duration, then for each rule level searched the number of searches, of rules found, their cumulated
+duration and the possible grids explored.
+The '--log json' option displays the solve in JSON format, one object per applied rule (kind, level,
+zone, actions and resulting grid), instead of the text of the solve (only available if star-battle is
+built with the 'json' feature).
+The '--lang en' option displays the applied rules and this help message in English (French by default).
+The '--rating' option displays at the end of the solve the difficulty rating of the grid: difficulty level
+and score, kinds of rules used, hardest rule and largest zone examined.
+The '--checkpoint' option regularly writes the state of the grid and the trace of the applied rules to the
+checkpoint file <file> (every 60 seconds by default or every <seconds> of the '--checkpoint-interval'
+option). The '--resume' option resumes the interrupted solve from the checkpoint file <file>, which keeps
+being updated (unless another file is given by '--checkpoint'), with the same display options as the solve
+of a grid.
+
+With the 'config' feature, the default options are read from the file ~/.config/star_battle/config.toml
+(or $XDG_CONFIG_HOME/star_battle/config.toml) and overridden by those of the command line:
+    nb_stars = 2                     # default number of stars
+    explain = true                   # like the '--explain' option
+    config = \"/path/rules.toml\"      # like the '--config' option
+    lang = \"en\"                      # like the '--lang' option
+    log = \"json\"                     # like the '--log' option
+    timeout = 60                     # maximum duration of the solve in seconds
+
+The 'generate' command generates <number> grids (1 by default) of <size> x <size> cells (8 by default)
+in numbered files of the directory <directory> (current directory by default).
+Each grid is generated from <seed> + its number (the default seed depends on the time):
+the same seed always produces the same grids.
+
+The 'serve' command starts a local (127.0.0.1) HTTP solving service on port <port> (8080 by default):
+'POST /solve' solves a grid and 'POST /hint' gives the next rule applicable to a grid
+(only available if star-battle is built with the 'server' feature).
+
+The 'dedupe' command looks for duplicate grids (up to a rotation, a symmetry or a renaming of the regions)
+among the '.txt' files of the directory <directory>. The '--remove' option deletes the duplicates, keeping
+the first file (in alphabetical order) of each group.
+
+The 'report' command solves the grids of the '.txt' files of the directory <directory> and produces an HTML
+page <file.html> (report.html by default) with one card per grid: the grid, its difficulty, the duration of
+its solve and the complete trace of the applied rules. The number of stars of a grid is the one of its
+'# stars: ...' metadata or <nb stars> (1 by default).
+
+The <grid> file defines each region of the grid by a character.
+For example:
+
+# Example of a 1★ grid with 5 regions 'A', 'B', 'C', 'D' and 'E'
+ABBBB
+ABBBB
+CCBBB
+DDDDD
+DEEED
+
+A line '* A1 C3' gives stars of the grid (here in column 'A' of line 1 and in column 'C' of line 3).
 ";
 
+/// Message d'aide pour l'utilisateur dans la langue `locale`
+const fn help_message(locale: Locale) -> &'static str {
+    match locale {
+        Locale::French => HELP_MESSAGE,
+        Locale::English => HELP_MESSAGE_EN,
+    }
+}
+
 fn main() {
     // Nom du fichier contenant la grille à résoudre en paramètre
-    let args: Vec<String> = env::args().collect();
-    let (file_name, nb_stars) = match args.len() {
-        2 => (&args[1], 1),
-        3 => (
+    let mut args: Vec<String> = env::args().collect();
+
+    // Options par défaut de l'utilisateur (remplacées par celles de la ligne de commande)
+    let defaults = match cli_defaults_path().map_or_else(
+        || Ok(CliDefaults::default()),
+        |path| load_cli_defaults(&path),
+    ) {
+        Ok(defaults) => defaults,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    // Langue des règles et du message d'aide ?
+    let locale = match take_option_value(&mut args, "--lang")
+        .and_then(|value| parse_locale(value.or_else(|| defaults.lang.clone()).as_deref()))
+    {
+        Ok(locale) => locale,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+    let help = help_message(locale);
+
+    // Génération de grilles ?
+    if args.len() > 1 && args[1] == "generate" {
+        match GenerateOptions::try_from(&args[2..]) {
+            Ok(options) => {
+                if let Err(e) = generate(&options) {
+                    println!("Erreur de génération : {e}");
+                }
+            }
+            Err(e) => println!("{e}\n{help}"),
+        }
+        return;
+    }
+    // Service HTTP ?
+    if args.len() > 1 && args[1] == "serve" {
+        match parse_serve_port(&args[2..]) {
+            Ok(port) => serve(port),
+            Err(e) => println!("{e}\n{help}"),
+        }
+        return;
+    }
+
+    // Recherche des grilles en double ?
+    if args.len() > 1 && args[1] == "dedupe" {
+        match &args[2..] {
+            [dir] => dedupe(dir, false),
+            [dir, option] if option == "--remove" => dedupe(dir, true),
+            _ => println!("Options invalides pour la commande 'dedupe'\n{help}"),
+        }
+        return;
+    }
+
+    // Rapport HTML des grilles d'un répertoire ?
+    if args.len() > 1 && args[1] == "report" {
+        match ReportOptions::try_from(&args[2..]) {
+            Ok(options) => {
+                if let Err(e) = report(&options) {
+                    println!("Erreur du rapport : {e}");
+                }
+            }
+            Err(e) => println!("{e}\n{help}"),
+        }
+        return;
+    }
+
+    // Configuration des règles dans un fichier ?
+    let config = match take_config(&mut args, defaults.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    // Statistiques de résolution ?
+    let stats_csv = match take_option_value(&mut args, "--stats-csv") {
+        Ok(stats_csv) => stats_csv,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    // Format du journal de la résolution ?
+    let log_format = match take_option_value(&mut args, "--log")
+        .and_then(|value| parse_log_format(value.or_else(|| defaults.log.clone()).as_deref()))
+    {
+        Ok(log_format) => log_format,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    // Points de reprise de la résolution ?
+    let (checkpoint, checkpoint_interval) = match take_checkpoint_options(&mut args) {
+        Ok(options) => options,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+    let resume = match take_option_value(&mut args, "--resume") {
+        Ok(resume) => resume,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
+    };
+
+    // Résumé de l'effort de résolution ?
+    let metrics = args.iter().any(|arg| arg == "--metrics");
+    args.retain(|arg| arg != "--metrics");
+
+    // Évaluation de la difficulté de la grille ?
+    let rating = args.iter().any(|arg| arg == "--rating");
+    args.retain(|arg| arg != "--rating");
+
+    // Explications détaillées des règles ?
+    let verbosity = if defaults.explain || args.iter().any(|arg| arg == "--explain") {
+        Verbosity::Pedagogical
+    } else {
+        config.verbosity()
+    };
+    args.retain(|arg| arg != "--explain");
+    let config = config.with_verbosity(verbosity).with_locale(locale);
+
+    // Durée maximum de la résolution ?
+    let config = match defaults.timeout {
+        Some(timeout) => {
+            let limits = config
+                .limits()
+                .clone()
+                .with_max_duration(Duration::from_secs(timeout));
+            config.with_limits(limits)
+        }
+        None => config,
+    };
+
+    // Reprise d'une résolution interrompue (le fichier de reprise continue d'être mis à jour) ?
+    let resumed = match resume {
+        Some(resume) if args.len() == 1 => match Checkpoint::load(&resume) {
+            Ok(resumed) => Some((resume, resumed)),
+            Err(e) => {
+                println!("{e}");
+                return;
+            }
+        },
+        Some(_) => {
+            println!("{help}");
+            return;
+        }
+        None => None,
+    };
+
+    let (file_name, nb_stars) = match (&resumed, args.len()) {
+        (Some((resume, resumed)), _) => (resume, resumed.nb_stars),
+        (None, 2) => (&args[1], defaults.nb_stars.unwrap_or(1)),
+        (None, 3) => (
             &args[1],
             args[2]
                 .parse::<usize>()
                 .expect("Le nombre d'étoiles doit être un nombre"),
         ),
         _ => {
-            println!("{HELP_MESSAGE}");
+            println!("{help}");
             return;
         }
     };
 
     // Demande d'aide ?
     if ["-h", "--help", "aide"].contains(&file_name.to_lowercase().as_str()) {
-        println!("{HELP_MESSAGE}");
+        println!("{help}");
         return;
     }
 
-    // Traitement du contenu du fichier
-    match read_lines(file_name) {
-        Ok(lines) => match GridParser::try_from(&lines) {
-            Ok(grid_parsed) => solve(&grid_parsed, nb_stars),
-
-            Err(e) => {
-                println!("Erreur dans le fichier {file_name}: {e}");
+    // Traitement du contenu du fichier : les règles sont affichées dès qu'elles sont trouvées
+    if let (Some((_, resumed)), LogFormat::Text) = (&resumed, log_format) {
+        print_resumed_trace(resumed, locale);
+    }
+    let checkpoint_config = checkpoint
+        .or_else(|| resumed.as_ref().map(|(resume, _)| resume.clone()))
+        .map(|checkpoint| CheckpointConfig::new(checkpoint).with_interval(checkpoint_interval));
+    let mut print_rule_found = rule_printer(nb_stars, &config);
+    let on_rule = |grid_handler: &GridHandler, grid: &Grid, good_rule: &GoodRule| {
+        if log_format == LogFormat::Text {
+            print_rule_found(grid_handler, grid, good_rule);
+        }
+    };
+    let (outcome, solve_metrics) = SolveMetrics::measure(|| match (&resumed, &checkpoint_config) {
+        (Some((_, resumed)), Some(checkpoint_config)) => resumed
+            .solve_with_steps(&config, checkpoint_config, on_rule)
+            .map_err(SolveFileError::from),
+        (None, Some(checkpoint_config)) => {
+            solve_file_with_checkpoint(file_name, nb_stars, &config, checkpoint_config, on_rule)
+        }
+        // Une reprise a toujours un fichier de reprise
+        (_, None) => solve_file_with_steps(file_name, nb_stars, &config, on_rule),
+    });
+    match outcome {
+        Ok(outcome) => {
+            let duration = solve_metrics.duration;
+            match log_format {
+                LogFormat::Text => print_outcome_end(&outcome, nb_stars, &config),
+                LogFormat::Json => print_json_log(&outcome),
             }
-        },
-        Err(e) => println!("Erreur dans le fichier {file_name}: {e}"),
+            if metrics {
+                println!("\n{solve_metrics}");
+            }
+            if rating {
+                println!("\n{}", outcome.rating());
+            }
+            if let Some(stats_csv) = stats_csv {
+                let record = outcome.stats_csv_record(file_name, duration);
+                if let Err(e) = append_stats_csv(&stats_csv, &record) {
+                    println!("Erreur écriture du fichier {stats_csv}: {e}");
+                }
+            }
+        }
+        Err(e) => println!("{e}"),
     }
 }
 
-fn solve(grid_parsed: &GridParser, nb_stars: usize) {
-    let grid_handler = GridHandler::new(grid_parsed, nb_stars);
-    let mut grid = Grid::from(&grid_handler);
+/// Options de la commande 'report'
+#[derive(Debug, PartialEq, Eq)]
+struct ReportOptions {
+    /// Répertoire des grilles
+    dir: PathBuf,
 
-    println!("\nGrid {nb_stars}★\n{}", grid_handler.display(&grid, true));
-    loop {
-        match get_good_rule(&grid_handler, &grid) {
-            Ok(option_good_rule) => {
-                if option_good_rule.is_some() {
-                    let good_rule = option_good_rule.unwrap();
-                    println!("{good_rule}");
-                    grid.apply_good_rule(&good_rule);
-                    println!("\n{}", grid_handler.display(&grid, true));
-                } else {
-                    break;
+    /// Nombre d'étoiles des grilles sans métadonnée '# stars: ...'
+    nb_stars: usize,
+
+    /// Fichier HTML du rapport
+    out: PathBuf,
+}
+
+impl TryFrom<&[String]> for ReportOptions {
+    type Error = String;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        let Some((dir, args)) = args.split_first() else {
+            return Err("Répertoire manquant pour la commande 'report'".to_string());
+        };
+        let mut options = Self {
+            dir: PathBuf::from(dir),
+            nb_stars: 1,
+            out: PathBuf::from("report.html"),
+        };
+        let mut iter = args.iter();
+        while let Some(option) = iter.next() {
+            let Some(value) = iter.next() else {
+                return Err(format!("Valeur manquante pour l'option {option}"));
+            };
+            match option.as_str() {
+                "--stars" => {
+                    options.nb_stars = value
+                        .parse()
+                        .map_err(|_| format!("Valeur '{value}' invalide pour l'option {option}"))?;
                 }
-            }
-            Err(bad_rule) => {
-                println!("{bad_rule} !!!");
-                break;
+                "--out" => options.out = PathBuf::from(value),
+                _ => return Err(format!("Option {option} inconnue")),
             }
         }
+        Ok(options)
     }
+}
 
-    if grid_handler.is_done(&grid) {
-        println!("Grille résolue !\n");
-    } else {
-        println!("Grille non résolue :(\n");
+/// Résout les grilles d'un répertoire et écrit leur rapport HTML selon les options de la commande 'report'
+fn report(options: &ReportOptions) -> Result<(), String> {
+    let report =
+        DirectoryReport::from_dir(&options.dir, options.nb_stars, &SolverConfig::default())
+            .map_err(|e| e.to_string())?;
+    for (file, e) in &report.invalid {
+        println!("{} ignoré : {e}", file.display());
     }
+    report.write_html(&options.out).map_err(|e| e.to_string())?;
+    println!(
+        "{} grilles dans {}",
+        report.entries.len(),
+        options.out.display()
+    );
+    Ok(())
 }
 
-fn read_lines(filename: &str) -> Result<Vec<String>, String> {
-    // Ouverture du fichier
-    let mut file = match File::open(filename) {
-        Ok(file) => file,
-        Err(e) => return Err(format!("Erreur ouverture du fichier {filename}: {e}")),
+/// Affiche les grilles en double du répertoire `dir` et les supprime si `remove`
+fn dedupe(dir: &str, remove: bool) {
+    let report = match find_duplicates(dir) {
+        Ok(report) => report,
+        Err(e) => {
+            println!("{e}");
+            return;
+        }
     };
-    // Lecture du fichier
-    let mut file_contents = String::new();
-    match file.read_to_string(&mut file_contents) {
-        Ok(_) => {}
-        Err(e) => return Err(format!("Erreur lecture du fichier {filename}: {e}")),
+    for (file, e) in &report.invalid {
+        println!("{} ignoré : {e}", file.display());
+    }
+    for files in &report.duplicates {
+        let files: Vec<String> = files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect();
+        println!("Grilles identiques : {}", files.join(", "));
+    }
+    println!(
+        "{} grilles examinées, {} en double",
+        report.nb_grids,
+        report.redundant_files().len()
+    );
+    if remove {
+        match report.remove_redundant_files() {
+            Ok(nb_removed) => println!("{nb_removed} fichiers supprimés"),
+            Err(e) => println!("{e}"),
+        }
     }
+}
 
-    // Extraction des lignes du fichier
-    let lines: Vec<String> = file_contents
-        .split('\n')
-        .map(|s: &str| s.to_string())
-        .collect();
-    Ok(lines)
+/// Fichier de reprise de l'option '--checkpoint <fichier>' et intervalle entre 2 points de reprise de
+/// l'option '--checkpoint-interval <secondes>' (toutes deux retirées des arguments `args`)
+fn take_checkpoint_options(args: &mut Vec<String>) -> Result<(Option<String>, Duration), String> {
+    let checkpoint = take_option_value(args, "--checkpoint")?;
+    let interval = match take_option_value(args, "--checkpoint-interval")? {
+        Some(value) => Duration::from_secs(value.parse().map_err(|_| {
+            format!("Valeur '{value}' invalide pour l'option --checkpoint-interval")
+        })?),
+        None => DEFAULT_CHECKPOINT_INTERVAL,
+    };
+    Ok((checkpoint, interval))
+}
+
+/// Affichage des règles déjà appliquées avant le point de reprise `resumed` (dans la langue de leur
+/// enregistrement, voir [`Checkpoint::trace`])
+fn print_resumed_trace(resumed: &Checkpoint, locale: Locale) {
+    match locale {
+        Locale::French => println!("Reprise après {} règles appliquées :", resumed.trace.len()),
+        Locale::English => println!("Resumed after {} applied rules:", resumed.trace.len()),
+    }
+    for (_, rule) in &resumed.trace {
+        println!("{rule}");
+    }
+}
+
+/// Affichage des étapes de la résolution d'une grille selon le niveau de détail et la langue de `config`
+#[cfg(test)]
+fn print_outcome(outcome: &SolveOutcome, nb_stars: usize, config: &SolverConfig) {
+    let mut print_rule_found = rule_printer(nb_stars, config);
+    let mut grid = outcome.initial_grid();
+    for (good_rule, _) in &outcome.steps {
+        print_rule_found(&outcome.handler, &grid, good_rule);
+        grid.apply_good_rule(good_rule);
+    }
+    print_outcome_end(outcome, nb_stars, config);
+}
+
+/// Affichage du titre et de la grille `grid` au début de la résolution
+fn print_grid_start(grid_handler: &GridHandler, grid: &Grid, nb_stars: usize) {
+    if let Some(title) = &grid_handler.metadata().title {
+        println!("\n{title}");
+    }
+    println!("\nGrid {nb_stars}★\n{}", grid_handler.display(grid, true));
+}
+
+/// Affichage de chaque règle dès qu'elle est trouvée (explication et grille obtenue), précédée pour la
+/// première règle du début de la résolution (voir [`print_grid_start`])
+fn rule_printer(
+    nb_stars: usize,
+    config: &SolverConfig,
+) -> impl FnMut(&GridHandler, &Grid, &GoodRule) + '_ {
+    let mut is_first = true;
+    move |grid_handler, grid, good_rule| {
+        if is_first {
+            print_grid_start(grid_handler, grid, nb_stars);
+            is_first = false;
+        }
+        println!(
+            "{}",
+            good_rule.explain_in_locale(grid_handler, grid, config.verbosity(), config.locale())
+        );
+        let mut grid = grid.clone();
+        grid.apply_good_rule(good_rule);
+        println!("\n{}", grid_handler.display(&grid, true));
+    }
+}
+
+/// Affichage de la fin de la résolution d'une grille dont les règles ont été affichées au fur et à mesure
+/// (voir [`rule_printer`]) : incohérence éventuelle et grille résolue ou non
+fn print_outcome_end(outcome: &SolveOutcome, nb_stars: usize, config: &SolverConfig) {
+    let locale = config.locale();
+    if outcome.steps.is_empty() {
+        print_grid_start(&outcome.handler, &outcome.initial_grid(), nb_stars);
+    }
+    if let Some(bad_rule) = &outcome.error {
+        println!("{} !!!", bad_rule.text(locale));
+    }
+
+    match (outcome.is_solved(), locale) {
+        (true, Locale::French) => println!("Grille résolue !\n"),
+        (true, Locale::English) => println!("Grid solved!\n"),
+        (false, Locale::French) => println!("Grille non résolue :(\n"),
+        (false, Locale::English) => println!("Grid not solved :(\n"),
+    }
+    match (outcome.interruption, locale) {
+        (Some(cause), Locale::French) => println!("Résolution interrompue : {cause}\n"),
+        (Some(cause), Locale::English) => println!("Solve interrupted: {cause}\n"),
+        (None, _) => (),
+    }
+    if !outcome.is_solved() && outcome.nb_abandoned_zones > 0 {
+        match locale {
+            Locale::French => println!(
+                "{} zones abandonnées (trop de grilles possibles, voir 'max_possible_grids')\n",
+                outcome.nb_abandoned_zones
+            ),
+            Locale::English => println!(
+                "{} zones abandoned (too many possible grids, see 'max_possible_grids')\n",
+                outcome.nb_abandoned_zones
+            ),
+        }
+    }
+}
+
+/// Options par défaut de la ligne de commande (fichier `config.toml` de l'utilisateur)
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+struct CliDefaults {
+    /// Nombre d'étoiles par défaut à placer dans chaque ligne, colonne et région
+    nb_stars: Option<usize>,
+
+    /// Explications détaillées des règles (option '--explain')
+    explain: bool,
+
+    /// Fichier de configuration des règles (option '--config')
+    config: Option<String>,
+
+    /// Langue des règles et du message d'aide (option '--lang')
+    lang: Option<String>,
+
+    /// Format du journal de la résolution (option '--log')
+    log: Option<String>,
+
+    /// Durée maximum de la résolution en secondes (la résolution est interrompue au-delà)
+    timeout: Option<u64>,
+}
+
+/// Chemin du fichier des options par défaut : `$XDG_CONFIG_HOME/star_battle/config.toml` ou
+/// `~/.config/star_battle/config.toml`
+fn cli_defaults_path() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("star_battle").join("config.toml"))
+}
+
+/// Lecture des options par défaut dans le fichier `path` (options vides si le fichier n'existe pas)
+#[cfg(feature = "config")]
+fn load_cli_defaults(path: &Path) -> Result<CliDefaults, String> {
+    if !path.exists() {
+        return Ok(CliDefaults::default());
+    }
+    let file_contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Erreur lecture du fichier {}: {e}", path.display()))?;
+    toml::from_str(&file_contents)
+        .map_err(|e| format!("Erreur dans le fichier {}: {}", path.display(), e.message()))
+}
+
+/// Les options par défaut ne sont pas lues sans la feature 'config'
+#[cfg(not(feature = "config"))]
+#[allow(clippy::unnecessary_wraps)]
+fn load_cli_defaults(_path: &Path) -> Result<CliDefaults, String> {
+    Ok(CliDefaults::default())
+}
+
+/// Configuration des règles selon l'option '--config <fichier>' (retirée des arguments `args`) ou selon
+/// le fichier par défaut `default_file`
+fn take_config(args: &mut Vec<String>, default_file: Option<&str>) -> Result<SolverConfig, String> {
+    match take_option_value(args, "--config")? {
+        Some(file_name) => load_config(&file_name),
+        None => default_file.map_or_else(|| Ok(SolverConfig::default()), load_config),
+    }
+}
+
+/// Valeur de l'option `option` suivie de sa valeur (toutes deux retirées des arguments `args`)
+fn take_option_value(args: &mut Vec<String>, option: &str) -> Result<Option<String>, String> {
+    let Some(index) = args.iter().position(|arg| arg == option) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err(format!("Valeur manquante pour l'option {option}"));
+    }
+    let value = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(value))
+}
+
+/// Lecture de la configuration des règles dans le fichier TOML `file_name`
+#[cfg(feature = "config")]
+fn load_config(file_name: &str) -> Result<SolverConfig, String> {
+    SolverConfig::from_path(file_name).map_err(|e| e.to_string())
+}
+
+/// La lecture d'une configuration n'est pas disponible sans la feature 'config'
+#[cfg(not(feature = "config"))]
+fn load_config(_file_name: &str) -> Result<SolverConfig, String> {
+    Err(
+        "star-battle doit être compilé avec la feature 'config' pour utiliser l'option --config"
+            .to_string(),
+    )
+}
+
+/// Format du journal de la résolution (option '--log <format>')
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogFormat {
+    /// Texte de la résolution (par défaut)
+    Text,
+
+    /// Un objet JSON par règle appliquée (feature 'json')
+    Json,
+}
+
+/// Format du journal de la résolution selon la valeur de l'option '--log' (texte si absente)
+fn parse_log_format(value: Option<&str>) -> Result<LogFormat, String> {
+    match value {
+        None | Some("text") => Ok(LogFormat::Text),
+        Some("json") if cfg!(feature = "json") => Ok(LogFormat::Json),
+        Some("json") => Err(
+            "star-battle doit être compilé avec la feature 'json' pour utiliser l'option --log json"
+                .to_string(),
+        ),
+        Some(value) => Err(format!(
+            "Valeur '{value}' invalide pour l'option --log (text ou json)"
+        )),
+    }
+}
+
+/// Langue selon la valeur de l'option '--lang' (français si absente)
+fn parse_locale(value: Option<&str>) -> Result<Locale, String> {
+    value.map_or(Ok(Locale::French), |value| {
+        Locale::from_code(value)
+            .ok_or_else(|| format!("Valeur '{value}' invalide pour l'option --lang (fr ou en)"))
+    })
+}
+
+/// Affichage du journal de la résolution au format JSON : un objet par règle appliquée
+#[cfg(feature = "json")]
+fn print_json_log(outcome: &SolveOutcome) {
+    print!("{}", star_battle::solve_log_json(outcome));
+}
+
+/// Le journal JSON n'est pas disponible sans la feature 'json' (option '--log json' refusée)
+#[cfg(not(feature = "json"))]
+fn print_json_log(_outcome: &SolveOutcome) {}
+
+/// Port du service HTTP selon les options de la commande 'serve'
+fn parse_serve_port(args: &[String]) -> Result<u16, String> {
+    match args {
+        [] => Ok(8080),
+        [option, value] if option == "--port" => value
+            .parse::<u16>()
+            .map_err(|_| format!("Valeur '{value}' invalide pour l'option {option}")),
+        _ => Err("Options invalides pour la commande 'serve'".to_string()),
+    }
+}
+
+/// Lance le service HTTP de résolution sur le port `port`
+#[cfg(feature = "server")]
+fn serve(port: u16) {
+    println!("Service star-battle sur le port {port}");
+    if let Err(e) = star_battle::serve(port) {
+        println!("Erreur du service : {e}");
+    }
+}
+
+/// Le service HTTP n'est pas disponible sans la feature 'server'
+#[cfg(not(feature = "server"))]
+fn serve(_port: u16) {
+    println!("star-battle doit être compilé avec la feature 'server' pour lancer le service HTTP");
+}
+
+/// Options de la commande 'generate'
+#[derive(Debug, PartialEq, Eq)]
+struct GenerateOptions {
+    /// Nombre de lignes et de colonnes des grilles
+    size: usize,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    nb_stars: usize,
+
+    /// Nombre de grilles à générer
+    count: usize,
+
+    /// Graine de la première grille (les suivantes utilisent les graines suivantes)
+    seed: Option<u64>,
+
+    /// Répertoire des fichiers générés
+    out: PathBuf,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        Self {
+            size: 8,
+            nb_stars: 1,
+            count: 1,
+            seed: None,
+            out: PathBuf::from("."),
+        }
+    }
+}
+
+impl TryFrom<&[String]> for GenerateOptions {
+    type Error = String;
+
+    fn try_from(args: &[String]) -> Result<Self, Self::Error> {
+        fn parse_value<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+            value
+                .parse::<T>()
+                .map_err(|_| format!("Valeur '{value}' invalide pour l'option {option}"))
+        }
+
+        let mut options = Self::default();
+        let mut iter = args.iter();
+        while let Some(option) = iter.next() {
+            let Some(value) = iter.next() else {
+                return Err(format!("Valeur manquante pour l'option {option}"));
+            };
+            match option.as_str() {
+                "--size" => options.size = parse_value(option, value)?,
+                "--stars" => options.nb_stars = parse_value(option, value)?,
+                "--count" => options.count = parse_value(option, value)?,
+                "--seed" => options.seed = Some(parse_value(option, value)?),
+                "--out" => options.out = PathBuf::from(value),
+                _ => return Err(format!("Option {option} inconnue")),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Génère des grilles dans des fichiers numérotés selon les options de la commande 'generate'
+fn generate(options: &GenerateOptions) -> Result<(), String> {
+    let first_seed = options.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs())
+    });
+    GeneratorConfig::new(options.size, options.nb_stars, first_seed)
+        .generate_files(options.count, &options.out, |file| {
+            println!("{}", file.display());
+        })
+        .map(|_| ())
+        .map_err(|e| e.to_string())
 }
 
 #[cfg(test)]
@@ -126,11 +867,171 @@ mod tests {
         let test_files = vec!["./test_grids/test01.txt"];
 
         for test_file in test_files {
-            let lines = read_lines(test_file).unwrap();
-            let grid_parsed = GridParser::try_from(&lines).unwrap();
-            let grid_handler = GridHandler::new(&grid_parsed, 1);
-            let grid = Grid::from(&grid_handler);
-            println!("Grid: \n{grid}");
+            let outcome = solve_file(test_file, 1, &SolverConfig::default()).unwrap();
+            print_outcome(&outcome, 1, &SolverConfig::default());
+            print_outcome(
+                &outcome,
+                1,
+                &SolverConfig::new()
+                    .with_verbosity(Verbosity::Pedagogical)
+                    .with_locale(Locale::English),
+            );
+            assert!(outcome.is_solved());
         }
     }
+
+    #[test]
+    fn test_generate_options() {
+        let args: Vec<String> = [
+            "--size", "10", "--stars", "2", "--count", "50", "--out", "dir",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        let options = GenerateOptions::try_from(args.as_slice()).unwrap();
+        assert_eq!(
+            options,
+            GenerateOptions {
+                size: 10,
+                nb_stars: 2,
+                count: 50,
+                seed: None,
+                out: PathBuf::from("dir"),
+            }
+        );
+
+        for invalid_args in [vec!["--size"], vec!["--size", "x"], vec!["--unknown", "1"]] {
+            let args: Vec<String> = invalid_args.iter().map(ToString::to_string).collect();
+            assert!(GenerateOptions::try_from(args.as_slice()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_report_options() {
+        let args =
+            |args: &[&str]| -> Vec<String> { args.iter().map(ToString::to_string).collect() };
+        assert_eq!(
+            ReportOptions::try_from(args(&["grilles"]).as_slice()),
+            Ok(ReportOptions {
+                dir: PathBuf::from("grilles"),
+                nb_stars: 1,
+                out: PathBuf::from("report.html"),
+            })
+        );
+        assert_eq!(
+            ReportOptions::try_from(
+                args(&["grilles", "--out", "r.html", "--stars", "2"]).as_slice()
+            ),
+            Ok(ReportOptions {
+                dir: PathBuf::from("grilles"),
+                nb_stars: 2,
+                out: PathBuf::from("r.html"),
+            })
+        );
+        assert!(ReportOptions::try_from(args(&[]).as_slice()).is_err());
+        assert!(ReportOptions::try_from(args(&["grilles", "--stars"]).as_slice()).is_err());
+        assert!(ReportOptions::try_from(args(&["grilles", "--size", "8"]).as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!(parse_log_format(None), Ok(LogFormat::Text));
+        assert_eq!(parse_log_format(Some("text")), Ok(LogFormat::Text));
+        assert_eq!(
+            parse_log_format(Some("json")).is_ok(),
+            cfg!(feature = "json")
+        );
+        assert!(parse_log_format(Some("xml")).is_err());
+    }
+
+    #[test]
+    fn test_parse_locale() {
+        assert_eq!(parse_locale(None), Ok(Locale::French));
+        assert_eq!(parse_locale(Some("fr")), Ok(Locale::French));
+        assert_eq!(parse_locale(Some("en")), Ok(Locale::English));
+        assert!(parse_locale(Some("de")).is_err());
+        assert!(help_message(Locale::English).contains("--lang <fr|en>"));
+    }
+
+    #[test]
+    fn test_parse_serve_port() {
+        let args =
+            |args: &[&str]| -> Vec<String> { args.iter().map(ToString::to_string).collect() };
+        assert_eq!(parse_serve_port(&args(&[])), Ok(8080));
+        assert_eq!(parse_serve_port(&args(&["--port", "3000"])), Ok(3000));
+        assert!(parse_serve_port(&args(&["--port", "x"])).is_err());
+        assert!(parse_serve_port(&args(&["--port"])).is_err());
+        assert!(parse_serve_port(&args(&["--size", "10"])).is_err());
+    }
+
+    #[test]
+    fn test_take_config() {
+        let args =
+            |args: &[&str]| -> Vec<String> { args.iter().map(ToString::to_string).collect() };
+        let mut no_config = args(&["star-battle", "grille.txt", "2"]);
+        assert_eq!(
+            take_config(&mut no_config, None),
+            Ok(SolverConfig::default())
+        );
+        assert_eq!(no_config, args(&["star-battle", "grille.txt", "2"]));
+
+        let mut missing = args(&["star-battle", "grille.txt", "--config"]);
+        assert!(take_config(&mut missing, None).is_err());
+
+        // Fichier inconnu (ou feature 'config' absente) : l'option est retirée des arguments
+        let mut unknown = args(&["star-battle", "--config", "inconnu.toml", "grille.txt"]);
+        assert!(take_config(&mut unknown, None).is_err());
+        assert_eq!(unknown, args(&["star-battle", "grille.txt"]));
+
+        // Fichier par défaut
+        assert!(take_config(&mut no_config, Some("inconnu.toml")).is_err());
+    }
+
+    #[test]
+    fn test_cli_defaults() {
+        // Fichier absent : pas d'options par défaut
+        assert_eq!(
+            load_cli_defaults(Path::new("./test_grids/inconnu.toml")),
+            Ok(CliDefaults::default())
+        );
+        assert!(cli_defaults_path().is_none_or(|path| path.ends_with("star_battle/config.toml")));
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_load_cli_defaults() {
+        let path = env::temp_dir().join("star_battle_test_cli_defaults.toml");
+        std::fs::write(&path, "nb_stars = 2\nconfig = \"regles.toml\"\n").unwrap();
+        assert_eq!(
+            load_cli_defaults(&path),
+            Ok(CliDefaults {
+                nb_stars: Some(2),
+                config: Some("regles.toml".to_string()),
+                ..CliDefaults::default()
+            })
+        );
+
+        // Toutes les options
+        std::fs::write(
+            &path,
+            "nb_stars = 2\nexplain = true\nconfig = \"regles.toml\"\nlang = \"en\"\nlog = \"json\"\n\
+             timeout = 30\n",
+        )
+        .unwrap();
+        assert_eq!(
+            load_cli_defaults(&path),
+            Ok(CliDefaults {
+                nb_stars: Some(2),
+                explain: true,
+                config: Some("regles.toml".to_string()),
+                lang: Some("en".to_string()),
+                log: Some("json".to_string()),
+                timeout: Some(30),
+            })
+        );
+
+        std::fs::write(&path, "couleur = true\n").unwrap();
+        assert!(load_cli_defaults(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
 }
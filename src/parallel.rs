@@ -0,0 +1,93 @@
+//! Répartition optionnelle des traitements indépendants entre plusieurs threads.
+//!
+//! Avec la feature `parallel`, les traitements sont répartis entre les threads de `rayon`. Sans cette
+//! feature, ils sont exécutés séquentiellement. Dans les deux cas, les résultats sont fusionnés dans
+//! l'ordre des éléments traités : un traitement produit exactement les mêmes résultats (et donc les mêmes
+//! règles appliquées) en mode parallèle et en mode séquentiel.
+//!
+//! Toute utilisation de `rayon` dans ce crate passe par ce module.
+
+/// Résultats de `f` pour chacun des éléments de `items`, dans l'ordre des éléments
+#[cfg(feature = "parallel")]
+pub fn map_ordered<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    use rayon::prelude::*;
+
+    // `collect` d'un itérateur indexé de `rayon` conserve l'ordre des éléments
+    items.par_iter().map(f).collect()
+}
+
+/// Résultats de `f` pour chacun des éléments de `items`, dans l'ordre des éléments
+#[cfg(not(feature = "parallel"))]
+pub fn map_ordered<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    items.iter().map(f).collect()
+}
+
+/// Premier résultat non None de `f` pour les éléments de `items`, dans l'ordre des éléments.<br>
+/// Les éléments sont traités séquentiellement si `parallel` est `false`
+#[cfg(feature = "parallel")]
+pub fn find_map_first<T, R, F>(items: &[T], parallel: bool, f: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    use rayon::prelude::*;
+
+    if !parallel {
+        return items.iter().find_map(f);
+    }
+    // `find_map_first` de `rayon` retourne le résultat du premier élément, même s'il n'est pas trouvé en
+    // premier
+    items.par_iter().find_map_first(f)
+}
+
+/// Premier résultat non None de `f` pour les éléments de `items`, dans l'ordre des éléments
+#[cfg(not(feature = "parallel"))]
+pub fn find_map_first<T, R, F>(items: &[T], _parallel: bool, f: F) -> Option<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    items.iter().find_map(f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_ordered() {
+        let items: Vec<usize> = (0..1000).collect();
+        assert_eq!(
+            map_ordered(&items, |item| item * 2),
+            items.iter().map(|item| item * 2).collect::<Vec<_>>()
+        );
+        assert!(map_ordered(&[] as &[usize], |item| *item).is_empty());
+    }
+
+    #[test]
+    fn test_find_map_first() {
+        let items: Vec<usize> = (0..1000).collect();
+        for parallel in [true, false] {
+            assert_eq!(
+                find_map_first(&items, parallel, |item| (item % 7 == 6).then_some(*item)),
+                Some(6)
+            );
+            assert_eq!(
+                find_map_first(&items, parallel, |item| (*item > 1000).then_some(*item)),
+                None
+            );
+        }
+    }
+}
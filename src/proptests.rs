@@ -0,0 +1,209 @@
+//! Tests de propriétés (`proptest`) du parser et des règles de construction.
+//!
+//! Les grilles sont générées aléatoirement : un placement d'étoiles valide (1 étoile par ligne, colonne et
+//! région) sert de germe à des régions connexes qui grandissent case par case. Le placement d'étoiles est
+//! alors une solution de la grille et une grille partielle s'obtient en révélant quelques cases de cette
+//! solution.
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::count_solutions;
+use crate::cross_check_rules;
+use crate::get_good_rule;
+use crate::CellValue;
+use crate::CrossCheckError;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::LineColumn;
+use crate::Solution;
+use crate::StarConfigurations;
+
+/// Taille maximale (en lignes et colonnes) des grilles générées
+const MAX_SIZE: usize = 6;
+
+/// Grille générée : texte des régions (une ligne de texte par ligne de la grille) et solution
+#[derive(Clone, Debug)]
+struct Puzzle {
+    lines: Vec<String>,
+    solution: Solution,
+}
+
+impl Puzzle {
+    /// Handler de la grille générée
+    fn handler(&self) -> GridHandler {
+        GridHandler::new(&GridParser::try_from(self.lines.clone()).unwrap(), 1)
+    }
+
+    /// Grille partielle où seules les cases de `revealed` sont définies selon la solution
+    fn partial_grid(&self, handler: &GridHandler, revealed: &[bool]) -> Grid {
+        let mut grid = Grid::from(handler);
+        let size = self.lines.len();
+        for (index, _) in revealed
+            .iter()
+            .take(size * size)
+            .enumerate()
+            .filter(|(_, r)| **r)
+        {
+            let line_column = LineColumn::new(index / size, index % size);
+            grid.cell_mut(line_column).value = if self.solution.is_star(line_column) {
+                CellValue::Star
+            } else {
+                CellValue::NoStar
+            };
+        }
+        grid
+    }
+}
+
+/// Construction d'une grille de `size` x `size` cases dont les régions grandissent autour des étoiles
+/// d'un placement tiré au hasard (selon `seed`) : chaque nouvelle case d'une région est choisie selon
+/// `choices` parmi les cases voisines (horizontalement ou verticalement) des régions
+fn build_puzzle(size: usize, seed: u64, choices: &[Index]) -> Puzzle {
+    let stars = StarConfigurations::new(size, 1)
+        .sample(&mut StdRng::seed_from_u64(seed))
+        .unwrap();
+
+    let mut regions: Vec<Option<usize>> = vec![None; size * size];
+    for (region, star) in stars.iter().enumerate() {
+        regions[star.line * size + star.column] = Some(region);
+    }
+    for choice in choices.iter().cycle() {
+        // Cases non attribuées voisines d'une région
+        let mut frontier = Vec::new();
+        for (cell, region) in regions.iter().enumerate() {
+            if let Some(region) = region {
+                let (line, column) = (cell / size, cell % size);
+                let mut neighbors = Vec::new();
+                if line > 0 {
+                    neighbors.push(cell - size);
+                }
+                if line + 1 < size {
+                    neighbors.push(cell + size);
+                }
+                if column > 0 {
+                    neighbors.push(cell - 1);
+                }
+                if column + 1 < size {
+                    neighbors.push(cell + 1);
+                }
+                for neighbor in neighbors {
+                    if regions[neighbor].is_none() {
+                        frontier.push((neighbor, *region));
+                    }
+                }
+            }
+        }
+        if frontier.is_empty() {
+            break;
+        }
+        let (cell, region) = frontier[choice.index(frontier.len())];
+        regions[cell] = Some(region);
+    }
+
+    let lines = regions
+        .chunks(size)
+        .map(|line| {
+            line.iter()
+                .map(|region| char::from(b'A' + u8::try_from(region.unwrap()).unwrap()))
+                .collect()
+        })
+        .collect();
+    Puzzle {
+        lines,
+        solution: Solution::new(stars),
+    }
+}
+
+/// Générateur de grilles avec des régions connexes
+fn puzzle_strategy() -> impl Strategy<Value = Puzzle> {
+    (
+        4..=MAX_SIZE,
+        any::<u64>(),
+        prop::collection::vec(any::<Index>(), MAX_SIZE * MAX_SIZE),
+    )
+        .prop_map(|(size, seed, choices)| build_puzzle(size, seed, &choices))
+}
+
+/// Générateur de cases révélées pour une grille partielle
+fn revealed_strategy() -> impl Strategy<Value = Vec<bool>> {
+    prop::collection::vec(prop::bool::weighted(0.3), MAX_SIZE * MAX_SIZE)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn prop_parser_round_trip(puzzle in puzzle_strategy(), given in any::<Index>()) {
+        let mut lines = puzzle.lines.clone();
+        let given_star = puzzle.solution.stars()[given.index(puzzle.solution.nb_stars())];
+        lines.push(format!("* {given_star}"));
+        let parser = GridParser::try_from(lines.clone()).unwrap();
+        prop_assert_eq!(parser.to_string(), lines.join("\n"));
+
+        let parser_again = GridParser::try_from(parser.to_string().as_str()).unwrap();
+        prop_assert_eq!(parser_again.to_string(), parser.to_string());
+        prop_assert_eq!(parser_again.given_stars(), vec![given_star]);
+        for (line, text) in puzzle.lines.iter().enumerate() {
+            for (column, region) in text.chars().enumerate() {
+                prop_assert_eq!(parser_again.cell_region(LineColumn::new(line, column)), region);
+            }
+        }
+    }
+
+    #[test]
+    fn prop_grid_encode_round_trip(puzzle in puzzle_strategy(), revealed in revealed_strategy()) {
+        let handler = puzzle.handler();
+        let grid = puzzle.partial_grid(&handler, &revealed);
+        prop_assert_eq!(Grid::decode(&handler, &grid.encode()).unwrap(), grid);
+    }
+
+    #[test]
+    fn prop_solution_round_trip(puzzle in puzzle_strategy()) {
+        let text = puzzle.solution.to_string();
+        prop_assert_eq!(text.parse::<Solution>().unwrap(), puzzle.solution.clone());
+
+        let handler = puzzle.handler();
+        let grid = puzzle.solution.to_grid(&handler);
+        prop_assert_eq!(handler.extract_solution(&grid), Some(puzzle.solution));
+    }
+
+    #[test]
+    fn prop_good_rules_sound(puzzle in puzzle_strategy(), revealed in revealed_strategy()) {
+        let handler = puzzle.handler();
+        let mut grid = puzzle.partial_grid(&handler, &revealed);
+        // Une règle de construction est une déduction valable pour toutes les solutions de la grille
+        // partielle : en particulier pour la solution qui a servi à la construire
+        while let Some(good_rule) = get_good_rule(&handler, &grid).unwrap() {
+            for action in good_rule.actions() {
+                match action {
+                    GridAction::SetStar(line_column) => {
+                        prop_assert!(puzzle.solution.is_star(*line_column), "{}", good_rule);
+                    }
+                    GridAction::SetNoStar(line_column) => {
+                        prop_assert!(!puzzle.solution.is_star(*line_column), "{}", good_rule);
+                    }
+                    GridAction::SetUnknown(_) | GridAction::Toggle(_) => {
+                        prop_assert!(false, "{}", good_rule);
+                    }
+                }
+            }
+            grid.apply_good_rule(&good_rule);
+        }
+        prop_assert!(count_solutions(&handler, &grid, 1) >= 1);
+    }
+
+    #[test]
+    fn prop_good_rules_cross_check(puzzle in puzzle_strategy()) {
+        // Les grilles générées ont souvent plusieurs solutions : chaque déduction doit les respecter toutes
+        let handler = puzzle.handler();
+        match cross_check_rules(&handler, &Grid::from(&handler), 1_000) {
+            Ok(_) | Err(CrossCheckError::TooManySolutions(_)) => (),
+            Err(e) => prop_assert!(false, "{}", e),
+        }
+    }
+}
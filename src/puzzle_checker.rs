@@ -0,0 +1,205 @@
+//! Vérification d'une grille construite à partir d'une solution.
+//!
+//! On dispose ici d'un placement complet des étoiles et d'un découpage en régions (par exemple dessinés
+//! dans un éditeur) et on vérifie que l'ensemble forme une grille valide à solution unique.<br>
+//! Tous les problèmes détectés sont signalés pour aider à corriger la grille.
+
+use std::fmt::Display;
+
+use crate::grid_brute_force::find_solutions;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::GridSurfer;
+use crate::LineColumn;
+
+/// Problème détecté dans une grille construite à partir d'une solution
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleIssue {
+    /// Nombre d'étoiles à placer incohérent avec la taille de la grille
+    #[error("Impossible de placer {0} étoile(s) par ligne, colonne et région dans une grille de {1} lignes et {2} colonnes")]
+    InvalidNbStars(usize, usize, usize),
+
+    /// Étoile en dehors de la grille
+    #[error("Etoile {0} en dehors de la grille")]
+    StarOutsideGrid(LineColumn),
+
+    /// Étoile en double dans la solution
+    #[error("Etoile {0} en double")]
+    DuplicateStar(LineColumn),
+
+    /// Étoile adjacente à une autre étoile
+    #[error("Etoile {0} adjacente à l'étoile {1}")]
+    StarAdjacent(LineColumn, LineColumn),
+
+    /// Nombre d'étoiles incorrect dans une 'zone' : (zone, nombre d'étoiles, nombre attendu)
+    #[error("{1} étoile(s) dans '{0}' au lieu de {2}")]
+    WrongNbStarsInZone(GridSurfer, usize, usize),
+
+    /// La grille a une autre solution (étoiles de cette autre solution)
+    #[error("La grille a une autre solution : {}", DisplayStars(.0))]
+    OtherSolution(Vec<LineColumn>),
+}
+
+/// Affichage d'une liste d'étoiles
+struct DisplayStars<'a>(&'a [LineColumn]);
+
+impl Display for DisplayStars<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, star) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{star}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Vérifie qu'un découpage en régions `parser` et un placement des étoiles `stars` (`nb_stars` par ligne,
+/// colonne et région) forment une grille valide à solution unique.
+///
+/// ### Errors
+/// Retourne la liste des [`PuzzleIssue`] détectés si ce n'est pas le cas
+pub fn check_puzzle(
+    parser: &GridParser,
+    nb_stars: usize,
+    stars: &[LineColumn],
+) -> Result<(), Vec<PuzzleIssue>> {
+    let (nb_lines, nb_columns) = (parser.nb_lines(), parser.nb_columns());
+    if nb_stars == 0 || nb_lines < 2 * nb_stars - 1 || nb_columns < 2 * nb_stars - 1 {
+        return Err(vec![PuzzleIssue::InvalidNbStars(
+            nb_stars, nb_lines, nb_columns,
+        )]);
+    }
+
+    let mut issues = Vec::new();
+    let mut valid_stars: Vec<LineColumn> = Vec::with_capacity(stars.len());
+    for star in stars {
+        if star.line >= nb_lines || star.column >= nb_columns {
+            issues.push(PuzzleIssue::StarOutsideGrid(*star));
+        } else if valid_stars.contains(star) {
+            issues.push(PuzzleIssue::DuplicateStar(*star));
+        } else {
+            if let Some(other) = valid_stars.iter().find(|other| {
+                star.line.abs_diff(other.line) <= 1 && star.column.abs_diff(other.column) <= 1
+            }) {
+                issues.push(PuzzleIssue::StarAdjacent(*other, *star));
+            }
+            valid_stars.push(*star);
+        }
+    }
+
+    // Nombre d'étoiles dans chaque zone (ligne, colonne et région)
+    let mut zones: Vec<(GridSurfer, usize)> = Vec::new();
+    for line in 0..nb_lines {
+        let nb = valid_stars.iter().filter(|star| star.line == line).count();
+        zones.push((GridSurfer::Line(line), nb));
+    }
+    for column in 0..nb_columns {
+        let nb = valid_stars
+            .iter()
+            .filter(|star| star.column == column)
+            .count();
+        zones.push((GridSurfer::Column(column), nb));
+    }
+    for region in parser.regions() {
+        let nb = valid_stars
+            .iter()
+            .filter(|star| parser.cell_region(**star) == region)
+            .count();
+        zones.push((GridSurfer::Region(region), nb));
+    }
+    for (surfer, nb) in zones {
+        if nb != nb_stars {
+            issues.push(PuzzleIssue::WrongNbStarsInZone(surfer, nb, nb_stars));
+        }
+    }
+    if !issues.is_empty() {
+        return Err(issues);
+    }
+
+    // Ici, les étoiles forment une solution : on en cherche une autre
+    let handler = GridHandler::new(parser, nb_stars);
+    let solutions = find_solutions(&handler, &Grid::from(&handler), 2);
+    let other_solution = solutions.iter().find(|solution| {
+        valid_stars
+            .iter()
+            .any(|star| !solution.cell(*star).is_star())
+    });
+    match other_solution {
+        Some(solution) => {
+            let other_stars = handler
+                .surfer(solution, &GridSurfer::AllCells)
+                .into_iter()
+                .filter(|line_column| solution.cell(*line_column).is_star())
+                .collect();
+            Err(vec![PuzzleIssue::OtherSolution(other_stars)])
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_grid() -> GridParser {
+        GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap()
+    }
+
+    fn stars(list: &[(usize, usize)]) -> Vec<LineColumn> {
+        list.iter()
+            .map(|(line, column)| LineColumn::new(*line, *column))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_puzzle() {
+        let solution = stars(&[(0, 0), (1, 3), (2, 1), (3, 4), (4, 2)]);
+        assert_eq!(check_puzzle(&get_test_grid(), 1, &solution), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_stars() {
+        let solution = stars(&[(0, 0), (1, 1), (2, 4), (4, 3), (4, 3), (5, 0)]);
+        let issues = check_puzzle(&get_test_grid(), 1, &solution).unwrap_err();
+        assert!(issues.contains(&PuzzleIssue::StarOutsideGrid(LineColumn::new(5, 0))));
+        assert!(issues.contains(&PuzzleIssue::DuplicateStar(LineColumn::new(4, 3))));
+        assert!(issues.contains(&PuzzleIssue::StarAdjacent(
+            LineColumn::new(0, 0),
+            LineColumn::new(1, 1)
+        )));
+        assert!(issues.contains(&PuzzleIssue::WrongNbStarsInZone(GridSurfer::Line(3), 0, 1)));
+        assert!(issues.contains(&PuzzleIssue::WrongNbStarsInZone(
+            GridSurfer::Region('B'),
+            2,
+            1
+        )));
+
+        assert_eq!(
+            check_puzzle(&get_test_grid(), 4, &solution),
+            Err(vec![PuzzleIssue::InvalidNbStars(4, 5, 5)])
+        );
+    }
+
+    #[test]
+    fn test_other_solution() {
+        let parser = GridParser::try_from(vec!["AABB", "AABB", "CCDD", "CCDD"]).unwrap();
+        let solution = stars(&[(0, 1), (1, 3), (2, 0), (3, 2)]);
+        let issues = check_puzzle(&parser, 1, &solution).unwrap_err();
+        assert_eq!(
+            issues,
+            vec![PuzzleIssue::OtherSolution(stars(&[
+                (0, 2),
+                (1, 0),
+                (2, 3),
+                (3, 1)
+            ]))]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "La grille a une autre solution : C1, A2, D3, B4"
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Représentation JSON d'une grille (feature `serde`).
+//!
+//! Une grille est échangée avec une interface graphique sous la forme d'un objet [`PuzzleJson`] : dimensions,
+//! nombre d'étoiles, carte des régions (une chaîne par ligne, `.` pour une case bloquée), étoiles données,
+//! contenu optionnel des cases (une chaîne par ligne au format de [`Grid::encode`]) et métadonnées.
+//!
+//! ```json
+//! {
+//!   "nb_lines": 5,
+//!   "nb_columns": 5,
+//!   "nb_stars": 1,
+//!   "regions": ["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"],
+//!   "given_stars": ["A1"],
+//!   "cells": ["*----", "-????", "-????", "-????", "-????"],
+//!   "metadata": { "title": "Grille du jour" }
+//! }
+//! ```
+//!
+//! Les champs `given_stars`, `cells` et `metadata` sont optionnels.
+
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::LineColumn;
+use crate::PuzzleMetadata;
+
+use crate::grid_parser::GIVEN_STARS_PREFIX;
+
+/// Grille au format JSON : définition de la grille et contenu optionnel de ses cases
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PuzzleJson {
+    /// Nombre de lignes de la grille
+    pub nb_lines: usize,
+
+    /// Nombre de colonnes de la grille
+    pub nb_columns: usize,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    pub nb_stars: usize,
+
+    /// Régions des cases : une chaîne par ligne, un caractère par case (`.` pour une case bloquée)
+    pub regions: Vec<String>,
+
+    /// Étoiles données par la grille ('A1' par exemple)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub given_stars: Vec<String>,
+
+    /// Contenu des cases : une chaîne par ligne au format de [`Grid::encode`] (grille initiale si absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cells: Option<Vec<String>>,
+
+    /// Métadonnées de la grille
+    #[serde(default)]
+    pub metadata: PuzzleMetadata,
+}
+
+impl PuzzleJson {
+    /// Représentation JSON de la grille parsée `parser` avec `nb_stars` étoiles (sans contenu des cases)
+    #[must_use]
+    pub fn from_parser(parser: &GridParser, nb_stars: usize) -> Self {
+        let regions = (0..parser.nb_lines())
+            .map(|line| {
+                (0..parser.nb_columns())
+                    .map(|column| parser.cell_region(LineColumn::new(line, column)))
+                    .collect()
+            })
+            .collect();
+        Self {
+            nb_lines: parser.nb_lines(),
+            nb_columns: parser.nb_columns(),
+            nb_stars,
+            regions,
+            given_stars: parser
+                .given_stars()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            cells: None,
+            metadata: parser.metadata().clone(),
+        }
+    }
+
+    /// Représentation JSON de la grille `handler` et du contenu des cases de `grid` (si défini)
+    #[must_use]
+    pub fn from_handler(handler: &GridHandler, grid: Option<&Grid>) -> Self {
+        let cells = (0..handler.nb_lines())
+            .map(|line| (0..handler.nb_columns()).map(move |column| LineColumn::new(line, column)));
+        let regions = cells
+            .clone()
+            .map(|line| line.map(|cell| handler.cell_region(cell)).collect())
+            .collect();
+        let given_stars = cells
+            .flatten()
+            .filter(|cell| handler.is_given(*cell))
+            .map(|cell| cell.to_string())
+            .collect();
+        Self {
+            nb_lines: handler.nb_lines(),
+            nb_columns: handler.nb_columns(),
+            nb_stars: handler.nb_stars(),
+            regions,
+            given_stars,
+            cells: grid.map(|grid| {
+                grid.encode()
+                    .chars()
+                    .collect::<Vec<char>>()
+                    .chunks(handler.nb_columns())
+                    .map(|line| line.iter().collect())
+                    .collect()
+            }),
+            metadata: handler.metadata().clone(),
+        }
+    }
+
+    /// Grille parsée de cette représentation JSON
+    /// ### Errors
+    /// Retourne une erreur si les régions ne correspondent pas aux dimensions annoncées ou si la grille est
+    /// invalide (voir [`GridParser`])
+    pub fn parser(&self) -> Result<GridParser, String> {
+        if self.regions.len() != self.nb_lines
+            || self
+                .regions
+                .iter()
+                .any(|line| line.chars().count() != self.nb_columns)
+        {
+            return Err(format!(
+                "Les régions ne correspondent pas à une grille de {} lignes et {} colonnes",
+                self.nb_lines, self.nb_columns
+            ));
+        }
+        let mut lines = self.regions.clone();
+        if !self.given_stars.is_empty() {
+            lines.push(format!(
+                "{GIVEN_STARS_PREFIX}{}",
+                self.given_stars.join(" ")
+            ));
+        }
+        Ok(GridParser::try_from(lines)?.with_metadata(self.metadata.clone()))
+    }
+
+    /// Grille de cette représentation JSON
+    /// ### Errors
+    /// Retourne une erreur si la grille est invalide (voir [`PuzzleJson::parser`]) ou incompatible avec
+    /// le nombre d'étoiles (voir [`GridHandler::try_new`])
+    pub fn handler(&self) -> Result<GridHandler, String> {
+        GridHandler::try_new(&self.parser()?, self.nb_stars)
+    }
+
+    /// Contenu des cases de cette représentation JSON pour la grille `handler` (grille initiale si le
+    /// contenu n'est pas défini)
+    /// ### Errors
+    /// Retourne une erreur si le contenu des cases est invalide (voir [`Grid::decode`])
+    pub fn grid(&self, handler: &GridHandler) -> Result<Grid, String> {
+        self.cells.as_ref().map_or_else(
+            || Ok(Grid::from(handler)),
+            |cells| Grid::decode(handler, &cells.concat()),
+        )
+    }
+
+    /// Texte JSON de cette représentation
+    /// ### Errors
+    /// Retourne une erreur si la représentation ne peut pas être écrite en JSON
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Erreur d'écriture JSON : {e}"))
+    }
+
+    /// Représentation d'une grille à partir de son texte JSON
+    /// ### Errors
+    /// Retourne une erreur si le texte n'est pas une représentation JSON valide
+    #[cfg(feature = "json")]
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("Grille JSON invalide : {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::CellValue;
+
+    #[test]
+    fn test_puzzle_json() {
+        let parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED", "* A1"])
+                .unwrap()
+                .with_metadata(PuzzleMetadata {
+                    title: Some("Grille du jour".to_string()),
+                    ..PuzzleMetadata::default()
+                });
+        let puzzle = PuzzleJson::from_parser(&parser, 1);
+        assert_eq!(puzzle.regions[2], "CCBBB");
+        assert_eq!(puzzle.given_stars, vec!["A1".to_string()]);
+        assert_eq!(puzzle.parser().unwrap().list_cells(), parser.list_cells());
+
+        // Aller-retour avec le contenu des cases
+        let handler = puzzle.handler().unwrap();
+        let mut grid = puzzle.grid(&handler).unwrap();
+        grid.cell_mut(LineColumn::new(1, 1)).value = CellValue::NoStar;
+        let puzzle = PuzzleJson::from_handler(&handler, Some(&grid));
+        assert_eq!(puzzle.cells.as_ref().unwrap()[1].chars().nth(1), Some('-'));
+        assert_eq!(puzzle.grid(&handler).unwrap().encode(), grid.encode());
+        assert_eq!(puzzle.metadata.title.as_deref(), Some("Grille du jour"));
+
+        // Dimensions incohérentes
+        let bad = PuzzleJson {
+            nb_lines: 4,
+            ..puzzle
+        };
+        assert!(bad.parser().is_err());
+
+        // Nombre d'étoiles incompatible avec la grille
+        for nb_stars in [0, 3] {
+            assert!(PuzzleJson::from_parser(&parser, nb_stars)
+                .handler()
+                .is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_puzzle_json_text() {
+        let text = r#"{"nb_lines": 5, "nb_columns": 5, "nb_stars": 1,
+            "regions": ["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]}"#;
+        let puzzle = PuzzleJson::from_json(text).unwrap();
+        assert!(puzzle.cells.is_none());
+        assert_eq!(
+            PuzzleJson::from_json(&puzzle.to_json().unwrap()).unwrap(),
+            puzzle
+        );
+        assert!(PuzzleJson::from_json("{}").is_err());
+    }
+}
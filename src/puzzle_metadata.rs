@@ -0,0 +1,126 @@
+//! Métadonnées d'une grille : titre, auteur, source, difficulté annoncée et nombre d'étoiles.
+//!
+//! Dans le fichier d'une grille, les métadonnées sont des commentaires de la forme `# clé: valeur` avec les
+//! clés `title`, `author`, `source`, `difficulty` et `stars`. Les autres commentaires sont ignorés.
+//!
+//! ```text
+//! # title: Grille du jour
+//! # author: ddurler
+//! # source: https://example.com/grilles/42
+//! # difficulty: Expert
+//! # stars: 2
+//! ```
+
+use std::fmt::Display;
+
+/// Début d'une ligne de métadonnée dans le fichier d'une grille
+const METADATA_PREFIX: &str = "#";
+
+/// Métadonnées d'une grille (provenance et caractéristiques annoncées)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PuzzleMetadata {
+    /// Titre de la grille
+    pub title: Option<String>,
+
+    /// Auteur de la grille
+    pub author: Option<String>,
+
+    /// Source de la grille (URL d'origine par exemple)
+    pub source: Option<String>,
+
+    /// Difficulté annoncée par la source de la grille
+    pub difficulty: Option<String>,
+
+    /// Nombre d'étoiles annoncé à placer dans chaque ligne, colonne et région
+    pub nb_stars: Option<usize>,
+}
+
+impl Display for PuzzleMetadata {
+    /// Lignes de commentaires `# clé: valeur` des métadonnées définies
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nb_stars = self.nb_stars.map(|nb_stars| nb_stars.to_string());
+        let entries = [
+            ("title", self.title.as_ref()),
+            ("author", self.author.as_ref()),
+            ("source", self.source.as_ref()),
+            ("difficulty", self.difficulty.as_ref()),
+            ("stars", nb_stars.as_ref()),
+        ];
+        let mut first = true;
+        for (key, value) in entries {
+            if let Some(value) = value {
+                if !first {
+                    writeln!(f)?;
+                }
+                write!(f, "{METADATA_PREFIX} {key}: {value}")?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PuzzleMetadata {
+    /// Retourne `true` si aucune métadonnée n'est définie
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Analyse une ligne de commentaire du fichier d'une grille.<br>
+    /// Retourne `true` si la ligne est une métadonnée reconnue (`# clé: valeur`)
+    pub(crate) fn parse_comment(&mut self, comment: &str) -> bool {
+        let Some((key, value)) = comment
+            .strip_prefix(METADATA_PREFIX)
+            .and_then(|comment| comment.split_once(':'))
+        else {
+            return false;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            return false;
+        }
+        match key.trim() {
+            "title" => self.title = Some(value.to_string()),
+            "author" => self.author = Some(value.to_string()),
+            "source" => self.source = Some(value.to_string()),
+            "difficulty" => self.difficulty = Some(value.to_string()),
+            "stars" => match value.parse() {
+                Ok(nb_stars) => self.nb_stars = Some(nb_stars),
+                Err(_) => return false,
+            },
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_comment() {
+        let mut metadata = PuzzleMetadata::default();
+        assert!(metadata.is_empty());
+        assert!(metadata.parse_comment("# title: Grille du jour"));
+        assert!(metadata.parse_comment("# source: https://example.com/grilles/42"));
+        assert!(metadata.parse_comment("#stars:2"));
+        assert!(!metadata.parse_comment("# Exemple de grille 2★"));
+        assert!(!metadata.parse_comment("# seed: 7"));
+        assert!(!metadata.parse_comment("# stars: deux"));
+        assert!(!metadata.parse_comment("; title: commentaire"));
+
+        assert_eq!(metadata.title.as_deref(), Some("Grille du jour"));
+        assert_eq!(
+            metadata.source.as_deref(),
+            Some("https://example.com/grilles/42")
+        );
+        assert_eq!(metadata.nb_stars, Some(2));
+        assert_eq!(
+            metadata.to_string(),
+            "# title: Grille du jour\n# source: https://example.com/grilles/42\n# stars: 2"
+        );
+    }
+}
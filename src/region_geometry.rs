@@ -0,0 +1,136 @@
+//! Géométrie des régions d'une grille.
+//!
+//! Ces méthodes de [`GridHandler`] décrivent la forme d'une région à partir des cases voisines
+//! orthogonalement (haut, bas, gauche, droite) : cases en bordure de la région, cases intérieures et
+//! frontière commune avec une autre région.<br>
+//! Les bords de la grille sont des bordures de région, y compris pour une grille 'torique' (comme le trait
+//! épais du bord de la grille dessiné par [`GridHandler::display_svg`]).
+
+use crate::GridHandler;
+use crate::LineColumn;
+use crate::Region;
+
+impl GridHandler {
+    /// Cases voisines orthogonalement d'une case dans la grille (au plus 4)
+    fn orthogonal_cells(&self, line_column: LineColumn) -> impl Iterator<Item = LineColumn> {
+        let (nb_lines, nb_columns) = (self.nb_lines(), self.nb_columns());
+        let LineColumn { line, column } = line_column;
+        [
+            line.checked_sub(1).map(|up| LineColumn::new(up, column)),
+            (line + 1 < nb_lines).then(|| LineColumn::new(line + 1, column)),
+            column
+                .checked_sub(1)
+                .map(|left| LineColumn::new(line, left)),
+            (column + 1 < nb_columns).then(|| LineColumn::new(line, column + 1)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Nombre de côtés de la case `line_column` qui sont une bordure de sa région
+    fn nb_border_sides(&self, line_column: LineColumn) -> usize {
+        let region = self.cell_region(line_column);
+        4 - self
+            .orthogonal_cells(line_column)
+            .filter(|other| self.cell_region(*other) == region)
+            .count()
+    }
+
+    /// Cases de la région `region` en bordure de la région : un de leurs côtés touche une autre région ou
+    /// le bord de la grille
+    /// # Panics
+    /// Panic si la région n'existe pas dans la grille
+    #[must_use]
+    pub fn region_perimeter_cells(&self, region: Region) -> Vec<LineColumn> {
+        self.region_cells(region)
+            .iter()
+            .filter(|line_column| self.nb_border_sides(**line_column) > 0)
+            .copied()
+            .collect()
+    }
+
+    /// Cases intérieures de la région `region` : leurs 4 voisines orthogonales sont dans la région
+    /// # Panics
+    /// Panic si la région n'existe pas dans la grille
+    #[must_use]
+    pub fn region_interior_cells(&self, region: Region) -> Vec<LineColumn> {
+        self.region_cells(region)
+            .iter()
+            .filter(|line_column| self.nb_border_sides(**line_column) == 0)
+            .copied()
+            .collect()
+    }
+
+    /// Périmètre de la région `region` : nombre de côtés de cases en bordure de la région
+    /// # Panics
+    /// Panic si la région n'existe pas dans la grille
+    #[must_use]
+    pub fn region_perimeter(&self, region: Region) -> usize {
+        self.region_cells(region)
+            .iter()
+            .map(|line_column| self.nb_border_sides(*line_column))
+            .sum()
+    }
+
+    /// Frontière orthogonale entre les régions `region` et `other` : couples de cases voisines
+    /// orthogonalement (case de `region`, case de `other`), dans l'ordre des cases de `region`.<br>
+    /// La frontière est vide si les régions ne se touchent pas (ou seulement en diagonale)
+    /// # Panics
+    /// Panic si la région `region` n'existe pas dans la grille
+    #[must_use]
+    pub fn region_frontier(&self, region: Region, other: Region) -> Vec<(LineColumn, LineColumn)> {
+        let mut frontier = Vec::new();
+        for line_column in self.region_cells(region) {
+            for neighbor in self.orthogonal_cells(*line_column) {
+                if self.cell_region(neighbor) == other && other != region {
+                    frontier.push((*line_column, neighbor));
+                }
+            }
+        }
+        frontier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::GridParser;
+
+    #[test]
+    fn test_region_geometry() {
+        let grid_parser =
+            GridParser::try_from(vec!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"]).unwrap();
+        let grid_handler = GridHandler::new(&grid_parser, 1);
+
+        // Région B : seules les cases C2 et D2 (entourées de cases B) sont intérieures
+        let b_cells = grid_handler.region_cells('B');
+        let b_interior = grid_handler.region_interior_cells('B');
+        assert_eq!(
+            b_interior,
+            vec![LineColumn::new(1, 2), LineColumn::new(1, 3)]
+        );
+        assert_eq!(
+            grid_handler.region_perimeter_cells('B').len() + b_interior.len(),
+            b_cells.len()
+        );
+        assert!(grid_handler.region_interior_cells('A').is_empty());
+
+        // Région A : 2 cases verticales
+        assert_eq!(grid_handler.region_perimeter('A'), 6);
+        assert_eq!(grid_handler.region_perimeter('E'), 8);
+
+        // Frontières
+        assert_eq!(
+            grid_handler.region_frontier('A', 'C'),
+            vec![(LineColumn::new(1, 0), LineColumn::new(2, 0))]
+        );
+        assert_eq!(grid_handler.region_frontier('E', 'D').len(), 5);
+        assert_eq!(
+            grid_handler.region_frontier('D', 'E').len(),
+            grid_handler.region_frontier('E', 'D').len()
+        );
+        assert!(grid_handler.region_frontier('A', 'E').is_empty());
+        assert!(grid_handler.region_frontier('A', 'A').is_empty());
+    }
+}
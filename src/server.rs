@@ -0,0 +1,270 @@
+//! Service HTTP de résolution de grilles (feature `server`).
+//!
+//! Le service expose une petite API REST au format JSON :
+//!
+//! * `POST /solve` : résout une grille ([`SolveRequest`]) et retourne la solution et la liste des règles
+//!   de construction appliquées ([`SolveResponse`])
+//! * `POST /hint` : retourne la prochaine règle de construction applicable à une grille dans un état
+//!   donné ([`HintRequest`], [`HintResponse`])
+//!
+//! Une requête invalide (grille ou nombre d'étoiles incohérents) est refusée avec le statut 400. Les
+//! recherches sont exécutées hors des threads du service et interrompues au-delà de
+//! [`REQUEST_MAX_DURATION`]. Le service écoute par défaut sur l'interface locale ([`DEFAULT_HOST`]).
+
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+
+use crate::grid_solve::solve_from;
+use crate::json_rule::JsonRule;
+use crate::try_get_good_rule;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::Interruption;
+use crate::PuzzleMetadata;
+use crate::SolverConfig;
+use crate::SolverLimits;
+
+/// Durée maximum de la recherche pour une requête
+pub const REQUEST_MAX_DURATION: Duration = Duration::from_secs(10);
+
+/// Interface d'écoute par défaut du service (locale uniquement)
+pub const DEFAULT_HOST: &str = "127.0.0.1";
+
+/// Requête de résolution d'une grille
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SolveRequest {
+    /// Texte de la grille (une ligne de texte par ligne de la grille, voir [`GridParser`])
+    pub puzzle: String,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    pub nb_stars: usize,
+}
+
+/// Réponse à une requête de résolution d'une grille
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SolveResponse {
+    /// Grille entièrement résolue par les règles de construction
+    pub solved: bool,
+
+    /// Solution (coordonnées des étoiles, voir [`crate::Solution`]) si la grille est résolue
+    pub solution: Option<String>,
+
+    /// Règles de construction appliquées successivement
+    pub steps: Vec<JsonRule>,
+
+    /// Grille obtenue après l'application des règles (voir [`Grid::encode`])
+    pub state: String,
+
+    /// Incohérence détectée dans la grille qui a interrompu la résolution
+    pub error: Option<String>,
+
+    /// Cause de l'interruption de la résolution (durée maximum dépassée, voir [`REQUEST_MAX_DURATION`])
+    pub interruption: Option<Interruption>,
+
+    /// Métadonnées de la grille (titre, auteur, source...)
+    pub metadata: PuzzleMetadata,
+}
+
+/// Requête de la prochaine règle de construction applicable à une grille
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct HintRequest {
+    /// Texte de la grille (une ligne de texte par ligne de la grille, voir [`GridParser`])
+    pub puzzle: String,
+
+    /// Nombre d'étoiles à placer dans chaque ligne, colonne et région
+    pub nb_stars: usize,
+
+    /// État de la grille (voir [`Grid::encode`])
+    pub state: String,
+}
+
+/// Réponse à une requête de la prochaine règle de construction
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct HintResponse {
+    /// Prochaine règle de construction applicable (None si aucune règle n'est applicable)
+    pub hint: Option<JsonRule>,
+}
+
+/// Grille définie par le texte `puzzle` avec `nb_stars` étoiles
+fn parse_puzzle(puzzle: &str, nb_stars: usize) -> Result<GridHandler, String> {
+    GridHandler::try_new(&GridParser::try_from(puzzle)?, nb_stars)
+}
+
+/// Configuration de la recherche pour une requête (limitée à [`REQUEST_MAX_DURATION`])
+fn request_config() -> SolverConfig {
+    SolverConfig::default().with_limits(SolverLimits::new().with_max_duration(REQUEST_MAX_DURATION))
+}
+
+/// Traitement d'une requête de résolution d'une grille
+/// ### Errors
+/// Retourne une erreur si la grille n'est pas valide
+pub fn solve_request(request: &SolveRequest) -> Result<SolveResponse, String> {
+    let handler = parse_puzzle(&request.puzzle, request.nb_stars)?;
+    let grid = Grid::from(&handler);
+    let outcome = solve_from(handler, grid, &request_config(), |_, _| true);
+    let solved = outcome.is_solved();
+    Ok(SolveResponse {
+        solved,
+        solution: outcome
+            .handler
+            .extract_solution(&outcome.grid)
+            .map(|solution| solution.to_string()),
+        state: outcome.grid.encode(),
+        error: outcome.error.map(|e| e.to_string()),
+        interruption: outcome.interruption,
+        metadata: outcome.handler.metadata().clone(),
+        steps: outcome
+            .steps
+            .into_iter()
+            .map(|(rule, level)| JsonRule::new(rule, level))
+            .collect(),
+    })
+}
+
+/// Traitement d'une requête de la prochaine règle de construction
+/// ### Errors
+/// Retourne une erreur si la grille ou son état ne sont pas valides ou si la recherche est interrompue
+pub fn hint_request(request: &HintRequest) -> Result<HintResponse, String> {
+    let handler = parse_puzzle(&request.puzzle, request.nb_stars)?;
+    let grid = Grid::decode(&handler, &request.state)?;
+    let hint = try_get_good_rule(&handler, &grid, &request_config())
+        .map_err(|e| e.to_string())?
+        .map(|(rule, level)| JsonRule::new(rule, level));
+    Ok(HintResponse { hint })
+}
+
+/// Traitement d'une requête par `handle` dans un thread dédié aux traitements bloquants
+async fn blocking_request<Request, Response>(
+    request: Request,
+    handle: fn(&Request) -> Result<Response, String>,
+) -> Result<Json<Response>, (StatusCode, String)>
+where
+    Request: Send + 'static,
+    Response: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || handle(&request))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+/// Routes de l'API REST du service
+pub fn router() -> Router {
+    Router::new()
+        .route(
+            "/solve",
+            post(|Json(request): Json<SolveRequest>| blocking_request(request, solve_request)),
+        )
+        .route(
+            "/hint",
+            post(|Json(request): Json<HintRequest>| blocking_request(request, hint_request)),
+        )
+}
+
+/// Lance le service HTTP sur le port `port` de l'interface locale [`DEFAULT_HOST`] (bloquant)
+/// ### Errors
+/// Retourne une erreur si le service ne peut pas être lancé sur ce port
+pub fn serve(port: u16) -> std::io::Result<()> {
+    serve_on(DEFAULT_HOST, port)
+}
+
+/// Lance le service HTTP sur l'interface `host` et le port `port` (bloquant).<br>
+/// `0.0.0.0` rend le service accessible depuis les autres machines du réseau
+/// ### Errors
+/// Retourne une erreur si le service ne peut pas être lancé sur cette interface et ce port
+pub fn serve_on(host: &str, port: u16) -> std::io::Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind((host, port)).await?;
+        axum::serve(listener, router()).await
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED";
+
+    #[test]
+    fn test_solve_request() {
+        let response = solve_request(&SolveRequest {
+            puzzle: PUZZLE.to_string(),
+            nb_stars: 1,
+        })
+        .unwrap();
+        assert!(response.solved);
+        assert_eq!(response.solution.unwrap().split_whitespace().count(), 5);
+        assert!(!response.steps.is_empty());
+        assert!(!response.state.contains('?'));
+        assert!(response.error.is_none());
+        assert!(response.metadata.is_empty());
+
+        assert!(response.interruption.is_none());
+
+        assert!(solve_request(&SolveRequest {
+            puzzle: "ABB\nAB".to_string(),
+            nb_stars: 1,
+        })
+        .is_err());
+
+        // Nombre d'étoiles incohérent avec la grille
+        for (puzzle, nb_stars) in [
+            (PUZZLE, 0),
+            (PUZZLE, 3),
+            ("AABBB\nAABBB\nCCBBB\nDDDDD\nDDDDD", 1),
+        ] {
+            let request = SolveRequest {
+                puzzle: puzzle.to_string(),
+                nb_stars,
+            };
+            assert!(solve_request(&request).is_err());
+        }
+    }
+
+    #[test]
+    fn test_hint_request() {
+        let mut request = HintRequest {
+            puzzle: PUZZLE.to_string(),
+            nb_stars: 1,
+            state: "?".repeat(25),
+        };
+        let response = hint_request(&request).unwrap();
+        assert!(response.hint.is_some());
+
+        // État invalide
+        request.state = "?".repeat(24);
+        assert!(hint_request(&request).is_err());
+        request.state = "?".repeat(25);
+        request.nb_stars = 3;
+        assert!(hint_request(&request).is_err());
+        request.nb_stars = 1;
+
+        // Grille résolue : pas de règle
+        let solved = solve_request(&SolveRequest {
+            puzzle: PUZZLE.to_string(),
+            nb_stars: 1,
+        })
+        .unwrap();
+        request.state = solved.state;
+        assert!(hint_request(&request).unwrap().hint.is_none());
+    }
+
+    #[test]
+    fn test_json() {
+        let request: SolveRequest = serde_json::from_str(
+            r#"{"puzzle": "ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED", "nb_stars": 1}"#,
+        )
+        .unwrap();
+        let response = solve_request(&request).unwrap();
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"solved\":true"));
+    }
+}
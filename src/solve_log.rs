@@ -0,0 +1,107 @@
+//! Journal structuré d'une résolution.
+//!
+//! Chaque règle appliquée par une résolution ([`crate::SolveOutcome`]) est décrite par un
+//! [`SolveLogStep`] : catégorie et niveau de la règle, zone examinée, actions et grille obtenue. Avec la
+//! feature `json`, ce journal est produit au format JSON (un objet par ligne, voir [`solve_log_json`]) pour
+//! être exploité par d'autres outils.
+
+use crate::GridAction;
+use crate::GridSurfer;
+use crate::RuleKind;
+use crate::SolveOutcome;
+
+/// Étape du journal d'une résolution : règle appliquée et grille obtenue
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveLogStep {
+    /// Numéro de l'étape (à partir de 1)
+    pub step: usize,
+
+    /// Niveau de la règle (voir [`crate::get_good_rule_with_level`])
+    pub level: usize,
+
+    /// Catégorie de la règle
+    pub kind: RuleKind,
+
+    /// Zone examinée par la règle sous sa forme canonique (None si la règle ne porte pas sur une zone)
+    pub zone: Option<GridSurfer>,
+
+    /// Actions de la règle
+    pub actions: Vec<GridAction>,
+
+    /// Description textuelle de la règle
+    pub text: String,
+
+    /// Grille obtenue après la règle (voir [`crate::Grid::encode`])
+    pub grid: String,
+}
+
+impl SolveOutcome {
+    /// Journal structuré de la résolution : une étape par règle appliquée
+    #[must_use]
+    pub fn log_steps(&self) -> Vec<SolveLogStep> {
+        let mut grid = self.initial_grid();
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(index, (good_rule, level))| {
+                grid.apply_good_rule(good_rule);
+                SolveLogStep {
+                    step: index + 1,
+                    level: *level,
+                    kind: good_rule.kind(),
+                    zone: good_rule.zone().map(GridSurfer::normalized),
+                    actions: good_rule.actions().to_vec(),
+                    text: good_rule.to_string(),
+                    grid: grid.encode(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Journal de la résolution `outcome` au format JSON : un objet [`SolveLogStep`] par ligne
+#[cfg(feature = "json")]
+#[must_use]
+pub fn solve_log_json(outcome: &SolveOutcome) -> String {
+    outcome
+        .log_steps()
+        .iter()
+        .map(|step| serde_json::to_string(step).unwrap_or_default() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::solve_file;
+    use crate::SolverConfig;
+
+    #[test]
+    fn test_log_steps() {
+        let outcome = solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap();
+        let steps: Vec<SolveLogStep> = outcome.log_steps();
+        assert_eq!(steps.len(), outcome.steps.len());
+        assert_eq!(steps[0].step, 1);
+        for (step, (good_rule, level)) in steps.iter().zip(&outcome.steps) {
+            assert_eq!(step.level, *level);
+            assert_eq!(step.kind, good_rule.kind());
+            assert_eq!(step.actions, good_rule.actions());
+        }
+        // La dernière étape donne la grille résolue
+        assert_eq!(steps.last().unwrap().grid, outcome.grid.encode());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_solve_log_json() {
+        let outcome = solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap();
+        let json = solve_log_json(&outcome);
+        let steps: Vec<SolveLogStep> = json
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(steps, outcome.log_steps());
+    }
+}
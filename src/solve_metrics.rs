@@ -0,0 +1,393 @@
+//! Mesures de l'effort de résolution d'une grille.
+//!
+//! [`SolveMetrics::measure`] exécute un traitement (par exemple [`crate::solve_file`]) en relevant les
+//! règles de construction trouvées, les zones examinées par la recherche des grilles possibles (stratégie
+//! utilisée, zones abandonnées), le nombre de grilles possibles explorées, le pic mémoire de ces grilles
+//! et la durée du traitement.
+//!
+//! Les statistiques par niveau de règle ([`SolveStats`]) détaillent le nombre de recherches de chaque
+//! règle, le nombre de règles trouvées, la durée cumulée de ces recherches et le nombre de grilles
+//! possibles qu'elles ont explorées : elles montrent où passe le temps de résolution d'une grille difficile.
+//!
+//! Aucun taux de succès de cache n'est relevé : les listes de cases d'un [`crate::GridHandler`] (régions,
+//! lignes, colonnes, cases adjacentes) sont toutes calculées à sa construction et partagées par ses copies,
+//! chaque consultation est donc un succès.
+//!
+//! Les mesures sont relevées pour le thread courant : les grilles résolues en parallèle par d'autres
+//! threads (feature `parallel`) ne sont pas comptabilisées. Les zones d'une même règle examinées en
+//! parallèle sont en revanche comptabilisées pour le thread qui recherche la règle.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::CollectorStrategy;
+use crate::NB_GOOD_RULE_LEVELS;
+
+thread_local! {
+    /// Mesures en cours de relevé pour le thread courant (None si aucun relevé en cours)
+    static RECORDER: RefCell<Option<SolveMetrics>> = const { RefCell::new(None) };
+}
+
+/// Mesures de l'effort de résolution d'une grille (voir [`SolveMetrics::measure`])
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolveMetrics {
+    /// Nombre de règles de construction trouvées pour chaque niveau de règle
+    pub rules_by_level: [usize; NB_GOOD_RULE_LEVELS],
+
+    /// Nombre de zones examinées par la recherche récursive des grilles possibles pour chaque stratégie
+    /// (force brute, récursive)
+    pub zones_by_strategy: [usize; 2],
+
+    /// Nombre de zones examinées par la recherche des grilles possibles limitée au contenu de la zone
+    pub nb_simple_zones: usize,
+
+    /// Nombre de zones abandonnées car elles ont trop de grilles possibles
+    pub nb_overflow_zones: usize,
+
+    /// Nombre de zones écartées car elles ont au moins autant de grilles possibles que la meilleure zone
+    /// déjà trouvée pour la même règle (ces zones ne sont pas abandonnées)
+    pub nb_pruned_zones: usize,
+
+    /// Nombre total de grilles possibles explorées
+    pub nb_possible_grids: usize,
+
+    /// Nombre maximum de grilles possibles mémorisées simultanément (pour une zone)
+    pub peak_possible_grids: usize,
+
+    /// Mémoire occupée par ce nombre maximum de grilles possibles (en octets)
+    pub peak_possible_grids_bytes: usize,
+
+    /// Statistiques de la recherche des règles pour chaque niveau de règle
+    pub stats: SolveStats,
+
+    /// Durée du traitement mesuré
+    pub duration: Duration,
+}
+
+impl SolveMetrics {
+    /// Exécute le traitement `f` et retourne son résultat avec les mesures relevées pendant son
+    /// exécution.<br>
+    /// Les mesures d'un traitement imbriqué ne sont pas comptabilisées dans le traitement englobant
+    pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Self) {
+        let previous = RECORDER.with(|recorder| recorder.borrow_mut().replace(Self::default()));
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        let mut metrics = RECORDER
+            .with(|recorder| std::mem::replace(&mut *recorder.borrow_mut(), previous))
+            .unwrap_or_default();
+        metrics.duration = duration;
+        (result, metrics)
+    }
+
+    /// Nombre total de règles de construction trouvées
+    #[must_use]
+    pub fn nb_rules(&self) -> usize {
+        self.rules_by_level.iter().sum()
+    }
+
+    /// Nombre total de zones examinées par la recherche des grilles possibles
+    #[must_use]
+    pub fn nb_zones(&self) -> usize {
+        self.zones_by_strategy.iter().sum::<usize>() + self.nb_simple_zones
+    }
+}
+
+/// Statistiques de la recherche d'une règle de construction d'un niveau (voir [`SolveStats`])
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RuleStats {
+    /// Nombre de recherches de la règle
+    pub invocations: usize,
+
+    /// Nombre de recherches qui ont trouvé une règle
+    pub hits: usize,
+
+    /// Durée cumulée des recherches de la règle
+    pub duration: Duration,
+
+    /// Nombre de grilles possibles explorées par les recherches de la règle
+    pub nb_possible_grids: usize,
+}
+
+impl RuleStats {
+    /// Ajoute les statistiques `other`
+    fn add(&mut self, other: &Self) {
+        self.invocations += other.invocations;
+        self.hits += other.hits;
+        self.duration += other.duration;
+        self.nb_possible_grids += other.nb_possible_grids;
+    }
+}
+
+/// Statistiques de la recherche des règles de construction pour chaque niveau de règle (voir
+/// [`SolveMetrics::stats`])
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Statistiques de chaque niveau de règle (voir [`crate::get_good_rule_with_level`])
+    pub levels: [RuleStats; NB_GOOD_RULE_LEVELS],
+}
+
+impl SolveStats {
+    /// Niveaux de règle recherchés au moins une fois avec leurs statistiques
+    pub fn invoked_levels(&self) -> impl Iterator<Item = (usize, &RuleStats)> {
+        self.levels
+            .iter()
+            .enumerate()
+            .filter(|(_, stats)| stats.invocations > 0)
+    }
+
+    /// Niveau de règle dont les recherches ont duré le plus longtemps (None si aucune recherche)
+    #[must_use]
+    pub fn slowest_level(&self) -> Option<usize> {
+        self.invoked_levels()
+            .max_by_key(|(_, stats)| stats.duration)
+            .map(|(level, _)| level)
+    }
+}
+
+/// Modifie les mesures en cours de relevé pour le thread courant (sans effet si aucun relevé en cours)
+fn record(update: impl FnOnce(&mut SolveMetrics)) {
+    RECORDER.with(|recorder| {
+        if let Some(metrics) = recorder.borrow_mut().as_mut() {
+            update(metrics);
+        }
+    });
+}
+
+/// Relève une règle de construction de niveau `level` trouvée
+pub(crate) fn record_rule(level: usize) {
+    record(|metrics| metrics.rules_by_level[level] += 1);
+}
+
+/// Exécute la recherche `search` de la règle de niveau `level` en relevant ses statistiques (voir
+/// [`SolveStats`]) et retourne son résultat
+pub(crate) fn record_rule_search<T>(level: usize, search: impl FnOnce() -> Option<T>) -> Option<T> {
+    // Grilles possibles déjà explorées (None si aucun relevé en cours)
+    let Some(nb_possible_grids) = RECORDER.with(|recorder| {
+        recorder
+            .borrow()
+            .as_ref()
+            .map(|metrics| metrics.nb_possible_grids)
+    }) else {
+        return search();
+    };
+    let start = Instant::now();
+    let result = search();
+    let duration = start.elapsed();
+    record(|metrics| {
+        metrics.stats.levels[level].add(&RuleStats {
+            invocations: 1,
+            hits: usize::from(result.is_some()),
+            duration,
+            nb_possible_grids: metrics.nb_possible_grids - nb_possible_grids,
+        });
+    });
+    result
+}
+
+/// Issue de l'examen d'une zone par la recherche des grilles possibles (voir [`record_zone`])
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ZoneExamination {
+    /// Toutes les grilles possibles de la zone ont été collectées
+    Complete,
+
+    /// Zone abandonnée car elle a trop de grilles possibles (voir [`SolveMetrics::nb_overflow_zones`])
+    Overflow,
+
+    /// Zone écartée car une meilleure zone a déjà été trouvée (voir [`SolveMetrics::nb_pruned_zones`])
+    Pruned,
+}
+
+/// Relève l'examen d'une zone par la recherche des grilles possibles : stratégie de la recherche (None si
+/// la recherche est limitée au contenu de la zone), nombre de grilles possibles collectées et mémoire
+/// qu'elles occupent, issue de l'examen
+pub(crate) fn record_zone(
+    strategy: Option<CollectorStrategy>,
+    nb_possible_grids: usize,
+    possible_grids_bytes: usize,
+    examination: ZoneExamination,
+) {
+    record(|metrics| {
+        match strategy {
+            Some(CollectorStrategy::BruteForce) => metrics.zones_by_strategy[0] += 1,
+            Some(CollectorStrategy::Recursive) => metrics.zones_by_strategy[1] += 1,
+            None => metrics.nb_simple_zones += 1,
+        }
+        match examination {
+            ZoneExamination::Complete => (),
+            ZoneExamination::Overflow => metrics.nb_overflow_zones += 1,
+            ZoneExamination::Pruned => metrics.nb_pruned_zones += 1,
+        }
+        metrics.nb_possible_grids += nb_possible_grids;
+        if nb_possible_grids > metrics.peak_possible_grids {
+            metrics.peak_possible_grids = nb_possible_grids;
+            metrics.peak_possible_grids_bytes = possible_grids_bytes;
+        }
+    });
+}
+
+/// Exécute la recherche `search` d'une règle de construction et retourne son résultat avec le nombre de
+/// zones abandonnées par cette recherche (voir [`SolveMetrics::nb_overflow_zones`]).<br>
+/// Les mesures de la recherche restent comptabilisées dans le relevé en cours
+pub(crate) fn count_overflow_zones<R>(search: impl FnOnce() -> R) -> (R, usize) {
+    let (result, metrics) = SolveMetrics::measure(search);
+    record_metrics(&metrics);
+    (result, metrics.nb_overflow_zones)
+}
+
+/// Ajoute aux mesures en cours de relevé pour le thread courant les mesures `other` relevées par un
+/// autre thread ou par un relevé imbriqué (sans effet si aucun relevé en cours)
+pub(crate) fn record_metrics(other: &SolveMetrics) {
+    record(|metrics| {
+        for (nb_rules, other_nb_rules) in
+            metrics.rules_by_level.iter_mut().zip(other.rules_by_level)
+        {
+            *nb_rules += other_nb_rules;
+        }
+        for (nb_zones, other_nb_zones) in metrics
+            .zones_by_strategy
+            .iter_mut()
+            .zip(other.zones_by_strategy)
+        {
+            *nb_zones += other_nb_zones;
+        }
+        for (stats, other_stats) in metrics.stats.levels.iter_mut().zip(&other.stats.levels) {
+            stats.add(other_stats);
+        }
+        metrics.nb_simple_zones += other.nb_simple_zones;
+        metrics.nb_overflow_zones += other.nb_overflow_zones;
+        metrics.nb_pruned_zones += other.nb_pruned_zones;
+        metrics.nb_possible_grids += other.nb_possible_grids;
+        if other.peak_possible_grids > metrics.peak_possible_grids {
+            metrics.peak_possible_grids = other.peak_possible_grids;
+            metrics.peak_possible_grids_bytes = other.peak_possible_grids_bytes;
+        }
+    });
+}
+
+impl Display for SolveMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Métriques de la résolution :")?;
+        let levels = self
+            .rules_by_level
+            .iter()
+            .enumerate()
+            .filter(|(_, nb_rules)| **nb_rules > 0)
+            .map(|(level, nb_rules)| format!("{level}:{nb_rules}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(
+            f,
+            "  Règles trouvées        : {} (par niveau {levels})",
+            self.nb_rules()
+        )?;
+        writeln!(
+            f,
+            "  Zones examinées        : {} (force brute {}, récursive {}, zone seule {}, abandonnées {}, \
+             écartées {})",
+            self.nb_zones(),
+            self.zones_by_strategy[0],
+            self.zones_by_strategy[1],
+            self.nb_simple_zones,
+            self.nb_overflow_zones,
+            self.nb_pruned_zones
+        )?;
+        writeln!(
+            f,
+            "  Grilles possibles      : {} explorées, au plus {} simultanément ({} Ko)",
+            self.nb_possible_grids,
+            self.peak_possible_grids,
+            self.peak_possible_grids_bytes.div_ceil(1024)
+        )?;
+        write!(
+            f,
+            "  Durée                  : {} ms",
+            self.duration.as_millis()
+        )?;
+        for (level, stats) in self.stats.invoked_levels() {
+            write!(
+                f,
+                "\n  Niveau {level:>2}              : {} recherches, {} trouvées, {} ms, {} grilles possibles",
+                stats.invocations,
+                stats.hits,
+                stats.duration.as_millis(),
+                stats.nb_possible_grids
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::solve_file;
+    use crate::SolverConfig;
+
+    #[test]
+    fn test_measure() {
+        let (outcome, metrics) = SolveMetrics::measure(|| {
+            solve_file("./test_grids/facile01_2.txt", 2, &SolverConfig::default()).unwrap()
+        });
+        assert!(outcome.is_solved());
+        assert_eq!(metrics.nb_rules(), outcome.steps.len());
+        for (_, level) in &outcome.steps {
+            assert!(metrics.rules_by_level[*level] > 0);
+        }
+        assert!(metrics.nb_zones() > 0);
+        assert!(metrics.nb_possible_grids >= metrics.peak_possible_grids);
+        assert!(metrics.peak_possible_grids_bytes > 0);
+        assert!(metrics
+            .to_string()
+            .contains(&format!("Règles trouvées        : {}", outcome.steps.len())));
+
+        // Statistiques par niveau : chaque règle trouvée l'est par une recherche de son niveau
+        for (level, stats) in metrics.stats.levels.iter().enumerate() {
+            assert_eq!(stats.hits, metrics.rules_by_level[level]);
+            assert!(stats.invocations >= stats.hits);
+        }
+        // Aucune recherche une fois la grille résolue
+        assert_eq!(metrics.stats.levels[0].invocations, outcome.steps.len());
+        assert_eq!(
+            metrics
+                .stats
+                .levels
+                .iter()
+                .map(|stats| stats.nb_possible_grids)
+                .sum::<usize>(),
+            metrics.nb_possible_grids
+        );
+        assert!(metrics.stats.slowest_level().is_some());
+        assert!(metrics.to_string().contains("Niveau  0"));
+
+        // Les zones écartées par une meilleure zone ne sont pas abandonnées, en mode séquentiel comme en
+        // mode parallèle
+        let measure = |config: &SolverConfig| {
+            SolveMetrics::measure(|| solve_file("./test_grids/expert01_2.txt", 2, config).unwrap())
+                .1
+        };
+        let sequential = measure(&SolverConfig::new().with_parallel(false));
+        let parallel = measure(&SolverConfig::new());
+        assert_eq!(sequential.nb_overflow_zones, 0);
+        assert!(sequential.nb_pruned_zones > 0);
+        assert_eq!(
+            (parallel.nb_overflow_zones, parallel.nb_pruned_zones),
+            (sequential.nb_overflow_zones, sequential.nb_pruned_zones)
+        );
+
+        // Mesures imbriquées : le traitement englobant ne comptabilise pas le traitement imbriqué
+        let ((_, inner), outer) = SolveMetrics::measure(|| {
+            SolveMetrics::measure(|| {
+                solve_file("./test_grids/test01.txt", 1, &SolverConfig::default()).unwrap()
+            })
+        });
+        assert!(inner.nb_rules() > 0);
+        assert_eq!(outer.nb_rules(), 0);
+
+        // Aucun relevé en dehors d'une mesure
+        record_rule(0);
+        assert!(RECORDER.with(|recorder| recorder.borrow().is_none()));
+    }
+}
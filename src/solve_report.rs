@@ -0,0 +1,129 @@
+//! Rapport détaillé de la résolution d'une grille.
+//!
+//! [`solve_with_report`] résout une grille comme [`crate::solve_grid`] en conservant pour chaque règle
+//! de construction appliquée l'état de la grille avant et après la règle et la durée de l'étape : la trace
+//! complète de la résolution est disponible sans analyser l'affichage du programme `star-battle`.
+
+use std::time::{Duration, Instant};
+
+use crate::grid_solve::solve_from;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridAction;
+use crate::GridHandler;
+use crate::GridParser;
+use crate::SolveOutcome;
+use crate::SolverConfig;
+
+/// Étape de la résolution d'une grille : une règle de construction appliquée
+#[derive(Clone, Debug)]
+pub struct SolveReportStep {
+    /// Règle de construction appliquée
+    pub good_rule: GoodRule,
+
+    /// Niveau de la règle (voir [`crate::get_good_rule_with_level`])
+    pub level: usize,
+
+    /// Grille avant l'application de la règle
+    pub before: Grid,
+
+    /// Grille après l'application de la règle
+    pub after: Grid,
+
+    /// Durée de l'étape (recherche et application de la règle)
+    pub duration: Duration,
+}
+
+impl SolveReportStep {
+    /// Actions de la règle appliquée
+    #[must_use]
+    pub fn actions(&self) -> &[GridAction] {
+        self.good_rule.actions()
+    }
+}
+
+/// Rapport de la résolution d'une grille (voir [`solve_with_report`])
+#[derive(Debug)]
+pub struct SolveReport {
+    /// Étapes de la résolution, dans l'ordre d'application des règles
+    pub steps: Vec<SolveReportStep>,
+
+    /// Résultat de la résolution
+    pub outcome: SolveOutcome,
+
+    /// Durée totale de la résolution
+    pub duration: Duration,
+}
+
+/// Résolution d'une grille avec `nb_stars` étoiles par ligne, colonne et région selon la configuration
+/// `config` en conservant le détail de chaque étape
+#[must_use]
+pub fn solve_with_report(
+    parser: &GridParser,
+    nb_stars: usize,
+    config: &SolverConfig,
+) -> SolveReport {
+    let handler = GridHandler::new(parser, nb_stars);
+    let grid = Grid::from(&handler);
+
+    let start = Instant::now();
+    let mut step_start = start;
+    let mut before = grid.clone();
+    let mut steps = Vec::new();
+    let outcome = solve_from(handler, grid, config, |grid, applied| {
+        if let Some((good_rule, level)) = applied.last() {
+            let now = Instant::now();
+            steps.push(SolveReportStep {
+                good_rule: good_rule.clone(),
+                level: *level,
+                before: std::mem::replace(&mut before, grid.clone()),
+                after: grid.clone(),
+                duration: now - step_start,
+            });
+            step_start = now;
+        }
+        true
+    });
+    SolveReport {
+        steps,
+        outcome,
+        duration: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_with_report() {
+        let contents = std::fs::read_to_string("./test_grids/facile01_2.txt").unwrap();
+        let parser = GridParser::try_from(contents.as_str()).unwrap();
+        let report = solve_with_report(&parser, 2, &SolverConfig::default());
+        assert!(report.outcome.is_solved());
+        assert_eq!(report.steps.len(), report.outcome.steps.len());
+
+        // Chaque étape part de la grille obtenue par l'étape précédente
+        let mut grid = report.outcome.initial_grid();
+        for (step, (good_rule, level)) in report.steps.iter().zip(&report.outcome.steps) {
+            assert_eq!(&step.good_rule, good_rule);
+            assert_eq!(step.level, *level);
+            assert_eq!(step.before, grid);
+            grid.apply_good_rule(&step.good_rule);
+            assert_eq!(step.after, grid);
+            assert!(step
+                .actions()
+                .iter()
+                .all(|action| step.before.cell(action.line_column()).is_unknown()));
+        }
+        assert_eq!(grid, report.outcome.grid);
+        assert!(
+            report
+                .steps
+                .iter()
+                .map(|step| step.duration)
+                .sum::<Duration>()
+                <= report.duration
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Itérateur sur les étapes de la résolution d'une grille.
+//!
+//! [`SolverSteps`] enchaîne la recherche et l'application des règles de construction : chaque itération
+//! applique la règle trouvée à la grille et la retourne. L'itération s'arrête quand aucune règle n'est
+//! applicable ou après une incohérence de la grille (retournée comme dernier élément).
+
+use crate::get_good_rule_with_config;
+use crate::BadRuleError;
+use crate::GoodRule;
+use crate::Grid;
+use crate::GridHandler;
+use crate::SolverConfig;
+
+/// Itérateur sur les règles de construction appliquées successivement à une grille (voir
+/// [`GridHandler::steps`])
+#[derive(Debug)]
+pub struct SolverSteps<'a> {
+    /// Handler de la grille
+    handler: &'a GridHandler,
+
+    /// Grille en cours de résolution
+    grid: &'a mut Grid,
+
+    /// Configuration de la recherche des règles
+    config: SolverConfig,
+
+    /// Niveau de la dernière règle appliquée
+    level: Option<usize>,
+
+    /// Fin de l'itération (plus de règle applicable ou grille incohérente)
+    is_done: bool,
+}
+
+impl<'a> SolverSteps<'a> {
+    /// Constructeur : étapes de la résolution de la grille `grid` selon la configuration `config`
+    #[must_use]
+    pub const fn new(handler: &'a GridHandler, grid: &'a mut Grid, config: SolverConfig) -> Self {
+        Self {
+            handler,
+            grid,
+            config,
+            level: None,
+            is_done: false,
+        }
+    }
+
+    /// Grille dans son état courant
+    #[must_use]
+    pub const fn grid(&self) -> &Grid {
+        self.grid
+    }
+
+    /// Niveau de la dernière règle appliquée (voir [`crate::get_good_rule_with_level`])
+    #[must_use]
+    pub const fn level(&self) -> Option<usize> {
+        self.level
+    }
+}
+
+impl Iterator for SolverSteps<'_> {
+    type Item = Result<GoodRule, BadRuleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+        match get_good_rule_with_config(self.handler, self.grid, &self.config) {
+            Ok(Some((good_rule, level))) => {
+                self.grid.apply_good_rule(&good_rule);
+                self.level = Some(level);
+                Some(Ok(good_rule))
+            }
+            Ok(None) => {
+                self.is_done = true;
+                None
+            }
+            Err(bad_rule) => {
+                self.is_done = true;
+                Some(Err(bad_rule))
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for SolverSteps<'_> {}
+
+impl GridHandler {
+    /// Étapes de la résolution de la grille `grid` : chaque itération applique à la grille la règle de
+    /// construction suivante et la retourne
+    #[must_use]
+    pub fn steps<'a>(&'a self, grid: &'a mut Grid) -> SolverSteps<'a> {
+        SolverSteps::new(self, grid, SolverConfig::default())
+    }
+
+    /// Étapes de la résolution de la grille `grid` en recherchant les règles selon la configuration
+    /// `config`
+    #[must_use]
+    pub fn steps_with_config<'a>(
+        &'a self,
+        grid: &'a mut Grid,
+        config: &SolverConfig,
+    ) -> SolverSteps<'a> {
+        SolverSteps::new(self, grid, config.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::get_good_rule;
+    use crate::GridAction;
+    use crate::LineColumn;
+
+    #[test]
+    fn test_solver_steps() {
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+
+        // Mêmes règles que la boucle de résolution
+        let mut expected_grid = grid.clone();
+        let mut expected_rules = Vec::new();
+        while let Some(good_rule) = get_good_rule(&grid_handler, &expected_grid).unwrap() {
+            expected_grid.apply_good_rule(&good_rule);
+            expected_rules.push(good_rule);
+        }
+        let rules = grid_handler
+            .steps(&mut grid)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rules, expected_rules);
+        assert_eq!(grid, expected_grid);
+        assert!(grid_handler.steps(&mut grid).next().is_none());
+
+        // Une grille incohérente arrête l'itération après l'erreur
+        let (grid_handler, mut grid) =
+            grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        grid.apply_action(&GridAction::SetStar(LineColumn::new(0, 0)))
+            .unwrap();
+        grid.apply_action(&GridAction::SetStar(LineColumn::new(1, 0)))
+            .unwrap();
+        let mut steps = grid_handler.steps_with_config(&mut grid, &SolverConfig::default());
+        assert!(matches!(steps.next(), Some(Err(_))));
+        assert!(steps.next().is_none());
+        assert_eq!(steps.level(), None);
+    }
+}
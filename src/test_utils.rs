@@ -0,0 +1,60 @@
+//! Utilitaires pour les tests.
+//!
+//! La macro `grid!` construit le couple ([`crate::GridHandler`], [`crate::Grid`]) d'une grille de test à
+//! partir des lignes de texte de la grille et du nombre d'étoiles, en définissant éventuellement le contenu
+//! de quelques cases :
+//!
+//! ```text
+//! let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+//! let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1;
+//!     star = "A1 C3", no_star = "B1"];
+//! ```
+
+use crate::CellValue;
+use crate::Grid;
+use crate::LineColumn;
+
+/// Construction d'un couple (`GridHandler`, `Grid`) pour les tests (voir [`crate::test_utils`])
+macro_rules! grid {
+    [$($line:expr),+ $(,)?; stars = $nb_stars:expr] => {{
+        let grid_parser = $crate::GridParser::try_from(vec![$($line),+]).unwrap();
+        let grid_handler = $crate::GridHandler::new(&grid_parser, $nb_stars);
+        let grid = $crate::Grid::from(&grid_handler);
+        (grid_handler, grid)
+    }};
+    [$($line:expr),+ $(,)?; stars = $nb_stars:expr; $($value:ident = $cells:expr),+ $(,)?] => {{
+        let (grid_handler, mut grid) = grid![$($line),+; stars = $nb_stars];
+        $($crate::test_utils::set_cells(&mut grid, $cells, grid!(@value $value));)+
+        (grid_handler, grid)
+    }};
+    (@value star) => { $crate::CellValue::Star };
+    (@value no_star) => { $crate::CellValue::NoStar };
+    (@value unknown) => { $crate::CellValue::Unknown };
+}
+
+/// Définit la valeur `value` des cases de la liste textuelle `cells` (`"A1 C3"` par exemple)
+pub fn set_cells(grid: &mut Grid, cells: &str, value: CellValue) {
+    for cell in cells.split_whitespace() {
+        let line_column: LineColumn = cell.parse().unwrap();
+        grid.cell_mut(line_column).value = value.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_macro() {
+        let (grid_handler, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1];
+        assert_eq!(grid_handler.nb_stars(), 1);
+        assert_eq!(grid, Grid::from(&grid_handler));
+
+        let (_, grid) = grid!["ABBBB", "ABBBB", "CCBBB", "DDDDD", "DEEED"; stars = 1;
+            star = "A1 C3", no_star = "B1"];
+        assert!(grid.cell(LineColumn::new(0, 0)).is_star());
+        assert!(grid.cell(LineColumn::new(2, 2)).is_star());
+        assert_eq!(grid.cell(LineColumn::new(0, 1)).value, CellValue::NoStar);
+        assert_eq!(grid.enumerate_unknown().count(), 22);
+    }
+}
@@ -0,0 +1,125 @@
+//! Interface `WebAssembly` (feature `wasm`) pour une résolution pas à pas dans un navigateur.
+//!
+//! Un [`WasmSolver`] est construit à partir du texte d'une grille (voir [`GridParser`]), propose la
+//! prochaine règle de construction au format JSON, l'applique et affiche la grille au format SVG.
+
+use wasm_bindgen::prelude::*;
+
+use crate::get_good_rule_with_level;
+use crate::json_rule::JsonRule;
+use crate::Grid;
+use crate::GridHandler;
+use crate::GridParser;
+
+/// Résolution pas à pas d'une grille depuis `JavaScript`
+#[wasm_bindgen]
+pub struct WasmSolver {
+    /// Handler de la grille
+    handler: GridHandler,
+
+    /// Grille en cours de résolution
+    grid: Grid,
+}
+
+#[wasm_bindgen]
+impl WasmSolver {
+    /// Constructeur selon le texte de la grille (une ligne de texte par ligne de la grille) et le nombre
+    /// d'étoiles à placer dans chaque ligne, colonne et région
+    /// ### Errors
+    /// Retourne une erreur si le texte n'est pas une grille valide avec ce nombre d'étoiles
+    #[wasm_bindgen(constructor)]
+    pub fn new(puzzle: &str, nb_stars: usize) -> Result<Self, JsError> {
+        Self::try_new(puzzle, nb_stars).map_err(|e| JsError::new(&e))
+    }
+
+    /// Prochaine règle de construction au format JSON (`null` si aucune règle n'est applicable)
+    /// ### Errors
+    /// Retourne une erreur si la grille n'est pas valide
+    pub fn next_rule(&self) -> Result<String, JsError> {
+        self.try_next_rule().map_err(|e| JsError::new(&e))
+    }
+
+    /// Applique une règle de construction au format JSON (voir [`WasmSolver::next_rule`])
+    /// ### Errors
+    /// Retourne une erreur si la règle n'est pas valide (case en dehors de la grille par exemple) ou rend
+    /// la grille invalide
+    pub fn apply_rule(&mut self, json_rule: &str) -> Result<(), JsError> {
+        self.try_apply_rule(json_rule).map_err(|e| JsError::new(&e))
+    }
+
+    /// Affichage de la grille au format SVG
+    #[must_use]
+    pub fn render_svg(&self) -> String {
+        self.handler.display_svg(&self.grid)
+    }
+
+    /// Retourne `true` si la grille est résolue
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.handler.is_done(&self.grid)
+    }
+}
+
+impl WasmSolver {
+    /// Constructeur (voir [`WasmSolver::new`])
+    fn try_new(puzzle: &str, nb_stars: usize) -> Result<Self, String> {
+        let parser = GridParser::try_from(puzzle)?;
+        let handler = GridHandler::try_new(&parser, nb_stars)?;
+        let grid = Grid::from(&handler);
+        Ok(Self { handler, grid })
+    }
+
+    /// Prochaine règle de construction au format JSON (voir [`WasmSolver::next_rule`])
+    fn try_next_rule(&self) -> Result<String, String> {
+        let json_rule = get_good_rule_with_level(&self.handler, &self.grid)
+            .map_err(|e| e.to_string())?
+            .map(|(rule, level)| JsonRule::new(rule, level));
+        serde_json::to_string(&json_rule).map_err(|e| e.to_string())
+    }
+
+    /// Applique une règle de construction au format JSON (voir [`WasmSolver::apply_rule`])
+    fn try_apply_rule(&mut self, json_rule: &str) -> Result<(), String> {
+        let json_rule: JsonRule = serde_json::from_str(json_rule).map_err(|e| e.to_string())?;
+        self.grid
+            .apply_good_rule_checked(&self.handler, &json_rule.rule)
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wasm_solver() {
+        let mut solver = WasmSolver::try_new("ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED", 1).unwrap();
+        assert!(!solver.is_done());
+        loop {
+            let json_rule = solver.try_next_rule().unwrap();
+            if json_rule == "null" {
+                break;
+            }
+            assert!(json_rule.contains("\"level\""));
+            solver.try_apply_rule(&json_rule).unwrap();
+        }
+        assert!(solver.is_done());
+        assert_eq!(solver.render_svg().matches('★').count(), 5);
+    }
+
+    #[test]
+    fn test_wasm_solver_errors() {
+        assert!(WasmSolver::try_new("ABBBB\nABBB", 1).is_err());
+        assert!(WasmSolver::try_new("ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED", 0).is_err());
+
+        let mut solver = WasmSolver::try_new("ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED", 1).unwrap();
+        assert!(solver.try_apply_rule("{}").is_err());
+        // Règle sur une case en dehors de la grille
+        let json_rule = solver.try_next_rule().unwrap();
+        let outside = json_rule.replace("\"line\":4", "\"line\":9");
+        assert_ne!(outside, json_rule);
+        assert!(solver.try_apply_rule(&outside).is_err());
+
+        // Nombre d'étoiles incompatible avec la grille
+        assert!(WasmSolver::try_new("ABBBB\nABBBB\nCCBBB\nDDDDD\nDEEED", 3).is_err());
+    }
+}